@@ -5,6 +5,8 @@
 
 #![warn(missing_docs)]
 
+pub mod healthchain;
+
 use std::sync::Arc;
 
 use healthchain_runtime::{opaque::Block, AccountId, Balance, Nonce};
@@ -20,6 +22,8 @@ pub struct FullDeps<C, P> {
     pub client: Arc<C>,
     /// Transaction pool instance.
     pub pool: Arc<P>,
+    /// Signed read requests already served, shared by every RPC instance.
+    pub served_reads: Arc<healthchain::ServedReads>,
 }
 
 /// Instantiate all full RPC extensions.
@@ -32,17 +36,26 @@ where
     C: Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: healthchain_runtime::apis::MedicalHistoryReaderApi<
+        Block,
+        AccountId,
+        u64,
+        healthchain_runtime::BlockNumber,
+        healthchain_runtime::Signature,
+    >,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
 {
+    use healthchain::{Healthchain, HealthchainApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
     let mut module = RpcModule::new(());
-    let FullDeps { client, pool } = deps;
+    let FullDeps { client, pool, served_reads } = deps;
 
     module.merge(System::new(client.clone(), pool).into_rpc())?;
-    module.merge(TransactionPayment::new(client).into_rpc())?;
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(Healthchain::new(client, served_reads).into_rpc())?;
 
     // Extend this RPC with a custom API by using the following syntax.
     // `YourRpcStruct` should have a reference to a client, which is needed
@@ -0,0 +1,455 @@
+//! `healthchain_*` RPC methods.
+//!
+//! Thin JSON-RPC wrapper around the signed reads of the
+//! `MedicalHistoryReaderApi` runtime API: record reads, version histories and
+//! paginated listings, each checked against the reader's permissions without
+//! requiring a transaction. The runtime cannot remember which requests it
+//! already served, so this module refuses a `(reader, nonce)` pair it has
+//! seen until the request expires (see [`ServedReads`]). Reads run at the
+//! best block or at most [`MAX_READ_VALIDITY`] blocks before it, so an
+//! expired request cannot be replayed against an older state.
+
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+use codec::{Codec, Decode, Encode};
+use healthchain_runtime::{
+    apis::MedicalHistoryReaderApi, BlockNumber, Signature, MAX_READ_VALIDITY,
+};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use pallet_medical_history::{MedicalRecord, Provenance, RecordPage, MAX_PAGE_SIZE};
+use pallet_medical_history_reader::{ListPayload, ReadPayload};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Error code returned for malformed parameters.
+const INVALID_PARAMS: i32 = 2;
+
+/// Error code returned when a signed read request is refused.
+const READ_REFUSED: i32 = 3;
+
+/// Error code returned when a signed request was already served.
+const READ_REPLAYED: i32 = 4;
+
+/// Maximum number of served `(reader, nonce)` pairs remembered at once.
+///
+/// Requests are refused while the set is full; entries are dropped as soon
+/// as their request expires, at most [`MAX_READ_VALIDITY`] blocks later.
+const MAX_SERVED_READS: usize = 65_536;
+
+/// Maximum number of served nonces remembered per reader, so a single
+/// account cannot fill [`MAX_SERVED_READS`] and lock out other readers.
+const MAX_SERVED_READS_PER_READER: usize = 256;
+
+/// A medical record as returned by the RPC.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordInfo<AccountId> {
+    /// Account that created the record.
+    pub created_by: AccountId,
+    /// Creation timestamp, in milliseconds.
+    pub created_at: u64,
+    /// Hash (CID) of the medical file.
+    pub file_hash: Bytes,
+    /// Record category (e.g. `Exam`, `Prescription`).
+    pub category: String,
+    /// MIME type of the file.
+    pub content_type: String,
+    /// Date of the clinical event, in milliseconds.
+    pub clinical_date: u64,
+    /// Hash of the encrypted title, if any.
+    pub title_hash: Option<Bytes>,
+    /// `ClinicianAuthored` or `SelfReported`.
+    pub provenance: String,
+}
+
+impl<AccountId> From<MedicalRecord<AccountId, u64>> for RecordInfo<AccountId> {
+    fn from(record: MedicalRecord<AccountId, u64>) -> Self {
+        let metadata = record.metadata;
+
+        Self {
+            created_by: record.created_by,
+            created_at: record.created_at,
+            file_hash: record.file_hash.into_inner().into(),
+            category: format!("{:?}", metadata.category),
+            content_type: String::from_utf8_lossy(&metadata.content_type).into_owned(),
+            clinical_date: metadata.clinical_date,
+            title_hash: metadata.title_hash.map(|hash| hash.to_vec().into()),
+            provenance: format!("{:?}", record.provenance),
+        }
+    }
+}
+
+/// A record created by a doctor, with the patient it belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorRecordInfo<AccountId> {
+    /// Patient that owns the record.
+    pub patient: AccountId,
+    /// The record itself.
+    pub record: RecordInfo<AccountId>,
+}
+
+/// One page of a record listing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<Item> {
+    /// Entries of the page, oldest first.
+    pub items: Vec<Item>,
+    /// `from` of the next page, or `None` if this is the last one.
+    pub next: Option<u64>,
+}
+
+impl<Item> Page<Item> {
+    fn from_runtime<T>(page: RecordPage<T>, item: impl Fn(T) -> Item) -> Self {
+        Self { items: page.items.into_iter().map(item).collect(), next: page.next }
+    }
+}
+
+/// HealthChain RPC methods.
+#[rpc(server)]
+pub trait HealthchainApi<BlockHash, AccountId> {
+    /// Lists the records of the patient `payload.owner` that the reader may
+    /// read, oldest first.
+    ///
+    /// `payload` is a SCALE-encoded `ListPayload` and `signature` the
+    /// SCALE-encoded signature of its signing message
+    /// (`"healthchain/list:" ++ payload`) by `payload.reader`. `from` is `0`
+    /// for the first page and the `next` of the previous page afterwards;
+    /// `limit` is capped at [`MAX_PAGE_SIZE`]. `provenance`
+    /// (`ClinicianAuthored`, `SelfReported` or `LaboratoryReported`), which
+    /// follows `at` so that callers passing positional parameters without it
+    /// keep working, restricts the list to records of that provenance.
+    #[method(name = "healthchain_readRecordsOfPatient")]
+    fn read_records_of_patient(
+        &self,
+        payload: Bytes,
+        signature: Bytes,
+        from: u64,
+        limit: u32,
+        at: Option<BlockHash>,
+        provenance: Option<String>,
+    ) -> RpcResult<Page<RecordInfo<AccountId>>>;
+
+    /// Lists the records created by the doctor `payload.owner`, who must be
+    /// the reader, oldest first.
+    ///
+    /// Same parameters as `healthchain_readRecordsOfPatient`.
+    #[method(name = "healthchain_readRecordsByDoctor")]
+    fn read_records_by_doctor(
+        &self,
+        payload: Bytes,
+        signature: Bytes,
+        from: u64,
+        limit: u32,
+        at: Option<BlockHash>,
+        provenance: Option<String>,
+    ) -> RpcResult<Page<DoctorRecordInfo<AccountId>>>;
+
+    /// Returns the versions of a record the reader may read, oldest first,
+    /// if the signed read request is accepted.
+    ///
+    /// Same parameters as `healthchain_readRecord`.
+    #[method(name = "healthchain_readRecordHistory")]
+    fn read_record_history(
+        &self,
+        payload: Bytes,
+        signature: Bytes,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<RecordInfo<AccountId>>>;
+
+    /// Returns a record if the signed read request is accepted.
+    ///
+    /// `payload` is a SCALE-encoded `ReadPayload` and `signature` the
+    /// SCALE-encoded signature of its signing message
+    /// (`"healthchain/read:" ++ payload`) by `payload.reader`. Each
+    /// `(reader, nonce)` pair is served once.
+    #[method(name = "healthchain_readRecord")]
+    fn read_record(
+        &self,
+        payload: Bytes,
+        signature: Bytes,
+        at: Option<BlockHash>,
+    ) -> RpcResult<RecordInfo<AccountId>>;
+}
+
+/// Signed requests already served by this node: the nonces of each reader,
+/// with the last block at which each request is valid.
+#[derive(Default)]
+pub struct ServedReads {
+    served: Mutex<HashMap<Vec<u8>, HashMap<u64, BlockNumber>>>,
+}
+
+impl ServedReads {
+    /// Marks `nonce` of `reader` as served until `valid_until`.
+    ///
+    /// Fails if the request expired before block `best`, if it was already
+    /// served, or if [`MAX_SERVED_READS_PER_READER`] requests of the reader
+    /// or [`MAX_SERVED_READS`] requests overall are being tracked.
+    fn reserve(
+        &self,
+        reader: &[u8],
+        nonce: u64,
+        valid_until: BlockNumber,
+        best: BlockNumber,
+    ) -> Result<(), ErrorObjectOwned> {
+        if valid_until < best {
+            return Err(ErrorObject::owned(READ_REFUSED, "Request expired", None::<()>));
+        }
+
+        let mut served = self.served.lock().expect("lock is never poisoned; qed");
+        served.retain(|_, nonces| {
+            nonces.retain(|_, until| *until >= best);
+            !nonces.is_empty()
+        });
+        let total = served.values().map(HashMap::len).sum::<usize>();
+
+        let nonces = served.entry(reader.to_vec()).or_default();
+        if nonces.contains_key(&nonce) {
+            return Err(ErrorObject::owned(READ_REPLAYED, "Request already served", None::<()>));
+        }
+        if nonces.len() >= MAX_SERVED_READS_PER_READER || total >= MAX_SERVED_READS {
+            return Err(ErrorObject::owned(RUNTIME_ERROR, "Too many pending reads", None::<()>));
+        }
+        nonces.insert(nonce, valid_until);
+
+        Ok(())
+    }
+
+    /// Forgets `nonce` of `reader`, so a refused request does not burn it.
+    fn release(&self, reader: &[u8], nonce: u64) {
+        let mut served = self.served.lock().expect("lock is never poisoned; qed");
+        if let Some(nonces) = served.get_mut(reader) {
+            nonces.remove(&nonce);
+        }
+    }
+}
+
+/// Implementation of [`HealthchainApiServer`].
+pub struct Healthchain<C, Block> {
+    client: Arc<C>,
+    served: Arc<ServedReads>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> Healthchain<C, Block> {
+    /// Creates a new instance backed by `client`, sharing `served` with the
+    /// node's other RPC instances.
+    pub fn new(client: Arc<C>, served: Arc<ServedReads>) -> Self {
+        Self { client, served, _marker: Default::default() }
+    }
+}
+
+impl<C, Block> Healthchain<C, Block>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    /// Runs `read` at block `at` (the best block by default) once for the
+    /// signed request `(reader, nonce)`.
+    ///
+    /// Refuses the request if it expired or was already served, and `at` if
+    /// it is unknown or more than [`MAX_READ_VALIDITY`] blocks behind the
+    /// best block.
+    fn serve_once<R>(
+        &self,
+        at: Option<Block::Hash>,
+        reader: &impl Encode,
+        nonce: u64,
+        valid_until: BlockNumber,
+        read: impl FnOnce(Block::Hash) -> RpcResult<R>,
+    ) -> RpcResult<R> {
+        let info = self.client.info();
+        let best: BlockNumber = info.best_number.unique_saturated_into();
+        let at = at.unwrap_or(info.best_hash);
+        let at_number: BlockNumber = self
+            .client
+            .number(at)
+            .map_err(runtime_error)?
+            .ok_or_else(|| invalid_params("Unknown block"))?
+            .unique_saturated_into();
+        if at_number.saturating_add(MAX_READ_VALIDITY) < best {
+            return Err(invalid_params("Block too old"));
+        }
+
+        let reader = reader.encode();
+        self.served.reserve(&reader, nonce, valid_until, best)?;
+        read(at).inspect_err(|_| self.served.release(&reader, nonce))
+    }
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> ErrorObjectOwned {
+    ErrorObject::owned(RUNTIME_ERROR, "Runtime error", Some(format!("{err:?}")))
+}
+
+fn invalid_params(message: &str) -> ErrorObjectOwned {
+    ErrorObject::owned(INVALID_PARAMS, message, None::<()>)
+}
+
+fn parse_provenance(provenance: Option<String>) -> Result<Option<Provenance>, ErrorObjectOwned> {
+    provenance
+        .map(|provenance| match provenance.as_str() {
+            "ClinicianAuthored" => Ok(Provenance::ClinicianAuthored),
+            "SelfReported" => Ok(Provenance::SelfReported),
+            "LaboratoryReported" => Ok(Provenance::LaboratoryReported),
+            _ => Err(invalid_params("Unknown provenance")),
+        })
+        .transpose()
+}
+
+fn read_refused(err: impl std::fmt::Debug) -> ErrorObjectOwned {
+    ErrorObject::owned(READ_REFUSED, "Read refused", Some(format!("{err:?}")))
+}
+
+fn decode_read<AccountId: Decode>(
+    payload: Bytes,
+    signature: Bytes,
+) -> Result<(ReadPayload<AccountId, BlockNumber>, Signature), ErrorObjectOwned> {
+    let payload = ReadPayload::decode(&mut &payload[..])
+        .map_err(|_| invalid_params("Malformed read payload"))?;
+    Ok((payload, decode_signature(signature)?))
+}
+
+fn decode_list<AccountId: Decode>(
+    payload: Bytes,
+    signature: Bytes,
+) -> Result<(ListPayload<AccountId, BlockNumber>, Signature), ErrorObjectOwned> {
+    let payload = ListPayload::decode(&mut &payload[..])
+        .map_err(|_| invalid_params("Malformed list payload"))?;
+    Ok((payload, decode_signature(signature)?))
+}
+
+fn decode_signature(signature: Bytes) -> Result<Signature, ErrorObjectOwned> {
+    Signature::decode(&mut &signature[..]).map_err(|_| invalid_params("Malformed signature"))
+}
+
+impl<C, Block, AccountId> HealthchainApiServer<<Block as BlockT>::Hash, AccountId>
+    for Healthchain<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: MedicalHistoryReaderApi<Block, AccountId, u64, BlockNumber, Signature>,
+    AccountId: Codec + Clone + Send + Sync + Serialize + 'static,
+{
+    fn read_records_of_patient(
+        &self,
+        payload: Bytes,
+        signature: Bytes,
+        from: u64,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+        provenance: Option<String>,
+    ) -> RpcResult<Page<RecordInfo<AccountId>>> {
+        let (payload, signature) = decode_list::<AccountId>(payload, signature)?;
+        let provenance = parse_provenance(provenance)?;
+
+        let (reader, nonce, valid_until) =
+            (payload.reader.clone(), payload.nonce, payload.valid_until);
+        let page = self.serve_once(at, &reader, nonce, valid_until, |at| {
+            self.client
+                .runtime_api()
+                .read_records_of_patient(
+                    at,
+                    payload,
+                    signature,
+                    provenance,
+                    from,
+                    limit.min(MAX_PAGE_SIZE),
+                )
+                .map_err(runtime_error)?
+                .map_err(read_refused)
+        })?;
+
+        Ok(Page::from_runtime(page, Into::into))
+    }
+
+    fn read_records_by_doctor(
+        &self,
+        payload: Bytes,
+        signature: Bytes,
+        from: u64,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+        provenance: Option<String>,
+    ) -> RpcResult<Page<DoctorRecordInfo<AccountId>>> {
+        let (payload, signature) = decode_list::<AccountId>(payload, signature)?;
+        let provenance = parse_provenance(provenance)?;
+
+        let (reader, nonce, valid_until) =
+            (payload.reader.clone(), payload.nonce, payload.valid_until);
+        let page = self.serve_once(at, &reader, nonce, valid_until, |at| {
+            self.client
+                .runtime_api()
+                .read_records_by_doctor(
+                    at,
+                    payload,
+                    signature,
+                    provenance,
+                    from,
+                    limit.min(MAX_PAGE_SIZE),
+                )
+                .map_err(runtime_error)?
+                .map_err(read_refused)
+        })?;
+
+        Ok(Page::from_runtime(page, |(patient, record)| DoctorRecordInfo {
+            patient,
+            record: record.into(),
+        }))
+    }
+
+    fn read_record_history(
+        &self,
+        payload: Bytes,
+        signature: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<RecordInfo<AccountId>>> {
+        let (payload, signature) = decode_read::<AccountId>(payload, signature)?;
+
+        let (reader, nonce, valid_until) =
+            (payload.reader.clone(), payload.nonce, payload.valid_until);
+        let history = self.serve_once(at, &reader, nonce, valid_until, |at| {
+            self.client
+                .runtime_api()
+                .read_record_history(at, payload, signature)
+                .map_err(runtime_error)?
+                .map_err(read_refused)
+        })?;
+
+        Ok(history.into_iter().map(Into::into).collect())
+    }
+
+    fn read_record(
+        &self,
+        payload: Bytes,
+        signature: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<RecordInfo<AccountId>> {
+        let (payload, signature) = decode_read::<AccountId>(payload, signature)?;
+
+        let (reader, nonce, valid_until) =
+            (payload.reader.clone(), payload.nonce, payload.valid_until);
+        let record = self.serve_once(at, &reader, nonce, valid_until, |at| {
+            self.client
+                .runtime_api()
+                .read_record(at, payload, signature)
+                .map_err(runtime_error)?
+                .map_err(read_refused)
+        })?;
+
+        Ok(record.into())
+    }
+}
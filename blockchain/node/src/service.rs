@@ -222,11 +222,13 @@ pub fn new_full<
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
+        let served_reads = Arc::new(crate::rpc::healthchain::ServedReads::default());
 
         Box::new(move |_| {
             let deps = crate::rpc::FullDeps {
                 client: client.clone(),
                 pool: pool.clone(),
+                served_reads: served_reads.clone(),
             };
             crate::rpc::create_full(deps).map_err(Into::into)
         })
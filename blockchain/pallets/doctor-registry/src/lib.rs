@@ -0,0 +1,415 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Doctor Registry pallet (HealthChain).
+//!
+//! Keeps an on-chain profile (CRM, state, specialty, name hash) for every
+//! doctor account. Doctors apply on their own; a configurable registrar
+//! origin (sudo or a council) approves, suspends or reinstates them.
+//! Development chains can seed active doctors through the genesis config.
+//!
+//! ## Cross-pallet access
+//! Other pallets use [`DoctorRegistryVerifier`] to make sure an account is
+//! a registered, non-suspended doctor before accepting records or grants.
+
+extern crate alloc;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+pub mod types;
+pub use types::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use alloc::vec::Vec;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    /// Main pallet struct.
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Pallet configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Origin allowed to approve, suspend and reinstate doctors.
+        type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Weight information for extrinsics.
+        type WeightInfo: WeightInfo;
+    }
+
+    // ---------------------------------------------------------------------
+    // Storage
+    // ---------------------------------------------------------------------
+
+    /// Doctor profiles: `doctor -> profile`.
+    #[pallet::storage]
+    #[pallet::getter(fn doctors)]
+    pub type Doctors<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        DoctorProfile<T::Hash, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// CRM index: `(state, crm) -> doctor`.
+    ///
+    /// Guarantees that a CRM is bound to a single account. A CRM is only
+    /// bound once the registrar approves the application, so a pending
+    /// application cannot hold another doctor's CRM.
+    #[pallet::storage]
+    #[pallet::getter(fn crm_owner)]
+    pub type CrmIndex<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        StateCode,
+        Blake2_128Concat,
+        Crm,
+        T::AccountId,
+        OptionQuery,
+    >;
+
+    // ---------------------------------------------------------------------
+    // Genesis
+    // ---------------------------------------------------------------------
+
+    /// Doctors registered at genesis, e.g. on development chains.
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Active doctors: `(account, crm, state, specialty)`.
+        pub doctors: Vec<(T::AccountId, Crm, StateCode, Specialty)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (doctor, crm, state, specialty) in &self.doctors {
+                assert!(
+                    !Doctors::<T>::contains_key(doctor),
+                    "duplicate doctor in genesis config"
+                );
+                assert!(
+                    !CrmIndex::<T>::contains_key(state, crm),
+                    "duplicate CRM in genesis config"
+                );
+
+                CrmIndex::<T>::insert(state, crm, doctor);
+                Doctors::<T>::insert(
+                    doctor,
+                    DoctorProfile {
+                        crm: crm.clone(),
+                        state: *state,
+                        specialty: specialty.clone(),
+                        name_hash: Default::default(),
+                        status: DoctorStatus::Active,
+                        applied_at: Default::default(),
+                    },
+                );
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Events
+    // ---------------------------------------------------------------------
+
+    /// Events emitted by the Doctor Registry pallet.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A doctor applied for registration.
+        DoctorApplied {
+            doctor: T::AccountId,
+            crm: Crm,
+            state: StateCode,
+        },
+
+        /// The registrar approved a doctor.
+        DoctorApproved { doctor: T::AccountId },
+
+        /// The registrar rejected a pending application.
+        DoctorRejected { doctor: T::AccountId },
+
+        /// The registrar suspended a doctor.
+        DoctorSuspended { doctor: T::AccountId },
+
+        /// The registrar reinstated a suspended doctor.
+        DoctorReinstated { doctor: T::AccountId },
+    }
+
+    // ---------------------------------------------------------------------
+    // Errors
+    // ---------------------------------------------------------------------
+
+    /// Errors returned by the Doctor Registry pallet.
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The account already has a profile.
+        AlreadyRegistered,
+
+        /// The CRM is already bound to another account.
+        CrmAlreadyInUse,
+
+        /// The CRM is empty or contains non-digit characters.
+        InvalidCrm,
+
+        /// The state code is not two uppercase ASCII letters.
+        InvalidState,
+
+        /// The account has no profile.
+        DoctorNotFound,
+
+        /// The profile is not in the status required by this call.
+        InvalidStatus,
+    }
+
+    // ---------------------------------------------------------------------
+    // Calls (extrinsics)
+    // ---------------------------------------------------------------------
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Applies for registration as a doctor.
+        ///
+        /// The CRM is not reserved by the application; it is bound to the
+        /// account when the registrar approves it.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the doctor.
+        /// - `crm`: CRM registration number (digits only).
+        /// - `state`: State (UF) that issued the CRM.
+        /// - `specialty`: Medical specialty.
+        /// - `name_hash`: Hash of the doctor's full name.
+        ///
+        /// # Storage
+        /// - Writes to [`Doctors`]
+        ///
+        /// # Emits
+        /// - [`Event::DoctorApplied`]
+        ///
+        /// # Errors
+        /// - [`Error::AlreadyRegistered`] if the account already has a profile
+        /// - [`Error::CrmAlreadyInUse`] if the CRM is bound to an approved doctor
+        /// - [`Error::InvalidCrm`] / [`Error::InvalidState`] on malformed input
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::apply())]
+        pub fn apply(
+            origin: OriginFor<T>,
+            crm: Crm,
+            state: StateCode,
+            specialty: Specialty,
+            name_hash: T::Hash,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
+            ensure!(
+                !crm.is_empty() && crm.iter().all(u8::is_ascii_digit),
+                Error::<T>::InvalidCrm
+            );
+            ensure!(state.iter().all(u8::is_ascii_uppercase), Error::<T>::InvalidState);
+            ensure!(!Doctors::<T>::contains_key(&doctor), Error::<T>::AlreadyRegistered);
+            ensure!(
+                !CrmIndex::<T>::contains_key(state, &crm),
+                Error::<T>::CrmAlreadyInUse
+            );
+
+            Doctors::<T>::insert(
+                &doctor,
+                DoctorProfile {
+                    crm: crm.clone(),
+                    state,
+                    specialty,
+                    name_hash,
+                    status: DoctorStatus::Pending,
+                    applied_at: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::DoctorApplied { doctor, crm, state });
+
+            Ok(())
+        }
+
+        /// Approves a pending application and binds its CRM to the doctor.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::RegistrarOrigin`].
+        /// - `doctor`: The applicant.
+        ///
+        /// # Storage
+        /// - Writes to [`Doctors`], [`CrmIndex`]
+        ///
+        /// # Emits
+        /// - [`Event::DoctorApproved`]
+        ///
+        /// # Errors
+        /// - [`Error::DoctorNotFound`] if the account has no profile
+        /// - [`Error::InvalidStatus`] if the profile is not pending
+        /// - [`Error::CrmAlreadyInUse`] if the CRM is bound to another account
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::approve())]
+        pub fn approve(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            let profile = Doctors::<T>::get(&doctor).ok_or(Error::<T>::DoctorNotFound)?;
+            ensure!(
+                !CrmIndex::<T>::contains_key(profile.state, &profile.crm),
+                Error::<T>::CrmAlreadyInUse
+            );
+
+            Self::transition(&doctor, DoctorStatus::Pending, DoctorStatus::Active)?;
+            CrmIndex::<T>::insert(profile.state, &profile.crm, &doctor);
+
+            Self::deposit_event(Event::DoctorApproved { doctor });
+
+            Ok(())
+        }
+
+        /// Rejects a pending application.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::RegistrarOrigin`].
+        /// - `doctor`: The applicant.
+        ///
+        /// # Emits
+        /// - [`Event::DoctorRejected`]
+        ///
+        /// # Errors
+        /// - [`Error::DoctorNotFound`] if the account has no profile
+        /// - [`Error::InvalidStatus`] if the profile is not pending
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::reject())]
+        pub fn reject(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            let profile = Doctors::<T>::get(&doctor).ok_or(Error::<T>::DoctorNotFound)?;
+            ensure!(profile.status == DoctorStatus::Pending, Error::<T>::InvalidStatus);
+
+            Doctors::<T>::remove(&doctor);
+
+            Self::deposit_event(Event::DoctorRejected { doctor });
+
+            Ok(())
+        }
+
+        /// Suspends an active doctor.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::RegistrarOrigin`].
+        /// - `doctor`: The doctor to suspend.
+        ///
+        /// # Emits
+        /// - [`Event::DoctorSuspended`]
+        ///
+        /// # Errors
+        /// - [`Error::DoctorNotFound`] if the account has no profile
+        /// - [`Error::InvalidStatus`] if the doctor is not active
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_status())]
+        pub fn suspend(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            Self::transition(&doctor, DoctorStatus::Active, DoctorStatus::Suspended)?;
+
+            Self::deposit_event(Event::DoctorSuspended { doctor });
+
+            Ok(())
+        }
+
+        /// Reinstates a suspended doctor.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::RegistrarOrigin`].
+        /// - `doctor`: The doctor to reinstate.
+        ///
+        /// # Emits
+        /// - [`Event::DoctorReinstated`]
+        ///
+        /// # Errors
+        /// - [`Error::DoctorNotFound`] if the account has no profile
+        /// - [`Error::InvalidStatus`] if the doctor is not suspended
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_status())]
+        pub fn reinstate(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            Self::transition(&doctor, DoctorStatus::Suspended, DoctorStatus::Active)?;
+
+            Self::deposit_event(Event::DoctorReinstated { doctor });
+
+            Ok(())
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Internal helpers
+    // ---------------------------------------------------------------------
+
+    impl<T: Config> Pallet<T> {
+        /// Moves `doctor` from status `from` to status `to`.
+        fn transition(
+            doctor: &T::AccountId,
+            from: DoctorStatus,
+            to: DoctorStatus,
+        ) -> DispatchResult {
+            Doctors::<T>::try_mutate(doctor, |maybe_profile| {
+                let profile = maybe_profile.as_mut().ok_or(Error::<T>::DoctorNotFound)?;
+                ensure!(profile.status == from, Error::<T>::InvalidStatus);
+                profile.status = to;
+                Ok(())
+            })
+        }
+    }
+
+    //Para calibrar pesos nos benchmarks ----------------  START ------------------
+    #[cfg(feature = "runtime-benchmarks")]
+    impl<T: Config> Pallet<T> {
+        /// Registra um médico ativo (somente para benchmarking).
+        pub fn bench_register_doctor(doctor: &T::AccountId) {
+            Doctors::<T>::insert(
+                doctor,
+                DoctorProfile {
+                    crm: Default::default(),
+                    state: *b"MG",
+                    specialty: Default::default(),
+                    name_hash: Default::default(),
+                    status: DoctorStatus::Active,
+                    applied_at: Default::default(),
+                },
+            );
+        }
+    }
+    //Para calibrar pesos nos benchmarks ----------------  END ------------------
+}
+
+// -------------------------------------------------------------------------
+// Public interface (cross-pallet)
+// -------------------------------------------------------------------------
+
+/// Public verifier interface for checking whether an account is a
+/// registered doctor.
+///
+/// Other pallets can depend on this trait to reject records and grants
+/// involving unregistered or suspended accounts.
+pub trait DoctorRegistryVerifier<AccountId> {
+    /// Returns `true` if `who` is a registered, non-suspended doctor.
+    fn is_active_doctor(who: &AccountId) -> bool;
+}
+
+impl<T: pallet::Config> DoctorRegistryVerifier<T::AccountId> for pallet::Pallet<T> {
+    fn is_active_doctor(who: &T::AccountId) -> bool {
+        pallet::Doctors::<T>::get(who).is_some_and(|profile| profile.status == DoctorStatus::Active)
+    }
+}
@@ -0,0 +1,62 @@
+//! Mock runtime for unit testing the Doctor Registry pallet.
+
+use crate as pallet_doctor_registry;
+
+use frame_support::derive_impl;
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    //! Test runtime that wires the pallet under test.
+
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    /// System pallet.
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    /// Pallet under test.
+    #[runtime::pallet_index(1)]
+    pub type DoctorRegistry = pallet_doctor_registry::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+impl pallet_doctor_registry::Config for Test {
+    /// Root plays the registrar role in tests.
+    type RegistrarOrigin = EnsureRoot<u64>;
+    type WeightInfo = ();
+}
+
+/// Builds the [`sp_io::TestExternalities`] environment for unit tests.
+///
+/// # Panics
+/// Panics if the genesis storage cannot be built (should not happen in a
+/// correctly configured mock runtime).
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .expect("genesis storage should build")
+        .into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
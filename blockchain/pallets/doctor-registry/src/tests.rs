@@ -0,0 +1,128 @@
+//! Unit tests for the Doctor Registry pallet.
+
+use crate::{mock::*, Crm, DoctorRegistryVerifier, DoctorStatus, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+/// Doctor account used across tests.
+const DOCTOR: u64 = 10;
+
+fn crm(digits: &[u8]) -> Crm {
+    digits.to_vec().try_into().unwrap()
+}
+
+fn apply(who: u64, number: &[u8]) -> frame_support::dispatch::DispatchResult {
+    DoctorRegistry::apply(
+        RuntimeOrigin::signed(who),
+        crm(number),
+        *b"MG",
+        b"Cardiologia".to_vec().try_into().unwrap(),
+        H256::repeat_byte(1),
+    )
+}
+
+/// Ensures the full lifecycle: apply, approve, suspend and reinstate.
+#[test]
+fn registration_lifecycle_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(apply(DOCTOR, b"12345"));
+        System::assert_last_event(
+            Event::DoctorApplied {
+                doctor: DOCTOR,
+                crm: crm(b"12345"),
+                state: *b"MG",
+            }
+            .into(),
+        );
+
+        // A pending application is not an active doctor yet.
+        assert!(!DoctorRegistry::is_active_doctor(&DOCTOR));
+
+        assert_ok!(DoctorRegistry::approve(RuntimeOrigin::root(), DOCTOR));
+        System::assert_last_event(Event::DoctorApproved { doctor: DOCTOR }.into());
+        assert!(DoctorRegistry::is_active_doctor(&DOCTOR));
+
+        assert_ok!(DoctorRegistry::suspend(RuntimeOrigin::root(), DOCTOR));
+        assert_eq!(DoctorRegistry::doctors(DOCTOR).unwrap().status, DoctorStatus::Suspended);
+        assert!(!DoctorRegistry::is_active_doctor(&DOCTOR));
+
+        assert_ok!(DoctorRegistry::reinstate(RuntimeOrigin::root(), DOCTOR));
+        assert!(DoctorRegistry::is_active_doctor(&DOCTOR));
+    });
+}
+
+/// Ensures only the registrar origin can approve.
+#[test]
+fn approve_requires_registrar_origin() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(apply(DOCTOR, b"12345"));
+
+        assert_noop!(
+            DoctorRegistry::approve(RuntimeOrigin::signed(DOCTOR), DOCTOR),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+/// Ensures a CRM is bound to a single account once approved, and that a
+/// pending application does not reserve it.
+#[test]
+fn crm_is_unique_per_state() {
+    new_test_ext().execute_with(|| {
+        // Squatting applications do not block the rightful doctor.
+        assert_ok!(apply(11, b"12345"));
+        assert_ok!(apply(DOCTOR, b"12345"));
+        assert_eq!(DoctorRegistry::crm_owner(*b"MG", crm(b"12345")), None);
+
+        assert_ok!(DoctorRegistry::approve(RuntimeOrigin::root(), DOCTOR));
+        assert_eq!(DoctorRegistry::crm_owner(*b"MG", crm(b"12345")), Some(DOCTOR));
+        assert_noop!(
+            DoctorRegistry::approve(RuntimeOrigin::root(), 11),
+            Error::<Test>::CrmAlreadyInUse
+        );
+        assert_noop!(apply(12, b"12345"), Error::<Test>::CrmAlreadyInUse);
+
+        // Rejecting the squatter leaves the approved doctor's CRM bound.
+        assert_ok!(DoctorRegistry::reject(RuntimeOrigin::root(), 11));
+        assert_eq!(DoctorRegistry::doctors(11), None);
+        assert_eq!(DoctorRegistry::crm_owner(*b"MG", crm(b"12345")), Some(DOCTOR));
+    });
+}
+
+/// Ensures doctors listed in the genesis config start active.
+#[test]
+fn genesis_registers_active_doctors() {
+    use sp_runtime::BuildStorage;
+
+    let storage = crate::GenesisConfig::<Test> {
+        doctors: vec![(DOCTOR, crm(b"12345"), *b"MG", Default::default())],
+    }
+    .build_storage()
+    .unwrap();
+
+    sp_io::TestExternalities::from(storage).execute_with(|| {
+        assert!(DoctorRegistry::is_active_doctor(&DOCTOR));
+        assert_eq!(DoctorRegistry::crm_owner(*b"MG", crm(b"12345")), Some(DOCTOR));
+        assert_noop!(apply(11, b"12345"), Error::<Test>::CrmAlreadyInUse);
+    });
+}
+
+/// Ensures malformed CRM and state codes are rejected.
+#[test]
+fn apply_validates_input() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(apply(DOCTOR, b"12A45"), Error::<Test>::InvalidCrm);
+        assert_noop!(apply(DOCTOR, b""), Error::<Test>::InvalidCrm);
+        assert_noop!(
+            DoctorRegistry::apply(
+                RuntimeOrigin::signed(DOCTOR),
+                crm(b"12345"),
+                *b"mg",
+                Default::default(),
+                H256::zero(),
+            ),
+            Error::<Test>::InvalidState
+        );
+    });
+}
@@ -0,0 +1,54 @@
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+
+/// CRM registration number (digits only, up to 16).
+pub type Crm = BoundedVec<u8, ConstU32<16>>;
+
+/// Brazilian state (UF) that issued the CRM, e.g. `*b"MG"`.
+pub type StateCode = [u8; 2];
+
+/// Medical specialty, as free text (up to 64 bytes).
+pub type Specialty = BoundedVec<u8, ConstU32<64>>;
+
+/// Registration status of a doctor.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum DoctorStatus {
+    /// Application submitted, waiting for the registrar.
+    Pending,
+    /// Approved by the registrar; the doctor may write records and receive grants.
+    Active,
+    /// Suspended by the registrar.
+    Suspended,
+}
+
+/// On-chain profile of a doctor.
+///
+/// The doctor's name is never stored in clear text; only its hash is kept
+/// so the registrar can check it against the CRM off-chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DoctorProfile<Hash, BlockNumber> {
+    /// CRM registration number.
+    pub crm: Crm,
+    /// State (UF) that issued the CRM.
+    pub state: StateCode,
+    /// Medical specialty.
+    pub specialty: Specialty,
+    /// Hash of the doctor's full name.
+    pub name_hash: Hash,
+    /// Current registration status.
+    pub status: DoctorStatus,
+    /// Block in which the application was submitted.
+    pub applied_at: BlockNumber,
+}
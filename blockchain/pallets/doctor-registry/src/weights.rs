@@ -0,0 +1,62 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+// ==========================================================================
+// ⚠️  PLACEHOLDER / MOCK WEIGHTS
+// 
+// TODO: This file is currently manually maintained for MVP development.
+// Before Mainnet release, replace this entire file with the output of:
+// ./target/release/healthchain-node benchmark pallet ...
+// ==========================================================================
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+pub trait WeightInfo {
+    fn apply() -> Weight { Weight::from_parts(10_000, 0) }
+    fn set_status() -> Weight { Weight::from_parts(10_000, 0) }
+    fn approve() -> Weight { Weight::from_parts(10_000, 0) }
+    fn reject() -> Weight { Weight::from_parts(10_000, 0) }
+}
+
+/// Weights for pallet_doctor_registry using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn apply() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 1))
+    }
+    fn set_status() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn approve() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 2))
+    }
+    fn reject() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+    fn apply() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 1))
+    }
+    fn set_status() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn approve() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+    }
+    fn reject() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+}
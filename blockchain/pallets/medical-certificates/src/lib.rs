@@ -0,0 +1,295 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Medical Certificates pallet (HealthChain).
+//!
+//! Registered doctors issue sick-leave certificates ("atestados") with a
+//! start date, a number of days, a hashed CID-10 code and a hash of the
+//! patient's identity. Each certificate gets a short [`VerificationCode`]
+//! that is printed on the paper or PDF handed to the patient.
+//!
+//! ## Public verification
+//! A third party (e.g. an employer) calls [`Pallet::verify`] through the
+//! runtime API with the code. It learns who issued the certificate, the
+//! leave period, the patient hash and whether it was revoked, but never
+//! the diagnosis hash.
+//!
+//! ## Revocation
+//! Only the issuing doctor can revoke a certificate, giving the hash of an
+//! off-chain reason. Revoked certificates are kept so verification reports
+//! them as revoked instead of unknown.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+pub mod types;
+pub use types::*;
+
+use frame_support::traits::Time;
+use pallet_doctor_registry::DoctorRegistryVerifier;
+
+/// Timestamp type of the configured [`Time`] provider.
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+
+/// Certificate type for a runtime.
+pub type CertificateOf<T> = Certificate<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    MomentOf<T>,
+>;
+
+/// Verification result type for a runtime.
+pub type CertificateVerificationOf<T> = CertificateVerification<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    MomentOf<T>,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{pallet_prelude::*, sp_runtime::traits::Hash};
+    use frame_system::pallet_prelude::*;
+
+    /// Main pallet struct.
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Pallet configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Registry used to make sure issuers are licensed doctors.
+        type DoctorRegistry: DoctorRegistryVerifier<Self::AccountId>;
+
+        /// Time provider used to timestamp issuance and revocation.
+        type Time: Time;
+
+        /// Longest leave, in days, a single certificate may grant.
+        #[pallet::constant]
+        type MaxCertificateDays: Get<u16>;
+
+        /// Weight information for extrinsics.
+        type WeightInfo: WeightInfo;
+    }
+
+    // ---------------------------------------------------------------------
+    // Storage
+    // ---------------------------------------------------------------------
+
+    /// Issued certificates: `code -> certificate`.
+    #[pallet::storage]
+    #[pallet::getter(fn certificates)]
+    pub type Certificates<T: Config> =
+        StorageMap<_, Blake2_128Concat, VerificationCode, CertificateOf<T>, OptionQuery>;
+
+    /// Number of certificates issued so far.
+    ///
+    /// Mixed into the verification code so two certificates with the same
+    /// content get different codes.
+    #[pallet::storage]
+    #[pallet::getter(fn certificate_count)]
+    pub type CertificateCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    // ---------------------------------------------------------------------
+    // Events
+    // ---------------------------------------------------------------------
+
+    /// Events emitted by the Medical Certificates pallet.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A doctor issued a certificate.
+        CertificateIssued { doctor: T::AccountId, code: VerificationCode },
+
+        /// The issuing doctor revoked a certificate.
+        CertificateRevoked {
+            doctor: T::AccountId,
+            code: VerificationCode,
+            reason_hash: T::Hash,
+        },
+    }
+
+    // ---------------------------------------------------------------------
+    // Errors
+    // ---------------------------------------------------------------------
+
+    /// Errors returned by the Medical Certificates pallet.
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The caller is not a registered, active doctor.
+        DoctorNotRegistered,
+
+        /// The number of days is zero or above [`Config::MaxCertificateDays`].
+        InvalidDuration,
+
+        /// The derived code is already taken; retry in a later block.
+        CodeCollision,
+
+        /// No certificate has this code.
+        CertificateNotFound,
+
+        /// Only the issuing doctor can revoke a certificate.
+        NotIssuer,
+
+        /// The certificate was already revoked.
+        AlreadyRevoked,
+    }
+
+    // ---------------------------------------------------------------------
+    // Calls (extrinsics)
+    // ---------------------------------------------------------------------
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Issues a sick-leave certificate.
+        ///
+        /// Both hashes should be salted off-chain (`hash(salt ++ value)`):
+        /// CID-10 codes and identity numbers are easy to brute-force.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the doctor.
+        /// - `patient_hash`: Hash of the patient's identity.
+        /// - `cid_hash`: Hash of the CID-10 code of the diagnosis.
+        /// - `start_date`: First day of leave.
+        /// - `days`: Number of days of leave.
+        ///
+        /// # Storage
+        /// - Writes to [`Certificates`], [`CertificateCount`]
+        ///
+        /// # Emits
+        /// - [`Event::CertificateIssued`] with the verification code
+        ///
+        /// # Errors
+        /// - [`Error::DoctorNotRegistered`] if the caller is not an active doctor
+        /// - [`Error::InvalidDuration`] if `days` is out of range
+        /// - [`Error::CodeCollision`] if the derived code is taken
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::issue())]
+        pub fn issue(
+            origin: OriginFor<T>,
+            patient_hash: T::Hash,
+            cid_hash: T::Hash,
+            start_date: MomentOf<T>,
+            days: u16,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&doctor),
+                Error::<T>::DoctorNotRegistered
+            );
+            ensure!(
+                days > 0 && days <= T::MaxCertificateDays::get(),
+                Error::<T>::InvalidDuration
+            );
+
+            let count = CertificateCount::<T>::get();
+            let code = Self::derive_code(count, &doctor, &patient_hash);
+            ensure!(!Certificates::<T>::contains_key(code), Error::<T>::CodeCollision);
+
+            Certificates::<T>::insert(
+                code,
+                Certificate {
+                    doctor: doctor.clone(),
+                    patient_hash,
+                    cid_hash,
+                    start_date,
+                    days,
+                    issued_at: T::Time::now(),
+                    revocation: None,
+                },
+            );
+            CertificateCount::<T>::put(count.saturating_add(1));
+
+            Self::deposit_event(Event::CertificateIssued { doctor, code });
+
+            Ok(())
+        }
+
+        /// Revokes a certificate.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be the doctor that issued the certificate.
+        /// - `code`: Verification code of the certificate.
+        /// - `reason_hash`: Hash of the off-chain reason.
+        ///
+        /// # Storage
+        /// - Writes to [`Certificates`]
+        ///
+        /// # Emits
+        /// - [`Event::CertificateRevoked`]
+        ///
+        /// # Errors
+        /// - [`Error::CertificateNotFound`] if no certificate has this code
+        /// - [`Error::NotIssuer`] if the caller did not issue it
+        /// - [`Error::AlreadyRevoked`] if it was already revoked
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::revoke())]
+        pub fn revoke(
+            origin: OriginFor<T>,
+            code: VerificationCode,
+            reason_hash: T::Hash,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+            let code = normalize_code(&code);
+
+            Certificates::<T>::try_mutate(code, |maybe_certificate| -> DispatchResult {
+                let certificate =
+                    maybe_certificate.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
+                ensure!(certificate.doctor == doctor, Error::<T>::NotIssuer);
+                ensure!(certificate.revocation.is_none(), Error::<T>::AlreadyRevoked);
+
+                certificate.revocation =
+                    Some(Revocation { reason_hash, revoked_at: T::Time::now() });
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::CertificateRevoked { doctor, code, reason_hash });
+
+            Ok(())
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Internal helpers
+    // ---------------------------------------------------------------------
+
+    impl<T: Config> Pallet<T> {
+        /// Derives the verification code of the `count`-th certificate.
+        ///
+        /// The parent block hash makes codes unpredictable before inclusion.
+        fn derive_code(
+            count: u64,
+            doctor: &T::AccountId,
+            patient_hash: &T::Hash,
+        ) -> VerificationCode {
+            let parent_hash = frame_system::Pallet::<T>::parent_hash();
+            let digest = T::Hashing::hash_of(&(count, doctor, patient_hash, parent_hash));
+            verification_code(digest.as_ref())
+        }
+    }
+}
+
+// -------------------------------------------------------------------------
+// Public queries (runtime API)
+// -------------------------------------------------------------------------
+
+impl<T: Config> Pallet<T> {
+    /// Looks a certificate up by its verification code.
+    ///
+    /// The code is normalized first, so `o`, `i` and `l` typed by a person
+    /// still match. The diagnosis hash is left out of the result.
+    pub fn verify(code: VerificationCode) -> Option<CertificateVerificationOf<T>> {
+        let certificate = Certificates::<T>::get(normalize_code(&code))?;
+        let doctor_active = T::DoctorRegistry::is_active_doctor(&certificate.doctor);
+
+        Some(CertificateVerification { doctor_active, ..certificate.into() })
+    }
+}
@@ -0,0 +1,163 @@
+//! Unit tests for the Medical Certificates pallet.
+
+use crate::{
+    mock::*, normalize_code, verification_code, Error, Event, Revocation, VerificationCode,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+
+/// Doctor account used across tests.
+const DOCTOR: u64 = 10;
+
+/// Hash of the patient's identity used across tests.
+fn patient_hash() -> H256 {
+    H256::repeat_byte(1)
+}
+
+/// Hash of the CID-10 code used across tests.
+fn cid_hash() -> H256 {
+    H256::repeat_byte(2)
+}
+
+/// Issues a 3-day certificate from `doctor` and returns its code.
+fn issue(doctor: u64) -> VerificationCode {
+    assert_ok!(MedicalCertificates::issue(
+        RuntimeOrigin::signed(doctor),
+        patient_hash(),
+        cid_hash(),
+        1_000,
+        3,
+    ));
+    match System::events().last().map(|record| record.event.clone()) {
+        Some(RuntimeEvent::MedicalCertificates(Event::CertificateIssued { code, .. })) => code,
+        other => panic!("unexpected last event: {other:?}"),
+    }
+}
+
+/// Ensures a doctor can issue a certificate and a third party can verify it
+/// without learning the diagnosis.
+#[test]
+fn issue_and_verify_works() {
+    new_test_ext().execute_with(|| {
+        Now::set(500);
+        let code = issue(DOCTOR);
+        System::assert_last_event(Event::CertificateIssued { doctor: DOCTOR, code }.into());
+
+        let stored = MedicalCertificates::certificates(code).unwrap();
+        assert_eq!(stored.cid_hash, cid_hash());
+        assert_eq!(MedicalCertificates::certificate_count(), 1);
+
+        let verification = MedicalCertificates::verify(code).unwrap();
+        assert_eq!(verification.doctor, DOCTOR);
+        assert!(verification.doctor_active);
+        assert_eq!(verification.patient_hash, patient_hash());
+        assert_eq!(verification.start_date, 1_000);
+        assert_eq!(verification.days, 3);
+        assert_eq!(verification.issued_at, 500);
+        assert_eq!(verification.revocation, None);
+
+        assert_eq!(MedicalCertificates::verify(*b"0000000000"), None);
+    });
+}
+
+/// Ensures two identical certificates get different codes.
+#[test]
+fn codes_are_unique() {
+    new_test_ext().execute_with(|| {
+        let first = issue(DOCTOR);
+        let second = issue(DOCTOR);
+
+        assert_ne!(first, second);
+        assert_eq!(MedicalCertificates::certificate_count(), 2);
+    });
+}
+
+/// Ensures only active doctors issue certificates, within the allowed days.
+#[test]
+fn issue_validates_doctor_and_duration() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            MedicalCertificates::issue(
+                RuntimeOrigin::signed(UNREGISTERED_DOCTOR),
+                patient_hash(),
+                cid_hash(),
+                1_000,
+                3,
+            ),
+            Error::<Test>::DoctorNotRegistered
+        );
+
+        for days in [0, 31] {
+            assert_noop!(
+                MedicalCertificates::issue(
+                    RuntimeOrigin::signed(DOCTOR),
+                    patient_hash(),
+                    cid_hash(),
+                    1_000,
+                    days,
+                ),
+                Error::<Test>::InvalidDuration
+            );
+        }
+    });
+}
+
+/// Ensures only the issuer can revoke, once, and verification reports it.
+#[test]
+fn revoke_works() {
+    new_test_ext().execute_with(|| {
+        let code = issue(DOCTOR);
+        let reason = H256::repeat_byte(3);
+
+        assert_noop!(
+            MedicalCertificates::revoke(RuntimeOrigin::signed(11), code, reason),
+            Error::<Test>::NotIssuer
+        );
+        assert_noop!(
+            MedicalCertificates::revoke(RuntimeOrigin::signed(DOCTOR), *b"0000000000", reason),
+            Error::<Test>::CertificateNotFound
+        );
+
+        Now::set(700);
+        assert_ok!(MedicalCertificates::revoke(RuntimeOrigin::signed(DOCTOR), code, reason));
+        System::assert_last_event(
+            Event::CertificateRevoked { doctor: DOCTOR, code, reason_hash: reason }.into(),
+        );
+        assert_eq!(
+            MedicalCertificates::verify(code).unwrap().revocation,
+            Some(Revocation { reason_hash: reason, revoked_at: 700 })
+        );
+
+        assert_noop!(
+            MedicalCertificates::revoke(RuntimeOrigin::signed(DOCTOR), code, reason),
+            Error::<Test>::AlreadyRevoked
+        );
+    });
+}
+
+/// Ensures verification flags certificates whose issuer is no longer active.
+#[test]
+fn verify_reports_inactive_issuer() {
+    new_test_ext().execute_with(|| {
+        let code = issue(DOCTOR);
+        Suspended::mutate(|suspended| suspended.push(DOCTOR));
+
+        assert!(!MedicalCertificates::verify(code).unwrap().doctor_active);
+    });
+}
+
+/// Ensures codes use the Crockford alphabet and tolerate typing mistakes.
+#[test]
+fn verification_codes_are_human_friendly() {
+    assert_eq!(verification_code(&[0u8; 32]), *b"0000000000");
+    assert_eq!(verification_code(&[0xff; 32]), *b"ZZZZZZZZZZ");
+    assert_eq!(normalize_code(b"o1il7kxyza"), *b"01117KXYZA");
+
+    new_test_ext().execute_with(|| {
+        let code = issue(DOCTOR);
+        assert!(code.iter().all(|c| !b"ILOU".contains(c)));
+
+        let typed = code.map(|c| c.to_ascii_lowercase());
+        assert_eq!(MedicalCertificates::verify(typed).unwrap().doctor, DOCTOR);
+    });
+}
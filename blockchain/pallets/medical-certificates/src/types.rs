@@ -0,0 +1,108 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// Length of a [`VerificationCode`].
+pub const CODE_LEN: usize = 10;
+
+/// Crockford base32 alphabet: no `I`, `L`, `O` or `U`, so codes survive
+/// being read aloud or typed from paper.
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Short code printed on a certificate, e.g. `*b"7K3QH2M9XA"`.
+///
+/// Uppercase Crockford base32 text, used by third parties to look the
+/// certificate up.
+pub type VerificationCode = [u8; CODE_LEN];
+
+/// Derives a [`VerificationCode`] from the first 50 bits of `digest`.
+pub fn verification_code(digest: &[u8]) -> VerificationCode {
+    let mut bytes = [0u8; 8];
+    let len = digest.len().min(8);
+    bytes[..len].copy_from_slice(&digest[..len]);
+    let bits = u64::from_be_bytes(bytes);
+
+    let mut code = [0u8; CODE_LEN];
+    for (i, c) in code.iter_mut().enumerate() {
+        *c = CODE_ALPHABET[((bits >> (59 - 5 * i)) & 0x1f) as usize];
+    }
+    code
+}
+
+/// Normalizes a code typed by a person: lowercase letters are uppercased,
+/// `O` is read as `0` and `I` / `L` as `1`, as Crockford base32 allows.
+pub fn normalize_code(code: &VerificationCode) -> VerificationCode {
+    code.map(|c| match c.to_ascii_uppercase() {
+        b'O' => b'0',
+        b'I' | b'L' => b'1',
+        other => other,
+    })
+}
+
+/// Revocation of a certificate by its issuing doctor.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Revocation<Hash, Moment> {
+    /// Hash of the off-chain reason for the revocation.
+    pub reason_hash: Hash,
+    /// Timestamp of the revocation.
+    pub revoked_at: Moment,
+}
+
+/// A sick-leave certificate ("atestado") issued by a doctor.
+///
+/// Neither the diagnosis nor the patient is stored in clear text. Both
+/// hashes should be salted (`hash(salt ++ value)`), with the salt kept by
+/// the patient: CID-10 codes and CPF numbers are few enough to brute-force.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Certificate<AccountId, Hash, Moment> {
+    /// The doctor that issued the certificate.
+    pub doctor: AccountId,
+    /// Hash of the patient's identity.
+    pub patient_hash: Hash,
+    /// Hash of the CID-10 code of the diagnosis.
+    pub cid_hash: Hash,
+    /// First day of leave.
+    pub start_date: Moment,
+    /// Number of days of leave.
+    pub days: u16,
+    /// Timestamp of issuance.
+    pub issued_at: Moment,
+    /// Set once the doctor revokes the certificate.
+    pub revocation: Option<Revocation<Hash, Moment>>,
+}
+
+/// What a third party (e.g. an employer) learns when verifying a
+/// certificate: everything but the diagnosis.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CertificateVerification<AccountId, Hash, Moment> {
+    /// The doctor that issued the certificate.
+    pub doctor: AccountId,
+    /// Whether the doctor is still an active, registered doctor.
+    pub doctor_active: bool,
+    /// Hash of the patient's identity, to compare with the bearer's.
+    pub patient_hash: Hash,
+    /// First day of leave.
+    pub start_date: Moment,
+    /// Number of days of leave.
+    pub days: u16,
+    /// Timestamp of issuance.
+    pub issued_at: Moment,
+    /// Set if the certificate was revoked.
+    pub revocation: Option<Revocation<Hash, Moment>>,
+}
+
+impl<AccountId, Hash, Moment> From<Certificate<AccountId, Hash, Moment>>
+    for CertificateVerification<AccountId, Hash, Moment>
+{
+    fn from(certificate: Certificate<AccountId, Hash, Moment>) -> Self {
+        Self {
+            doctor: certificate.doctor,
+            doctor_active: false,
+            patient_hash: certificate.patient_hash,
+            start_date: certificate.start_date,
+            days: certificate.days,
+            issued_at: certificate.issued_at,
+            revocation: certificate.revocation,
+        }
+    }
+}
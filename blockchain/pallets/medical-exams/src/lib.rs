@@ -0,0 +1,751 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Medical Exams pallet (HealthChain).
+//!
+//! A doctor with write access to a patient orders an exam, and the doctor
+//! or the patient designates the laboratory that will perform it. Only
+//! that laboratory, registered by a configurable origin, accepts the order
+//! when it collects the sample, then attaches the result. The result is indexed in the
+//! patient's history through [`LabResultRecorder`], with the laboratory as
+//! its author and the ordering doctor linked to it.
+//!
+//! ## Statuses
+//! `Ordered -> Collected -> Resulted`. Before a result is attached, the
+//! doctor or the patient may cancel the order, and so may the laboratory
+//! holding it once collected. While the order is `Ordered`, the doctor or
+//! the patient may designate another laboratory.
+//!
+//! ## Queries
+//! Orders name the patient, the exam and its result, so every query
+//! exposed by the runtime API takes a [`QueryPayload`] signed by the
+//! reader: [`Pallet::exam_order`] answers the patient, the ordering doctor
+//! and the designated laboratory, and [`Pallet::exam_orders_of_patient`] /
+//! [`Pallet::exam_orders_by_doctor`] list only the reader's own orders.
+//!
+//! ## Redaction
+//! When the patient redacts a result from their history, the pallet, as an
+//! [`OnRecordRedacted`] hook, replaces the result of its order by the
+//! tombstone commitment ([`ExamResult::Redacted`]).
+
+pub use pallet::*;
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+pub mod types;
+pub use types::*;
+
+pub mod migrations;
+
+use alloc::vec::Vec;
+use frame_support::{
+    ensure,
+    sp_runtime::traits::{Saturating, Verify},
+    traits::{Get, Time},
+    weights::Weight,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_doctor_registry::DoctorRegistryVerifier;
+use pallet_medical_history::{ContentType, FileHash, LabResultRecorder, OnRecordRedacted};
+use pallet_medical_permissions::MedicalPermissionsVerifier;
+
+/// Timestamp type of the configured [`Time`] provider.
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+
+/// Exam order type for a runtime.
+pub type ExamOrderOf<T> = ExamOrder<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    MomentOf<T>,
+>;
+
+/// Exam order listing page type for a runtime.
+pub type ExamOrderPageOf<T> = ExamOrderPage<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    MomentOf<T>,
+>;
+
+/// Laboratory type for a runtime.
+pub type LaboratoryOf<T> = Laboratory<<T as frame_system::Config>::Hash, BlockNumberFor<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{pallet_prelude::*, sp_runtime::traits::IdentifyAccount};
+    use frame_system::pallet_prelude::*;
+    use pallet_medical_history::{RecordCategory, RecordMetadata};
+
+    /// Current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+    /// Main pallet struct.
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
+    /// Pallet configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Registry used to make sure ordering doctors are licensed.
+        type DoctorRegistry: DoctorRegistryVerifier<Self::AccountId>;
+
+        /// Permissions verifier used to authorize ordering doctors.
+        type Permissions: MedicalPermissionsVerifier<Self::AccountId>;
+
+        /// Medical history that indexes the results.
+        type History: LabResultRecorder<Self::AccountId, MomentOf<Self>>;
+
+        /// Time provider used to timestamp orders and collections.
+        type Time: Time;
+
+        /// Origin allowed to register and remove laboratories.
+        type LaboratoryRegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Signature used by readers to sign queries.
+        type Signature: Verify<Signer = Self::Signer> + Parameter;
+
+        /// Public key type matching [`Config::Signature`].
+        type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Maximum number of blocks a signed query stays valid.
+        #[pallet::constant]
+        type MaxQueryValidity: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of versions a single redaction of the medical
+        /// history reports (the length of its version chains).
+        #[pallet::constant]
+        type MaxRedactedVersions: Get<u32>;
+
+        /// Weight information for extrinsics.
+        type WeightInfo: WeightInfo;
+    }
+
+    // ---------------------------------------------------------------------
+    // Storage
+    // ---------------------------------------------------------------------
+
+    /// Registered laboratories: `laboratory -> profile`.
+    #[pallet::storage]
+    #[pallet::getter(fn laboratories)]
+    pub type Laboratories<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, LaboratoryOf<T>, OptionQuery>;
+
+    /// Exam orders: `id -> order`.
+    #[pallet::storage]
+    #[pallet::getter(fn exam_orders)]
+    pub type ExamOrders<T: Config> =
+        StorageMap<_, Twox64Concat, ExamOrderId, ExamOrderOf<T>, OptionQuery>;
+
+    /// Id of the next exam order.
+    #[pallet::storage]
+    #[pallet::getter(fn next_exam_order_id)]
+    pub type NextExamOrderId<T: Config> = StorageValue<_, ExamOrderId, ValueQuery>;
+
+    /// Exam orders of each patient: `(patient, id) -> ()`.
+    ///
+    /// Keyed by [`ExamOrderKey`], so iterating a patient's prefix walks
+    /// their orders oldest first.
+    #[pallet::storage]
+    pub type PatientExamOrders<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Identity,
+        ExamOrderKey,
+        (),
+        OptionQuery,
+    >;
+
+    /// Exam orders placed by each doctor: `(doctor, id) -> ()`.
+    ///
+    /// Same ordering as [`PatientExamOrders`].
+    #[pallet::storage]
+    pub type DoctorExamOrders<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Doctor
+        Identity,
+        ExamOrderKey,
+        (),
+        OptionQuery,
+    >;
+
+    /// Resulted orders by result: `(patient, file_hash) -> id`, so that a
+    /// redaction of the result finds its order.
+    #[pallet::storage]
+    pub type ResultOrders<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Blake2_128Concat,
+        FileHash,
+        ExamOrderId,
+        OptionQuery,
+    >;
+
+    // ---------------------------------------------------------------------
+    // Events
+    // ---------------------------------------------------------------------
+
+    /// Events emitted by the Medical Exams pallet.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The registrar registered a laboratory.
+        LaboratoryRegistered { laboratory: T::AccountId },
+
+        /// The registrar removed a laboratory.
+        LaboratoryRemoved { laboratory: T::AccountId },
+
+        /// A doctor ordered an exam.
+        ExamOrdered {
+            doctor: T::AccountId,
+            patient: T::AccountId,
+            order: ExamOrderId,
+        },
+
+        /// The designated laboratory accepted an order and collected the
+        /// sample.
+        ExamCollected { laboratory: T::AccountId, order: ExamOrderId },
+
+        /// A laboratory attached the result to the patient's history.
+        ///
+        /// `hash` is the file hash of the record created for the result.
+        ExamResulted {
+            laboratory: T::AccountId,
+            order: ExamOrderId,
+            hash: FileHash,
+        },
+
+        /// An order was cancelled before a result was attached.
+        ///
+        /// `cancelled_by` is the doctor, the patient or the laboratory that
+        /// collected the sample.
+        ExamCancelled { order: ExamOrderId, cancelled_by: T::AccountId },
+
+        /// The doctor or the patient designated the laboratory that may
+        /// collect an order, replacing any previous designation.
+        LaboratoryDesignated {
+            order: ExamOrderId,
+            laboratory: T::AccountId,
+            designated_by: T::AccountId,
+        },
+    }
+
+    // ---------------------------------------------------------------------
+    // Errors
+    // ---------------------------------------------------------------------
+
+    /// Errors returned by the Medical Exams pallet.
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The caller is not a registered, active doctor.
+        DoctorNotRegistered,
+
+        /// The doctor does not have write permission for this patient.
+        NoPermission,
+
+        /// The account is already a registered laboratory.
+        AlreadyLaboratory,
+
+        /// The account is not a registered laboratory.
+        NotLaboratory,
+
+        /// No exam order has this id.
+        OrderNotFound,
+
+        /// The order is not in the status required by this call.
+        InvalidStatus,
+
+        /// The caller may not act on this order.
+        NotAuthorized,
+
+        /// The caller is not the laboratory designated for this order.
+        LaboratoryNotDesignated,
+    }
+
+    // ---------------------------------------------------------------------
+    // Calls (extrinsics)
+    // ---------------------------------------------------------------------
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Registers a laboratory.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::LaboratoryRegistrarOrigin`].
+        /// - `laboratory`: The laboratory account.
+        /// - `license_hash`: Hash of the laboratory's license.
+        ///
+        /// # Storage
+        /// - Writes to [`Laboratories`]
+        ///
+        /// # Emits
+        /// - [`Event::LaboratoryRegistered`]
+        ///
+        /// # Errors
+        /// - [`Error::AlreadyLaboratory`] if the account is already registered
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::register_laboratory())]
+        pub fn register_laboratory(
+            origin: OriginFor<T>,
+            laboratory: T::AccountId,
+            license_hash: T::Hash,
+        ) -> DispatchResult {
+            T::LaboratoryRegistrarOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !Laboratories::<T>::contains_key(&laboratory),
+                Error::<T>::AlreadyLaboratory
+            );
+
+            Laboratories::<T>::insert(
+                &laboratory,
+                Laboratory {
+                    license_hash,
+                    registered_at: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::LaboratoryRegistered { laboratory });
+
+            Ok(())
+        }
+
+        /// Removes a laboratory. Orders it already holds can still be
+        /// cancelled, but no longer resulted.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::LaboratoryRegistrarOrigin`].
+        /// - `laboratory`: The laboratory account.
+        ///
+        /// # Storage
+        /// - Writes to [`Laboratories`]
+        ///
+        /// # Emits
+        /// - [`Event::LaboratoryRemoved`]
+        ///
+        /// # Errors
+        /// - [`Error::NotLaboratory`] if the account is not registered
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::remove_laboratory())]
+        pub fn remove_laboratory(origin: OriginFor<T>, laboratory: T::AccountId) -> DispatchResult {
+            T::LaboratoryRegistrarOrigin::ensure_origin(origin)?;
+
+            ensure!(Laboratories::<T>::contains_key(&laboratory), Error::<T>::NotLaboratory);
+            Laboratories::<T>::remove(&laboratory);
+
+            Self::deposit_event(Event::LaboratoryRemoved { laboratory });
+
+            Ok(())
+        }
+
+        /// Orders an exam for `patient`.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the doctor.
+        /// - `patient`: The patient.
+        /// - `exam_hash`: Hash of the exam request.
+        ///
+        /// # Authorization
+        /// Requires `T::Permissions::can_write(patient, doctor) == true`.
+        ///
+        /// # Storage
+        /// - Writes to [`ExamOrders`], [`NextExamOrderId`],
+        ///   [`PatientExamOrders`], [`DoctorExamOrders`]
+        ///
+        /// # Emits
+        /// - [`Event::ExamOrdered`]
+        ///
+        /// # Errors
+        /// - [`Error::DoctorNotRegistered`] if the caller is not an active doctor
+        /// - [`Error::NoPermission`] if the doctor lacks write permission
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::order_exam())]
+        pub fn order_exam(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            exam_hash: T::Hash,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&doctor),
+                Error::<T>::DoctorNotRegistered
+            );
+            ensure!(
+                patient != doctor && T::Permissions::can_write(&patient, &doctor),
+                Error::<T>::NoPermission
+            );
+
+            let order = NextExamOrderId::<T>::get();
+            ExamOrders::<T>::insert(
+                order,
+                ExamOrder {
+                    doctor: doctor.clone(),
+                    patient: patient.clone(),
+                    exam_hash,
+                    status: ExamStatus::Ordered,
+                    ordered_at: T::Time::now(),
+                    laboratory: None,
+                    collected_at: None,
+                    result: None,
+                },
+            );
+            NextExamOrderId::<T>::put(order.saturating_add(1));
+            PatientExamOrders::<T>::insert(&patient, ExamOrderKey::from(order), ());
+            DoctorExamOrders::<T>::insert(&doctor, ExamOrderKey::from(order), ());
+
+            Self::deposit_event(Event::ExamOrdered { doctor, patient, order });
+
+            Ok(())
+        }
+
+        /// Accepts an order and records the sample collection.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be the registered laboratory designated for the
+        ///   order through [`Pallet::designate_laboratory`].
+        /// - `order`: The exam order.
+        ///
+        /// # Storage
+        /// - Writes to [`ExamOrders`]
+        ///
+        /// # Emits
+        /// - [`Event::ExamCollected`]
+        ///
+        /// # Errors
+        /// - [`Error::NotLaboratory`] if the caller is not a registered laboratory
+        /// - [`Error::OrderNotFound`] if the order does not exist
+        /// - [`Error::InvalidStatus`] if the order is not `Ordered`
+        /// - [`Error::LaboratoryNotDesignated`] if no laboratory or another
+        ///   laboratory is designated for the order
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::collect())]
+        pub fn collect(origin: OriginFor<T>, order: ExamOrderId) -> DispatchResult {
+            let laboratory = ensure_signed(origin)?;
+
+            ensure!(Laboratories::<T>::contains_key(&laboratory), Error::<T>::NotLaboratory);
+
+            ExamOrders::<T>::try_mutate(order, |maybe_order| -> DispatchResult {
+                let entry = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+                ensure!(entry.status == ExamStatus::Ordered, Error::<T>::InvalidStatus);
+                ensure!(
+                    entry.laboratory.as_ref() == Some(&laboratory),
+                    Error::<T>::LaboratoryNotDesignated
+                );
+
+                entry.status = ExamStatus::Collected;
+                entry.collected_at = Some(T::Time::now());
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ExamCollected { laboratory, order });
+
+            Ok(())
+        }
+
+        /// Attaches the result of a collected order to the patient's history.
+        ///
+        /// The record is created with category `Exam`, the collection time
+        /// as its clinical date and the laboratory as its author.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be the laboratory that collected the sample.
+        /// - `order`: The exam order.
+        /// - `file_hash`: Content identifier of the result file.
+        /// - `content_type`: MIME type of the result file.
+        ///
+        /// # Storage
+        /// - Writes to [`ExamOrders`], [`ResultOrders`] and, through
+        ///   [`Config::History`], the patient's history
+        ///
+        /// # Emits
+        /// - [`Event::ExamResulted`]
+        ///
+        /// # Errors
+        /// - [`Error::NotLaboratory`] if the caller is not a registered laboratory
+        /// - [`Error::OrderNotFound`] if the order does not exist
+        /// - [`Error::InvalidStatus`] if the order is not `Collected`
+        /// - [`Error::NotAuthorized`] if another laboratory collected the sample
+        /// - any error of [`LabResultRecorder::record_lab_result`]
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::submit_result())]
+        pub fn submit_result(
+            origin: OriginFor<T>,
+            order: ExamOrderId,
+            file_hash: FileHash,
+            content_type: ContentType,
+        ) -> DispatchResult {
+            let laboratory = ensure_signed(origin)?;
+
+            ensure!(Laboratories::<T>::contains_key(&laboratory), Error::<T>::NotLaboratory);
+
+            let mut entry = ExamOrders::<T>::get(order).ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(entry.status == ExamStatus::Collected, Error::<T>::InvalidStatus);
+            ensure!(entry.laboratory.as_ref() == Some(&laboratory), Error::<T>::NotAuthorized);
+
+            let metadata = RecordMetadata {
+                category: RecordCategory::Exam,
+                content_type,
+                clinical_date: entry.collected_at.unwrap_or(entry.ordered_at),
+                title_hash: None,
+            };
+            T::History::record_lab_result(
+                &entry.patient,
+                &laboratory,
+                &entry.doctor,
+                file_hash.clone(),
+                metadata,
+            )?;
+
+            ResultOrders::<T>::insert(&entry.patient, &file_hash, order);
+            entry.status = ExamStatus::Resulted;
+            entry.result = Some(ExamResult::File(file_hash.clone()));
+            ExamOrders::<T>::insert(order, entry);
+
+            Self::deposit_event(Event::ExamResulted { laboratory, order, hash: file_hash });
+
+            Ok(())
+        }
+
+        /// Cancels an order before its result is attached.
+        ///
+        /// # Parameters
+        /// - `origin`: The ordering doctor, the patient, or the laboratory
+        ///   that collected the sample.
+        /// - `order`: The exam order.
+        ///
+        /// # Storage
+        /// - Writes to [`ExamOrders`]
+        ///
+        /// # Emits
+        /// - [`Event::ExamCancelled`]
+        ///
+        /// # Errors
+        /// - [`Error::OrderNotFound`] if the order does not exist
+        /// - [`Error::InvalidStatus`] if the order is resulted or cancelled
+        /// - [`Error::NotAuthorized`] if the caller may not cancel it
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::cancel())]
+        pub fn cancel(origin: OriginFor<T>, order: ExamOrderId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ExamOrders::<T>::try_mutate(order, |maybe_order| -> DispatchResult {
+                let entry = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+                ensure!(
+                    matches!(entry.status, ExamStatus::Ordered | ExamStatus::Collected),
+                    Error::<T>::InvalidStatus
+                );
+                let collected_by_caller = entry.status == ExamStatus::Collected &&
+                    entry.laboratory.as_ref() == Some(&who);
+                ensure!(
+                    who == entry.doctor || who == entry.patient || collected_by_caller,
+                    Error::<T>::NotAuthorized
+                );
+
+                entry.status = ExamStatus::Cancelled;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ExamCancelled { order, cancelled_by: who });
+
+            Ok(())
+        }
+
+        /// Designates the laboratory that may collect an order.
+        ///
+        /// The designation can be changed until the sample is collected, so
+        /// the patient can pick a laboratory other than the one suggested by
+        /// the doctor.
+        ///
+        /// # Parameters
+        /// - `origin`: The ordering doctor or the patient.
+        /// - `order`: The exam order.
+        /// - `laboratory`: A registered laboratory.
+        ///
+        /// # Storage
+        /// - Writes to [`ExamOrders`]
+        ///
+        /// # Emits
+        /// - [`Event::LaboratoryDesignated`]
+        ///
+        /// # Errors
+        /// - [`Error::NotLaboratory`] if `laboratory` is not registered
+        /// - [`Error::OrderNotFound`] if the order does not exist
+        /// - [`Error::InvalidStatus`] if the order is not `Ordered`
+        /// - [`Error::NotAuthorized`] if the caller is neither the doctor
+        ///   nor the patient
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::designate_laboratory())]
+        pub fn designate_laboratory(
+            origin: OriginFor<T>,
+            order: ExamOrderId,
+            laboratory: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(Laboratories::<T>::contains_key(&laboratory), Error::<T>::NotLaboratory);
+
+            ExamOrders::<T>::try_mutate(order, |maybe_order| -> DispatchResult {
+                let entry = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+                ensure!(entry.status == ExamStatus::Ordered, Error::<T>::InvalidStatus);
+                ensure!(who == entry.doctor || who == entry.patient, Error::<T>::NotAuthorized);
+
+                entry.laboratory = Some(laboratory.clone());
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::LaboratoryDesignated {
+                order,
+                laboratory,
+                designated_by: who,
+            });
+
+            Ok(())
+        }
+    }
+}
+
+// -------------------------------------------------------------------------
+// Public queries (runtime API)
+// -------------------------------------------------------------------------
+
+impl<T: Config> Pallet<T> {
+    /// Returns the exam order `id`, if any, to its patient, its ordering
+    /// doctor or its designated laboratory.
+    pub fn exam_order(
+        payload: QueryPayload<T::AccountId, BlockNumberFor<T>>,
+        signature: T::Signature,
+        id: ExamOrderId,
+    ) -> Result<Option<ExamOrderOf<T>>, QueryError> {
+        Self::verify_query(&payload, &signature)?;
+        let reader = &payload.reader;
+
+        let Some(entry) = ExamOrders::<T>::get(id) else {
+            return Ok(None);
+        };
+        ensure!(
+            *reader == entry.patient ||
+                *reader == entry.doctor ||
+                entry.laboratory.as_ref() == Some(reader),
+            QueryError::AccessDenied
+        );
+
+        Ok(Some(entry))
+    }
+
+    /// Returns a page of the exam orders of the patient `payload.reader`,
+    /// oldest first, walking at most `limit` entries of
+    /// [`PatientExamOrders`] after the order `after` (`None` for the first
+    /// page).
+    pub fn exam_orders_of_patient(
+        payload: QueryPayload<T::AccountId, BlockNumberFor<T>>,
+        signature: T::Signature,
+        after: Option<ExamOrderId>,
+        limit: u32,
+    ) -> Result<ExamOrderPageOf<T>, QueryError> {
+        Self::verify_query(&payload, &signature)?;
+        let reader = &payload.reader;
+
+        let ids = match after {
+            Some(after) => PatientExamOrders::<T>::iter_key_prefix_from(
+                reader,
+                PatientExamOrders::<T>::hashed_key_for(reader, ExamOrderKey::from(after)),
+            ),
+            None => PatientExamOrders::<T>::iter_key_prefix(reader),
+        };
+
+        Ok(Self::page(ids, limit))
+    }
+
+    /// Returns a page of the exam orders placed by the doctor
+    /// `payload.reader`, oldest first, walking at most `limit` entries of
+    /// [`DoctorExamOrders`] after the order `after`.
+    pub fn exam_orders_by_doctor(
+        payload: QueryPayload<T::AccountId, BlockNumberFor<T>>,
+        signature: T::Signature,
+        after: Option<ExamOrderId>,
+        limit: u32,
+    ) -> Result<ExamOrderPageOf<T>, QueryError> {
+        Self::verify_query(&payload, &signature)?;
+        let reader = &payload.reader;
+
+        let ids = match after {
+            Some(after) => DoctorExamOrders::<T>::iter_key_prefix_from(
+                reader,
+                DoctorExamOrders::<T>::hashed_key_for(reader, ExamOrderKey::from(after)),
+            ),
+            None => DoctorExamOrders::<T>::iter_key_prefix(reader),
+        };
+
+        Ok(Self::page(ids, limit))
+    }
+
+    /// Checks that a signed query is still valid, not valid for longer than
+    /// [`Config::MaxQueryValidity`], and signed by its reader.
+    fn verify_query(
+        payload: &QueryPayload<T::AccountId, BlockNumberFor<T>>,
+        signature: &T::Signature,
+    ) -> Result<(), QueryError> {
+        let now = frame_system::Pallet::<T>::block_number();
+        ensure!(payload.valid_until >= now, QueryError::Expired);
+        ensure!(
+            payload.valid_until <= now.saturating_add(T::MaxQueryValidity::get()),
+            QueryError::ValidityTooLong
+        );
+        ensure!(
+            signature.verify(&payload.signing_message()[..], &payload.reader),
+            QueryError::BadSignature
+        );
+
+        Ok(())
+    }
+
+    /// Walks up to `limit` of the exam order `keys` and returns the orders,
+    /// with the id to resume from if keys remain.
+    fn page(keys: impl Iterator<Item = ExamOrderKey>, limit: u32) -> ExamOrderPageOf<T> {
+        let mut keys = keys.peekable();
+        let mut items = Vec::new();
+        let mut last = None;
+
+        for id in keys.by_ref().take(limit as usize).map(ExamOrderId::from) {
+            last = Some(id);
+            items.extend(ExamOrders::<T>::get(id).map(|entry| (id, entry)));
+        }
+
+        let next = keys.peek().and(last);
+        ExamOrderPage { items, next }
+    }
+}
+
+impl<T: Config> OnRecordRedacted<T::AccountId, T::Hash> for Pallet<T> {
+    /// Replaces the results of `patient`'s orders that were redacted by
+    /// their tombstone commitments.
+    fn on_record_redacted(patient: &T::AccountId, redacted: &[(FileHash, T::Hash)]) {
+        for (file_hash, commitment) in redacted {
+            let Some(order) = ResultOrders::<T>::take(patient, file_hash) else {
+                continue;
+            };
+            ExamOrders::<T>::mutate(order, |maybe_order| {
+                if let Some(entry) = maybe_order {
+                    entry.result = Some(ExamResult::Redacted(*commitment));
+                }
+            });
+        }
+    }
+
+    fn weight() -> Weight {
+        let versions = T::MaxRedactedVersions::get() as u64;
+        T::DbWeight::get().reads_writes(versions.saturating_mul(2), versions.saturating_mul(2))
+    }
+}
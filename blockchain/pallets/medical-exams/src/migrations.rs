@@ -0,0 +1,122 @@
+//! Storage migrations for the Medical Exams pallet.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+    weights::Weight,
+};
+
+/// Migration from storage version `0` to `1`.
+///
+/// Version `1` stores the result of an [`ExamOrder`] as an [`ExamResult`],
+/// so that redacted results can be scrubbed from their orders, and indexes
+/// resulted orders in [`ResultOrders`]. Existing results become
+/// [`ExamResult::File`].
+pub mod v1 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`MigrateV0ToV1`] instead.
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+            let mut indexed: u64 = 0;
+
+            ExamOrders::<T>::translate::<ExamOrderV0<T::AccountId, T::Hash, MomentOf<T>>, _>(
+                |order, old| {
+                    translated = translated.saturating_add(1);
+                    if let Some(file_hash) = &old.result {
+                        indexed = indexed.saturating_add(1);
+                        ResultOrders::<T>::insert(&old.patient, file_hash, order);
+                    }
+                    Some(ExamOrder {
+                        doctor: old.doctor,
+                        patient: old.patient,
+                        exam_hash: old.exam_hash,
+                        status: old.status,
+                        ordered_at: old.ordered_at,
+                        laboratory: old.laboratory,
+                        collected_at: old.collected_at,
+                        result: old.result.map(ExamResult::File),
+                    })
+                },
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated.saturating_add(indexed))
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `0`.
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `1` to `2`.
+///
+/// Version `1` keyed [`PatientExamOrders`] and [`DoctorExamOrders`] by
+/// [`ExamOrderId`] under `Twox64Concat`, so a listing had to collect and
+/// sort every order of the account. Version `2` keys them by
+/// [`ExamOrderKey`] under `Identity`, so a prefix iteration is already in
+/// id order and listings page from an `after` key.
+pub mod v2 {
+    use super::*;
+    use frame_support::{storage_alias, Blake2_128Concat, Twox64Concat};
+
+    /// [`pallet::PatientExamOrders`] as stored in version `1`.
+    #[storage_alias]
+    type PatientExamOrders<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Twox64Concat,
+        ExamOrderId,
+        (),
+    >;
+
+    /// [`pallet::DoctorExamOrders`] as stored in version `1`.
+    #[storage_alias]
+    type DoctorExamOrders<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Twox64Concat,
+        ExamOrderId,
+        (),
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV1ToV2`] instead.
+    pub struct InnerMigrateV1ToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let patients: Vec<_> = PatientExamOrders::<T>::drain().collect();
+            let doctors: Vec<_> = DoctorExamOrders::<T>::drain().collect();
+
+            for (patient, id, ()) in patients.iter() {
+                pallet::PatientExamOrders::<T>::insert(patient, ExamOrderKey::from(*id), ());
+            }
+            for (doctor, id, ()) in doctors.iter() {
+                pallet::DoctorExamOrders::<T>::insert(doctor, ExamOrderKey::from(*id), ());
+            }
+
+            let moved = (patients.len() as u64).saturating_add(doctors.len() as u64);
+            T::DbWeight::get().reads_writes(moved, moved.saturating_mul(2))
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `1`.
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,142 @@
+//! Mock runtime for unit testing the Medical Exams pallet.
+
+use crate as pallet_medical_exams;
+
+use frame_support::{
+    derive_impl, dispatch::DispatchResult, parameter_types,
+    traits::{ConstU32, ConstU64, Time},
+};
+use frame_system::EnsureRoot;
+use pallet_doctor_registry::DoctorRegistryVerifier;
+use pallet_medical_history::{FileHash, LabResultRecorder, RecordMetadata};
+use pallet_medical_permissions::MedicalPermissionsVerifier;
+use sp_runtime::{
+    testing::{TestSignature, UintAuthorityId},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// Account that is not a registered doctor in [`MockDoctorRegistry`].
+pub const UNREGISTERED_DOCTOR: u64 = 99;
+
+/// The only doctor with write access in [`MockPermissions`].
+pub const AUTHORIZED_DOCTOR: u64 = 10;
+
+/// Mock doctor registry: every account except [`UNREGISTERED_DOCTOR`] is an
+/// active doctor.
+pub struct MockDoctorRegistry;
+
+impl DoctorRegistryVerifier<u64> for MockDoctorRegistry {
+    fn is_active_doctor(who: &u64) -> bool {
+        *who != UNREGISTERED_DOCTOR
+    }
+}
+
+/// Mock permissions: only [`AUTHORIZED_DOCTOR`] has access, to every patient.
+pub struct MockPermissions;
+
+impl MedicalPermissionsVerifier<u64> for MockPermissions {
+    fn has_access(_patient: &u64, doctor: &u64) -> bool {
+        *doctor == AUTHORIZED_DOCTOR
+    }
+}
+
+parameter_types! {
+    /// Timestamp returned by [`MockTime`]; tests set it explicitly.
+    pub static Now: u64 = 0;
+
+    /// `(patient, laboratory, ordered_by, file_hash, metadata)` recorded by
+    /// [`MockHistory`].
+    pub static LabResults: Vec<(u64, u64, u64, FileHash, RecordMetadata<u64>)> = Vec::new();
+}
+
+/// Mock time provider backed by the [`Now`] static.
+pub struct MockTime;
+
+impl Time for MockTime {
+    type Moment = u64;
+
+    fn now() -> u64 {
+        Now::get()
+    }
+}
+
+/// Records every result in [`LabResults`].
+pub struct MockHistory;
+
+impl LabResultRecorder<u64, u64> for MockHistory {
+    fn record_lab_result(
+        patient: &u64,
+        laboratory: &u64,
+        ordered_by: &u64,
+        file_hash: FileHash,
+        metadata: RecordMetadata<u64>,
+    ) -> DispatchResult {
+        LabResults::mutate(|results| {
+            results.push((*patient, *laboratory, *ordered_by, file_hash, metadata))
+        });
+        Ok(())
+    }
+}
+
+#[frame_support::runtime]
+mod runtime {
+    //! Test runtime that wires the pallet under test.
+
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    /// System pallet.
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    /// Pallet under test.
+    #[runtime::pallet_index(1)]
+    pub type MedicalExams = pallet_medical_exams::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+impl pallet_medical_exams::Config for Test {
+    type DoctorRegistry = MockDoctorRegistry;
+    type Permissions = MockPermissions;
+    type History = MockHistory;
+    type Time = MockTime;
+    /// Root plays the registrar role in tests.
+    type LaboratoryRegistrarOrigin = EnsureRoot<u64>;
+    type Signature = TestSignature;
+    type Signer = UintAuthorityId;
+    type MaxQueryValidity = ConstU64<10>;
+    type MaxRedactedVersions = ConstU32<2>;
+    type WeightInfo = ();
+}
+
+/// Builds the [`sp_io::TestExternalities`] environment for unit tests.
+///
+/// # Panics
+/// Panics if the genesis storage cannot be built (should not happen in a
+/// correctly configured mock runtime).
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .expect("genesis storage should build")
+        .into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
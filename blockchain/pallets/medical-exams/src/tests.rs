@@ -0,0 +1,467 @@
+//! Unit tests for the Medical Exams pallet.
+
+use crate::{mock::*, Error, Event, ExamResult, ExamStatus, QueryError, QueryPayload};
+use frame_support::{assert_noop, assert_ok};
+use pallet_medical_history::{ContentType, FileHash, OnRecordRedacted, RecordCategory};
+use sp_core::H256;
+use sp_runtime::{testing::TestSignature, DispatchError};
+
+/// Patient account used across tests.
+const PATIENT: u64 = 1;
+
+/// Laboratory account used across tests.
+const LABORATORY: u64 = 30;
+
+/// Another registered laboratory.
+const OTHER_LABORATORY: u64 = 31;
+
+fn file_hash() -> FileHash {
+    [7u8; 32].to_vec().try_into().unwrap()
+}
+
+fn pdf() -> ContentType {
+    b"application/pdf".to_vec().try_into().unwrap()
+}
+
+/// Orders an exam for [`PATIENT`] and designates [`LABORATORY`] for it,
+/// returning its id.
+fn order_exam() -> u64 {
+    let order = MedicalExams::next_exam_order_id();
+    assert_ok!(MedicalExams::order_exam(
+        RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+        PATIENT,
+        H256::repeat_byte(1),
+    ));
+    assert_ok!(MedicalExams::designate_laboratory(
+        RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+        order,
+        LABORATORY,
+    ));
+    order
+}
+
+/// Query by `reader`, valid until block 5, with its signature.
+fn query(reader: u64) -> (QueryPayload<u64, u64>, TestSignature) {
+    let payload = QueryPayload { reader, valid_until: 5 };
+    let signature = TestSignature(reader, payload.signing_message());
+    (payload, signature)
+}
+
+/// Registers both laboratories and orders an exam, returning its id.
+fn setup() -> u64 {
+    for laboratory in [LABORATORY, OTHER_LABORATORY] {
+        assert_ok!(MedicalExams::register_laboratory(
+            RuntimeOrigin::root(),
+            laboratory,
+            H256::repeat_byte(9),
+        ));
+    }
+    order_exam()
+}
+
+/// Ensures the full flow: order, collection and result indexed in the
+/// patient's history with the laboratory as author.
+#[test]
+fn exam_flow_works() {
+    new_test_ext().execute_with(|| {
+        Now::set(100);
+        let order = setup();
+        System::assert_has_event(
+            Event::ExamOrdered { doctor: AUTHORIZED_DOCTOR, patient: PATIENT, order }.into(),
+        );
+        System::assert_last_event(
+            Event::LaboratoryDesignated {
+                order,
+                laboratory: LABORATORY,
+                designated_by: AUTHORIZED_DOCTOR,
+            }
+            .into(),
+        );
+        assert_eq!(MedicalExams::exam_orders(order).unwrap().status, ExamStatus::Ordered);
+
+        Now::set(200);
+        assert_ok!(MedicalExams::collect(RuntimeOrigin::signed(LABORATORY), order));
+        System::assert_last_event(Event::ExamCollected { laboratory: LABORATORY, order }.into());
+
+        Now::set(300);
+        assert_ok!(MedicalExams::submit_result(
+            RuntimeOrigin::signed(LABORATORY),
+            order,
+            file_hash(),
+            pdf(),
+        ));
+        System::assert_last_event(
+            Event::ExamResulted { laboratory: LABORATORY, order, hash: file_hash() }.into(),
+        );
+
+        let entry = MedicalExams::exam_orders(order).unwrap();
+        assert_eq!(entry.status, ExamStatus::Resulted);
+        assert_eq!(entry.laboratory, Some(LABORATORY));
+        assert_eq!(entry.collected_at, Some(200));
+        assert_eq!(entry.result, Some(ExamResult::File(file_hash())));
+
+        let results = LabResults::get();
+        assert_eq!(results.len(), 1);
+        let (patient, laboratory, ordered_by, hash, metadata) = results[0].clone();
+        assert_eq!((patient, laboratory, ordered_by), (PATIENT, LABORATORY, AUTHORIZED_DOCTOR));
+        assert_eq!(hash, file_hash());
+        assert_eq!(metadata.category, RecordCategory::Exam);
+        assert_eq!(metadata.clinical_date, 200);
+
+        let (payload, signature) = query(PATIENT);
+        let of_patient =
+            MedicalExams::exam_orders_of_patient(payload, signature, None, 10).unwrap();
+        assert_eq!(of_patient.items, vec![(order, entry)]);
+        let (payload, signature) = query(AUTHORIZED_DOCTOR);
+        let by_doctor = MedicalExams::exam_orders_by_doctor(payload, signature, None, 10).unwrap();
+        assert_eq!(by_doctor.items[0].0, order);
+    });
+}
+
+/// Ensures only active doctors with write access order exams.
+#[test]
+fn order_exam_requires_write_access() {
+    new_test_ext().execute_with(|| {
+        let order = |doctor: u64, patient: u64| {
+            MedicalExams::order_exam(RuntimeOrigin::signed(doctor), patient, H256::zero())
+        };
+
+        assert_noop!(order(UNREGISTERED_DOCTOR, PATIENT), Error::<Test>::DoctorNotRegistered);
+        assert_noop!(order(11, PATIENT), Error::<Test>::NoPermission);
+        assert_noop!(order(AUTHORIZED_DOCTOR, AUTHORIZED_DOCTOR), Error::<Test>::NoPermission);
+    });
+}
+
+/// Ensures statuses move forward only, and only the collecting laboratory
+/// attaches the result.
+#[test]
+fn transitions_are_enforced() {
+    new_test_ext().execute_with(|| {
+        let order = setup();
+        let submit = |laboratory: u64| {
+            MedicalExams::submit_result(
+                RuntimeOrigin::signed(laboratory),
+                order,
+                file_hash(),
+                pdf(),
+            )
+        };
+
+        assert_noop!(
+            MedicalExams::collect(RuntimeOrigin::signed(AUTHORIZED_DOCTOR), order),
+            Error::<Test>::NotLaboratory
+        );
+        assert_noop!(
+            MedicalExams::collect(RuntimeOrigin::signed(LABORATORY), order + 1),
+            Error::<Test>::OrderNotFound
+        );
+        assert_noop!(submit(LABORATORY), Error::<Test>::InvalidStatus);
+
+        assert_ok!(MedicalExams::collect(RuntimeOrigin::signed(LABORATORY), order));
+        assert_noop!(
+            MedicalExams::collect(RuntimeOrigin::signed(OTHER_LABORATORY), order),
+            Error::<Test>::InvalidStatus
+        );
+        assert_noop!(submit(OTHER_LABORATORY), Error::<Test>::NotAuthorized);
+
+        assert_ok!(submit(LABORATORY));
+        assert_noop!(submit(LABORATORY), Error::<Test>::InvalidStatus);
+        assert_noop!(
+            MedicalExams::cancel(RuntimeOrigin::signed(PATIENT), order),
+            Error::<Test>::InvalidStatus
+        );
+        assert_eq!(LabResults::get().len(), 1);
+    });
+}
+
+/// Ensures the doctor, the patient or the collecting laboratory can cancel
+/// an order, and nobody else.
+#[test]
+fn cancel_works() {
+    new_test_ext().execute_with(|| {
+        let order = setup();
+
+        assert_noop!(
+            MedicalExams::cancel(RuntimeOrigin::signed(LABORATORY), order),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(MedicalExams::cancel(RuntimeOrigin::signed(PATIENT), order));
+        System::assert_last_event(Event::ExamCancelled { order, cancelled_by: PATIENT }.into());
+        assert_noop!(
+            MedicalExams::collect(RuntimeOrigin::signed(LABORATORY), order),
+            Error::<Test>::InvalidStatus
+        );
+
+        let order = order_exam();
+        assert_ok!(MedicalExams::collect(RuntimeOrigin::signed(LABORATORY), order));
+        assert_noop!(
+            MedicalExams::cancel(RuntimeOrigin::signed(OTHER_LABORATORY), order),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(MedicalExams::cancel(RuntimeOrigin::signed(LABORATORY), order));
+        assert_eq!(MedicalExams::exam_orders(order).unwrap().status, ExamStatus::Cancelled);
+    });
+}
+
+/// Ensures only the registrar origin manages laboratories.
+#[test]
+fn laboratory_registration_requires_registrar() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            MedicalExams::register_laboratory(
+                RuntimeOrigin::signed(LABORATORY),
+                LABORATORY,
+                H256::zero(),
+            ),
+            DispatchError::BadOrigin
+        );
+
+        let order = setup();
+        assert_noop!(
+            MedicalExams::register_laboratory(RuntimeOrigin::root(), LABORATORY, H256::zero()),
+            Error::<Test>::AlreadyLaboratory
+        );
+
+        assert_ok!(MedicalExams::remove_laboratory(RuntimeOrigin::root(), LABORATORY));
+        System::assert_last_event(Event::LaboratoryRemoved { laboratory: LABORATORY }.into());
+        assert_noop!(
+            MedicalExams::collect(RuntimeOrigin::signed(LABORATORY), order),
+            Error::<Test>::NotLaboratory
+        );
+    });
+}
+
+/// Ensures only the laboratory designated by the doctor or the patient
+/// collects an order, and the designation can change until collection.
+#[test]
+fn collect_requires_designated_laboratory() {
+    new_test_ext().execute_with(|| {
+        let order = setup();
+        let designate = |who: u64, laboratory: u64| {
+            MedicalExams::designate_laboratory(RuntimeOrigin::signed(who), order, laboratory)
+        };
+
+        assert_noop!(
+            MedicalExams::collect(RuntimeOrigin::signed(OTHER_LABORATORY), order),
+            Error::<Test>::LaboratoryNotDesignated
+        );
+        assert_noop!(designate(LABORATORY, OTHER_LABORATORY), Error::<Test>::NotAuthorized);
+        assert_noop!(designate(PATIENT, UNREGISTERED_DOCTOR), Error::<Test>::NotLaboratory);
+        assert_noop!(
+            MedicalExams::designate_laboratory(
+                RuntimeOrigin::signed(PATIENT),
+                order + 1,
+                LABORATORY,
+            ),
+            Error::<Test>::OrderNotFound
+        );
+
+        // The patient picks another laboratory than the doctor's.
+        assert_ok!(designate(PATIENT, OTHER_LABORATORY));
+        System::assert_last_event(
+            Event::LaboratoryDesignated {
+                order,
+                laboratory: OTHER_LABORATORY,
+                designated_by: PATIENT,
+            }
+            .into(),
+        );
+        assert_noop!(
+            MedicalExams::collect(RuntimeOrigin::signed(LABORATORY), order),
+            Error::<Test>::LaboratoryNotDesignated
+        );
+
+        assert_ok!(MedicalExams::collect(RuntimeOrigin::signed(OTHER_LABORATORY), order));
+        assert_eq!(MedicalExams::exam_orders(order).unwrap().laboratory, Some(OTHER_LABORATORY));
+        assert_noop!(designate(PATIENT, LABORATORY), Error::<Test>::InvalidStatus);
+    });
+}
+
+/// Ensures orders are only served to signed queries from the patient, the
+/// ordering doctor or the designated laboratory.
+#[test]
+fn queries_require_signed_reader() {
+    new_test_ext().execute_with(|| {
+        let order = setup();
+        let stranger = 40;
+
+        for reader in [PATIENT, AUTHORIZED_DOCTOR, LABORATORY] {
+            let (payload, signature) = query(reader);
+            assert!(MedicalExams::exam_order(payload, signature, order).unwrap().is_some());
+        }
+        for reader in [stranger, OTHER_LABORATORY] {
+            let (payload, signature) = query(reader);
+            assert_eq!(
+                MedicalExams::exam_order(payload, signature, order),
+                Err(QueryError::AccessDenied)
+            );
+        }
+
+        // Listings only return the reader's own orders.
+        let (payload, signature) = query(stranger);
+        let page = MedicalExams::exam_orders_of_patient(payload, signature, None, 10).unwrap();
+        assert!(page.items.is_empty());
+        let (payload, signature) = query(PATIENT);
+        let page = MedicalExams::exam_orders_by_doctor(payload, signature, None, 10).unwrap();
+        assert!(page.items.is_empty());
+
+        let (payload, _) = query(PATIENT);
+        assert_eq!(
+            MedicalExams::exam_order(
+                payload.clone(),
+                TestSignature(stranger, payload.signing_message()),
+                order
+            ),
+            Err(QueryError::BadSignature)
+        );
+
+        let payload = QueryPayload { reader: PATIENT, valid_until: 20 };
+        let signature = TestSignature(PATIENT, payload.signing_message());
+        assert_eq!(
+            MedicalExams::exam_orders_of_patient(payload, signature, None, 10),
+            Err(QueryError::ValidityTooLong)
+        );
+
+        System::set_block_number(6);
+        let (payload, signature) = query(PATIENT);
+        assert_eq!(
+            MedicalExams::exam_orders_of_patient(payload, signature, None, 10),
+            Err(QueryError::Expired)
+        );
+    });
+}
+
+/// Ensures a redacted result is replaced by its tombstone commitment.
+#[test]
+fn redacted_results_are_scrubbed() {
+    new_test_ext().execute_with(|| {
+        let order = setup();
+        assert_ok!(MedicalExams::collect(RuntimeOrigin::signed(LABORATORY), order));
+        assert_ok!(MedicalExams::submit_result(
+            RuntimeOrigin::signed(LABORATORY),
+            order,
+            file_hash(),
+            pdf(),
+        ));
+        let commitment = H256::repeat_byte(5);
+
+        // Another patient's redaction of the same hash leaves the order alone.
+        MedicalExams::on_record_redacted(&2, &[(file_hash(), commitment)]);
+        assert_eq!(
+            MedicalExams::exam_orders(order).unwrap().result,
+            Some(ExamResult::File(file_hash()))
+        );
+
+        MedicalExams::on_record_redacted(&PATIENT, &[(file_hash(), commitment)]);
+        assert_eq!(
+            MedicalExams::exam_orders(order).unwrap().result,
+            Some(ExamResult::Redacted(commitment))
+        );
+        assert!(!crate::ResultOrders::<Test>::contains_key(PATIENT, file_hash()));
+    });
+}
+
+/// Ensures the v1 migration wraps stored results and indexes their orders.
+#[test]
+fn migration_v0_to_v1_wraps_results() {
+    use codec::Encode;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<MedicalExams>();
+
+        let old = crate::ExamOrderV0 {
+            doctor: AUTHORIZED_DOCTOR,
+            patient: PATIENT,
+            exam_hash: H256::repeat_byte(1),
+            status: ExamStatus::Resulted,
+            ordered_at: 100u64,
+            laboratory: Some(LABORATORY),
+            collected_at: Some(200u64),
+            result: Some(file_hash()),
+        };
+        frame_support::storage::unhashed::put_raw(
+            &crate::ExamOrders::<Test>::hashed_key_for(3),
+            &old.encode(),
+        );
+
+        crate::migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        let entry = MedicalExams::exam_orders(3).unwrap();
+        assert_eq!(entry.patient, PATIENT);
+        assert_eq!(entry.collected_at, Some(200));
+        assert_eq!(entry.result, Some(ExamResult::File(file_hash())));
+        assert_eq!(crate::ResultOrders::<Test>::get(PATIENT, file_hash()), Some(3));
+        assert_eq!(MedicalExams::on_chain_storage_version(), 1);
+    });
+}
+
+/// Ensures listings walk ids in numeric order and resume after the last id
+/// of the previous page.
+#[test]
+fn listings_are_paginated_by_id() {
+    new_test_ext().execute_with(|| {
+        // Little-endian or hashed keys would not keep these in order.
+        crate::NextExamOrderId::<Test>::put(255);
+        let ids = vec![setup(), order_exam(), order_exam()];
+        assert_eq!(ids, vec![255, 256, 257]);
+
+        let page = |reader, after| {
+            let (payload, signature) = query(reader);
+            let page = if reader == PATIENT {
+                MedicalExams::exam_orders_of_patient(payload, signature, after, 2)
+            } else {
+                MedicalExams::exam_orders_by_doctor(payload, signature, after, 2)
+            }
+            .unwrap();
+            (page.items.into_iter().map(|(id, _)| id).collect::<Vec<_>>(), page.next)
+        };
+        for reader in [PATIENT, AUTHORIZED_DOCTOR] {
+            assert_eq!(page(reader, None), (vec![255, 256], Some(256)));
+            assert_eq!(page(reader, Some(256)), (vec![257], None));
+        }
+    });
+}
+
+/// Ensures the v2 migration re-keys the listing indexes in id order.
+#[test]
+fn migration_v1_to_v2_orders_indexes() {
+    use codec::Encode;
+    use frame_support::{
+        storage::{unhashed, StoragePrefixedMap},
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        Blake2_128Concat, StorageHasher, Twox64Concat,
+    };
+
+    new_test_ext().execute_with(|| {
+        crate::NextExamOrderId::<Test>::put(255);
+        let ids = [setup(), order_exam()];
+
+        StorageVersion::new(1).put::<MedicalExams>();
+        let _ = crate::PatientExamOrders::<Test>::clear(u32::MAX, None);
+        let _ = crate::DoctorExamOrders::<Test>::clear(u32::MAX, None);
+        let old_key = |prefix: [u8; 32], account: u64, id: u64| {
+            let mut key = prefix.to_vec();
+            key.extend(Blake2_128Concat::hash(&account.encode()));
+            key.extend(Twox64Concat::hash(&id.encode()));
+            key
+        };
+        for id in ids {
+            let patients = crate::PatientExamOrders::<Test>::final_prefix();
+            unhashed::put(&old_key(patients, PATIENT, id), &());
+            let doctors = crate::DoctorExamOrders::<Test>::final_prefix();
+            unhashed::put(&old_key(doctors, AUTHORIZED_DOCTOR, id), &());
+        }
+
+        crate::migrations::v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+
+        let (payload, signature) = query(PATIENT);
+        let page = MedicalExams::exam_orders_of_patient(payload, signature, None, 10).unwrap();
+        assert_eq!(page.items.into_iter().map(|(id, _)| id).collect::<Vec<_>>(), ids);
+        let (payload, signature) = query(AUTHORIZED_DOCTOR);
+        let page = MedicalExams::exam_orders_by_doctor(payload, signature, None, 10).unwrap();
+        assert_eq!(page.items.into_iter().map(|(id, _)| id).collect::<Vec<_>>(), ids);
+        assert_eq!(crate::PatientExamOrders::<Test>::iter().count(), 2);
+        assert_eq!(MedicalExams::on_chain_storage_version(), 2);
+    });
+}
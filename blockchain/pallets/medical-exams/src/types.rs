@@ -0,0 +1,175 @@
+use alloc::vec::Vec;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::RuntimeDebug;
+use pallet_medical_history::FileHash;
+use scale_info::TypeInfo;
+
+/// Identifier of an exam order, assigned sequentially.
+pub type ExamOrderId = u64;
+
+/// Key of an exam order in the per-patient and per-doctor indexes.
+///
+/// Stored big-endian so that, under the `Identity` hasher, the order of the
+/// storage keys is the numeric order of the ids and a prefix iteration walks
+/// orders oldest first.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct ExamOrderKey([u8; 8]);
+
+impl From<ExamOrderId> for ExamOrderKey {
+    fn from(id: ExamOrderId) -> Self {
+        Self(id.to_be_bytes())
+    }
+}
+
+impl From<ExamOrderKey> for ExamOrderId {
+    fn from(key: ExamOrderKey) -> Self {
+        ExamOrderId::from_be_bytes(key.0)
+    }
+}
+
+/// Status of an exam order.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum ExamStatus {
+    /// Ordered by the doctor, waiting for the designated laboratory.
+    Ordered,
+    /// A laboratory accepted the order and collected the sample.
+    Collected,
+    /// The laboratory attached the result to the patient's history.
+    Resulted,
+    /// Cancelled before a result was attached.
+    Cancelled,
+}
+
+/// An exam ordered by a doctor for a patient.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ExamOrder<AccountId, Hash, Moment> {
+    /// The ordering doctor.
+    pub doctor: AccountId,
+    /// The patient.
+    pub patient: AccountId,
+    /// Hash of the exam request (exam codes and clinical indication).
+    pub exam_hash: Hash,
+    /// Current status.
+    pub status: ExamStatus,
+    /// Timestamp of the order.
+    pub ordered_at: Moment,
+    /// The laboratory designated by the doctor or the patient; once
+    /// collected, the laboratory that holds the sample.
+    pub laboratory: Option<AccountId>,
+    /// Timestamp of the collection.
+    pub collected_at: Option<Moment>,
+    /// The result in the patient's history, once resulted.
+    pub result: Option<ExamResult<Hash>>,
+}
+
+/// The result of a resulted [`ExamOrder`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ExamResult<Hash> {
+    /// File hash of the result in the patient's history.
+    File(FileHash),
+    /// The patient redacted the result since: its hash was replaced by the
+    /// commitment of its tombstone in `pallet-medical-history`.
+    Redacted(Hash),
+}
+
+/// [`ExamOrder`] as stored in storage version `0` and returned up to
+/// version `2` of the `MedicalExamsApi` runtime API, before redacted
+/// results were scrubbed from their orders.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ExamOrderV0<AccountId, Hash, Moment> {
+    /// The ordering doctor.
+    pub doctor: AccountId,
+    /// The patient.
+    pub patient: AccountId,
+    /// Hash of the exam request (exam codes and clinical indication).
+    pub exam_hash: Hash,
+    /// Current status.
+    pub status: ExamStatus,
+    /// Timestamp of the order.
+    pub ordered_at: Moment,
+    /// The designated laboratory, or the one that holds the sample.
+    pub laboratory: Option<AccountId>,
+    /// Timestamp of the collection.
+    pub collected_at: Option<Moment>,
+    /// File hash of the result in the patient's history, once resulted.
+    pub result: Option<FileHash>,
+}
+
+/// One page of an `exam_orders_of_patient` / `exam_orders_by_doctor`
+/// listing.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ExamOrderPage<AccountId, Hash, Moment> {
+    /// Orders of the page, oldest first.
+    pub items: Vec<(ExamOrderId, ExamOrder<AccountId, Hash, Moment>)>,
+    /// `after` of the next page, or `None` if this is the last one.
+    pub next: Option<ExamOrderId>,
+}
+
+/// A registered laboratory.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Laboratory<Hash, BlockNumber> {
+    /// Hash of the laboratory's license (e.g. CNPJ and health authority permit).
+    pub license_hash: Hash,
+    /// Block in which the laboratory was registered.
+    pub registered_at: BlockNumber,
+}
+
+/// Domain separator prepended to [`QueryPayload`] before signing.
+pub const QUERY_DOMAIN: &[u8] = b"healthchain/exams:";
+
+/// Off-chain exam order query, signed by `reader`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct QueryPayload<AccountId, BlockNumber> {
+    /// Account asking: the patient, the ordering doctor or the designated
+    /// laboratory.
+    pub reader: AccountId,
+    /// Last block at which the query is accepted.
+    pub valid_until: BlockNumber,
+}
+
+impl<AccountId: Encode, BlockNumber: Encode> QueryPayload<AccountId, BlockNumber> {
+    /// Bytes the reader must sign: [`QUERY_DOMAIN`] followed by the
+    /// SCALE-encoded payload.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = QUERY_DOMAIN.to_vec();
+        self.encode_to(&mut message);
+        message
+    }
+}
+
+/// Reasons a signed exam order query is refused.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum QueryError {
+    /// The signature does not match `reader`.
+    BadSignature,
+    /// `valid_until` is in the past.
+    Expired,
+    /// `valid_until` is further ahead than `MaxQueryValidity` allows.
+    ValidityTooLong,
+    /// `reader` is not the patient, the ordering doctor or the designated
+    /// laboratory.
+    AccessDenied,
+}
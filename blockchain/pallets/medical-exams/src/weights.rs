@@ -0,0 +1,90 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+// ==========================================================================
+// ⚠️  PLACEHOLDER / MOCK WEIGHTS
+// 
+// TODO: This file is currently manually maintained for MVP development.
+// Before Mainnet release, replace this entire file with the output of:
+// ./target/release/healthchain-node benchmark pallet ...
+// ==========================================================================
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+pub trait WeightInfo {
+    fn register_laboratory() -> Weight { Weight::from_parts(10_000, 0) }
+    fn remove_laboratory() -> Weight { Weight::from_parts(10_000, 0) }
+    fn order_exam() -> Weight { Weight::from_parts(10_000, 0) }
+    fn collect() -> Weight { Weight::from_parts(10_000, 0) }
+    fn submit_result() -> Weight { Weight::from_parts(10_000, 0) }
+    fn cancel() -> Weight { Weight::from_parts(10_000, 0) }
+    fn designate_laboratory() -> Weight { Weight::from_parts(10_000, 0) }
+}
+
+/// Weights for pallet_medical_exams using the Substrate node and recommended hardware.
+/// `submit_result` includes the record written to pallet_medical_history.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn register_laboratory() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn remove_laboratory() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn order_exam() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(4, 4))
+    }
+    fn collect() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 1))
+    }
+    fn submit_result() -> Weight {
+        Weight::from_parts(20_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(8, 12))
+    }
+    fn cancel() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn designate_laboratory() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 1))
+    }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+    fn register_laboratory() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn remove_laboratory() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn order_exam() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(4, 4))
+    }
+    fn collect() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 1))
+    }
+    fn submit_result() -> Weight {
+        Weight::from_parts(20_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(8, 12))
+    }
+    fn cancel() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn designate_laboratory() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 1))
+    }
+}
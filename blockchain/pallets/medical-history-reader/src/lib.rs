@@ -8,8 +8,23 @@
 //!   provided they have explicit permission granted via the
 //!   `pallet-medical-permissions` pallet.
 //!
-//! This pallet **does not store data**. It acts as a secure reader
+//! The extrinsics are the *notarized access* mode: each read is paid for,
+//! included in a block and kept in the audit trail. For instant reads, the
+//! same checks are available off-chain through [`Pallet::read_record`] and
+//! [`Pallet::read_record_history`] (runtime API), which take a
+//! [`ReadPayload`] signed by the reader. Record listings are only served the
+//! same way, through [`Pallet::read_records_of_patient`] and
+//! [`Pallet::read_records_by_doctor`], which take a signed [`ListPayload`].
+//!
+//! This pallet **does not store medical data**. It acts as a secure reader
 //! over the data indexed and stored by `pallet-medical-history`.
+//!
+//! ## Access audit trail
+//! Every successful read is appended to a per-patient audit trail
+//! ([`AccessLog`]) so patients can query and export who read which record,
+//! and when, even after events are pruned. When a patient redacts a record,
+//! its hash is replaced in the trail by the commitment of its tombstone (see
+//! [`pallet_medical_history::OnRecordRedacted`]).
 
 pub use pallet::*;
 
@@ -42,20 +57,37 @@ pub use weights::*;
 
 extern crate alloc; //Para possibilitar ativar os benchmarks e calcular na real qual é o peso de cada extrínseco
 
+pub mod types;
+pub use types::*;
+
+pub mod migrations;
+
+use frame_support::traits::Get;
+use pallet_medical_history::{FileHash, OnRecordRedacted};
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
-    use crate::WeightInfo;
+    use crate::{
+        AccessLogEntry, AccessedRecord, ListPayload, ReadError, ReadPayload, WeightInfo,
+    };
+    use alloc::vec::Vec;
+    use frame_support::sp_runtime::traits::{IdentifyAccount, Saturating, Verify};
+    use pallet_medical_history::{MedicalRecord, Provenance, RecordPage};
 
     /// Interface to access medical history records.
     use pallet_medical_history::{FileHash, MedicalHistoryAccessor};
 
     /// Interface to verify patient ↔ doctor permissions.
-    use pallet_medical_permissions::MedicalPermissionsVerifier;
+    use pallet_medical_permissions::{InstitutionId, MedicalPermissionsVerifier};
+
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     /// The pallet type.
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Configuration trait for the Medical History Reader pallet.
@@ -75,8 +107,65 @@ pub mod pallet {
 
         /// Permission system used to authorize doctor access.
         type Permissions: MedicalPermissionsVerifier<Self::AccountId>;
+
+        /// Number of access log entries kept per patient.
+        ///
+        /// Once reached, the oldest entry is dropped for each new read.
+        #[pallet::constant]
+        type MaxAccessLogEntries: Get<u32>;
+
+        /// Signature type used to authenticate off-chain read requests.
+        type ReadSignature: Verify<Signer = Self::ReadSigner>;
+
+        /// Public key type matching [`Config::ReadSignature`].
+        type ReadSigner: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Maximum number of blocks an off-chain read request stays valid.
+        #[pallet::constant]
+        type MaxReadValidity: Get<BlockNumberFor<Self>>;
     }
 
+    /// Off-chain read request type for a runtime.
+    pub type ReadPayloadOf<T> =
+        ReadPayload<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    /// Off-chain listing request type for a runtime.
+    pub type ListPayloadOf<T> =
+        ListPayload<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    /// Access log entry type for a runtime.
+    pub type AccessLogEntryOf<T> = AccessLogEntry<
+        <T as frame_system::Config>::AccountId,
+        <T as pallet_timestamp::Config>::Moment,
+        BlockNumberFor<T>,
+        <T as frame_system::Config>::Hash,
+    >;
+
+    // ---------------------------------------------------------------------
+    // Storage
+    // ---------------------------------------------------------------------
+
+    /// Access audit trail: `(patient, sequence) -> entry`.
+    ///
+    /// Sequence numbers grow monotonically per patient. Only the last
+    /// [`Config::MaxAccessLogEntries`] entries are kept.
+    #[pallet::storage]
+    pub type AccessLog<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Twox64Concat,
+        u64, // Sequence number
+        AccessLogEntryOf<T>,
+        OptionQuery,
+    >;
+
+    /// Next sequence number of each patient's access audit trail.
+    #[pallet::storage]
+    #[pallet::getter(fn access_log_len)]
+    pub type AccessLogNext<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
     // REMOVIDO: trait WeightInfo inline e impl for ()
     // (Agora eles vêm do arquivo weights.rs automaticamente)
 
@@ -96,6 +185,15 @@ pub mod pallet {
             patient: T::AccountId,
             file_hash: FileHash,
         },
+
+        /// The read above was authorized by a grant to an institution;
+        /// `member` is the doctor of its care team who acted.
+        InstitutionDataAccessed {
+            institution: InstitutionId,
+            member: T::AccountId,
+            patient: T::AccountId,
+            file_hash: FileHash,
+        },
     }
 
     /// Errors returned by this pallet.
@@ -111,12 +209,17 @@ pub mod pallet {
     /// Dispatchable calls.
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Read the caller's **own** medical record.
+        /// Read the caller's **own** medical record (notarized access).
+        ///
+        /// For reads without a transaction use [`Pallet::read_record`].
         ///
         /// # Parameters
         /// - `origin`: Must be a signed account (the patient).
         /// - `file_hash`: Hash identifying the medical record.
         ///
+        /// # Storage
+        /// - Appends to [`AccessLog`]
+        ///
         /// # Emits
         /// - [`Event::OwnDataAccessed`]
         ///
@@ -130,6 +233,8 @@ pub mod pallet {
             let record = T::HistoryProvider::get_patient_record(&patient, &file_hash)
                 .ok_or(Error::<T>::RecordNotFound)?;
 
+            Self::log_access(&patient, &patient, &record.file_hash);
+
             Self::deposit_event(Event::OwnDataAccessed {
                 patient,
                 file_hash: record.file_hash,
@@ -138,7 +243,10 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Read a **patient's** medical record as an authorized doctor.
+        /// Read a **patient's** medical record as an authorized doctor
+        /// (notarized access).
+        ///
+        /// For reads without a transaction use [`Pallet::read_record`].
         ///
         /// This call enforces explicit permission via the
         /// `pallet-medical-permissions` pallet.
@@ -148,11 +256,15 @@ pub mod pallet {
         /// - `patient_id`: The patient whose data is being accessed.
         /// - `file_hash`: Hash identifying the medical record.
         ///
+        /// # Storage
+        /// - Appends to [`AccessLog`]
+        ///
         /// # Emits
         /// - [`Event::PatientDataAccessed`]
+        /// - [`Event::InstitutionDataAccessed`] if access comes from an institution
         ///
         /// # Errors
-        /// - [`Error::AccessDenied`] if the doctor lacks permission.
+        /// - [`Error::AccessDenied`] if the doctor lacks read permission.
         /// - [`Error::RecordNotFound`] if the record does not exist.
         #[pallet::call_index(1)]
         #[pallet::weight(<T as Config>::WeightInfo::read_patient_data())]
@@ -164,7 +276,7 @@ pub mod pallet {
             let doctor = ensure_signed(origin)?;
 
             // Permission check (Issue #12)
-            if !<T as Config>::Permissions::has_access(&patient_id, &doctor) {
+            if !<T as Config>::Permissions::can_read_record(&patient_id, &doctor, &file_hash) {
 
                 //Para calcular pesos corretamente.
                 return Err(Error::<T>::AccessDenied.into());
@@ -173,13 +285,250 @@ pub mod pallet {
             let record = T::HistoryProvider::get_patient_record(&patient_id, &file_hash)
                 .ok_or(Error::<T>::RecordNotFound)?;
 
+            Self::log_access(&patient_id, &doctor, &record.file_hash);
+
+            let institution =
+                <T as Config>::Permissions::acting_institution(&patient_id, &doctor);
+
             Self::deposit_event(Event::PatientDataAccessed {
-                doctor,
-                patient: patient_id,
-                file_hash: record.file_hash,
+                doctor: doctor.clone(),
+                patient: patient_id.clone(),
+                file_hash: record.file_hash.clone(),
+            });
+
+            if let Some(institution) = institution {
+                Self::deposit_event(Event::InstitutionDataAccessed {
+                    institution,
+                    member: doctor,
+                    patient: patient_id,
+                    file_hash: record.file_hash,
+                });
+            }
+
+            Ok(())
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Internal helpers
+    // ---------------------------------------------------------------------
+
+    impl<T: Config> Pallet<T> {
+        /// Appends a read of `file_hash` by `reader` to `patient`'s audit
+        /// trail, dropping the oldest entry once
+        /// [`Config::MaxAccessLogEntries`] is exceeded.
+        fn log_access(patient: &T::AccountId, reader: &T::AccountId, file_hash: &FileHash) {
+            let seq = AccessLogNext::<T>::get(patient);
+
+            AccessLog::<T>::insert(
+                patient,
+                seq,
+                AccessLogEntry {
+                    reader: reader.clone(),
+                    record: AccessedRecord::File(file_hash.clone()),
+                    accessed_at: pallet_timestamp::Now::<T>::get(),
+                    block: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+            AccessLogNext::<T>::insert(patient, seq.saturating_add(1));
+
+            if let Some(oldest) = seq.checked_sub(T::MaxAccessLogEntries::get().into()) {
+                AccessLog::<T>::remove(patient, oldest);
+            }
+        }
+
+        /// Returns up to `limit` entries of `patient`'s access audit trail,
+        /// starting at sequence number `from` (oldest first).
+        ///
+        /// Entries that already fell out of the retention window are skipped.
+        /// Used by the `MedicalHistoryReaderApi` runtime API.
+        pub fn access_log(
+            patient: &T::AccountId,
+            from: u64,
+            limit: u32,
+        ) -> Vec<(u64, AccessLogEntryOf<T>)> {
+            let next = AccessLogNext::<T>::get(patient);
+            let first_kept = next.saturating_sub(T::MaxAccessLogEntries::get().into());
+
+            (from.max(first_kept)..next)
+                .take(limit as usize)
+                .filter_map(|seq| AccessLog::<T>::get(patient, seq).map(|entry| (seq, entry)))
+                .collect()
+        }
+
+        /// Returns `payload.file_hash` if `signature` proves the request comes
+        /// from `payload.reader` and the reader is the patient or a doctor
+        /// with read access.
+        ///
+        /// Nothing is written to storage: this is the transaction-free read
+        /// path used by the `MedicalHistoryReaderApi` runtime API. The request
+        /// is only accepted until `valid_until`, which may be at most
+        /// [`Config::MaxReadValidity`] blocks ahead. The runtime cannot
+        /// remember `payload.nonce`; the node RPC refuses a nonce it already
+        /// served, so a leaked request cannot be replayed through it.
+        pub fn read_record(
+            payload: ReadPayloadOf<T>,
+            signature: T::ReadSignature,
+        ) -> Result<MedicalRecord<T::AccountId, T::Moment>, ReadError> {
+            Self::verify_request(
+                &payload.reader,
+                payload.valid_until,
+                &payload.signing_message(),
+                &signature,
+            )?;
+            ensure!(
+                Self::may_read(&payload.patient, &payload.reader, &payload.file_hash),
+                ReadError::AccessDenied
+            );
+
+            T::HistoryProvider::get_patient_record(&payload.patient, &payload.file_hash)
+                .ok_or(ReadError::RecordNotFound)
+        }
+
+        /// Returns every version of the record `payload.file_hash`, oldest
+        /// first, under the same checks as [`Self::read_record`].
+        ///
+        /// Versions the reader may not read (e.g. outside a record-limited
+        /// referral) are left out; the last one returned is the latest the
+        /// reader can see.
+        pub fn read_record_history(
+            payload: ReadPayloadOf<T>,
+            signature: T::ReadSignature,
+        ) -> Result<Vec<MedicalRecord<T::AccountId, T::Moment>>, ReadError> {
+            Self::verify_request(
+                &payload.reader,
+                payload.valid_until,
+                &payload.signing_message(),
+                &signature,
+            )?;
+            ensure!(
+                Self::may_read(&payload.patient, &payload.reader, &payload.file_hash),
+                ReadError::AccessDenied
+            );
+
+            let (patient, reader) = (&payload.patient, &payload.reader);
+            let mut history =
+                T::HistoryProvider::get_patient_record_history(patient, &payload.file_hash);
+            ensure!(!history.is_empty(), ReadError::RecordNotFound);
+            history.retain(|record| Self::may_read(patient, reader, &record.file_hash));
+
+            Ok(history)
+        }
+
+        /// Returns a page of the records of the patient `payload.owner`, as
+        /// listed by [`pallet_medical_history::Pallet::records_of_patient`].
+        ///
+        /// Signed and time-limited like [`Self::read_record`]. Records the
+        /// reader may not read are left out of the page, so a doctor without
+        /// access gets empty pages.
+        pub fn read_records_of_patient(
+            payload: ListPayloadOf<T>,
+            signature: T::ReadSignature,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> Result<RecordPage<MedicalRecord<T::AccountId, T::Moment>>, ReadError> {
+            Self::verify_request(
+                &payload.reader,
+                payload.valid_until,
+                &payload.signing_message(),
+                &signature,
+            )?;
+
+            let (patient, reader) = (&payload.owner, &payload.reader);
+            let mut page =
+                T::HistoryProvider::get_patient_records(patient, provenance, from, limit);
+            page.items.retain(|record| Self::may_read(patient, reader, &record.file_hash));
+
+            Ok(page)
+        }
+
+        /// Returns a page of the `(patient, record)` pairs created by the
+        /// doctor `payload.owner`, who must be the reader.
+        ///
+        /// Records of patients that no longer grant the doctor read access
+        /// are left out of the page.
+        pub fn read_records_by_doctor(
+            payload: ListPayloadOf<T>,
+            signature: T::ReadSignature,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> Result<RecordPage<(T::AccountId, MedicalRecord<T::AccountId, T::Moment>)>, ReadError>
+        {
+            Self::verify_request(
+                &payload.reader,
+                payload.valid_until,
+                &payload.signing_message(),
+                &signature,
+            )?;
+            ensure!(payload.reader == payload.owner, ReadError::AccessDenied);
+
+            let mut page =
+                T::HistoryProvider::get_doctor_records(&payload.owner, provenance, from, limit);
+            page.items.retain(|(patient, record)| {
+                Self::may_read(patient, &payload.reader, &record.file_hash)
             });
 
+            Ok(page)
+        }
+
+        /// Checks that a signed off-chain request is still valid, not valid
+        /// for longer than [`Config::MaxReadValidity`], and signed by `reader`.
+        fn verify_request(
+            reader: &T::AccountId,
+            valid_until: BlockNumberFor<T>,
+            message: &[u8],
+            signature: &T::ReadSignature,
+        ) -> Result<(), ReadError> {
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(valid_until >= now, ReadError::Expired);
+            ensure!(
+                valid_until <= now.saturating_add(T::MaxReadValidity::get()),
+                ReadError::ValidityTooLong
+            );
+            ensure!(signature.verify(message, reader), ReadError::BadSignature);
+
             Ok(())
         }
+
+        /// Whether `reader` is `patient` or a doctor allowed to read `file_hash`.
+        fn may_read(patient: &T::AccountId, reader: &T::AccountId, file_hash: &FileHash) -> bool {
+            reader == patient
+                || <T as Config>::Permissions::can_read_record(patient, reader, file_hash)
+        }
+
+        /// Returns every retained entry of `patient`'s access audit trail,
+        /// oldest first, so the patient can export it.
+        pub fn export_access_log(patient: &T::AccountId) -> Vec<(u64, AccessLogEntryOf<T>)> {
+            Self::access_log(patient, 0, T::MaxAccessLogEntries::get())
+        }
+    }
+}
+
+impl<T: Config> OnRecordRedacted<T::AccountId, T::Hash> for Pallet<T> {
+    /// Replaces the hashes of the redacted versions in `patient`'s audit
+    /// trail by their tombstone commitments.
+    fn on_record_redacted(patient: &T::AccountId, redacted: &[(FileHash, T::Hash)]) {
+        let next = AccessLogNext::<T>::get(patient);
+        let first_kept = next.saturating_sub(T::MaxAccessLogEntries::get().into());
+
+        for seq in first_kept..next {
+            let Some(mut entry) = AccessLog::<T>::get(patient, seq) else {
+                continue;
+            };
+            let AccessedRecord::File(file_hash) = &entry.record else {
+                continue;
+            };
+            if let Some((_, commitment)) = redacted.iter().find(|(hash, _)| hash == file_hash) {
+                entry.record = AccessedRecord::Redacted(*commitment);
+                AccessLog::<T>::insert(patient, seq, entry);
+            }
+        }
+    }
+
+    fn weight() -> Weight {
+        let entries = T::MaxAccessLogEntries::get() as u64;
+        T::DbWeight::get().reads_writes(entries.saturating_add(1), entries)
     }
 }
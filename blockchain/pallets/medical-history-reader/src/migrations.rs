@@ -0,0 +1,51 @@
+//! Storage migrations for the Medical History Reader pallet.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+    weights::Weight,
+};
+
+/// Migration from storage version `0` to `1`.
+///
+/// Version `1` stores the record of an [`AccessLogEntry`] as an
+/// [`AccessedRecord`], so that redacted records can be scrubbed from the
+/// audit trail. Existing entries become [`AccessedRecord::File`].
+pub mod v1 {
+    use super::*;
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// Unversioned inner migration. Use [`MigrateV0ToV1`] instead.
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+
+            AccessLog::<T>::translate::<
+                AccessLogEntryV1<T::AccountId, T::Moment, BlockNumberFor<T>>,
+                _,
+            >(|_patient, _seq, old| {
+                translated = translated.saturating_add(1);
+                Some(AccessLogEntry {
+                    reader: old.reader,
+                    record: AccessedRecord::File(old.file_hash),
+                    accessed_at: old.accessed_at,
+                    block: old.block,
+                })
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `0`.
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
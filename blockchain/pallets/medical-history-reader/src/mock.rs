@@ -2,22 +2,31 @@
 //!
 //! This file provides:
 //! - A **mock history provider** (`MockHistoryAccessor`) that returns a single
-//!   known record for `(patient = 1, file_hash = [1; 64])`.
+//!   known record for `(patient = 1, file_hash = [1; 64])`, created by
+//!   `doctor = 10`.
 //! - A **mock permissions provider** (`MockPermissions`) that authorizes only
-//!   `(patient = 1, doctor = 10)`.
-//! - A minimal FRAME test runtime wiring `System`, `Timestamp`, and
-//!   `MedicalHistoryReader`.
+//!   `(patient = 1, doctor = 10)`, through an institution
+//!   `(patient = 1, doctor = 20)` and, for the record `[1; 64]` only,
+//!   `(patient = 1, doctor = 30)`.
+//! - A minimal FRAME test runtime wiring `System`, `Timestamp`, `Balances`
+//!   and `MedicalHistoryReader`.
 
 use crate as pallet_medical_history_reader;
 
 use frame_support::{
     derive_impl,
-    traits::{ConstU32, ConstU64},
+    traits::{ConstBool, ConstU32, ConstU64},
     BoundedVec,
 };
-use pallet_medical_history::{MedicalHistoryAccessor, MedicalRecord};
-use pallet_medical_permissions::MedicalPermissionsVerifier;
-use sp_runtime::BuildStorage;
+use pallet_doctor_registry::DoctorRegistryVerifier;
+use pallet_medical_history::{
+    MedicalHistoryAccessor, MedicalRecord, Provenance, RecordCategory, RecordMetadata, RecordPage,
+};
+use pallet_medical_permissions::{InstitutionId, MedicalPermissionsVerifier};
+use sp_runtime::{
+    testing::{TestSignature, UintAuthorityId},
+    BuildStorage,
+};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -32,22 +41,49 @@ type Block = frame_system::mocking::MockBlock<Test>;
 /// - `file_hash == [1; 64]`
 pub struct MockHistoryAccessor;
 
+/// The only record known to [`MockHistoryAccessor`].
+pub fn mock_record() -> MedicalRecord<u64, u64> {
+    MedicalRecord {
+        created_by: 10,
+        created_at: 100,
+        file_hash: vec![1; 64].try_into().unwrap(),
+        metadata: RecordMetadata {
+            category: RecordCategory::Exam,
+            content_type: b"application/pdf".to_vec().try_into().unwrap(),
+            clinical_date: 100,
+            title_hash: None,
+        },
+        provenance: Provenance::ClinicianAuthored,
+    }
+}
+
 impl MedicalHistoryAccessor<u64, u64> for MockHistoryAccessor {
     fn get_patient_record(
         patient: &u64,
         file_hash: &BoundedVec<u8, ConstU32<64>>,
     ) -> Option<MedicalRecord<u64, u64>> {
-        let target_hash: BoundedVec<u8, ConstU32<64>> = vec![1; 64].try_into().unwrap();
+        let record = mock_record();
+        (*patient == 1 && *file_hash == record.file_hash).then_some(record)
+    }
 
-        if *patient == 1 && *file_hash == target_hash {
-            return Some(MedicalRecord {
-                created_by: 10,
-                created_at: 100,
-                file_hash: target_hash,
-            });
-        }
+    fn get_patient_records(
+        patient: &u64,
+        _provenance: Option<Provenance>,
+        _from: u64,
+        _limit: u32,
+    ) -> RecordPage<MedicalRecord<u64, u64>> {
+        let items = if *patient == 1 { vec![mock_record()] } else { vec![] };
+        RecordPage { items, next: None }
+    }
 
-        None
+    fn get_doctor_records(
+        doctor: &u64,
+        _provenance: Option<Provenance>,
+        _from: u64,
+        _limit: u32,
+    ) -> RecordPage<(u64, MedicalRecord<u64, u64>)> {
+        let items = if *doctor == 10 { vec![(1, mock_record())] } else { vec![] };
+        RecordPage { items, next: None }
     }
 }
 
@@ -59,11 +95,40 @@ impl MedicalHistoryAccessor<u64, u64> for MockHistoryAccessor {
 ///
 /// Authorization rule:
 /// - Only `doctor == 10` is authorized to read data from `patient == 1`.
+/// - `doctor == 20` reads `patient == 1` as a member of [`MOCK_INSTITUTION`].
+/// - `doctor == 30` reads only the record `[1; 64]` of `patient == 1`, as a
+///   specialist holding a referral limited to that record.
 pub struct MockPermissions;
 
+/// Institution granted access by `patient == 1` in [`MockPermissions`].
+pub const MOCK_INSTITUTION: InstitutionId = 7;
+
 impl MedicalPermissionsVerifier<u64> for MockPermissions {
     fn has_access(patient: &u64, doctor: &u64) -> bool {
-        *patient == 1 && *doctor == 10
+        *patient == 1 && (*doctor == 10 || *doctor == 20)
+    }
+
+    fn acting_institution(patient: &u64, doctor: &u64) -> Option<InstitutionId> {
+        (*patient == 1 && *doctor == 20).then_some(MOCK_INSTITUTION)
+    }
+
+    fn can_read_record(patient: &u64, doctor: &u64, file_hash: &[u8]) -> bool {
+        Self::can_read(patient, doctor) || (*patient == 1 && *doctor == 30 && file_hash == [1; 64])
+    }
+}
+
+// -------------------------------------------------------------------------
+// Mock Doctor Registry
+// -------------------------------------------------------------------------
+
+/// Mock doctor registry that treats every account as an active doctor.
+///
+/// Only needed to satisfy the configs of the wired HealthChain pallets.
+pub struct MockDoctorRegistry;
+
+impl DoctorRegistryVerifier<u64> for MockDoctorRegistry {
+    fn is_active_doctor(_who: &u64) -> bool {
+        true
     }
 }
 
@@ -103,11 +168,20 @@ mod runtime {
 
     #[runtime::pallet_index(4)]
     pub type MedicalPermissions = pallet_medical_permissions::Pallet<Test>;
+
+    #[runtime::pallet_index(5)]
+    pub type Balances = pallet_balances::Pallet<Test>;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
     type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
 }
 
 impl pallet_timestamp::Config for Test {
@@ -123,11 +197,32 @@ impl pallet_medical_history_reader::Config for Test {
     type WeightInfo = crate::weights::WeightInfo<Test>;
     type HistoryProvider = MockHistoryAccessor;
     type Permissions = MockPermissions;
+    type MaxAccessLogEntries = ConstU32<2>;
+    type ReadSignature = TestSignature;
+    type ReadSigner = UintAuthorityId;
+    type MaxReadValidity = ConstU64<10>;
 }
 
 impl pallet_medical_permissions::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type DoctorRegistry = MockDoctorRegistry;
+    type MaxExpiriesPerBlock = ConstU32<16>;
+    type RequestTimeout = ConstU64<10>;
+    type Time = Timestamp;
+    type MaxLogEntries = ConstU32<16>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type PermissionDeposit = ConstU64<1>;
+    type OnAccessRevoked = ();
+    type EmergencyAuthorityOrigin = frame_system::EnsureRoot<u64>;
+    type EmergencyAccessDuration = ConstU64<10>;
+    type GuardianRegistrarOrigin = frame_system::EnsureRoot<u64>;
+    type InstitutionRegistrarOrigin = frame_system::EnsureRoot<u64>;
+    type MaxInstitutionMembers = ConstU32<16>;
+    type MaxInstitutionPatients = ConstU32<16>;
+    type MaxMembershipsPerDoctor = ConstU32<4>;
+    type MaxRevocationsPerCall = ConstU32<16>;
 }
 
 // Implementação para o pallet_medical_history (mock)
@@ -137,6 +232,17 @@ impl pallet_medical_history::Config for Test {
     // A implementação pública do pallet_medical_permissions (o `Pallet`) implementa esse trait,
     // então é seguro apontar para ele aqui.
     type Permissions = pallet_medical_permissions::Pallet<Test>;
+    type DoctorRegistry = MockDoctorRegistry;
+    type ConfirmationWindow = ConstU64<10>;
+    type MaxProposalsPerBlock = ConstU32<16>;
+    type RequireConfirmation = ConstBool<false>;
+    type MaxVersions = ConstU32<16>;
+    type RetentionPeriod = ConstU64<10_000>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type RecordDeposit = ConstU64<1>;
+    type MaxKeyEnvelopes = ConstU32<4>;
+    type OnRecordRedacted = MedicalHistoryReader;
 }
 
 /// Builds genesis storage according to the mock runtime configuration.
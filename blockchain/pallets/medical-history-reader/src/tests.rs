@@ -4,9 +4,16 @@
 //! - **Issue #11**: A patient can read their own medical record (`read_own_data`).
 //! - **Issue #12**: A doctor can read a patient's record only if permission exists
 //!   (`read_patient_data`).
+//! - Every successful read is appended to the patient's access audit trail.
+//! - Signed off-chain reads and listings (`read_record`, `read_record_history`,
+//!   `read_records_of_patient`, `read_records_by_doctor`) enforce the same
+//!   permissions.
 
-use crate::{mock::*, Error, Event};
+use crate::{
+    mock::*, AccessLog, AccessedRecord, Error, Event, ListPayload, ReadError, ReadPayload,
+};
 use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::testing::TestSignature;
 
 // -------------------------------------------------------------------------
 // Patient tests (Issue #11)
@@ -83,6 +90,43 @@ fn read_patient_data_works_with_permission() {
     });
 }
 
+/// Ensures reads authorized through an institution name the member who
+/// acted.
+#[test]
+fn read_patient_data_attributes_institution_member() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let member = 20; // Reads through `MOCK_INSTITUTION`
+        let patient_id = 1;
+        let file_hash: BoundedVec<u8, _> = vec![1; 64].try_into().unwrap();
+
+        assert_ok!(MedicalHistoryReader::read_patient_data(
+            RuntimeOrigin::signed(member),
+            patient_id,
+            file_hash.clone(),
+        ));
+
+        System::assert_has_event(
+            Event::PatientDataAccessed {
+                doctor: member,
+                patient: patient_id,
+                file_hash: file_hash.clone(),
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::InstitutionDataAccessed {
+                institution: MOCK_INSTITUTION,
+                member,
+                patient: patient_id,
+                file_hash,
+            }
+            .into(),
+        );
+    });
+}
+
 /// Ensures a doctor without permission cannot read a patient's record.
 #[test]
 fn read_patient_data_fails_without_permission() {
@@ -103,3 +147,311 @@ fn read_patient_data_fails_without_permission() {
         );
     });
 }
+
+/// Ensures a doctor whose access is limited to selected records reads only
+/// those records.
+#[test]
+fn read_patient_data_honours_record_limited_access() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let specialist = 30; // Limited to `[1; 64]` in `MockPermissions`
+        let patient_id = 1;
+        let selected: BoundedVec<u8, _> = vec![1; 64].try_into().unwrap();
+        let other: BoundedVec<u8, _> = vec![2; 64].try_into().unwrap();
+
+        assert_ok!(MedicalHistoryReader::read_patient_data(
+            RuntimeOrigin::signed(specialist),
+            patient_id,
+            selected,
+        ));
+        assert_noop!(
+            MedicalHistoryReader::read_patient_data(
+                RuntimeOrigin::signed(specialist),
+                patient_id,
+                other
+            ),
+            Error::<Test>::AccessDenied
+        );
+    });
+}
+
+// -------------------------------------------------------------------------
+// Access audit trail
+// -------------------------------------------------------------------------
+
+/// Ensures successful reads are stored in the patient's audit trail with
+/// reader, file hash, timestamp and block; failed reads are not.
+#[test]
+fn reads_are_recorded_in_access_log() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1_000);
+
+        let patient_id = 1;
+        let doctor_id = 10;
+        let file_hash: BoundedVec<u8, _> = vec![1; 64].try_into().unwrap();
+
+        assert_ok!(MedicalHistoryReader::read_own_data(
+            RuntimeOrigin::signed(patient_id),
+            file_hash.clone(),
+        ));
+
+        System::set_block_number(2);
+        assert_ok!(MedicalHistoryReader::read_patient_data(
+            RuntimeOrigin::signed(doctor_id),
+            patient_id,
+            file_hash.clone(),
+        ));
+        assert_noop!(
+            MedicalHistoryReader::read_patient_data(
+                RuntimeOrigin::signed(99),
+                patient_id,
+                file_hash.clone()
+            ),
+            Error::<Test>::AccessDenied
+        );
+
+        let log = MedicalHistoryReader::access_log(&patient_id, 0, 10);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].1.reader, patient_id);
+        assert_eq!(log[0].1.block, 1);
+        assert_eq!(log[1].1.reader, doctor_id);
+        assert_eq!(log[1].1.record, AccessedRecord::File(file_hash));
+        assert_eq!(log[1].1.accessed_at, 1_000);
+        assert_eq!(log[1].1.block, 2);
+    });
+}
+
+/// Ensures the audit trail keeps only `MaxAccessLogEntries` entries and
+/// supports pagination and export.
+#[test]
+fn access_log_is_bounded_and_paginated() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient_id = 1;
+        let file_hash: BoundedVec<u8, _> = vec![1; 64].try_into().unwrap();
+
+        for _ in 0..3 {
+            assert_ok!(MedicalHistoryReader::read_own_data(
+                RuntimeOrigin::signed(patient_id),
+                file_hash.clone(),
+            ));
+        }
+
+        // MaxAccessLogEntries = 2: the oldest entry was dropped.
+        assert_eq!(MedicalHistoryReader::access_log_len(patient_id), 3);
+        assert!(!AccessLog::<Test>::contains_key(patient_id, 0));
+
+        let seqs = |log: Vec<(u64, _)>| log.into_iter().map(|(seq, _)| seq).collect::<Vec<_>>();
+        assert_eq!(seqs(MedicalHistoryReader::access_log(&patient_id, 0, 1)), vec![1]);
+        assert_eq!(seqs(MedicalHistoryReader::access_log(&patient_id, 2, 10)), vec![2]);
+        assert_eq!(seqs(MedicalHistoryReader::export_access_log(&patient_id)), vec![1, 2]);
+    });
+}
+
+/// Ensures a redaction replaces the record's hash in the audit trail by its
+/// tombstone commitment and leaves other entries untouched.
+#[test]
+fn redacted_records_are_scrubbed_from_access_log() {
+    use pallet_medical_history::OnRecordRedacted;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient_id = 1;
+        let file_hash: BoundedVec<u8, _> = vec![1; 64].try_into().unwrap();
+        let other: BoundedVec<u8, _> = vec![2; 64].try_into().unwrap();
+        let commitment = sp_core::H256::repeat_byte(7);
+
+        assert_ok!(MedicalHistoryReader::read_own_data(
+            RuntimeOrigin::signed(patient_id),
+            file_hash.clone(),
+        ));
+        MedicalHistoryReader::on_record_redacted(&patient_id, &[(other, commitment)]);
+        assert_eq!(
+            MedicalHistoryReader::export_access_log(&patient_id)[0].1.record,
+            AccessedRecord::File(file_hash.clone())
+        );
+
+        MedicalHistoryReader::on_record_redacted(&patient_id, &[(file_hash, commitment)]);
+        assert_eq!(
+            MedicalHistoryReader::export_access_log(&patient_id)[0].1.record,
+            AccessedRecord::Redacted(commitment)
+        );
+    });
+}
+
+/// Ensures the v1 migration keeps existing audit entries.
+#[test]
+fn migration_v0_to_v1_wraps_logged_hashes() {
+    use codec::Encode;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<MedicalHistoryReader>();
+
+        let file_hash: BoundedVec<u8, _> = vec![1; 64].try_into().unwrap();
+        let old = crate::AccessLogEntryV1 {
+            reader: 10u64,
+            file_hash: file_hash.clone(),
+            accessed_at: 500u64,
+            block: 3u64,
+        };
+        frame_support::storage::unhashed::put_raw(
+            &AccessLog::<Test>::hashed_key_for(1, 0),
+            &old.encode(),
+        );
+
+        crate::migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        let entry = AccessLog::<Test>::get(1, 0).unwrap();
+        assert_eq!(entry.reader, 10);
+        assert_eq!(entry.record, AccessedRecord::File(file_hash));
+        assert_eq!(entry.accessed_at, 500);
+        assert_eq!(entry.block, 3);
+        assert_eq!(MedicalHistoryReader::on_chain_storage_version(), 1);
+    });
+}
+
+// -------------------------------------------------------------------------
+// Off-chain signed reads
+// -------------------------------------------------------------------------
+
+/// Builds a read request for the mock record and a matching signature.
+fn signed_read(reader: u64, valid_until: u64) -> (ReadPayload<u64, u64>, TestSignature) {
+    let payload = ReadPayload {
+        reader,
+        patient: 1,
+        file_hash: vec![1; 64].try_into().unwrap(),
+        valid_until,
+        nonce: 0,
+    };
+    let signature = TestSignature(reader, payload.signing_message());
+
+    (payload, signature)
+}
+
+/// Ensures the patient and an authorized doctor can read through a signed
+/// payload without submitting a transaction or touching storage.
+#[test]
+fn read_record_works_for_patient_and_authorized_doctor() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        for reader in [1, 10] {
+            let (payload, signature) = signed_read(reader, 5);
+            let record = MedicalHistoryReader::read_record(payload, signature).unwrap();
+            assert_eq!(record.created_by, 10);
+        }
+
+        assert!(MedicalHistoryReader::access_log(&1, 0, 10).is_empty());
+    });
+}
+
+/// Ensures off-chain reads are refused for bad signatures, unauthorized
+/// readers, expired or overly long requests and unknown records.
+#[test]
+fn read_record_rejects_invalid_requests() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        // Signed by someone else than `reader`.
+        let (payload, _) = signed_read(10, 5);
+        let forged = TestSignature(99, payload.signing_message());
+        assert_eq!(
+            MedicalHistoryReader::read_record(payload, forged),
+            Err(ReadError::BadSignature)
+        );
+
+        let (payload, signature) = signed_read(99, 5);
+        assert_eq!(
+            MedicalHistoryReader::read_record(payload, signature),
+            Err(ReadError::AccessDenied)
+        );
+
+        let (payload, signature) = signed_read(10, 4);
+        assert_eq!(
+            MedicalHistoryReader::read_record(payload, signature),
+            Err(ReadError::Expired)
+        );
+
+        // MaxReadValidity = 10.
+        let (payload, signature) = signed_read(10, 16);
+        assert_eq!(
+            MedicalHistoryReader::read_record(payload, signature),
+            Err(ReadError::ValidityTooLong)
+        );
+
+        let (mut payload, _) = signed_read(10, 5);
+        payload.file_hash = vec![2; 64].try_into().unwrap();
+        let signature = TestSignature(10, payload.signing_message());
+        assert_eq!(
+            MedicalHistoryReader::read_record(payload, signature),
+            Err(ReadError::RecordNotFound)
+        );
+    });
+}
+
+/// Builds a listing request for `owner`'s records and a matching signature.
+fn signed_list(reader: u64, owner: u64) -> (ListPayload<u64, u64>, TestSignature) {
+    let payload = ListPayload { reader, owner, valid_until: 5, nonce: 0 };
+    let signature = TestSignature(reader, payload.signing_message());
+
+    (payload, signature)
+}
+
+/// Ensures record histories are only returned for signed, authorized reads.
+#[test]
+fn read_record_history_is_permission_checked() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (payload, signature) = signed_read(10, 5);
+        assert_eq!(
+            MedicalHistoryReader::read_record_history(payload, signature),
+            Ok(vec![mock_record()])
+        );
+
+        let (payload, signature) = signed_read(99, 5);
+        assert_eq!(
+            MedicalHistoryReader::read_record_history(payload, signature),
+            Err(ReadError::AccessDenied)
+        );
+    });
+}
+
+/// Ensures listings require a valid signature and only return records the
+/// reader may read.
+#[test]
+fn record_listings_are_permission_checked() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let of_patient = |(payload, signature): (ListPayload<u64, u64>, TestSignature)| {
+            MedicalHistoryReader::read_records_of_patient(payload, signature, None, 0, 10)
+                .map(|page| page.items)
+        };
+        assert_eq!(of_patient(signed_list(1, 1)), Ok(vec![mock_record()]));
+        assert_eq!(of_patient(signed_list(10, 1)), Ok(vec![mock_record()]));
+        assert_eq!(of_patient(signed_list(99, 1)), Ok(vec![]));
+
+        let (payload, _) = signed_list(10, 1);
+        let forged = TestSignature(99, payload.signing_message());
+        assert_eq!(of_patient((payload, forged)), Err(ReadError::BadSignature));
+
+        // A read signature cannot be reused as a listing signature.
+        let (read, _) = signed_read(10, 5);
+        let (payload, _) = signed_list(10, 1);
+        let signature = TestSignature(10, read.signing_message());
+        assert_eq!(of_patient((payload, signature)), Err(ReadError::BadSignature));
+
+        let by_doctor = |(payload, signature): (ListPayload<u64, u64>, TestSignature)| {
+            MedicalHistoryReader::read_records_by_doctor(payload, signature, None, 0, 10)
+                .map(|page| page.items)
+        };
+        assert_eq!(by_doctor(signed_list(10, 10)), Ok(vec![(1, mock_record())]));
+        assert_eq!(by_doctor(signed_list(99, 10)), Err(ReadError::AccessDenied));
+    });
+}
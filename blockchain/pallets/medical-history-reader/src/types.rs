@@ -0,0 +1,142 @@
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::RuntimeDebug;
+use pallet_medical_history::FileHash;
+use scale_info::TypeInfo;
+
+/// One entry of a patient's access audit trail.
+///
+/// Recorded every time a medical record is successfully read, either by the
+/// patient (`read_own_data`) or by an authorized doctor (`read_patient_data`).
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AccessLogEntry<AccountId, Moment, BlockNumber, Hash> {
+    /// Account that read the record (the patient or a doctor).
+    pub reader: AccountId,
+    /// The record that was read.
+    pub record: AccessedRecord<Hash>,
+    /// Timestamp of the block in which the read happened.
+    pub accessed_at: Moment,
+    /// Block in which the read happened.
+    pub block: BlockNumber,
+}
+
+/// The record an [`AccessLogEntry`] refers to.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AccessedRecord<Hash> {
+    /// Hash of the record that was read.
+    File(FileHash),
+    /// The record was redacted since: its hash was replaced by the
+    /// commitment of its tombstone in `pallet-medical-history`.
+    Redacted(Hash),
+}
+
+/// [`AccessLogEntry`] as stored in storage version `0` and returned up to
+/// version `2` of the `MedicalHistoryReaderApi` runtime API, before
+/// redacted records were scrubbed from the audit trail.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AccessLogEntryV1<AccountId, Moment, BlockNumber> {
+    /// Account that read the record (the patient or a doctor).
+    pub reader: AccountId,
+    /// Hash of the record that was read.
+    pub file_hash: FileHash,
+    /// Timestamp of the block in which the read happened.
+    pub accessed_at: Moment,
+    /// Block in which the read happened.
+    pub block: BlockNumber,
+}
+
+/// Domain separator prepended to [`ReadPayload`] before signing, so a read
+/// signature cannot be replayed as any other kind of signed message.
+pub const READ_DOMAIN: &[u8] = b"healthchain/read:";
+
+/// Domain separator prepended to [`ListPayload`] before signing.
+pub const LIST_DOMAIN: &[u8] = b"healthchain/list:";
+
+/// Off-chain read request, signed by `reader`.
+///
+/// Lets the patient or an authorized doctor fetch a record through the
+/// runtime API without submitting a transaction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReadPayload<AccountId, BlockNumber> {
+    /// Account asking for the record (the patient or a doctor).
+    pub reader: AccountId,
+    /// Patient that owns the record.
+    pub patient: AccountId,
+    /// Hash of the requested record.
+    pub file_hash: FileHash,
+    /// Last block at which the request is accepted.
+    pub valid_until: BlockNumber,
+    /// Random value picked by the reader for each request.
+    ///
+    /// The runtime API cannot write state, so it cannot tell a replayed
+    /// request apart; the node RPC accepts each `(reader, nonce)` only once
+    /// until `valid_until`.
+    pub nonce: u64,
+}
+
+impl<AccountId: Encode, BlockNumber: Encode> ReadPayload<AccountId, BlockNumber> {
+    /// Bytes the reader must sign: [`READ_DOMAIN`] followed by the
+    /// SCALE-encoded payload.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = READ_DOMAIN.to_vec();
+        self.encode_to(&mut message);
+        message
+    }
+}
+
+/// [`ReadPayload`] as signed up to version `1` of the
+/// `MedicalHistoryReaderApi` runtime API, before it carried a nonce.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReadPayloadV1<AccountId, BlockNumber> {
+    /// Account asking for the record (the patient or a doctor).
+    pub reader: AccountId,
+    /// Patient that owns the record.
+    pub patient: AccountId,
+    /// Hash of the requested record.
+    pub file_hash: FileHash,
+    /// Last block at which the request is accepted.
+    pub valid_until: BlockNumber,
+}
+
+/// Off-chain listing request, signed by `reader`.
+///
+/// Lists the records of a patient (`owner` is the patient) or the records a
+/// doctor created (`owner` is the doctor, who must be the reader).
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ListPayload<AccountId, BlockNumber> {
+    /// Account asking for the list.
+    pub reader: AccountId,
+    /// Patient or doctor whose records are listed.
+    pub owner: AccountId,
+    /// Last block at which the request is accepted.
+    pub valid_until: BlockNumber,
+    /// Random value picked by the reader for each request, as in
+    /// [`ReadPayload::nonce`].
+    pub nonce: u64,
+}
+
+impl<AccountId: Encode, BlockNumber: Encode> ListPayload<AccountId, BlockNumber> {
+    /// Bytes the reader must sign: [`LIST_DOMAIN`] followed by the
+    /// SCALE-encoded payload.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = LIST_DOMAIN.to_vec();
+        self.encode_to(&mut message);
+        message
+    }
+}
+
+/// Reasons an off-chain read request is refused.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ReadError {
+    /// The signature does not match `reader`.
+    BadSignature,
+    /// `valid_until` is in the past.
+    Expired,
+    /// `valid_until` is further ahead than `MaxReadValidity` allows.
+    ValidityTooLong,
+    /// `reader` is neither the patient nor a doctor with read access, or
+    /// lists the records of another doctor.
+    AccessDenied,
+    /// The record does not exist for that patient.
+    RecordNotFound,
+}
@@ -40,6 +40,10 @@ pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Storage: `MedicalHistory::PatientRecords` (r:1 w:0)
 	/// Proof: `MedicalHistory::PatientRecords` (`max_values`: None, `max_size`: Some(236), added: 2711, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Storage: `MedicalHistoryReader::AccessLogNext` (r:1 w:1)
+	/// Storage: `MedicalHistoryReader::AccessLog` (r:0 w:2)
+	// Leituras/escritas do log de acesso ajustadas manualmente até o próximo benchmark.
 	fn read_own_data() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `270`
@@ -47,12 +51,17 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 23_638_000 picoseconds.
 		Weight::from_parts(24_024_000, 0)
 			.saturating_add(Weight::from_parts(0, 3701))
-			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
 	}
 	/// Storage: `MedicalPermissions::Permissions` (r:1 w:0)
 	/// Proof: `MedicalPermissions::Permissions` (`max_values`: None, `max_size`: Some(97), added: 2572, mode: `MaxEncodedLen`)
 	/// Storage: `MedicalHistory::PatientRecords` (r:1 w:0)
 	/// Proof: `MedicalHistory::PatientRecords` (`max_values`: None, `max_size`: Some(236), added: 2711, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Storage: `MedicalHistoryReader::AccessLogNext` (r:1 w:1)
+	/// Storage: `MedicalHistoryReader::AccessLog` (r:0 w:2)
+	// Leituras/escritas do log de acesso ajustadas manualmente até o próximo benchmark.
 	fn read_patient_data() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `397`
@@ -60,6 +69,7 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 32_935_000 picoseconds.
 		Weight::from_parts(33_427_000, 0)
 			.saturating_add(Weight::from_parts(0, 3701))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
 	}
 }
@@ -5,10 +5,14 @@ use super::*;
 use crate::Pallet as MedicalHistory;
 use frame_benchmarking::v2::*;
 use frame_system::RawOrigin;
-use frame_support::BoundedVec;
-
-// CORREÇÃO: Importamos a macro 'vec' do scale_info para funcionar no ambiente no_std
-use scale_info::prelude::vec;
+use frame_support::{
+    sp_runtime::traits::Saturating,
+    traits::{
+        fungible::{Inspect, Unbalanced},
+        tokens::Precision,
+    },
+    BoundedVec,
+};
 
 #[benchmarks]
 mod benchmarks {
@@ -19,12 +23,23 @@ mod benchmarks {
         // 1. Setup: Define quem chama (médico) e o paciente
         let caller: T::AccountId = whitelisted_caller();
         let patient: T::AccountId = account("patient", 0, 0);
+        let funds = T::Currency::minimum_balance().saturating_add(T::RecordDeposit::get());
+        let _ = T::Currency::increase_balance(&caller, funds, Precision::BestEffort);
         
-        // 2. Setup: Cria um hash dummy de 64 bytes
-        let file_hash: FileHash = BoundedVec::try_from(vec![1u8; 64]).unwrap();
+        // 2. Setup: Usa o maior CID aceito (CIDv1 base32 com Blake2b-256)
+        let file_hash: FileHash = BoundedVec::try_from(
+            b"bafk2bzacedjryyavjrosnbcgkz66c6vrdiklikgip6uhd3gnecy2du6ps4ccu".to_vec(),
+        )
+        .unwrap();
+        let metadata = RecordMetadata {
+            category: RecordCategory::Exam,
+            content_type: BoundedVec::try_from(b"application/pdf".to_vec()).unwrap(),
+            clinical_date: pallet_timestamp::Now::<T>::get(),
+            title_hash: None,
+        };
 
         #[extrinsic_call]
-        create_record(RawOrigin::Signed(caller), patient, file_hash);
+        create_record(RawOrigin::Signed(caller), patient, file_hash, metadata);
     }
 
     impl_benchmark_test_suite!(MedicalHistory, crate::mock::new_test_ext(), crate::mock::Test);
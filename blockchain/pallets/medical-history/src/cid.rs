@@ -0,0 +1,237 @@
+//! Content identifiers accepted as a record's [`FileHash`](crate::FileHash).
+//!
+//! The frontend stores IPFS CIDs in `file_hash`. [`ContentId::parse`]
+//! accepts:
+//! - a raw 32-byte SHA-256 or Blake2-256 digest;
+//! - a binary multihash (`<code><length><digest>`);
+//! - a CIDv0 as base58btc text (`Qm...`);
+//! - a CIDv1, either binary or as multibase base32 text (`b...`).
+//!
+//! Only SHA-256 and Blake2b-256 multihashes are supported. Parsing is done
+//! by hand so the pallet stays `no_std` without extra dependencies.
+
+use alloc::vec::Vec;
+use frame_support::pallet_prelude::RuntimeDebug;
+
+/// Multihash code of SHA2-256.
+const SHA2_256: u64 = 0x12;
+/// Multihash code of Blake2b-256.
+const BLAKE2B_256: u64 = 0xb220;
+/// Length of every supported digest.
+const DIGEST_LEN: usize = 32;
+/// Length of a CIDv0 in base58btc text.
+const CID_V0_TEXT_LEN: usize = 46;
+/// Multibase prefix of lowercase, unpadded base32.
+const MULTIBASE_BASE32: u8 = b'b';
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Hash function of a [`Multihash`].
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum HashAlgorithm {
+    /// SHA2-256 (multihash code `0x12`).
+    Sha256,
+    /// Blake2b-256 (multihash code `0xb220`).
+    Blake2b256,
+}
+
+/// A self-describing digest.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct Multihash {
+    /// Hash function that produced `digest`.
+    pub algorithm: HashAlgorithm,
+    /// The digest itself.
+    pub digest: [u8; DIGEST_LEN],
+}
+
+/// A validated content identifier.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ContentId {
+    /// Raw SHA-256 or Blake2-256 digest, without algorithm information.
+    Digest([u8; DIGEST_LEN]),
+    /// Binary multihash.
+    Multihash(Multihash),
+    /// CIDv0 (always a SHA2-256 multihash).
+    V0(Multihash),
+    /// CIDv1 with its content codec (e.g. `0x70` dag-pb, `0x55` raw).
+    V1 { codec: u64, hash: Multihash },
+}
+
+/// Reason why [`ContentId::parse`] rejected its input.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum CidError {
+    /// The input is empty.
+    Empty,
+    /// The input matches none of the supported formats.
+    UnknownFormat,
+    /// The CIDv0 text is not valid base58btc.
+    InvalidBase58,
+    /// The CIDv1 text is not valid lowercase, unpadded base32.
+    InvalidBase32,
+    /// A varint is malformed or overflows 64 bits.
+    InvalidVarint,
+    /// The CID version is not `1`.
+    UnsupportedVersion,
+    /// The multihash is neither SHA2-256 nor Blake2b-256.
+    UnsupportedHash,
+    /// The multihash declares a digest length other than 32 bytes.
+    InvalidDigestLength,
+    /// The input ends before the digest does.
+    Truncated,
+    /// The input has bytes after the digest.
+    TrailingBytes,
+}
+
+impl ContentId {
+    /// Parses and validates `bytes`.
+    ///
+    /// Formats are told apart by length and first byte: exactly 32 bytes is
+    /// a raw digest, `Q` starts a CIDv0, `b` a base32 CIDv1, `0x01` a binary
+    /// CIDv1, and anything else must be a binary multihash.
+    pub fn parse(bytes: &[u8]) -> Result<Self, CidError> {
+        match bytes {
+            [] => Err(CidError::Empty),
+            _ if bytes.len() == DIGEST_LEN => {
+                let mut digest = [0u8; DIGEST_LEN];
+                digest.copy_from_slice(bytes);
+                Ok(Self::Digest(digest))
+            },
+            [b'Q', ..] => {
+                if bytes.len() != CID_V0_TEXT_LEN {
+                    return Err(CidError::InvalidBase58);
+                }
+                let decoded = decode_base58(bytes)?;
+                let hash = parse_multihash_exact(&decoded)?;
+                if hash.algorithm != HashAlgorithm::Sha256 {
+                    return Err(CidError::UnsupportedHash);
+                }
+                Ok(Self::V0(hash))
+            },
+            [MULTIBASE_BASE32, text @ ..] => parse_cid_v1(&decode_base32(text)?),
+            [0x01, ..] => parse_cid_v1(bytes),
+            _ => match parse_multihash_exact(bytes) {
+                Err(CidError::InvalidVarint | CidError::UnsupportedHash) =>
+                    Err(CidError::UnknownFormat),
+                other => other.map(Self::Multihash),
+            },
+        }
+    }
+
+    /// Returns the digest, whatever the format.
+    pub fn digest(&self) -> &[u8; DIGEST_LEN] {
+        match self {
+            Self::Digest(digest) => digest,
+            Self::Multihash(hash) | Self::V0(hash) | Self::V1 { hash, .. } => &hash.digest,
+        }
+    }
+
+    /// Returns the hash function, if the format carries it.
+    pub fn algorithm(&self) -> Option<HashAlgorithm> {
+        match self {
+            Self::Digest(_) => None,
+            Self::Multihash(hash) | Self::V0(hash) | Self::V1 { hash, .. } =>
+                Some(hash.algorithm),
+        }
+    }
+}
+
+/// Parses a binary CIDv1: `<version><codec><multihash>`.
+fn parse_cid_v1(bytes: &[u8]) -> Result<ContentId, CidError> {
+    let (version, rest) = read_varint(bytes)?;
+    if version != 1 {
+        return Err(CidError::UnsupportedVersion);
+    }
+    let (codec, rest) = read_varint(rest)?;
+    let hash = parse_multihash_exact(rest)?;
+
+    Ok(ContentId::V1 { codec, hash })
+}
+
+/// Parses a multihash that must span the whole input.
+fn parse_multihash_exact(bytes: &[u8]) -> Result<Multihash, CidError> {
+    let (code, rest) = read_varint(bytes)?;
+    let algorithm = match code {
+        SHA2_256 => HashAlgorithm::Sha256,
+        BLAKE2B_256 => HashAlgorithm::Blake2b256,
+        _ => return Err(CidError::UnsupportedHash),
+    };
+
+    let (length, rest) = read_varint(rest)?;
+    if length != DIGEST_LEN as u64 {
+        return Err(CidError::InvalidDigestLength);
+    }
+    if rest.len() < DIGEST_LEN {
+        return Err(CidError::Truncated);
+    }
+    if rest.len() > DIGEST_LEN {
+        return Err(CidError::TrailingBytes);
+    }
+
+    let mut digest = [0u8; DIGEST_LEN];
+    digest.copy_from_slice(rest);
+    Ok(Multihash { algorithm, digest })
+}
+
+/// Reads an unsigned LEB128 varint, returning it and the remaining bytes.
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), CidError> {
+    let mut value: u64 = 0;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[i + 1..]));
+        }
+    }
+    Err(CidError::InvalidVarint)
+}
+
+/// Decodes base58btc text.
+fn decode_base58(text: &[u8]) -> Result<Vec<u8>, CidError> {
+    // Little-endian accumulator, reversed at the end.
+    let mut out: Vec<u8> = Vec::with_capacity(text.len());
+    for c in text {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|a| a == c)
+            .ok_or(CidError::InvalidBase58)? as u32;
+        for byte in out.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            out.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' encodes a leading zero byte.
+    out.extend(text.iter().take_while(|c| **c == b'1').map(|_| 0));
+    out.reverse();
+
+    Ok(out)
+}
+
+/// Decodes lowercase, unpadded RFC 4648 base32 text.
+fn decode_base32(text: &[u8]) -> Result<Vec<u8>, CidError> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+    for c in text {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|a| a == c)
+            .ok_or(CidError::InvalidBase32)? as u32;
+        buffer = ((buffer << 5) | value) & 0xfff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    // Leftover bits are padding and must be zero.
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(CidError::InvalidBase32);
+    }
+
+    Ok(out)
+}
@@ -5,6 +5,37 @@
 //! Stores immutable references (hashes) to medical files and indexes them by
 //! patient and doctor.
 //!
+//! ## Amendments
+//! A record is never modified. To correct it, its author calls
+//! `amend_record`, which adds the new file as a separate record linked to
+//! the previous one (`previous` / `superseded_by`), forming a version chain.
+//!
+//! ## Redaction
+//! Once [`Config::RetentionPeriod`] has passed, a patient may call
+//! `redact_record` to erase a record (and its whole version chain) from the
+//! indexes. A commitment computed by the patient off-chain is kept as a
+//! [`Tombstone`] instead, and [`OnRecordRedacted`] lets other pallets drop
+//! their copies of the hash.
+//!
+//! ## Key envelopes
+//! Files are encrypted with a per-file symmetric key. `share_key` stores
+//! that key wrapped to a recipient's public key (a [`WrappedKey`]
+//! envelope). When a doctor holding envelopes loses read access, the pair is
+//! flagged in `KeyRotationRequired`. The patient then re-encrypts each file
+//! the doctor could decrypt and replaces its envelopes with
+//! `complete_key_rotation`; the flag is cleared once the doctor holds no
+//! envelope anymore.
+//!
+//! ## Self-reported records
+//! A patient may add documents they already hold with `submit_own_record`.
+//! Such records carry [`Provenance::SelfReported`] and can later be vouched
+//! for by a doctor with write access through `endorse_record`.
+//!
+//! ## Laboratory results
+//! Exam results reach the history through [`LabResultRecorder`]: the
+//! laboratory is the record's author ([`Provenance::LaboratoryReported`])
+//! and the doctor that ordered the exam is kept in `OrderingDoctors`.
+//!
 //! ## Cross-pallet access
 //! This pallet exposes [`MedicalHistoryAccessor`] so other pallets (e.g. a
 //! reader pallet) can fetch a patient-scoped record without depending on
@@ -12,6 +43,8 @@
 
 pub use pallet::*;
 
+extern crate alloc;
+
 #[cfg(test)]
 mod mock;
 
@@ -28,7 +61,37 @@ pub use weights::*;
 pub mod types;
 pub use types::*;
 
-use pallet_medical_permissions::MedicalPermissionsVerifier;
+pub mod cid;
+pub use cid::*;
+
+pub mod migrations;
+
+use alloc::vec::Vec;
+use frame_support::{
+    dispatch::DispatchResult,
+    traits::{fungible, Get},
+    weights::Weight,
+};
+use pallet_doctor_registry::DoctorRegistryVerifier;
+use pallet_medical_permissions::{MedicalPermissionsVerifier, OnAccessRevoked};
+
+/// Maximum number of entries returned by one page of [`Pallet::records_of_patient`]
+/// or [`Pallet::records_by_doctor`].
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Balance type of the configured [`fungible`] currency.
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as fungible::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Maximum number of versions in a record's chain: the original record and
+/// up to [`Config::MaxVersions`] amendments.
+pub struct MaxChainLength<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> Get<u32> for MaxChainLength<T> {
+    fn get() -> u32 {
+        T::MaxVersions::get().saturating_add(1)
+    }
+}
 
 /// Public interface used by external pallets (e.g. `medical-history-reader`)
 /// to query a patient's record.
@@ -49,17 +112,127 @@ pub trait MedicalHistoryAccessor<AccountId, Moment> {
         patient: &AccountId,
         file_hash: &FileHash,
     ) -> Option<MedicalRecord<AccountId, Moment>>;
+
+    /// Returns the category, content type and clinical date of a record.
+    fn get_record_metadata(
+        patient: &AccountId,
+        file_hash: &FileHash,
+    ) -> Option<RecordMetadata<Moment>> {
+        Self::get_patient_record(patient, file_hash).map(|record| record.metadata)
+    }
+
+    /// Like [`Self::get_patient_record`], but follows amendments and returns
+    /// the latest version of the record.
+    fn get_latest_patient_record(
+        patient: &AccountId,
+        file_hash: &FileHash,
+    ) -> Option<MedicalRecord<AccountId, Moment>> {
+        Self::get_patient_record(patient, file_hash)
+    }
+
+    /// Returns every version of the record, oldest first.
+    ///
+    /// Empty if the record does not exist for `patient`.
+    fn get_patient_record_history(
+        patient: &AccountId,
+        file_hash: &FileHash,
+    ) -> Vec<MedicalRecord<AccountId, Moment>> {
+        Self::get_patient_record(patient, file_hash).into_iter().collect()
+    }
+
+    /// Returns a page of `patient`'s records, oldest first, starting at
+    /// position `from`. If `provenance` is set, only records with that
+    /// provenance are returned.
+    fn get_patient_records(
+        _patient: &AccountId,
+        _provenance: Option<Provenance>,
+        _from: u64,
+        _limit: u32,
+    ) -> RecordPage<MedicalRecord<AccountId, Moment>> {
+        RecordPage { items: Vec::new(), next: None }
+    }
+
+    /// Returns a page of `(patient, record)` pairs created by `doctor`,
+    /// oldest first, starting at position `from`.
+    fn get_doctor_records(
+        _doctor: &AccountId,
+        _provenance: Option<Provenance>,
+        _from: u64,
+        _limit: u32,
+    ) -> RecordPage<(AccountId, MedicalRecord<AccountId, Moment>)> {
+        RecordPage { items: Vec::new(), next: None }
+    }
+}
+
+/// Public interface used by external pallets (e.g. an exam-orders pallet)
+/// to index laboratory results.
+///
+/// The caller is responsible for checking that `laboratory` fulfilled an
+/// order `ordered_by` placed for `patient`.
+pub trait LabResultRecorder<AccountId, Moment> {
+    /// Adds `file_hash` to `patient`'s history, authored by `laboratory`
+    /// and linked to the doctor that ordered the exam.
+    fn record_lab_result(
+        patient: &AccountId,
+        laboratory: &AccountId,
+        ordered_by: &AccountId,
+        file_hash: FileHash,
+        metadata: RecordMetadata<Moment>,
+    ) -> DispatchResult;
+}
+
+/// Hook for pallets that keep record hashes of their own (e.g. an audit
+/// trail) and must forget them when a patient redacts a record.
+pub trait OnRecordRedacted<AccountId, Hash> {
+    /// Called after `patient` redacted a record. `redacted` holds each
+    /// erased version with the commitment of its [`Tombstone`].
+    fn on_record_redacted(patient: &AccountId, redacted: &[(FileHash, Hash)]);
+
+    /// Upper bound of the weight of one [`Self::on_record_redacted`] call.
+    fn weight() -> Weight;
+}
+
+impl<AccountId, Hash> OnRecordRedacted<AccountId, Hash> for () {
+    fn on_record_redacted(_patient: &AccountId, _redacted: &[(FileHash, Hash)]) {}
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Calls both hooks, in order.
+impl<AccountId, Hash, A, B> OnRecordRedacted<AccountId, Hash> for (A, B)
+where
+    A: OnRecordRedacted<AccountId, Hash>,
+    B: OnRecordRedacted<AccountId, Hash>,
+{
+    fn on_record_redacted(patient: &AccountId, redacted: &[(FileHash, Hash)]) {
+        A::on_record_redacted(patient, redacted);
+        B::on_record_redacted(patient, redacted);
+    }
+
+    fn weight() -> Weight {
+        A::weight().saturating_add(B::weight())
+    }
 }
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{One, Saturating},
+        traits::{fungible::MutateHold, tokens::Precision},
+    };
     use frame_system::pallet_prelude::*;
 
     // NOTA: FileHash e MedicalRecord agora vêm de `use super::*;` (types.rs)
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Pallet configuration.
@@ -70,6 +243,62 @@ pub mod pallet {
 
         /// Permissions verifier used to authorize doctors.
         type Permissions: MedicalPermissionsVerifier<Self::AccountId>;
+
+        /// Registry used to make sure record authors are licensed doctors.
+        type DoctorRegistry: DoctorRegistryVerifier<Self::AccountId>;
+
+        /// Number of blocks a patient has to confirm a proposed record.
+        #[pallet::constant]
+        type ConfirmationWindow: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of queued proposal expiries processed per block.
+        ///
+        /// Any number of proposals may expire in the same block; the ones
+        /// above this limit are discarded in the following blocks.
+        #[pallet::constant]
+        type MaxProposalsPerBlock: Get<u32>;
+
+        /// Whether `create_record` is disabled in favour of the
+        /// `propose_record` / `confirm_record` flow.
+        #[pallet::constant]
+        type RequireConfirmation: Get<bool>;
+
+        /// Maximum number of amendments in a record's version chain.
+        #[pallet::constant]
+        type MaxVersions: Get<u32>;
+
+        /// Legal retention period: minimum age of a record, in timestamp
+        /// units, before its patient may redact it.
+        #[pallet::constant]
+        type RetentionPeriod: Get<Self::Moment>;
+
+        /// Currency used to hold storage deposits.
+        type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Deposit held from the doctor for each proposed or created record.
+        ///
+        /// Released when the proposal is rejected or expires, or when the
+        /// record is redacted.
+        #[pallet::constant]
+        type RecordDeposit: Get<BalanceOf<Self>>;
+
+        /// Maximum number of key envelopes stored per record.
+        #[pallet::constant]
+        type MaxKeyEnvelopes: Get<u32>;
+
+        /// Pallets that must forget the hashes of redacted records.
+        type OnRecordRedacted: OnRecordRedacted<Self::AccountId, Self::Hash>;
+    }
+
+    /// Reasons for which this pallet holds funds.
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Storage deposit of a record or proposal.
+        #[codec(index = 0)]
+        RecordDeposit,
     }
 
     /// Global index: `file_hash -> record`.
@@ -111,6 +340,186 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// Creation-ordered patient index: `(patient, seq) -> file_hash`.
+    ///
+    /// `seq` comes from [`NextRecordSeq`], so iterating a patient's prefix
+    /// walks their records oldest first. Redacted records are removed
+    /// without shifting the position of the others.
+    #[pallet::storage]
+    pub type PatientRecordOrder<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        RecordSeq,
+        FileHash,
+        OptionQuery,
+    >;
+
+    /// Creation-ordered doctor index: `(doctor, seq) -> file_hash`.
+    ///
+    /// Same ordering as [`PatientRecordOrder`], keyed by the record's author.
+    #[pallet::storage]
+    pub type DoctorRecordOrder<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        RecordSeq,
+        FileHash,
+        OptionQuery,
+    >;
+
+    /// Position of each record in [`PatientRecordOrder`] and
+    /// [`DoctorRecordOrder`]: `file_hash -> seq`.
+    #[pallet::storage]
+    pub type RecordSeqs<T: Config> =
+        StorageMap<_, Blake2_128Concat, FileHash, RecordSeq, OptionQuery>;
+
+    /// Sequence number given to the next record.
+    #[pallet::storage]
+    pub type NextRecordSeq<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Proposals waiting for patient confirmation: `file_hash -> proposal`.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_records)]
+    pub type PendingRecords<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        FileHash,
+        PendingRecord<T::AccountId, T::Moment, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Proposals scheduled to expire at a given block:
+    /// `(expires_at, file_hash) -> ()`.
+    ///
+    /// Holds exactly one entry per entry of [`PendingRecords`]. Drained in
+    /// batches by [`Hooks::on_initialize`], starting at
+    /// [`ProposalExpiryCursor`].
+    #[pallet::storage]
+    pub type ProposalExpiryQueue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        FileHash,
+        (),
+        OptionQuery,
+    >;
+
+    /// First block of [`ProposalExpiryQueue`] that may still hold entries.
+    #[pallet::storage]
+    pub type ProposalExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Amendment links: `amended file_hash -> amendment`.
+    ///
+    /// Points from a version to the one it replaced.
+    #[pallet::storage]
+    #[pallet::getter(fn amendments)]
+    pub type Amendments<T: Config> =
+        StorageMap<_, Blake2_128Concat, FileHash, Amendment<T::Hash>, OptionQuery>;
+
+    /// Forward links: `file_hash -> hash of the version that replaced it`.
+    #[pallet::storage]
+    #[pallet::getter(fn superseded_by)]
+    pub type SupersededBy<T: Config> =
+        StorageMap<_, Blake2_128Concat, FileHash, FileHash, OptionQuery>;
+
+    /// Amendments proposed through the confirmation flow:
+    /// `proposed file_hash -> amendment`, applied on `confirm_record`.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_amendments)]
+    pub type PendingAmendments<T: Config> =
+        StorageMap<_, Blake2_128Concat, FileHash, Amendment<T::Hash>, OptionQuery>;
+
+    /// Deposits held for records and proposals: `file_hash -> (depositor, amount)`.
+    ///
+    /// Records created before deposits existed have no entry here.
+    #[pallet::storage]
+    #[pallet::getter(fn record_deposits)]
+    pub type RecordDeposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, FileHash, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+    /// Key envelopes: `(file_hash, recipient) -> wrapped file key`.
+    #[pallet::storage]
+    #[pallet::getter(fn key_envelopes)]
+    pub type KeyEnvelopes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        FileHash,
+        Blake2_128Concat,
+        T::AccountId,
+        WrappedKey,
+        OptionQuery,
+    >;
+
+    /// Number of envelopes stored for a record, bounded by
+    /// [`Config::MaxKeyEnvelopes`].
+    #[pallet::storage]
+    pub type KeyEnvelopeCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, FileHash, u32, ValueQuery>;
+
+    /// Number of envelopes a recipient holds for a patient's records:
+    /// `(patient, recipient) -> count`.
+    #[pallet::storage]
+    pub type SharedKeys<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
+        ValueQuery,
+    >;
+
+    /// Doctors that lost read access while holding envelopes:
+    /// `(patient, doctor) -> block of the revocation`.
+    #[pallet::storage]
+    #[pallet::getter(fn key_rotation_required)]
+    pub type KeyRotationRequired<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
+    /// Endorsements of self-reported records: `file_hash -> endorsement`.
+    #[pallet::storage]
+    #[pallet::getter(fn endorsements)]
+    pub type Endorsements<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        FileHash,
+        Endorsement<T::AccountId, T::Moment>,
+        OptionQuery,
+    >;
+
+    /// Doctors that ordered laboratory results: `file_hash -> doctor`.
+    #[pallet::storage]
+    #[pallet::getter(fn ordering_doctors)]
+    pub type OrderingDoctors<T: Config> =
+        StorageMap<_, Blake2_128Concat, FileHash, T::AccountId, OptionQuery>;
+
+    /// Redacted records: `(patient, commitment) -> tombstone`.
+    ///
+    /// The commitment is chosen by the patient, e.g. `hash(salt ++ file_hash)`
+    /// with a salt they keep off-chain.
+    #[pallet::storage]
+    #[pallet::getter(fn tombstones)]
+    pub type Tombstones<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::Hash,
+        Tombstone<T::AccountId, T::Moment>,
+        OptionQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -123,6 +532,123 @@ pub mod pallet {
             /// The file hash reference.
             hash: FileHash,
         },
+        /// A doctor proposed a record that awaits patient confirmation.
+        RecordProposed {
+            /// The patient that must confirm the record.
+            patient: T::AccountId,
+            /// The doctor that proposed the record.
+            doctor: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+            /// Block from which the proposal can no longer be confirmed.
+            expires_at: BlockNumberFor<T>,
+        },
+        /// The patient confirmed a proposed record.
+        RecordConfirmed {
+            /// The patient that owns the record.
+            patient: T::AccountId,
+            /// The doctor that proposed the record.
+            doctor: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+        },
+        /// The patient rejected a proposed record.
+        RecordRejected {
+            /// The patient that rejected the record.
+            patient: T::AccountId,
+            /// The doctor that proposed the record.
+            doctor: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+        },
+        /// A proposal was not confirmed in time and was discarded.
+        RecordProposalExpired {
+            /// The patient that did not confirm the record.
+            patient: T::AccountId,
+            /// The doctor that proposed the record.
+            doctor: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+        },
+        /// A record was superseded by an amended version.
+        RecordAmended {
+            /// The patient that owns the record.
+            patient: T::AccountId,
+            /// The doctor that amended the record.
+            doctor: T::AccountId,
+            /// Hash of the replaced version.
+            previous: FileHash,
+            /// Hash of the new version.
+            hash: FileHash,
+            /// Hash of the off-chain justification.
+            reason: T::Hash,
+        },
+        /// A wrapped file key was stored for a recipient.
+        KeyShared {
+            /// The patient that owns the record.
+            patient: T::AccountId,
+            /// The account that can unwrap the key.
+            recipient: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+        },
+        /// A doctor holding envelopes lost read access; the patient's file
+        /// keys must be rotated.
+        KeyRotationRequired {
+            /// The patient whose keys must be rotated.
+            patient: T::AccountId,
+            /// The doctor that lost access.
+            doctor: T::AccountId,
+        },
+        /// `doctor` holds no envelope of the patient's records anymore: every
+        /// key it knew was rotated or its record redacted.
+        KeyRotationCompleted {
+            /// The patient that rotated the keys.
+            patient: T::AccountId,
+            /// The doctor that lost access.
+            doctor: T::AccountId,
+        },
+        /// The patient redacted a record; only its commitment remains.
+        RecordRedacted {
+            /// The patient that owned the record.
+            patient: T::AccountId,
+            /// Commitment the patient chose for the erased record.
+            commitment: T::Hash,
+        },
+        /// A patient added a self-reported record to their history.
+        RecordSubmitted {
+            /// The patient that owns the record.
+            patient: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+        },
+        /// A doctor endorsed a self-reported record.
+        RecordEndorsed {
+            /// The patient that owns the record.
+            patient: T::AccountId,
+            /// The doctor that endorsed the record.
+            doctor: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+        },
+        /// A laboratory result was added to a patient's history.
+        LabResultRecorded {
+            /// The patient that owns the record.
+            patient: T::AccountId,
+            /// The laboratory that authored the record.
+            laboratory: T::AccountId,
+            /// The doctor that ordered the exam.
+            ordered_by: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+        },
+        /// The patient rotated a record's file key and replaced its envelopes.
+        KeyRotated {
+            /// The patient that owns the record.
+            patient: T::AccountId,
+            /// The file hash reference.
+            hash: FileHash,
+        },
     }
 
     #[pallet::error]
@@ -135,6 +661,98 @@ pub mod pallet {
         NotAuthorized,
         /// Doctor does not have permission to write for this patient.
         NoPermission,
+        /// No pending proposal exists for this hash.
+        ProposalNotFound,
+        /// The proposal's confirmation window is over.
+        ProposalExpired,
+        /// Records must be proposed and confirmed by the patient.
+        ConfirmationRequired,
+        /// The doctor is not registered or is suspended.
+        DoctorNotRegistered,
+        /// The record was already amended; amend its latest version instead.
+        AlreadySuperseded,
+        /// The record's version chain reached [`Config::MaxVersions`].
+        TooManyVersions,
+        /// The content type is not a valid MIME type.
+        InvalidContentType,
+        /// The clinical date is later than the current block timestamp.
+        ClinicalDateInFuture,
+        /// The file hash is not a valid CID, multihash or 32-byte digest.
+        InvalidContentId,
+        /// The record is still within the legal retention period.
+        RetentionPeriodActive,
+        /// The recipient is neither the patient nor a doctor with read access.
+        RecipientNotAuthorized,
+        /// The record already has [`Config::MaxKeyEnvelopes`] envelopes.
+        TooManyKeyEnvelopes,
+        /// No key rotation is pending for this doctor.
+        RotationNotRequired,
+        /// Only self-reported records can be endorsed.
+        NotSelfReported,
+        /// The record was already endorsed.
+        AlreadyEndorsed,
+        /// The number of commitments differs from the number of versions of
+        /// the record.
+        CommitmentCountMismatch,
+        /// A commitment is repeated or already keys one of the patient's
+        /// tombstones.
+        CommitmentInUse,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Discards the proposals whose confirmation window ended by block
+        /// `n`.
+        ///
+        /// At most [`Config::MaxProposalsPerBlock`] units of work are done,
+        /// one per proposal and one per block visited; the rest is left for
+        /// the following blocks.
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let limit = T::MaxProposalsPerBlock::get() as usize;
+            let mut budget = limit;
+            let mut block = ProposalExpiryCursor::<T>::get();
+            let mut due = Vec::new();
+
+            while block <= n && budget > 0 {
+                let batch: Vec<_> = ProposalExpiryQueue::<T>::drain_prefix(block)
+                    .take(budget)
+                    .map(|(file_hash, ())| file_hash)
+                    .collect();
+                budget -= batch.len();
+                due.extend(batch);
+                // Out of budget before the block was drained: resume here.
+                if budget == 0 {
+                    break;
+                }
+                budget -= 1;
+                block = block.saturating_add(One::one());
+            }
+            ProposalExpiryCursor::<T>::put(block);
+
+            let count = due.len() as u64;
+            for file_hash in due {
+                // The queue mirrors `PendingRecords`; checked defensively.
+                let Some(pending) = PendingRecords::<T>::get(&file_hash) else {
+                    continue;
+                };
+                if pending.expires_at > n {
+                    continue;
+                }
+
+                PendingRecords::<T>::remove(&file_hash);
+                PendingAmendments::<T>::remove(&file_hash);
+                Self::release_deposit(&file_hash);
+
+                Self::deposit_event(Event::RecordProposalExpired {
+                    patient: pending.patient,
+                    doctor: pending.doctor,
+                    hash: file_hash,
+                });
+            }
+
+            let visited = (limit - budget) as u64 - count;
+            T::DbWeight::get().reads_writes(1 + visited + 3 * count, 1 + 5 * count)
+        }
     }
 
     #[pallet::call]
@@ -144,68 +762,1072 @@ pub mod pallet {
         /// # Parameters
         /// - `origin`: Must be signed (doctor).
         /// - `patient`: Patient account that owns the record.
-        /// - `file_hash`: 64-byte file hash reference.
+        /// - `file_hash`: Content identifier of the file (see [`ContentId::parse`]).
+        /// - `metadata`: Category, content type and clinical date of the file.
         ///
         /// # Authorization
-        /// Requires `T::Permissions::has_access(patient, doctor) == true`.
+        /// Requires `T::Permissions::can_write(patient, doctor) == true`.
         ///
         /// # Storage
-        /// - Writes: [`Records`], [`DoctorRecords`], [`PatientRecords`]
+        /// - Writes: [`Records`], [`DoctorRecords`], [`PatientRecords`], [`RecordDeposits`]
+        /// - Appends to: [`PatientRecordOrder`], [`DoctorRecordOrder`]
+        /// - Holds [`Config::RecordDeposit`] from the doctor
         ///
         /// # Emits
         /// - [`Event::RecordCreated`]
         ///
         /// # Errors
+        /// - [`Error::ConfirmationRequired`]: if [`Config::RequireConfirmation`] is set.
+        /// - [`Error::DoctorNotRegistered`]: if the doctor is not an active doctor.
         /// - [`Error::NoPermission`]: if the doctor lacks permission.
         /// - [`Error::RecordAlreadyExists`]: if `file_hash` already exists in [`Records`].
+        /// - [`Error::InvalidContentId`]: if `file_hash` is not a valid content identifier.
+        /// - [`Error::InvalidContentType`] / [`Error::ClinicalDateInFuture`]: on
+        ///   invalid metadata.
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000 + T::DbWeight::get().writes(3).ref_time())]
+        #[pallet::weight(T::WeightInfo::create_record())]
         pub fn create_record(
             origin: OriginFor<T>,
             patient: T::AccountId,
             file_hash: FileHash,
+            metadata: RecordMetadata<T::Moment>,
         ) -> DispatchResult {
             let doctor = ensure_signed(origin)?;
 
-            if !T::Permissions::has_access(&patient, &doctor) {
-                return Err(Error::<T>::NoPermission.into());
-            }
-
-            if patient == doctor {
-                //Uma pessoa não pode alterar seu próprio prontuário.
-                return Err(Error::<T>::NoPermission.into());
-            }
-
             ensure!(
-                !Records::<T>::contains_key(&file_hash),
-                Error::<T>::RecordAlreadyExists
+                !T::RequireConfirmation::get(),
+                Error::<T>::ConfirmationRequired
             );
 
-            let now = pallet_timestamp::Now::<T>::get();
-
-            let record = MedicalRecord {
-                created_by: doctor.clone(),
-                created_at: now,
-                file_hash: file_hash.clone(),
-            };
-
-            // 1) Global index
-            Records::<T>::insert(&file_hash, record.clone());
-
-            // 2) Doctor index
-            DoctorRecords::<T>::insert(&doctor, &file_hash, (patient.clone(), now));
-
-            // 3) Patient index
-            PatientRecords::<T>::insert(&patient, &file_hash, record);
+            Self::ensure_can_write(&patient, &doctor, &file_hash)?;
+            Self::ensure_valid_metadata(&metadata)?;
+            Self::hold_deposit(&doctor, &file_hash)?;
 
-            Self::deposit_event(Event::RecordCreated {
+            Self::insert_record(
                 patient,
                 doctor,
-                hash: file_hash,
+                file_hash,
+                metadata,
+                Provenance::ClinicianAuthored,
+            );
+
+            Ok(())
+        }
+
+        /// Proposes a new record that only becomes part of the patient's
+        /// history once the patient confirms it.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (doctor).
+        /// - `patient`: Patient account that must confirm the record.
+        /// - `file_hash`: Content identifier of the file (see [`ContentId::parse`]).
+        /// - `metadata`: Category, content type and clinical date of the file.
+        ///
+        /// # Authorization
+        /// Requires `T::Permissions::can_write(patient, doctor) == true`.
+        ///
+        /// # Storage
+        /// - Writes: [`PendingRecords`], [`ProposalExpiryQueue`], [`RecordDeposits`]
+        /// - Holds [`Config::RecordDeposit`] from the doctor
+        ///
+        /// # Emits
+        /// - [`Event::RecordProposed`]
+        ///
+        /// # Errors
+        /// - [`Error::DoctorNotRegistered`]: if the doctor is not an active doctor.
+        /// - [`Error::NoPermission`]: if the doctor lacks permission.
+        /// - [`Error::RecordAlreadyExists`]: if `file_hash` is already recorded or proposed.
+        /// - [`Error::InvalidContentId`]: if `file_hash` is not a valid content identifier.
+        /// - [`Error::InvalidContentType`] / [`Error::ClinicalDateInFuture`]: on
+        ///   invalid metadata.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::propose_record())]
+        pub fn propose_record(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            file_hash: FileHash,
+            metadata: RecordMetadata<T::Moment>,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
+            Self::ensure_can_write(&patient, &doctor, &file_hash)?;
+            Self::ensure_valid_metadata(&metadata)?;
+            Self::hold_deposit(&doctor, &file_hash)?;
+
+            Self::insert_pending(patient, doctor, file_hash, metadata)
+        }
+
+        /// Confirms a proposed record and indexes it.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (patient the record was proposed for).
+        /// - `file_hash`: Hash of the proposed record.
+        ///
+        /// If the proposal is an amendment (see [`Pallet::amend_record`]), the
+        /// version link is applied as well.
+        ///
+        /// # Storage
+        /// - Removes: [`PendingRecords`], [`PendingAmendments`]
+        /// - Writes: [`Records`], [`DoctorRecords`], [`PatientRecords`]
+        /// - Appends to: [`PatientRecordOrder`], [`DoctorRecordOrder`]
+        /// - Writes (amendments): [`Amendments`], [`SupersededBy`]
+        ///
+        /// # Emits
+        /// - [`Event::RecordConfirmed`]
+        /// - [`Event::RecordCreated`]
+        /// - [`Event::RecordAmended`] (amendments only)
+        ///
+        /// # Errors
+        /// - [`Error::ProposalNotFound`]: if there is no such proposal.
+        /// - [`Error::NotAuthorized`]: if the caller is not the patient.
+        /// - [`Error::ProposalExpired`]: if the confirmation window is over.
+        /// - [`Error::DoctorNotRegistered`]: if the proposing doctor is no
+        ///   longer an active doctor.
+        /// - [`Error::NoPermission`]: if the proposing doctor lost write access.
+        /// - [`Error::RecordNotFound`]: if the amended version was redacted in
+        ///   the meantime.
+        /// - [`Error::AlreadySuperseded`]: if the amended version was replaced
+        ///   in the meantime.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::confirm_record())]
+        pub fn confirm_record(origin: OriginFor<T>, file_hash: FileHash) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            let pending = Self::take_pending(&patient, &file_hash)?;
+            ensure!(
+                pending.expires_at > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::ProposalExpired
+            );
+            // The doctor may have been suspended or lost access since proposing.
+            Self::ensure_doctor_can_write(&patient, &pending.doctor)?;
+
+            let amendment = PendingAmendments::<T>::take(&file_hash);
+            if let Some(amendment) = &amendment {
+                ensure!(
+                    Records::<T>::contains_key(&amendment.previous),
+                    Error::<T>::RecordNotFound
+                );
+                ensure!(
+                    !SupersededBy::<T>::contains_key(&amendment.previous),
+                    Error::<T>::AlreadySuperseded
+                );
+            }
+
+            Self::deposit_event(Event::RecordConfirmed {
+                patient: patient.clone(),
+                doctor: pending.doctor.clone(),
+                hash: file_hash.clone(),
             });
 
+            Self::insert_record(
+                patient.clone(),
+                pending.doctor.clone(),
+                file_hash.clone(),
+                pending.metadata,
+                Provenance::ClinicianAuthored,
+            );
+
+            if let Some(amendment) = amendment {
+                Self::link_amendment(patient, pending.doctor, file_hash, amendment);
+            }
+
             Ok(())
         }
+
+        /// Rejects a proposed record; it never reaches the record indexes.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (patient the record was proposed for).
+        /// - `file_hash`: Hash of the proposed record.
+        ///
+        /// # Storage
+        /// - Removes: [`PendingRecords`], [`PendingAmendments`], [`RecordDeposits`]
+        /// - Releases the proposal's deposit
+        ///
+        /// # Emits
+        /// - [`Event::RecordRejected`]
+        ///
+        /// # Errors
+        /// - [`Error::ProposalNotFound`]: if there is no such proposal.
+        /// - [`Error::NotAuthorized`]: if the caller is not the patient.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::reject_record())]
+        pub fn reject_record(origin: OriginFor<T>, file_hash: FileHash) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            let pending = Self::take_pending(&patient, &file_hash)?;
+            PendingAmendments::<T>::remove(&file_hash);
+            Self::release_deposit(&file_hash);
+
+            Self::deposit_event(Event::RecordRejected {
+                patient,
+                doctor: pending.doctor,
+                hash: file_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Replaces a record with a corrected version.
+        ///
+        /// The original record stays untouched; `new_hash` is added as a new
+        /// record linked to it. Only the latest version of a chain can be
+        /// amended. When [`Config::RequireConfirmation`] is set, the new
+        /// version is proposed and the link is applied once the patient
+        /// confirms it.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (doctor that created `original_hash`).
+        /// - `original_hash`: Hash of the version being replaced.
+        /// - `new_hash`: Hash of the corrected file.
+        /// - `reason`: Hash of the off-chain justification.
+        /// - `metadata`: Metadata of the corrected file.
+        ///
+        /// # Authorization
+        /// Requires `T::Permissions::can_write(patient, doctor) == true`.
+        ///
+        /// # Storage
+        /// - Writes: [`Records`], [`DoctorRecords`], [`PatientRecords`],
+        ///   [`Amendments`], [`SupersededBy`]
+        /// - Appends to: [`PatientRecordOrder`], [`DoctorRecordOrder`]
+        /// - Writes (with confirmation): [`PendingRecords`],
+        ///   [`ProposalExpiryQueue`], [`PendingAmendments`]
+        /// - Holds [`Config::RecordDeposit`] from the doctor ([`RecordDeposits`])
+        ///
+        /// # Emits
+        /// - [`Event::RecordCreated`] and [`Event::RecordAmended`], or
+        /// - [`Event::RecordProposed`] (with confirmation)
+        ///
+        /// # Errors
+        /// - [`Error::RecordNotFound`]: if `original_hash` does not exist.
+        /// - [`Error::NotAuthorized`]: if the caller did not create `original_hash`.
+        /// - [`Error::AlreadySuperseded`]: if `original_hash` was already amended.
+        /// - [`Error::TooManyVersions`]: if the chain reached [`Config::MaxVersions`].
+        /// - Any error of [`Pallet::propose_record`] for `new_hash`.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::amend_record())]
+        pub fn amend_record(
+            origin: OriginFor<T>,
+            original_hash: FileHash,
+            new_hash: FileHash,
+            reason: T::Hash,
+            metadata: RecordMetadata<T::Moment>,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
+            ensure!(Records::<T>::contains_key(&original_hash), Error::<T>::RecordNotFound);
+            let (patient, _) = DoctorRecords::<T>::get(&doctor, &original_hash)
+                .ok_or(Error::<T>::NotAuthorized)?;
+
+            ensure!(
+                !SupersededBy::<T>::contains_key(&original_hash),
+                Error::<T>::AlreadySuperseded
+            );
+            let version = Amendments::<T>::get(&original_hash)
+                .map_or(0, |amendment| amendment.version)
+                .saturating_add(1);
+            ensure!(version <= T::MaxVersions::get(), Error::<T>::TooManyVersions);
+
+            Self::ensure_can_write(&patient, &doctor, &new_hash)?;
+            Self::ensure_valid_metadata(&metadata)?;
+            Self::hold_deposit(&doctor, &new_hash)?;
+
+            let amendment = Amendment { previous: original_hash, reason, version };
+
+            if T::RequireConfirmation::get() {
+                PendingAmendments::<T>::insert(&new_hash, amendment);
+                return Self::insert_pending(patient, doctor, new_hash, metadata);
+            }
+
+            Self::insert_record(
+                patient.clone(),
+                doctor.clone(),
+                new_hash.clone(),
+                metadata,
+                Provenance::ClinicianAuthored,
+            );
+            Self::link_amendment(patient, doctor, new_hash, amendment);
+
+            Ok(())
+        }
+
+        /// Erases a record from the indexes (LGPD right to erasure).
+        ///
+        /// Every version of the record's chain is removed from [`Records`],
+        /// [`DoctorRecords`] and [`PatientRecords`], together with its
+        /// amendment links. Each version leaves a [`Tombstone`] keyed by the
+        /// commitment the patient computed for it off-chain, e.g.
+        /// `hash(salt ++ file_hash)`; the patient keeps the salt to prove, if
+        /// needed, which record an audit log entry referred to. Neither the
+        /// salt nor the commitment inputs ever reach the chain.
+        ///
+        /// [`Config::OnRecordRedacted`] is told which commitment replaced
+        /// each hash, so other pallets can scrub their own copies.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (patient that owns the record).
+        /// - `file_hash`: Hash of any version of the record.
+        /// - `commitments`: One commitment per version, oldest first.
+        ///
+        /// # Storage
+        /// - Removes: [`Records`], [`DoctorRecords`], [`PatientRecords`],
+        ///   [`PatientRecordOrder`], [`DoctorRecordOrder`], [`RecordSeqs`],
+        ///   [`Amendments`], [`SupersededBy`], [`Endorsements`], [`RecordDeposits`]
+        /// - Writes: [`Tombstones`]
+        /// - Removes: [`KeyEnvelopes`], [`KeyEnvelopeCount`] of every version,
+        ///   and [`KeyRotationRequired`] of doctors left without envelopes
+        /// - Releases the deposit of every version
+        ///
+        /// # Emits
+        /// - [`Event::RecordRedacted`] for each version
+        ///
+        /// # Errors
+        /// - [`Error::RecordNotFound`]: if the caller has no such record.
+        /// - [`Error::RetentionPeriodActive`]: if a version is younger than
+        ///   [`Config::RetentionPeriod`].
+        /// - [`Error::CommitmentCountMismatch`]: if there is not exactly one
+        ///   commitment per version.
+        /// - [`Error::CommitmentInUse`]: if a commitment is repeated or already
+        ///   keys one of the caller's tombstones.
+        #[pallet::call_index(5)]
+        #[pallet::weight(
+            T::WeightInfo::redact_record(
+                T::MaxVersions::get().saturating_add(1),
+                T::MaxKeyEnvelopes::get(),
+            )
+            .saturating_add(T::OnRecordRedacted::weight())
+        )]
+        pub fn redact_record(
+            origin: OriginFor<T>,
+            file_hash: FileHash,
+            commitments: BoundedVec<T::Hash, MaxChainLength<T>>,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            ensure!(
+                PatientRecords::<T>::contains_key(&patient, &file_hash),
+                Error::<T>::RecordNotFound
+            );
+
+            let now = pallet_timestamp::Now::<T>::get();
+            let retention = T::RetentionPeriod::get();
+            let versions = Self::record_history(&file_hash);
+            ensure!(
+                versions.iter().all(|record| record.created_at.saturating_add(retention) <= now),
+                Error::<T>::RetentionPeriodActive
+            );
+            ensure!(versions.len() == commitments.len(), Error::<T>::CommitmentCountMismatch);
+            for (i, commitment) in commitments.iter().enumerate() {
+                ensure!(
+                    !commitments[..i].contains(commitment)
+                        && !Tombstones::<T>::contains_key(&patient, commitment),
+                    Error::<T>::CommitmentInUse
+                );
+            }
+
+            let mut redacted = Vec::with_capacity(versions.len());
+            for (record, commitment) in versions.into_iter().zip(commitments) {
+                let hash = &record.file_hash;
+
+                Records::<T>::remove(hash);
+                DoctorRecords::<T>::remove(&record.created_by, hash);
+                PatientRecords::<T>::remove(&patient, hash);
+                Self::unindex_record(&patient, &record.created_by, hash);
+                Amendments::<T>::remove(hash);
+                SupersededBy::<T>::remove(hash);
+                Endorsements::<T>::remove(hash);
+                OrderingDoctors::<T>::remove(hash);
+                Self::release_deposit(hash);
+                Self::remove_key_envelopes(&patient, hash);
+
+                Tombstones::<T>::insert(
+                    &patient,
+                    commitment,
+                    Tombstone {
+                        created_by: record.created_by,
+                        created_at: record.created_at,
+                        redacted_at: now,
+                    },
+                );
+
+                Self::deposit_event(Event::RecordRedacted {
+                    patient: patient.clone(),
+                    commitment,
+                });
+                redacted.push((record.file_hash, commitment));
+            }
+            T::OnRecordRedacted::on_record_redacted(&patient, &redacted);
+
+            Ok(())
+        }
+
+        /// Stores the record's file key wrapped to `recipient`'s public key.
+        ///
+        /// Replaces any envelope `recipient` already had for this record.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (the patient, or the record's author
+        ///   while they still have read access).
+        /// - `file_hash`: Hash of the record.
+        /// - `recipient`: The patient or a doctor with read access.
+        /// - `envelope`: The file key wrapped to `recipient`'s x25519 /
+        ///   sr25519-derived public key.
+        ///
+        /// # Storage
+        /// - Writes: [`KeyEnvelopes`], [`KeyEnvelopeCount`], [`SharedKeys`]
+        ///
+        /// # Emits
+        /// - [`Event::KeyShared`]
+        ///
+        /// # Errors
+        /// - [`Error::RecordNotFound`]: if the record does not exist.
+        /// - [`Error::NotAuthorized`]: if the caller may not share the key.
+        /// - [`Error::RecipientNotAuthorized`]: if `recipient` may not read the record.
+        /// - [`Error::TooManyKeyEnvelopes`]: if the record has
+        ///   [`Config::MaxKeyEnvelopes`] envelopes already.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::share_key())]
+        pub fn share_key(
+            origin: OriginFor<T>,
+            file_hash: FileHash,
+            recipient: T::AccountId,
+            envelope: WrappedKey,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let record = Records::<T>::get(&file_hash).ok_or(Error::<T>::RecordNotFound)?;
+            let (patient, _) = DoctorRecords::<T>::get(&record.created_by, &file_hash)
+                .ok_or(Error::<T>::RecordNotFound)?;
+
+            ensure!(
+                who == patient
+                    || (who == record.created_by && T::Permissions::can_read(&patient, &who)),
+                Error::<T>::NotAuthorized
+            );
+            ensure!(
+                Self::may_unwrap(&patient, &recipient, &file_hash),
+                Error::<T>::RecipientNotAuthorized
+            );
+
+            Self::add_key_envelope(&patient, &file_hash, &recipient, envelope)?;
+
+            Self::deposit_event(Event::KeyShared { patient, recipient, hash: file_hash });
+
+            Ok(())
+        }
+
+        /// Replaces every envelope of a record after its file key was rotated.
+        ///
+        /// Called by the patient once the file was re-encrypted under a fresh
+        /// key, with that key wrapped to each remaining recipient. The old
+        /// envelopes are removed, including the ones of doctors that lost
+        /// read access. Once a doctor flagged in [`KeyRotationRequired`] holds
+        /// no envelope of the patient's records anymore, the flag is cleared.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (patient that owns the record).
+        /// - `file_hash`: Hash of a record whose key a flagged doctor held.
+        /// - `envelopes`: The new `(recipient, envelope)` pairs. Each
+        ///   recipient must be the patient or a doctor with read access.
+        ///
+        /// # Storage
+        /// - Writes: [`KeyEnvelopes`], [`KeyEnvelopeCount`], [`SharedKeys`]
+        /// - Removes: [`KeyRotationRequired`] of doctors left without envelopes
+        ///
+        /// # Emits
+        /// - [`Event::KeyRotated`]
+        /// - [`Event::KeyRotationCompleted`] for each doctor whose flag is cleared
+        ///
+        /// # Errors
+        /// - [`Error::RecordNotFound`]: if the caller has no such record.
+        /// - [`Error::RotationNotRequired`]: if no flagged doctor holds an
+        ///   envelope of the record.
+        /// - [`Error::RecipientNotAuthorized`]: if a recipient may not read
+        ///   the record.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::complete_key_rotation(T::MaxKeyEnvelopes::get()))]
+        pub fn complete_key_rotation(
+            origin: OriginFor<T>,
+            file_hash: FileHash,
+            envelopes: BoundedVec<(T::AccountId, WrappedKey), T::MaxKeyEnvelopes>,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            ensure!(
+                PatientRecords::<T>::contains_key(&patient, &file_hash),
+                Error::<T>::RecordNotFound
+            );
+            ensure!(
+                KeyEnvelopes::<T>::iter_key_prefix(&file_hash)
+                    .any(|holder| KeyRotationRequired::<T>::contains_key(&patient, &holder)),
+                Error::<T>::RotationNotRequired
+            );
+            ensure!(
+                envelopes
+                    .iter()
+                    .all(|(recipient, _)| Self::may_unwrap(&patient, recipient, &file_hash)),
+                Error::<T>::RecipientNotAuthorized
+            );
+
+            Self::remove_key_envelopes(&patient, &file_hash);
+            for (recipient, envelope) in envelopes {
+                Self::add_key_envelope(&patient, &file_hash, &recipient, envelope)?;
+            }
+
+            Self::deposit_event(Event::KeyRotated { patient, hash: file_hash });
+
+            Ok(())
+        }
+
+        /// Adds a document the patient already holds (e.g. an exam from
+        /// another country or a wearable export) to their own history.
+        ///
+        /// The record is stored with [`Provenance::SelfReported`] and the
+        /// patient as author. It needs no confirmation, even when
+        /// [`Config::RequireConfirmation`] is set.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (the patient).
+        /// - `file_hash`: Content identifier of the file (see [`ContentId::parse`]).
+        /// - `metadata`: Category, content type and clinical date of the file.
+        ///
+        /// # Storage
+        /// - Writes: [`Records`], [`DoctorRecords`], [`PatientRecords`], [`RecordDeposits`]
+        /// - Appends to: [`PatientRecordOrder`], [`DoctorRecordOrder`]
+        /// - Holds [`Config::RecordDeposit`] from the patient
+        ///
+        /// # Emits
+        /// - [`Event::RecordSubmitted`]
+        ///
+        /// # Errors
+        /// - [`Error::RecordAlreadyExists`]: if `file_hash` is already recorded or proposed.
+        /// - [`Error::InvalidContentId`]: if `file_hash` is not a valid content identifier.
+        /// - [`Error::InvalidContentType`] / [`Error::ClinicalDateInFuture`]: on
+        ///   invalid metadata.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::submit_own_record())]
+        pub fn submit_own_record(
+            origin: OriginFor<T>,
+            file_hash: FileHash,
+            metadata: RecordMetadata<T::Moment>,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            Self::ensure_new_file(&file_hash)?;
+            Self::ensure_valid_metadata(&metadata)?;
+            Self::hold_deposit(&patient, &file_hash)?;
+
+            Self::insert_record(
+                patient.clone(),
+                patient,
+                file_hash,
+                metadata,
+                Provenance::SelfReported,
+            );
+
+            Ok(())
+        }
+
+        /// Endorses a self-reported record: a doctor vouches for its content.
+        ///
+        /// The record itself is not modified; the endorsement is kept in
+        /// [`Endorsements`].
+        ///
+        /// # Parameters
+        /// - `origin`: Must be signed (doctor).
+        /// - `file_hash`: Hash of the self-reported record.
+        ///
+        /// # Authorization
+        /// Requires `T::Permissions::can_write(patient, doctor) == true`.
+        ///
+        /// # Storage
+        /// - Writes: [`Endorsements`]
+        ///
+        /// # Emits
+        /// - [`Event::RecordEndorsed`]
+        ///
+        /// # Errors
+        /// - [`Error::RecordNotFound`]: if the record does not exist.
+        /// - [`Error::NotSelfReported`]: if the record was authored by a doctor.
+        /// - [`Error::AlreadyEndorsed`]: if the record was already endorsed.
+        /// - [`Error::DoctorNotRegistered`]: if the doctor is not an active doctor.
+        /// - [`Error::NoPermission`]: if the doctor lacks write permission.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::endorse_record())]
+        pub fn endorse_record(origin: OriginFor<T>, file_hash: FileHash) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
+            let record = Records::<T>::get(&file_hash).ok_or(Error::<T>::RecordNotFound)?;
+            ensure!(record.provenance == Provenance::SelfReported, Error::<T>::NotSelfReported);
+            ensure!(
+                !Endorsements::<T>::contains_key(&file_hash),
+                Error::<T>::AlreadyEndorsed
+            );
+
+            // Self-reported records are authored by their patient.
+            let patient = record.created_by;
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&doctor),
+                Error::<T>::DoctorNotRegistered
+            );
+            ensure!(
+                patient != doctor && T::Permissions::can_write(&patient, &doctor),
+                Error::<T>::NoPermission
+            );
+
+            Endorsements::<T>::insert(
+                &file_hash,
+                Endorsement {
+                    endorsed_by: doctor.clone(),
+                    endorsed_at: pallet_timestamp::Now::<T>::get(),
+                },
+            );
+
+            Self::deposit_event(Event::RecordEndorsed { patient, doctor, hash: file_hash });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Checks that `doctor` may add `file_hash` to `patient`'s history.
+        fn ensure_can_write(
+            patient: &T::AccountId,
+            doctor: &T::AccountId,
+            file_hash: &FileHash,
+        ) -> DispatchResult {
+            Self::ensure_doctor_can_write(patient, doctor)?;
+            Self::ensure_new_file(file_hash)
+        }
+
+        /// Checks that `doctor` is an active doctor allowed to write to
+        /// `patient`'s history.
+        fn ensure_doctor_can_write(
+            patient: &T::AccountId,
+            doctor: &T::AccountId,
+        ) -> DispatchResult {
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(doctor),
+                Error::<T>::DoctorNotRegistered
+            );
+
+            if !T::Permissions::can_write(patient, doctor) {
+                return Err(Error::<T>::NoPermission.into());
+            }
+
+            if patient == doctor {
+                //Uma pessoa não pode alterar seu próprio prontuário.
+                return Err(Error::<T>::NoPermission.into());
+            }
+
+            Ok(())
+        }
+
+        /// Checks that `file_hash` is a valid content identifier that is
+        /// neither recorded nor proposed yet.
+        fn ensure_new_file(file_hash: &FileHash) -> DispatchResult {
+            ensure!(ContentId::parse(file_hash).is_ok(), Error::<T>::InvalidContentId);
+            ensure!(
+                !Records::<T>::contains_key(file_hash)
+                    && !PendingRecords::<T>::contains_key(file_hash),
+                Error::<T>::RecordAlreadyExists
+            );
+
+            Ok(())
+        }
+
+        /// Returns `true` if `recipient` may hold the key to `patient`'s
+        /// record `file_hash`.
+        fn may_unwrap(
+            patient: &T::AccountId,
+            recipient: &T::AccountId,
+            file_hash: &FileHash,
+        ) -> bool {
+            recipient == patient || T::Permissions::can_read_record(patient, recipient, file_hash)
+        }
+
+        /// Stores `recipient`'s envelope for `file_hash`, replacing any
+        /// previous one.
+        fn add_key_envelope(
+            patient: &T::AccountId,
+            file_hash: &FileHash,
+            recipient: &T::AccountId,
+            envelope: WrappedKey,
+        ) -> DispatchResult {
+            if !KeyEnvelopes::<T>::contains_key(file_hash, recipient) {
+                KeyEnvelopeCount::<T>::try_mutate(file_hash, |count| {
+                    ensure!(*count < T::MaxKeyEnvelopes::get(), Error::<T>::TooManyKeyEnvelopes);
+                    *count += 1;
+                    Ok::<_, Error<T>>(())
+                })?;
+                SharedKeys::<T>::mutate(patient, recipient, |count| {
+                    *count = count.saturating_add(1)
+                });
+            }
+            KeyEnvelopes::<T>::insert(file_hash, recipient, envelope);
+            Ok(())
+        }
+
+        /// Removes every envelope of `file_hash`.
+        ///
+        /// A doctor flagged in [`KeyRotationRequired`] that is left without
+        /// envelopes of `patient`'s records has nothing left to rotate: the
+        /// flag is cleared.
+        fn remove_key_envelopes(patient: &T::AccountId, file_hash: &FileHash) {
+            for (recipient, _) in KeyEnvelopes::<T>::drain_prefix(file_hash) {
+                let left = SharedKeys::<T>::mutate_exists(patient, &recipient, |count| {
+                    *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+                    *count
+                });
+                if left.is_none() && KeyRotationRequired::<T>::take(patient, &recipient).is_some() {
+                    Self::deposit_event(Event::KeyRotationCompleted {
+                        patient: patient.clone(),
+                        doctor: recipient,
+                    });
+                }
+            }
+            KeyEnvelopeCount::<T>::remove(file_hash);
+        }
+
+        /// Holds [`Config::RecordDeposit`] from `depositor` for `file_hash`.
+        fn hold_deposit(depositor: &T::AccountId, file_hash: &FileHash) -> DispatchResult {
+            let deposit = T::RecordDeposit::get();
+            T::Currency::hold(&HoldReason::RecordDeposit.into(), depositor, deposit)?;
+            RecordDeposits::<T>::insert(file_hash, (depositor, deposit));
+
+            Ok(())
+        }
+
+        /// Releases the deposit held for `file_hash`, if any.
+        fn release_deposit(file_hash: &FileHash) {
+            if let Some((depositor, deposit)) = RecordDeposits::<T>::take(file_hash) {
+                // Best effort: the item is gone either way.
+                let _ = T::Currency::release(
+                    &HoldReason::RecordDeposit.into(),
+                    &depositor,
+                    deposit,
+                    Precision::BestEffort,
+                );
+            }
+        }
+
+        /// Removes and returns the proposal for `file_hash`, checking that
+        /// it belongs to `patient`.
+        fn take_pending(
+            patient: &T::AccountId,
+            file_hash: &FileHash,
+        ) -> Result<PendingRecord<T::AccountId, T::Moment, BlockNumberFor<T>>, DispatchError> {
+            let pending =
+                PendingRecords::<T>::get(file_hash).ok_or(Error::<T>::ProposalNotFound)?;
+            ensure!(&pending.patient == patient, Error::<T>::NotAuthorized);
+
+            PendingRecords::<T>::remove(file_hash);
+            ProposalExpiryQueue::<T>::remove(pending.expires_at, file_hash);
+
+            Ok(pending)
+        }
+
+        /// Checks the content type and that the clinical date is not later
+        /// than the current block timestamp.
+        fn ensure_valid_metadata(metadata: &RecordMetadata<T::Moment>) -> DispatchResult {
+            ensure!(metadata.has_valid_content_type(), Error::<T>::InvalidContentType);
+            ensure!(
+                metadata.clinical_date <= pallet_timestamp::Now::<T>::get(),
+                Error::<T>::ClinicalDateInFuture
+            );
+
+            Ok(())
+        }
+
+        /// Stores a proposal for `file_hash` and schedules its expiry.
+        fn insert_pending(
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            file_hash: FileHash,
+            metadata: RecordMetadata<T::Moment>,
+        ) -> DispatchResult {
+            let expires_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::ConfirmationWindow::get());
+
+            ProposalExpiryQueue::<T>::insert(expires_at, &file_hash, ());
+
+            PendingRecords::<T>::insert(
+                &file_hash,
+                PendingRecord {
+                    patient: patient.clone(),
+                    doctor: doctor.clone(),
+                    proposed_at: pallet_timestamp::Now::<T>::get(),
+                    expires_at,
+                    metadata,
+                },
+            );
+
+            Self::deposit_event(Event::RecordProposed {
+                patient,
+                doctor,
+                hash: file_hash,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Links `file_hash` to the version it replaces and emits
+        /// [`Event::RecordAmended`].
+        fn link_amendment(
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            file_hash: FileHash,
+            amendment: Amendment<T::Hash>,
+        ) {
+            SupersededBy::<T>::insert(&amendment.previous, &file_hash);
+
+            Self::deposit_event(Event::RecordAmended {
+                patient,
+                doctor,
+                previous: amendment.previous.clone(),
+                hash: file_hash.clone(),
+                reason: amendment.reason,
+            });
+
+            Amendments::<T>::insert(&file_hash, amendment);
+        }
+
+        /// Appends `file_hash` to the creation-ordered indexes of `patient`
+        /// and `doctor`.
+        pub(crate) fn index_record(
+            patient: &T::AccountId,
+            doctor: &T::AccountId,
+            file_hash: &FileHash,
+        ) {
+            let seq = RecordSeq::from(NextRecordSeq::<T>::mutate(|next| {
+                let seq = *next;
+                *next = next.saturating_add(1);
+                seq
+            }));
+
+            PatientRecordOrder::<T>::insert(patient, seq, file_hash);
+            DoctorRecordOrder::<T>::insert(doctor, seq, file_hash);
+            RecordSeqs::<T>::insert(file_hash, seq);
+        }
+
+        /// Removes `file_hash` from the creation-ordered indexes.
+        fn unindex_record(patient: &T::AccountId, doctor: &T::AccountId, file_hash: &FileHash) {
+            if let Some(seq) = RecordSeqs::<T>::take(file_hash) {
+                PatientRecordOrder::<T>::remove(patient, seq);
+                DoctorRecordOrder::<T>::remove(doctor, seq);
+            }
+        }
+
+        /// Writes a record into the indexes and emits
+        /// [`Event::RecordCreated`], or [`Event::RecordSubmitted`] for
+        /// self-reported records.
+        ///
+        /// For self-reported records `doctor` is the patient; for laboratory
+        /// results it is the laboratory.
+        fn insert_record(
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            file_hash: FileHash,
+            metadata: RecordMetadata<T::Moment>,
+            provenance: Provenance,
+        ) {
+            let now = pallet_timestamp::Now::<T>::get();
+
+            let record = MedicalRecord {
+                created_by: doctor.clone(),
+                created_at: now,
+                file_hash: file_hash.clone(),
+                metadata,
+                provenance,
+            };
+
+            // 1) Global index
+            Records::<T>::insert(&file_hash, record.clone());
+
+            // 2) Doctor index
+            DoctorRecords::<T>::insert(&doctor, &file_hash, (patient.clone(), now));
+
+            // 3) Patient index
+            PatientRecords::<T>::insert(&patient, &file_hash, record);
+
+            // 4) Creation order
+            Self::index_record(&patient, &doctor, &file_hash);
+
+            match provenance {
+                Provenance::ClinicianAuthored => Self::deposit_event(Event::RecordCreated {
+                    patient,
+                    doctor,
+                    hash: file_hash,
+                }),
+                Provenance::SelfReported => {
+                    Self::deposit_event(Event::RecordSubmitted { patient, hash: file_hash })
+                },
+                // Announced by `record_lab_result`, together with the ordering doctor.
+                Provenance::LaboratoryReported => {},
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Queries (runtime API)
+    // ---------------------------------------------------------------------
+
+    impl<T: Config> Pallet<T> {
+        /// Returns a page of `patient`'s records, oldest first, starting at
+        /// position `from`.
+        ///
+        /// Walks at most `limit` (capped at [`MAX_PAGE_SIZE`]) entries of
+        /// [`PatientRecordOrder`]. If `provenance` is set, entries with another
+        /// provenance are walked but not returned, so a page may hold fewer
+        /// items than `limit` while [`RecordPage::next`] is still set. Positions
+        /// never change, so `next` stays valid when records are added or
+        /// redacted between pages. Backs
+        /// [`MedicalHistoryAccessor::get_patient_records`], which the reader
+        /// pallet only serves to signed, permission-checked requests.
+        pub fn records_of_patient(
+            patient: &T::AccountId,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> RecordPage<MedicalRecord<T::AccountId, T::Moment>> {
+            let entries = match from.checked_sub(1) {
+                None => PatientRecordOrder::<T>::iter_prefix(patient),
+                Some(last) => PatientRecordOrder::<T>::iter_prefix_from(
+                    patient,
+                    PatientRecordOrder::<T>::hashed_key_for(patient, RecordSeq::from(last)),
+                ),
+            };
+
+            Self::page(entries, limit, |file_hash| {
+                PatientRecords::<T>::get(patient, file_hash)
+                    .filter(|record| provenance.is_none_or(|p| record.provenance == p))
+            })
+        }
+
+        /// Returns a page of `(patient, record)` pairs created by `doctor`,
+        /// oldest first, starting at position `from`.
+        ///
+        /// Same paging and `provenance` filter as [`Self::records_of_patient`],
+        /// over [`DoctorRecordOrder`].
+        pub fn records_by_doctor(
+            doctor: &T::AccountId,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> RecordPage<(T::AccountId, MedicalRecord<T::AccountId, T::Moment>)> {
+            let entries = match from.checked_sub(1) {
+                None => DoctorRecordOrder::<T>::iter_prefix(doctor),
+                Some(last) => DoctorRecordOrder::<T>::iter_prefix_from(
+                    doctor,
+                    DoctorRecordOrder::<T>::hashed_key_for(doctor, RecordSeq::from(last)),
+                ),
+            };
+
+            Self::page(entries, limit, |file_hash| {
+                let (patient, _) = DoctorRecords::<T>::get(doctor, file_hash)?;
+                Records::<T>::get(file_hash)
+                    .filter(|record| provenance.is_none_or(|p| record.provenance == p))
+                    .map(|record| (patient, record))
+            })
+        }
+
+        /// Collects the items of up to `limit` (capped at [`MAX_PAGE_SIZE`])
+        /// `entries`, and the position of the first entry left over.
+        fn page<Item>(
+            mut entries: impl Iterator<Item = (RecordSeq, FileHash)>,
+            limit: u32,
+            item: impl Fn(&FileHash) -> Option<Item>,
+        ) -> RecordPage<Item> {
+            let items = entries
+                .by_ref()
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .filter_map(|(_, file_hash)| item(&file_hash))
+                .collect();
+            let next = entries.next().map(|(seq, _)| seq.into());
+
+            RecordPage { items, next }
+        }
+
+        /// Returns the record identified by `file_hash`, if any.
+        pub fn record(file_hash: &FileHash) -> Option<MedicalRecord<T::AccountId, T::Moment>> {
+            Records::<T>::get(file_hash)
+        }
+
+        /// Returns the hash of the latest version of `file_hash`.
+        ///
+        /// Returns `file_hash` itself if it was never amended.
+        pub fn latest_version(file_hash: &FileHash) -> FileHash {
+            let mut latest = file_hash.clone();
+            // Chains hold at most `MaxVersions` amendments.
+            for _ in 0..T::MaxVersions::get() {
+                match SupersededBy::<T>::get(&latest) {
+                    Some(next) => latest = next,
+                    None => break,
+                }
+            }
+            latest
+        }
+
+        /// Returns the endorsement of the self-reported record `file_hash`,
+        /// if a doctor endorsed it.
+        pub fn endorsement(file_hash: &FileHash) -> Option<Endorsement<T::AccountId, T::Moment>> {
+            Endorsements::<T>::get(file_hash)
+        }
+
+        /// Returns the doctor that ordered the laboratory result `file_hash`.
+        pub fn ordering_doctor(file_hash: &FileHash) -> Option<T::AccountId> {
+            OrderingDoctors::<T>::get(file_hash)
+        }
+
+        /// Returns the latest version of the record identified by `file_hash`.
+        pub fn latest_record(
+            file_hash: &FileHash,
+        ) -> Option<MedicalRecord<T::AccountId, T::Moment>> {
+            Records::<T>::get(Self::latest_version(file_hash))
+        }
+
+        /// Returns `recipient`'s envelope for `file_hash`.
+        ///
+        /// `None` if there is none, or if `recipient` is no longer the
+        /// patient or a doctor with read access.
+        pub fn key_envelope(recipient: &T::AccountId, file_hash: &FileHash) -> Option<WrappedKey> {
+            let record = Records::<T>::get(file_hash)?;
+            let (patient, _) = DoctorRecords::<T>::get(&record.created_by, file_hash)?;
+            if !Self::may_unwrap(&patient, recipient, file_hash) {
+                return None;
+            }
+            KeyEnvelopes::<T>::get(file_hash, recipient)
+        }
+
+        /// Returns the doctors whose revocation still requires a key
+        /// rotation, with the block of the revocation.
+        pub fn key_rotations_required(
+            patient: &T::AccountId,
+        ) -> Vec<(T::AccountId, BlockNumberFor<T>)> {
+            KeyRotationRequired::<T>::iter_prefix(patient).collect()
+        }
+
+        /// Returns every version of the record identified by any hash of its
+        /// chain, oldest first. Empty if the record does not exist.
+        pub fn record_history(file_hash: &FileHash) -> Vec<MedicalRecord<T::AccountId, T::Moment>> {
+            let mut original = file_hash.clone();
+            for _ in 0..T::MaxVersions::get() {
+                match Amendments::<T>::get(&original) {
+                    Some(amendment) => original = amendment.previous,
+                    None => break,
+                }
+            }
+
+            let mut history = Vec::new();
+            let mut next = Some(original);
+            while let Some(hash) = next {
+                let Some(record) = Records::<T>::get(&hash) else { break };
+                history.push(record);
+                next = SupersededBy::<T>::get(&hash);
+            }
+            history
+        }
     }
 
     //Método auxiliar para benchmarks. Usado na medição real de peso de uma extrinsic.----------  start ----------------
@@ -217,12 +1839,74 @@ pub mod pallet {
                 created_by: patient.clone(),
                 created_at: now,
                 file_hash: file_hash.clone(),
+                metadata: RecordMetadata {
+                    category: RecordCategory::Other,
+                    content_type: Default::default(),
+                    clinical_date: now,
+                    title_hash: None,
+                },
+                provenance: Provenance::SelfReported,
             };
             PatientRecords::<T>::insert(patient, file_hash, record);
         }
     }
     //Método auxiliar para benchmarks. Usado na medição real de peso de uma extrinsic.----------  end ----------------
 
+    /// Flags a key rotation when a doctor holding envelopes loses read access.
+    impl<T: Config> OnAccessRevoked<T::AccountId> for Pallet<T> {
+        fn on_access_revoked(patient: &T::AccountId, doctor: &T::AccountId) {
+            if SharedKeys::<T>::get(patient, doctor) == 0 {
+                return;
+            }
+
+            KeyRotationRequired::<T>::insert(
+                patient,
+                doctor,
+                frame_system::Pallet::<T>::block_number(),
+            );
+            Self::deposit_event(Event::KeyRotationRequired {
+                patient: patient.clone(),
+                doctor: doctor.clone(),
+            });
+        }
+    }
+
+    /// Indexes laboratory results on behalf of an exam-orders pallet.
+    ///
+    /// Results skip the confirmation flow: the patient consented when the
+    /// sample was collected. The laboratory pays the record deposit.
+    impl<T: Config> LabResultRecorder<T::AccountId, T::Moment> for Pallet<T> {
+        fn record_lab_result(
+            patient: &T::AccountId,
+            laboratory: &T::AccountId,
+            ordered_by: &T::AccountId,
+            file_hash: FileHash,
+            metadata: RecordMetadata<T::Moment>,
+        ) -> DispatchResult {
+            Self::ensure_new_file(&file_hash)?;
+            Self::ensure_valid_metadata(&metadata)?;
+            Self::hold_deposit(laboratory, &file_hash)?;
+
+            OrderingDoctors::<T>::insert(&file_hash, ordered_by);
+            Self::insert_record(
+                patient.clone(),
+                laboratory.clone(),
+                file_hash.clone(),
+                metadata,
+                Provenance::LaboratoryReported,
+            );
+
+            Self::deposit_event(Event::LabResultRecorded {
+                patient: patient.clone(),
+                laboratory: laboratory.clone(),
+                ordered_by: ordered_by.clone(),
+                hash: file_hash,
+            });
+
+            Ok(())
+        }
+    }
+
     /// Implementation of the public accessor interface used by reader pallets.
     impl<T: Config> MedicalHistoryAccessor<T::AccountId, T::Moment> for Pallet<T> {
         fn get_patient_record(
@@ -232,5 +1916,44 @@ pub mod pallet {
             // Patient-scoped lookup: if it exists here, it's owned by `patient`.
             PatientRecords::<T>::get(patient, file_hash)
         }
+
+        fn get_latest_patient_record(
+            patient: &T::AccountId,
+            file_hash: &FileHash,
+        ) -> Option<MedicalRecord<T::AccountId, T::Moment>> {
+            // Every version of a chain belongs to the same patient.
+            if !PatientRecords::<T>::contains_key(patient, file_hash) {
+                return None;
+            }
+            PatientRecords::<T>::get(patient, Self::latest_version(file_hash))
+        }
+
+        fn get_patient_record_history(
+            patient: &T::AccountId,
+            file_hash: &FileHash,
+        ) -> Vec<MedicalRecord<T::AccountId, T::Moment>> {
+            if !PatientRecords::<T>::contains_key(patient, file_hash) {
+                return Vec::new();
+            }
+            Self::record_history(file_hash)
+        }
+
+        fn get_patient_records(
+            patient: &T::AccountId,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> RecordPage<MedicalRecord<T::AccountId, T::Moment>> {
+            Self::records_of_patient(patient, provenance, from, limit)
+        }
+
+        fn get_doctor_records(
+            doctor: &T::AccountId,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> RecordPage<(T::AccountId, MedicalRecord<T::AccountId, T::Moment>)> {
+            Self::records_by_doctor(doctor, provenance, from, limit)
+        }
     }
 }
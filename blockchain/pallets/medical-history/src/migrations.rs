@@ -0,0 +1,311 @@
+//! Storage migrations for the Medical History pallet.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+    weights::Weight,
+};
+
+/// Migration from storage version `0` to `1`.
+///
+/// Version `1` adds [`RecordMetadata`] to [`MedicalRecord`] and
+/// [`PendingRecord`]. Existing entries get [`RecordCategory::Other`], an
+/// empty content type, no title hash and their creation (or proposal)
+/// timestamp as clinical date. Records are written in the version `1`
+/// layout ([`v2::OldMedicalRecord`]), which [`v2`] then upgrades.
+pub mod v1 {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// [`MedicalRecord`] as stored in version `0`.
+    #[derive(Encode, Decode)]
+    pub struct OldMedicalRecord<AccountId, Moment> {
+        pub created_by: AccountId,
+        pub created_at: Moment,
+        pub file_hash: FileHash,
+    }
+
+    /// [`PendingRecord`] as stored in version `0`.
+    #[derive(Encode, Decode)]
+    pub struct OldPendingRecord<AccountId, Moment, BlockNumber> {
+        pub patient: AccountId,
+        pub doctor: AccountId,
+        pub proposed_at: Moment,
+        pub expires_at: BlockNumber,
+    }
+
+    /// Metadata assigned to entries created before metadata existed.
+    fn legacy_metadata<Moment>(date: Moment) -> RecordMetadata<Moment> {
+        RecordMetadata {
+            category: RecordCategory::Other,
+            content_type: Default::default(),
+            clinical_date: date,
+            title_hash: None,
+        }
+    }
+
+    impl<AccountId, Moment: Clone> OldMedicalRecord<AccountId, Moment> {
+        fn upgrade(self) -> super::v2::OldMedicalRecord<AccountId, Moment> {
+            super::v2::OldMedicalRecord {
+                metadata: legacy_metadata(self.created_at.clone()),
+                created_by: self.created_by,
+                created_at: self.created_at,
+                file_hash: self.file_hash,
+            }
+        }
+    }
+
+    /// Unversioned inner migration. Use [`MigrateV0ToV1`] instead.
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+
+            super::v2::Records::<T>::translate::<OldMedicalRecord<T::AccountId, T::Moment>, _>(
+                |_file_hash, old| {
+                    translated = translated.saturating_add(1);
+                    Some(old.upgrade())
+                },
+            );
+
+            super::v2::PatientRecords::<T>::translate::<
+                OldMedicalRecord<T::AccountId, T::Moment>,
+                _,
+            >(|_patient, _file_hash, old| {
+                translated = translated.saturating_add(1);
+                Some(old.upgrade())
+            });
+
+            PendingRecords::<T>::translate::<
+                OldPendingRecord<T::AccountId, T::Moment, BlockNumberFor<T>>,
+                _,
+            >(|_file_hash, old| {
+                translated = translated.saturating_add(1);
+                Some(PendingRecord {
+                    metadata: legacy_metadata(old.proposed_at),
+                    patient: old.patient,
+                    doctor: old.doctor,
+                    proposed_at: old.proposed_at,
+                    expires_at: old.expires_at,
+                })
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `0`.
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `1` to `2`.
+///
+/// Version `2` adds [`Provenance`] to [`MedicalRecord`]. Every existing
+/// record was created by a doctor, so it becomes
+/// [`Provenance::ClinicianAuthored`].
+pub mod v2 {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_support::pallet_prelude::{
+        Blake2_128Concat, OptionQuery, StorageDoubleMap, StorageMap,
+    };
+
+    /// [`MedicalRecord`] as stored in version `1`.
+    #[derive(Encode, Decode)]
+    pub struct OldMedicalRecord<AccountId, Moment> {
+        pub created_by: AccountId,
+        pub created_at: Moment,
+        pub file_hash: FileHash,
+        pub metadata: RecordMetadata<Moment>,
+    }
+
+    impl<AccountId, Moment> OldMedicalRecord<AccountId, Moment> {
+        fn upgrade(self) -> MedicalRecord<AccountId, Moment> {
+            MedicalRecord {
+                created_by: self.created_by,
+                created_at: self.created_at,
+                file_hash: self.file_hash,
+                metadata: self.metadata,
+                provenance: Provenance::ClinicianAuthored,
+            }
+        }
+    }
+
+    /// [`crate::Records`] with its version `1` value type.
+    #[frame_support::storage_alias]
+    pub type Records<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        FileHash,
+        OldMedicalRecord<
+            <T as frame_system::Config>::AccountId,
+            <T as pallet_timestamp::Config>::Moment,
+        >,
+        OptionQuery,
+    >;
+
+    /// [`crate::PatientRecords`] with its version `1` value type.
+    #[frame_support::storage_alias]
+    pub type PatientRecords<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Blake2_128Concat,
+        FileHash,
+        OldMedicalRecord<
+            <T as frame_system::Config>::AccountId,
+            <T as pallet_timestamp::Config>::Moment,
+        >,
+        OptionQuery,
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV1ToV2`] instead.
+    pub struct InnerMigrateV1ToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+
+            crate::Records::<T>::translate::<OldMedicalRecord<T::AccountId, T::Moment>, _>(
+                |_file_hash, old| {
+                    translated = translated.saturating_add(1);
+                    Some(old.upgrade())
+                },
+            );
+
+            crate::PatientRecords::<T>::translate::<
+                OldMedicalRecord<T::AccountId, T::Moment>,
+                _,
+            >(|_patient, _file_hash, old| {
+                translated = translated.saturating_add(1);
+                Some(old.upgrade())
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `1`.
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `2` to `3`.
+///
+/// Version `2` stored [`ProposalExpiryQueue`] as one bounded list per block,
+/// which could fill up and reject proposals. Version `3` stores one entry
+/// per proposal and drains the queue from [`ProposalExpiryCursor`]. Stale
+/// entries are dropped and the cursor starts at the current block.
+pub mod v3 {
+    use super::*;
+    use frame_support::{BoundedVec, Twox64Concat};
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// [`crate::ProposalExpiryQueue`] as stored up to version `2`.
+    #[frame_support::storage_alias]
+    type ProposalExpiryQueue<T: Config> = StorageMap<
+        Pallet<T>,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<FileHash, <T as Config>::MaxProposalsPerBlock>,
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV2ToV3`] instead.
+    pub struct InnerMigrateV2ToV3<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let old: Vec<_> = ProposalExpiryQueue::<T>::drain().collect();
+            let mut moved: u64 = 0;
+
+            for (at, queue) in old.iter() {
+                for file_hash in queue {
+                    let pending = crate::PendingRecords::<T>::get(file_hash)
+                        .is_some_and(|pending| pending.expires_at == *at);
+                    if pending {
+                        crate::ProposalExpiryQueue::<T>::insert(at, file_hash, ());
+                        moved = moved.saturating_add(1);
+                    }
+                }
+            }
+            crate::ProposalExpiryCursor::<T>::put(frame_system::Pallet::<T>::block_number());
+
+            let entries = old.iter().map(|(_, queue)| queue.len() as u64).sum::<u64>();
+            T::DbWeight::get().reads_writes(
+                (old.len() as u64).saturating_add(entries),
+                (old.len() as u64).saturating_add(moved).saturating_add(1),
+            )
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `2`.
+    pub type MigrateV2ToV3<T> = VersionedMigration<
+        2,
+        3,
+        InnerMigrateV2ToV3<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `3` to `4`.
+///
+/// Version `4` adds the creation-ordered [`PatientRecordOrder`] and
+/// [`DoctorRecordOrder`] indexes. Existing records are indexed in
+/// `(created_at, file_hash)` order, the order the listing queries used
+/// before.
+pub mod v4 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`MigrateV3ToV4`] instead.
+    pub struct InnerMigrateV3ToV4<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV3ToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut records: Vec<_> = crate::Records::<T>::iter_values().collect();
+            records.sort_by(|a, b| {
+                (a.created_at, &a.file_hash).cmp(&(b.created_at, &b.file_hash))
+            });
+
+            let mut indexed: u64 = 0;
+            for record in records.iter() {
+                let doctor = &record.created_by;
+                let Some((patient, _)) = crate::DoctorRecords::<T>::get(doctor, &record.file_hash)
+                else {
+                    continue;
+                };
+
+                Pallet::<T>::index_record(&patient, doctor, &record.file_hash);
+                indexed = indexed.saturating_add(1);
+            }
+
+            T::DbWeight::get().reads_writes(
+                (records.len() as u64).saturating_add(indexed.saturating_mul(2)),
+                indexed.saturating_mul(4),
+            )
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `3`.
+    pub type MigrateV3ToV4<T> = VersionedMigration<
+        3,
+        4,
+        InnerMigrateV3ToV4<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
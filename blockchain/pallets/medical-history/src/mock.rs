@@ -1,7 +1,14 @@
 use crate as pallet_medical_history;
-
-use frame_support::{derive_impl, traits::ConstU64};
+use crate::{FileHash, OnRecordRedacted};
+
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64},
+    weights::Weight,
+};
+use pallet_doctor_registry::DoctorRegistryVerifier;
 use pallet_medical_permissions::MedicalPermissionsVerifier;
+use sp_core::H256;
 use sp_runtime::BuildStorage;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -13,17 +20,49 @@ type Block = frame_system::mocking::MockBlock<Test>;
 /// Mock implementation of [`MedicalPermissionsVerifier`] used by unit tests.
 ///
 /// # Behavior
-/// - Grants access **only** to the doctor with ID `10`.
+/// - Grants read and write access to the doctor with ID `10`.
+/// - Grants read-only access to the doctor with ID `11`.
+/// - Grants write access to the unregistered doctor with ID `12`.
 /// - Any other doctor ID is denied.
+/// - Doctors listed in [`RevokedWriters`] lose write access.
 ///
 /// # Notes
 /// The `patient` parameter is ignored because this mock focuses solely on
 /// exercising authorization branches in the pallet.
 pub struct MockPermissions;
 
+parameter_types! {
+    /// Doctors whose write access tests have revoked.
+    pub static RevokedWriters: Vec<u64> = Vec::new();
+    /// Doctors tests have suspended from [`MockDoctorRegistry`].
+    pub static SuspendedDoctors: Vec<u64> = Vec::new();
+}
+
 impl MedicalPermissionsVerifier<u64> for MockPermissions {
     fn has_access(_patient: &u64, doctor: &u64) -> bool {
-        *doctor == 10
+        *doctor == 10 || *doctor == 11
+    }
+
+    fn can_write(_patient: &u64, doctor: &u64) -> bool {
+        (*doctor == 10 || *doctor == 12) && !RevokedWriters::get().contains(doctor)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Mock Doctor Registry
+// -----------------------------------------------------------------------------
+
+/// Mock implementation of [`DoctorRegistryVerifier`] used by unit tests.
+///
+/// # Behavior
+/// - Every account is an active doctor except ID `12`, which has write
+///   permission in [`MockPermissions`] but is not registered, and the ones
+///   listed in [`SuspendedDoctors`].
+pub struct MockDoctorRegistry;
+
+impl DoctorRegistryVerifier<u64> for MockDoctorRegistry {
+    fn is_active_doctor(who: &u64) -> bool {
+        *who != 12 && !SuspendedDoctors::get().contains(who)
     }
 }
 
@@ -57,13 +96,28 @@ mod runtime {
 
     #[runtime::pallet_index(2)]
     pub type MedicalHistory = pallet_medical_history::Pallet<Test>;
+
+    #[runtime::pallet_index(3)]
+    pub type Balances = pallet_balances::Pallet<Test>;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
     type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
 }
 
+/// Deposit held for each record or proposal.
+pub const RECORD_DEPOSIT: u64 = 10;
+
+/// Free balance of the funded accounts at genesis.
+pub const INITIAL_BALANCE: u64 = 1_000;
+
 impl pallet_timestamp::Config for Test {
     type Moment = u64;
     type OnTimestampSet = ();
@@ -71,10 +125,46 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    /// `(file_hash, commitment)` pairs reported to [`MockOnRecordRedacted`].
+    pub static RedactedHashes: Vec<(FileHash, H256)> = Vec::new();
+}
+
+/// Records every redaction in [`RedactedHashes`].
+pub struct MockOnRecordRedacted;
+
+impl OnRecordRedacted<u64, H256> for MockOnRecordRedacted {
+    fn on_record_redacted(_patient: &u64, redacted: &[(FileHash, H256)]) {
+        RedactedHashes::mutate(|hashes| hashes.extend_from_slice(redacted));
+    }
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+parameter_types! {
+    /// Toggled by tests that exercise the confirmation-only mode.
+    pub static RequireConfirmation: bool = false;
+    /// Queued proposal expiries processed per block; tests may lower it.
+    pub static MaxProposals: u32 = 16;
+}
+
 impl pallet_medical_history::Config for Test {
     type WeightInfo = ();
     /// Mocked permissions verifier used by `create_record`.
     type Permissions = MockPermissions;
+    type DoctorRegistry = MockDoctorRegistry;
+    type ConfirmationWindow = ConstU64<10>;
+    type MaxProposalsPerBlock = MaxProposals;
+    type RequireConfirmation = RequireConfirmation;
+    type MaxVersions = ConstU32<2>;
+    type RetentionPeriod = ConstU64<10_000>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type RecordDeposit = ConstU64<RECORD_DEPOSIT>;
+    type MaxKeyEnvelopes = ConstU32<2>;
+    type OnRecordRedacted = MockOnRecordRedacted;
 }
 
 // -----------------------------------------------------------------------------
@@ -85,12 +175,21 @@ impl pallet_medical_history::Config for Test {
 /// configured for pallet tests.
 ///
 /// # Side effects
-/// Sets the initial block number to `1` so events work as expected.
+/// Funds patient `1`, doctors `10`, `11` and `12` and laboratory `20` with
+/// [`INITIAL_BALANCE`]
+/// and sets the initial block number to `1` so events work as expected.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    let storage = frame_system::GenesisConfig::<Test>::default()
+    let mut storage = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
         .expect("genesis storage should build");
 
+    pallet_balances::GenesisConfig::<Test> {
+        balances: [1, 10, 11, 12, 20].into_iter().map(|who| (who, INITIAL_BALANCE)).collect(),
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .expect("balances genesis should build");
+
     let mut ext = sp_io::TestExternalities::new(storage);
     ext.execute_with(|| {
         frame_system::Pallet::<Test>::set_block_number(1);
@@ -1,4 +1,7 @@
-use crate::{mock::*, Error, Event};
+use crate::{
+    mock::*, CidError, ContentId, Endorsement, Error, Event, HashAlgorithm, LabResultRecorder,
+    MedicalHistoryAccessor, Provenance, RecordCategory, RecordMetadata,
+};
 use frame_support::{assert_noop, assert_ok, BoundedVec};
 
 // -----------------------------------------------------------------------------
@@ -11,9 +14,33 @@ const PATIENT_ID: u64 = 1;
 /// The only doctor ID authorized by `MockPermissions`.
 const AUTHORIZED_DOCTOR: u64 = 10;
 
+/// Doctor ID granted read-only access by `MockPermissions`.
+const READ_ONLY_DOCTOR: u64 = 11;
+
+/// Doctor ID with write permission that is not in `MockDoctorRegistry`.
+const UNREGISTERED_DOCTOR: u64 = 12;
+
 /// Any other doctor ID is considered unauthorized by `MockPermissions`.
 const UNAUTHORIZED_DOCTOR: u64 = 99;
 
+/// Funded laboratory account, unknown to `MockPermissions`.
+const LABORATORY: u64 = 20;
+
+/// Distinct tombstone commitments for a chain of `versions` records.
+fn commitments(versions: u8) -> BoundedVec<sp_core::H256, crate::MaxChainLength<Test>> {
+    (1..=versions).map(sp_core::H256::repeat_byte).collect::<Vec<_>>().try_into().unwrap()
+}
+
+/// Valid metadata for a PDF exam dated at the genesis timestamp.
+fn metadata() -> RecordMetadata<u64> {
+    RecordMetadata {
+        category: RecordCategory::Exam,
+        content_type: b"application/pdf".to_vec().try_into().unwrap(),
+        clinical_date: 0,
+        title_hash: None,
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Tests
 // -----------------------------------------------------------------------------
@@ -21,13 +48,14 @@ const UNAUTHORIZED_DOCTOR: u64 = 99;
 #[test]
 fn create_record_works() {
     new_test_ext().execute_with(|| {
-        let file_hash: BoundedVec<u8, _> = vec![1, 2, 3].try_into().unwrap();
+        let file_hash = hash(1);
 
         // Authorized doctor creates a record for the patient.
         assert_ok!(MedicalHistory::create_record(
             RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
             PATIENT_ID,
-            file_hash.clone()
+            file_hash.clone(),
+            metadata()
         ));
 
         // Verify the expected event.
@@ -45,13 +73,14 @@ fn create_record_works() {
 #[test]
 fn create_duplicate_fails() {
     new_test_ext().execute_with(|| {
-        let file_hash: BoundedVec<u8, _> = vec![1, 2, 3].try_into().unwrap();
+        let file_hash = hash(1);
 
         // 1) Create the first record successfully.
         assert_ok!(MedicalHistory::create_record(
             RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
             PATIENT_ID,
-            file_hash.clone()
+            file_hash.clone(),
+            metadata()
         ));
 
         // 2) Creating the same record again must fail with duplication error
@@ -60,7 +89,8 @@ fn create_duplicate_fails() {
             MedicalHistory::create_record(
                 RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
                 PATIENT_ID,
-                file_hash
+                file_hash,
+                metadata()
             ),
             Error::<Test>::RecordAlreadyExists
         );
@@ -70,30 +100,68 @@ fn create_duplicate_fails() {
 #[test]
 fn create_record_fails_without_permission() {
     new_test_ext().execute_with(|| {
-        let file_hash: BoundedVec<u8, _> = vec![1, 2, 3].try_into().unwrap();
+        let file_hash = hash(1);
 
         // Unauthorized doctor attempts to create a record.
         assert_noop!(
             MedicalHistory::create_record(
                 RuntimeOrigin::signed(UNAUTHORIZED_DOCTOR),
                 PATIENT_ID,
-                file_hash
+                file_hash,
+                metadata()
+            ),
+            Error::<Test>::NoPermission
+        );
+    });
+}
+
+#[test]
+fn create_record_fails_with_read_only_permission() {
+    new_test_ext().execute_with(|| {
+        let file_hash = hash(7);
+
+        // A doctor allowed to read but not to write cannot create records.
+        assert_noop!(
+            MedicalHistory::create_record(
+                RuntimeOrigin::signed(READ_ONLY_DOCTOR),
+                PATIENT_ID,
+                file_hash,
+                metadata()
             ),
             Error::<Test>::NoPermission
         );
     });
 }
 
+#[test]
+fn create_record_fails_for_unregistered_doctor() {
+    new_test_ext().execute_with(|| {
+        let file_hash = hash(9);
+
+        // Write permission alone is not enough without a registry entry.
+        assert_noop!(
+            MedicalHistory::create_record(
+                RuntimeOrigin::signed(UNREGISTERED_DOCTOR),
+                PATIENT_ID,
+                file_hash,
+                metadata()
+            ),
+            Error::<Test>::DoctorNotRegistered
+        );
+    });
+}
+
 #[test]
 fn create_record_works_with_permission() {
     new_test_ext().execute_with(|| {
-        let file_hash: BoundedVec<u8, _> = vec![4, 5, 6].try_into().unwrap();
+        let file_hash = hash(4);
 
         // Authorized doctor creates a record with a different hash.
         assert_ok!(MedicalHistory::create_record(
             RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
             PATIENT_ID,
-            file_hash.clone()
+            file_hash.clone(),
+            metadata()
         ));
 
         // Verify the expected event.
@@ -107,3 +175,1259 @@ fn create_record_works_with_permission() {
         );
     });
 }
+
+// -----------------------------------------------------------------------------
+// Patient confirmation (2FA)
+// -----------------------------------------------------------------------------
+
+#[test]
+fn proposed_record_is_indexed_only_after_confirmation() {
+    new_test_ext().execute_with(|| {
+        let file_hash = hash(1);
+
+        assert_ok!(MedicalHistory::propose_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            file_hash.clone(),
+            metadata()
+        ));
+        System::assert_last_event(
+            Event::RecordProposed {
+                patient: PATIENT_ID,
+                doctor: AUTHORIZED_DOCTOR,
+                hash: file_hash.clone(),
+                expires_at: 11,
+            }
+            .into(),
+        );
+
+        // Nothing is indexed while the proposal is pending.
+        assert!(MedicalHistory::records(&file_hash).is_none());
+        assert!(MedicalHistory::patient_records(PATIENT_ID, &file_hash).is_none());
+
+        // Only the patient can confirm.
+        assert_noop!(
+            MedicalHistory::confirm_record(RuntimeOrigin::signed(UNAUTHORIZED_DOCTOR), file_hash.clone()),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(MedicalHistory::confirm_record(RuntimeOrigin::signed(PATIENT_ID), file_hash.clone()));
+        System::assert_has_event(
+            Event::RecordConfirmed {
+                patient: PATIENT_ID,
+                doctor: AUTHORIZED_DOCTOR,
+                hash: file_hash.clone(),
+            }
+            .into(),
+        );
+
+        assert!(MedicalHistory::records(&file_hash).is_some());
+        assert!(MedicalHistory::doctor_records(AUTHORIZED_DOCTOR, &file_hash).is_some());
+        assert!(MedicalHistory::patient_records(PATIENT_ID, &file_hash).is_some());
+        assert!(MedicalHistory::pending_records(&file_hash).is_none());
+    });
+}
+
+#[test]
+fn rejected_record_is_never_indexed() {
+    new_test_ext().execute_with(|| {
+        let file_hash = hash(4);
+
+        assert_ok!(MedicalHistory::propose_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            file_hash.clone(),
+            metadata()
+        ));
+        assert_ok!(MedicalHistory::reject_record(RuntimeOrigin::signed(PATIENT_ID), file_hash.clone()));
+
+        System::assert_last_event(
+            Event::RecordRejected {
+                patient: PATIENT_ID,
+                doctor: AUTHORIZED_DOCTOR,
+                hash: file_hash.clone(),
+            }
+            .into(),
+        );
+        assert!(MedicalHistory::records(&file_hash).is_none());
+        assert!(MedicalHistory::pending_records(&file_hash).is_none());
+    });
+}
+
+#[test]
+fn unconfirmed_record_expires() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        let file_hash = hash(7);
+
+        assert_ok!(MedicalHistory::propose_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            file_hash.clone(),
+            metadata()
+        ));
+
+        // `ConfirmationWindow` is 10 blocks in the mock runtime.
+        System::set_block_number(11);
+        assert_noop!(
+            MedicalHistory::confirm_record(RuntimeOrigin::signed(PATIENT_ID), file_hash.clone()),
+            Error::<Test>::ProposalExpired
+        );
+
+        MedicalHistory::on_initialize(11);
+        System::assert_last_event(
+            Event::RecordProposalExpired {
+                patient: PATIENT_ID,
+                doctor: AUTHORIZED_DOCTOR,
+                hash: file_hash.clone(),
+            }
+            .into(),
+        );
+        assert!(MedicalHistory::pending_records(&file_hash).is_none());
+        assert!(MedicalHistory::records(&file_hash).is_none());
+    });
+}
+
+#[test]
+fn proposals_beyond_block_limit_are_accepted() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        MaxProposals::set(2);
+
+        for byte in [7, 8, 9] {
+            assert_ok!(MedicalHistory::propose_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                PATIENT_ID,
+                hash(byte),
+                metadata()
+            ));
+        }
+        // Answered proposals leave nothing queued.
+        assert_ok!(MedicalHistory::reject_record(RuntimeOrigin::signed(PATIENT_ID), hash(7)));
+        assert_eq!(crate::ProposalExpiryQueue::<Test>::iter_prefix(11).count(), 2);
+
+        for n in 1..=11 {
+            System::set_block_number(n);
+            MedicalHistory::on_initialize(n);
+        }
+
+        assert!(MedicalHistory::pending_records(&hash(8)).is_none());
+        assert!(MedicalHistory::pending_records(&hash(9)).is_none());
+        assert_eq!(crate::ProposalExpiryQueue::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn confirm_record_rechecks_the_doctor() {
+    new_test_ext().execute_with(|| {
+        let file_hash = hash(7);
+
+        assert_ok!(MedicalHistory::propose_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            file_hash.clone(),
+            metadata()
+        ));
+
+        RevokedWriters::set(vec![AUTHORIZED_DOCTOR]);
+        assert_noop!(
+            MedicalHistory::confirm_record(RuntimeOrigin::signed(PATIENT_ID), file_hash.clone()),
+            Error::<Test>::NoPermission
+        );
+        RevokedWriters::set(Vec::new());
+
+        SuspendedDoctors::set(vec![AUTHORIZED_DOCTOR]);
+        assert_noop!(
+            MedicalHistory::confirm_record(RuntimeOrigin::signed(PATIENT_ID), file_hash.clone()),
+            Error::<Test>::DoctorNotRegistered
+        );
+        SuspendedDoctors::set(Vec::new());
+
+        assert_ok!(MedicalHistory::confirm_record(RuntimeOrigin::signed(PATIENT_ID), file_hash.clone()));
+        assert!(MedicalHistory::records(&file_hash).is_some());
+    });
+}
+
+#[test]
+fn create_record_is_disabled_when_confirmation_is_required() {
+    new_test_ext().execute_with(|| {
+        RequireConfirmation::set(true);
+        let file_hash = hash(1);
+
+        assert_noop!(
+            MedicalHistory::create_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                PATIENT_ID,
+                file_hash,
+                metadata()
+            ),
+            Error::<Test>::ConfirmationRequired
+        );
+    });
+}
+
+#[test]
+fn records_are_listed_in_creation_order_with_pagination() {
+    new_test_ext().execute_with(|| {
+        let hashes = [hash(3), hash(1), hash(2)];
+
+        for (i, file_hash) in hashes.iter().enumerate() {
+            Timestamp::set_timestamp(1_000 * (i as u64 + 1));
+            assert_ok!(MedicalHistory::create_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                PATIENT_ID,
+                file_hash.clone(),
+                metadata()
+            ));
+        }
+
+        let of_patient = |from, limit| {
+            let page = MedicalHistory::records_of_patient(&PATIENT_ID, None, from, limit);
+            let hashes: Vec<_> = page.items.into_iter().map(|record| record.file_hash).collect();
+            (hashes, page.next)
+        };
+        assert_eq!(of_patient(0, 10), (hashes.to_vec(), None));
+        assert_eq!(of_patient(0, 2), (hashes[..2].to_vec(), Some(2)));
+        assert_eq!(of_patient(2, 2), (hashes[2..].to_vec(), None));
+        assert_eq!(of_patient(3, 2), (vec![], None));
+        // An oversized `limit` is capped, not rejected.
+        assert_eq!(
+            MedicalHistory::records_of_patient(&PATIENT_ID, None, 0, u32::MAX).items.len(),
+            3
+        );
+
+        let by_doctor = MedicalHistory::records_by_doctor(&AUTHORIZED_DOCTOR, None, 1, 1);
+        assert_eq!(by_doctor.items.len(), 1);
+        assert_eq!(by_doctor.items[0].0, PATIENT_ID);
+        assert_eq!(by_doctor.items[0].1.file_hash, hashes[1]);
+        assert_eq!(by_doctor.next, Some(2));
+        assert!(MedicalHistory::records_by_doctor(&UNAUTHORIZED_DOCTOR, None, 0, 10)
+            .items
+            .is_empty());
+
+        assert_eq!(MedicalHistory::record(&hashes[0]).map(|r| r.created_at), Some(1_000));
+        assert!(MedicalHistory::record(&hash(9)).is_none());
+    });
+}
+
+/// Builds a SHA2-256 multihash whose digest repeats `byte`.
+fn hash(byte: u8) -> BoundedVec<u8, frame_support::traits::ConstU32<64>> {
+    let mut multihash = vec![0x12, 0x20];
+    multihash.extend([byte; 32]);
+    multihash.try_into().unwrap()
+}
+
+#[test]
+fn amend_record_links_versions_and_keeps_original() {
+    new_test_ext().execute_with(|| {
+        let reason = sp_core::H256::repeat_byte(7);
+
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+        let original = MedicalHistory::records(hash(1)).unwrap();
+
+        assert_ok!(MedicalHistory::amend_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            hash(1),
+            hash(2),
+            reason,
+            metadata()
+        ));
+        System::assert_last_event(
+            Event::RecordAmended {
+                patient: PATIENT_ID,
+                doctor: AUTHORIZED_DOCTOR,
+                previous: hash(1),
+                hash: hash(2),
+                reason,
+            }
+            .into(),
+        );
+
+        // The original is untouched and still readable.
+        assert_eq!(MedicalHistory::records(hash(1)), Some(original));
+        assert_eq!(MedicalHistory::superseded_by(hash(1)), Some(hash(2)));
+        assert_eq!(MedicalHistory::amendments(hash(2)).map(|a| a.version), Some(1));
+
+        // Only the latest version can be amended.
+        assert_noop!(
+            MedicalHistory::amend_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                hash(1),
+                hash(3),
+                reason,
+                metadata()
+            ),
+            Error::<Test>::AlreadySuperseded
+        );
+        assert_ok!(MedicalHistory::amend_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            hash(2),
+            hash(3),
+            reason,
+            metadata()
+        ));
+
+        // MaxVersions = 2.
+        assert_noop!(
+            MedicalHistory::amend_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                hash(3),
+                hash(4),
+                reason,
+                metadata()
+            ),
+            Error::<Test>::TooManyVersions
+        );
+
+        let versions = |history: Vec<crate::MedicalRecord<u64, u64>>| -> Vec<_> {
+            history.into_iter().map(|record| record.file_hash).collect()
+        };
+        for file_hash in [hash(1), hash(2), hash(3)] {
+            assert_eq!(MedicalHistory::latest_version(&file_hash), hash(3));
+            assert_eq!(
+                versions(MedicalHistory::record_history(&file_hash)),
+                vec![hash(1), hash(2), hash(3)]
+            );
+        }
+        assert_eq!(
+            MedicalHistory::get_latest_patient_record(&PATIENT_ID, &hash(1)).map(|r| r.file_hash),
+            Some(hash(3))
+        );
+        assert_eq!(MedicalHistory::get_patient_record_history(&PATIENT_ID, &hash(2)).len(), 3);
+        assert!(
+            MedicalHistory::get_patient_record_history(&UNAUTHORIZED_DOCTOR, &hash(2)).is_empty()
+        );
+    });
+}
+
+#[test]
+fn amend_record_requires_author() {
+    new_test_ext().execute_with(|| {
+        let reason = sp_core::H256::zero();
+
+        assert_noop!(
+            MedicalHistory::amend_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                hash(1),
+                hash(2),
+                reason,
+                metadata()
+            ),
+            Error::<Test>::RecordNotFound
+        );
+
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+        assert_noop!(
+            MedicalHistory::amend_record(
+                RuntimeOrigin::signed(READ_ONLY_DOCTOR),
+                hash(1),
+                hash(2),
+                reason,
+                metadata()
+            ),
+            Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn amend_record_goes_through_confirmation_when_required() {
+    new_test_ext().execute_with(|| {
+        let reason = sp_core::H256::zero();
+
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+
+        RequireConfirmation::set(true);
+        assert_ok!(MedicalHistory::amend_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            hash(1),
+            hash(2),
+            reason,
+            metadata()
+        ));
+        assert!(MedicalHistory::records(hash(2)).is_none());
+        assert!(MedicalHistory::superseded_by(hash(1)).is_none());
+
+        assert_ok!(MedicalHistory::confirm_record(RuntimeOrigin::signed(PATIENT_ID), hash(2)));
+        assert_eq!(MedicalHistory::superseded_by(hash(1)), Some(hash(2)));
+        assert!(MedicalHistory::pending_amendments(hash(2)).is_none());
+
+        // A rejected amendment leaves the chain unchanged.
+        assert_ok!(MedicalHistory::amend_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            hash(2),
+            hash(3),
+            reason,
+            metadata()
+        ));
+        assert_ok!(MedicalHistory::reject_record(RuntimeOrigin::signed(PATIENT_ID), hash(3)));
+        assert!(MedicalHistory::pending_amendments(hash(3)).is_none());
+        assert_eq!(MedicalHistory::latest_version(&hash(1)), hash(2));
+    });
+}
+
+#[test]
+fn record_metadata_is_stored_and_validated() {
+    new_test_ext().execute_with(|| {
+        Timestamp::set_timestamp(1_000);
+
+        let mut future = metadata();
+        future.clinical_date = 1_001;
+        assert_noop!(
+            MedicalHistory::create_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                PATIENT_ID,
+                hash(1),
+                future
+            ),
+            Error::<Test>::ClinicalDateInFuture
+        );
+
+        for content_type in [&b""[..], b"pdf", b"application/", b"text/plain; charset"] {
+            let mut invalid = metadata();
+            invalid.content_type = content_type.to_vec().try_into().unwrap();
+            assert_noop!(
+                MedicalHistory::propose_record(
+                    RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                    PATIENT_ID,
+                    hash(1),
+                    invalid
+                ),
+                Error::<Test>::InvalidContentType
+            );
+        }
+
+        let mut prescription = metadata();
+        prescription.category = RecordCategory::Prescription;
+        prescription.clinical_date = 1_000;
+        prescription.title_hash = Some([7; 32]);
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            prescription.clone()
+        ));
+
+        assert_eq!(
+            MedicalHistory::get_record_metadata(&PATIENT_ID, &hash(1)),
+            Some(prescription)
+        );
+    });
+}
+
+#[test]
+fn migration_v0_to_v1_adds_legacy_metadata() {
+    use codec::Encode;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<MedicalHistory>();
+
+        let old = crate::migrations::v1::OldMedicalRecord {
+            created_by: AUTHORIZED_DOCTOR,
+            created_at: 500u64,
+            file_hash: hash(1),
+        };
+        frame_support::storage::unhashed::put_raw(
+            &crate::Records::<Test>::hashed_key_for(hash(1)),
+            &old.encode(),
+        );
+        frame_support::storage::unhashed::put_raw(
+            &crate::PatientRecords::<Test>::hashed_key_for(PATIENT_ID, hash(1)),
+            &old.encode(),
+        );
+
+        crate::migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(MedicalHistory::on_chain_storage_version(), 1);
+        crate::migrations::v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+
+        let record = MedicalHistory::patient_records(PATIENT_ID, hash(1)).unwrap();
+        assert_eq!(record.created_at, 500);
+        assert_eq!(record.metadata.category, RecordCategory::Other);
+        assert_eq!(record.metadata.clinical_date, 500);
+        assert_eq!(record.provenance, Provenance::ClinicianAuthored);
+        assert_eq!(MedicalHistory::records(hash(1)), Some(record));
+        assert_eq!(MedicalHistory::on_chain_storage_version(), 2);
+    });
+}
+
+/// SHA2-256 digest of `b"healthchain"`.
+const HEALTHCHAIN_SHA256: [u8; 32] = [
+    91, 240, 98, 242, 228, 209, 143, 118, 147, 124, 20, 183, 201, 165, 137, 231, 49, 144, 194,
+    131, 139, 255, 47, 248, 16, 243, 60, 31, 12, 150, 234, 197,
+];
+
+#[test]
+fn content_id_parses_supported_formats() {
+    let v0 = ContentId::parse(b"QmUXYQgRzqMvxLep37xm6zRF2e264iBUZYJFmzeYMp6ZSc").unwrap();
+    assert!(matches!(v0, ContentId::V0(_)));
+    assert_eq!(v0.digest(), &HEALTHCHAIN_SHA256);
+
+    let v1 =
+        ContentId::parse(b"bafybeic36brpfzgrr53jg7auw7e2lcphggimfa4l74x7qehthqpqzfxkyu").unwrap();
+    assert!(matches!(v1, ContentId::V1 { codec: 0x70, .. }));
+    assert_eq!(v1.digest(), &HEALTHCHAIN_SHA256);
+    assert_eq!(v1.algorithm(), Some(HashAlgorithm::Sha256));
+
+    let blake2 =
+        ContentId::parse(b"bafk2bzacedjryyavjrosnbcgkz66c6vrdiklikgip6uhd3gnecy2du6ps4ccu")
+            .unwrap();
+    assert!(matches!(blake2, ContentId::V1 { codec: 0x55, .. }));
+    assert_eq!(blake2.algorithm(), Some(HashAlgorithm::Blake2b256));
+
+    let mut binary_v1 = vec![0x01, 0x70];
+    binary_v1.extend_from_slice(&hash(1));
+    assert_eq!(ContentId::parse(&binary_v1).map(|cid| *cid.digest()), Ok([1; 32]));
+    assert!(matches!(ContentId::parse(&hash(1)), Ok(ContentId::Multihash(_))));
+    assert_eq!(ContentId::parse(&[5; 32]), Ok(ContentId::Digest([5; 32])));
+}
+
+#[test]
+fn content_id_rejects_malformed_input() {
+    assert_eq!(ContentId::parse(b""), Err(CidError::Empty));
+    assert_eq!(ContentId::parse(&[1, 2, 3]), Err(CidError::UnsupportedHash));
+    assert_eq!(ContentId::parse(&[7, 7, 7]), Err(CidError::UnknownFormat));
+    // '0' is not part of the base58btc alphabet.
+    assert_eq!(
+        ContentId::parse(b"QmUXYQgRzqMvxLep37xm6zRF2e264iBUZYJFmzeYMp6ZS0"),
+        Err(CidError::InvalidBase58)
+    );
+    assert_eq!(
+        ContentId::parse(b"bafybeic36brpfzgrr53jg7auw7e2lcphggimfa4l74x7qehthqpqzfxkyU"),
+        Err(CidError::InvalidBase32)
+    );
+    assert_eq!(ContentId::parse(&hash(1)[..20]), Err(CidError::Truncated));
+
+    let mut wrong_length = vec![0x12, 0x14];
+    wrong_length.extend([1; 32]);
+    assert_eq!(ContentId::parse(&wrong_length), Err(CidError::InvalidDigestLength));
+
+    let mut trailing = hash(1).into_inner();
+    trailing.push(0);
+    assert_eq!(ContentId::parse(&trailing), Err(CidError::TrailingBytes));
+
+    let mut v2 = vec![0x02, 0x70];
+    v2.extend_from_slice(&hash(1));
+    let mut text = vec![b'b'];
+    text.extend(base32(&v2));
+    assert_eq!(ContentId::parse(&text), Err(CidError::UnsupportedVersion));
+}
+
+#[test]
+fn create_record_rejects_invalid_content_id() {
+    new_test_ext().execute_with(|| {
+        for file_hash in [vec![], vec![1, 2, 3], b"QmNotACid".to_vec()] {
+            assert_noop!(
+                MedicalHistory::create_record(
+                    RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                    PATIENT_ID,
+                    file_hash.try_into().unwrap(),
+                    metadata()
+                ),
+                Error::<Test>::InvalidContentId
+            );
+        }
+
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            b"QmUXYQgRzqMvxLep37xm6zRF2e264iBUZYJFmzeYMp6ZSc".to_vec().try_into().unwrap(),
+            metadata()
+        ));
+    });
+}
+
+#[test]
+fn legacy_raw_hash_is_still_readable() {
+    new_test_ext().execute_with(|| {
+        let legacy: BoundedVec<u8, _> = vec![1, 2, 3].try_into().unwrap();
+        let record = crate::MedicalRecord {
+            created_by: AUTHORIZED_DOCTOR,
+            created_at: 0u64,
+            file_hash: legacy.clone(),
+            metadata: metadata(),
+            provenance: Provenance::ClinicianAuthored,
+        };
+        crate::Records::<Test>::insert(&legacy, &record);
+        crate::PatientRecords::<Test>::insert(PATIENT_ID, &legacy, &record);
+
+        let stored = MedicalHistory::get_patient_record(&PATIENT_ID, &legacy).unwrap();
+        assert_eq!(stored.content_id(), None);
+        assert_eq!(stored.file_hash, legacy);
+
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+        let record = MedicalHistory::get_patient_record(&PATIENT_ID, &hash(1)).unwrap();
+        assert!(matches!(record.content_id(), Some(ContentId::Multihash(_))));
+    });
+}
+
+/// Encodes `bytes` as lowercase, unpadded base32.
+fn base32(bytes: &[u8]) -> Vec<u8> {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut out = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in bytes {
+        buffer = ((buffer << 8) | u32::from(*byte)) & 0xffff;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[(buffer >> bits) as usize & 31]);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[(buffer << (5 - bits)) as usize & 31]);
+    }
+    out
+}
+
+#[test]
+fn redact_record_respects_retention_and_leaves_tombstone() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+
+        assert_noop!(
+            MedicalHistory::redact_record(
+                RuntimeOrigin::signed(PATIENT_ID),
+                hash(1),
+                commitments(1)
+            ),
+            Error::<Test>::RetentionPeriodActive
+        );
+
+        Timestamp::set_timestamp(10_000);
+        assert_noop!(
+            MedicalHistory::redact_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                hash(1),
+                commitments(1)
+            ),
+            Error::<Test>::RecordNotFound
+        );
+        // Exactly one commitment per version.
+        for wrong in [commitments(0), commitments(2)] {
+            assert_noop!(
+                MedicalHistory::redact_record(RuntimeOrigin::signed(PATIENT_ID), hash(1), wrong),
+                Error::<Test>::CommitmentCountMismatch
+            );
+        }
+        assert_ok!(MedicalHistory::redact_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            commitments(1)
+        ));
+
+        let commitment = commitments(1)[0];
+        System::assert_last_event(
+            Event::RecordRedacted { patient: PATIENT_ID, commitment }.into(),
+        );
+        assert_eq!(RedactedHashes::get(), vec![(hash(1), commitment)]);
+        assert!(MedicalHistory::records(hash(1)).is_none());
+        assert!(MedicalHistory::patient_records(PATIENT_ID, hash(1)).is_none());
+        assert!(MedicalHistory::doctor_records(AUTHORIZED_DOCTOR, hash(1)).is_none());
+        assert_eq!(
+            MedicalHistory::tombstones(PATIENT_ID, commitment),
+            Some(crate::Tombstone {
+                created_by: AUTHORIZED_DOCTOR,
+                created_at: 0,
+                redacted_at: 10_000,
+            })
+        );
+
+        // A commitment cannot overwrite an existing tombstone.
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(2),
+            metadata()
+        ));
+        Timestamp::set_timestamp(20_000);
+        assert_noop!(
+            MedicalHistory::redact_record(
+                RuntimeOrigin::signed(PATIENT_ID),
+                hash(2),
+                commitments(1)
+            ),
+            Error::<Test>::CommitmentInUse
+        );
+    });
+}
+
+#[test]
+fn redact_record_erases_whole_version_chain() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+        assert_ok!(MedicalHistory::amend_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            hash(1),
+            hash(2),
+            sp_core::H256::repeat_byte(7),
+            metadata()
+        ));
+
+        Timestamp::set_timestamp(10_000);
+        let repeated: BoundedVec<_, _> = vec![commitments(1)[0]; 2].try_into().unwrap();
+        assert_noop!(
+            MedicalHistory::redact_record(RuntimeOrigin::signed(PATIENT_ID), hash(2), repeated),
+            Error::<Test>::CommitmentInUse
+        );
+        assert_ok!(MedicalHistory::redact_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(2),
+            commitments(2)
+        ));
+
+        for file_hash in [hash(1), hash(2)] {
+            assert!(MedicalHistory::records(&file_hash).is_none());
+            assert!(MedicalHistory::amendments(&file_hash).is_none());
+            assert!(MedicalHistory::superseded_by(&file_hash).is_none());
+        }
+        assert_eq!(crate::Tombstones::<Test>::iter_prefix(PATIENT_ID).count(), 2);
+        // Commitments are assigned oldest version first.
+        let [first, second] = [commitments(2)[0], commitments(2)[1]];
+        assert_eq!(RedactedHashes::get(), vec![(hash(1), first), (hash(2), second)]);
+        assert_eq!(
+            MedicalHistory::records_of_patient(&PATIENT_ID, None, 0, 10),
+            crate::RecordPage { items: vec![], next: None }
+        );
+        assert!(crate::RecordSeqs::<Test>::iter().next().is_none());
+        assert!(crate::DoctorRecordOrder::<Test>::iter().next().is_none());
+    });
+}
+
+#[test]
+fn listing_cursor_survives_redaction() {
+    new_test_ext().execute_with(|| {
+        for i in 1..=3 {
+            assert_ok!(MedicalHistory::create_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                PATIENT_ID,
+                hash(i),
+                metadata()
+            ));
+        }
+
+        let first = MedicalHistory::records_of_patient(&PATIENT_ID, None, 0, 2);
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(first.next, Some(2));
+
+        Timestamp::set_timestamp(10_000);
+        assert_ok!(MedicalHistory::redact_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            commitments(1)
+        ));
+
+        // The next page still starts at `hash(3)`, nothing is skipped or repeated.
+        let second = MedicalHistory::records_of_patient(&PATIENT_ID, None, 2, 2);
+        assert_eq!(
+            second.items.into_iter().map(|record| record.file_hash).collect::<Vec<_>>(),
+            vec![hash(3)]
+        );
+        assert_eq!(second.next, None);
+    });
+}
+
+#[test]
+fn record_deposit_is_held_and_released() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        // Created records hold a deposit until they are redacted.
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+        assert_eq!(Balances::reserved_balance(AUTHORIZED_DOCTOR), RECORD_DEPOSIT);
+        assert_eq!(
+            MedicalHistory::record_deposits(hash(1)),
+            Some((AUTHORIZED_DOCTOR, RECORD_DEPOSIT))
+        );
+
+        Timestamp::set_timestamp(10_000);
+        assert_ok!(MedicalHistory::redact_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            commitments(1)
+        ));
+        assert_eq!(Balances::reserved_balance(AUTHORIZED_DOCTOR), 0);
+        assert!(MedicalHistory::record_deposits(hash(1)).is_none());
+
+        // Rejected and expired proposals release theirs.
+        for file_hash in [hash(2), hash(3)] {
+            assert_ok!(MedicalHistory::propose_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                PATIENT_ID,
+                file_hash,
+                metadata()
+            ));
+        }
+        assert_eq!(Balances::reserved_balance(AUTHORIZED_DOCTOR), 2 * RECORD_DEPOSIT);
+
+        assert_ok!(MedicalHistory::reject_record(RuntimeOrigin::signed(PATIENT_ID), hash(2)));
+        assert_eq!(Balances::reserved_balance(AUTHORIZED_DOCTOR), RECORD_DEPOSIT);
+
+        MedicalHistory::on_initialize(11);
+        assert_eq!(Balances::reserved_balance(AUTHORIZED_DOCTOR), 0);
+        assert_eq!(Balances::free_balance(AUTHORIZED_DOCTOR), INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn share_key_stores_envelopes_for_authorized_recipients() {
+    new_test_ext().execute_with(|| {
+        let envelope = |byte: u8| -> crate::WrappedKey { vec![byte; 104].try_into().unwrap() };
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+
+        // The author and the patient may share; other readers may not.
+        assert_ok!(MedicalHistory::share_key(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            hash(1),
+            PATIENT_ID,
+            envelope(1)
+        ));
+        assert_ok!(MedicalHistory::share_key(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            READ_ONLY_DOCTOR,
+            envelope(2)
+        ));
+        System::assert_last_event(
+            Event::KeyShared { patient: PATIENT_ID, recipient: READ_ONLY_DOCTOR, hash: hash(1) }
+                .into(),
+        );
+        assert_noop!(
+            MedicalHistory::share_key(
+                RuntimeOrigin::signed(READ_ONLY_DOCTOR),
+                hash(1),
+                READ_ONLY_DOCTOR,
+                envelope(3)
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            MedicalHistory::share_key(
+                RuntimeOrigin::signed(PATIENT_ID),
+                hash(1),
+                UNAUTHORIZED_DOCTOR,
+                envelope(3)
+            ),
+            Error::<Test>::RecipientNotAuthorized
+        );
+
+        // MaxKeyEnvelopes = 2, but existing envelopes can be replaced.
+        assert_noop!(
+            MedicalHistory::share_key(
+                RuntimeOrigin::signed(PATIENT_ID),
+                hash(1),
+                AUTHORIZED_DOCTOR,
+                envelope(3)
+            ),
+            Error::<Test>::TooManyKeyEnvelopes
+        );
+        assert_ok!(MedicalHistory::share_key(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            READ_ONLY_DOCTOR,
+            envelope(4)
+        ));
+
+        assert_eq!(MedicalHistory::key_envelope(&READ_ONLY_DOCTOR, &hash(1)), Some(envelope(4)));
+        assert_eq!(MedicalHistory::key_envelope(&PATIENT_ID, &hash(1)), Some(envelope(1)));
+        assert!(MedicalHistory::key_envelope(&UNAUTHORIZED_DOCTOR, &hash(1)).is_none());
+
+        // Redaction removes the envelopes.
+        Timestamp::set_timestamp(10_000);
+        assert_ok!(MedicalHistory::redact_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            commitments(1)
+        ));
+        assert!(MedicalHistory::key_envelopes(hash(1), READ_ONLY_DOCTOR).is_none());
+        assert_eq!(crate::SharedKeys::<Test>::get(PATIENT_ID, READ_ONLY_DOCTOR), 0);
+        assert_eq!(crate::KeyEnvelopeCount::<Test>::get(hash(1)), 0);
+    });
+}
+
+#[test]
+fn losing_read_access_requires_key_rotation() {
+    use pallet_medical_permissions::OnAccessRevoked;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(1),
+            metadata()
+        ));
+        assert_ok!(MedicalHistory::share_key(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            READ_ONLY_DOCTOR,
+            vec![1; 104].try_into().unwrap()
+        ));
+
+        // Only doctors that held envelopes are flagged.
+        MedicalHistory::on_access_revoked(&PATIENT_ID, &AUTHORIZED_DOCTOR);
+        assert!(MedicalHistory::key_rotation_required(PATIENT_ID, AUTHORIZED_DOCTOR).is_none());
+
+        MedicalHistory::on_access_revoked(&PATIENT_ID, &READ_ONLY_DOCTOR);
+        System::assert_last_event(
+            Event::KeyRotationRequired { patient: PATIENT_ID, doctor: READ_ONLY_DOCTOR }.into(),
+        );
+        assert_eq!(
+            MedicalHistory::key_rotations_required(&PATIENT_ID),
+            vec![(READ_ONLY_DOCTOR, 1)]
+        );
+
+        // The doctor cannot receive the rotated key.
+        let fresh: crate::WrappedKey = vec![2; 104].try_into().unwrap();
+        assert_noop!(
+            MedicalHistory::complete_key_rotation(
+                RuntimeOrigin::signed(PATIENT_ID),
+                hash(1),
+                vec![(UNAUTHORIZED_DOCTOR, fresh.clone())].try_into().unwrap()
+            ),
+            Error::<Test>::RecipientNotAuthorized
+        );
+        assert_noop!(
+            MedicalHistory::complete_key_rotation(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                hash(1),
+                vec![(PATIENT_ID, fresh.clone())].try_into().unwrap()
+            ),
+            Error::<Test>::RecordNotFound
+        );
+
+        // Rotating replaces the envelopes and clears the flag.
+        assert_ok!(MedicalHistory::complete_key_rotation(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            vec![(PATIENT_ID, fresh.clone())].try_into().unwrap()
+        ));
+        System::assert_has_event(
+            Event::KeyRotationCompleted { patient: PATIENT_ID, doctor: READ_ONLY_DOCTOR }.into(),
+        );
+        System::assert_last_event(Event::KeyRotated { patient: PATIENT_ID, hash: hash(1) }.into());
+        assert!(MedicalHistory::key_rotation_required(PATIENT_ID, READ_ONLY_DOCTOR).is_none());
+        assert!(MedicalHistory::key_envelopes(hash(1), READ_ONLY_DOCTOR).is_none());
+        assert_eq!(MedicalHistory::key_envelopes(hash(1), PATIENT_ID), Some(fresh.clone()));
+        assert_eq!(crate::SharedKeys::<Test>::get(PATIENT_ID, READ_ONLY_DOCTOR), 0);
+        assert_eq!(crate::KeyEnvelopeCount::<Test>::get(hash(1)), 1);
+
+        assert_noop!(
+            MedicalHistory::complete_key_rotation(
+                RuntimeOrigin::signed(PATIENT_ID),
+                hash(1),
+                vec![(PATIENT_ID, fresh)].try_into().unwrap()
+            ),
+            Error::<Test>::RotationNotRequired
+        );
+    });
+}
+
+#[test]
+fn submit_own_record_stores_self_reported_record() {
+    new_test_ext().execute_with(|| {
+        // Patients add their own documents without any grant or confirmation.
+        RequireConfirmation::set(true);
+        assert_ok!(MedicalHistory::submit_own_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            metadata()
+        ));
+        System::assert_last_event(
+            Event::RecordSubmitted { patient: PATIENT_ID, hash: hash(1) }.into(),
+        );
+        RequireConfirmation::set(false);
+
+        let record = MedicalHistory::get_patient_record(&PATIENT_ID, &hash(1)).unwrap();
+        assert_eq!(record.created_by, PATIENT_ID);
+        assert_eq!(record.provenance, Provenance::SelfReported);
+        assert_eq!(Balances::reserved_balance(PATIENT_ID), RECORD_DEPOSIT);
+
+        let submit = |file_hash| {
+            let origin = RuntimeOrigin::signed(PATIENT_ID);
+            MedicalHistory::submit_own_record(origin, file_hash, metadata())
+        };
+        assert_noop!(submit(hash(1)), Error::<Test>::RecordAlreadyExists);
+        assert_noop!(
+            submit(vec![1, 2, 3].try_into().unwrap()),
+            Error::<Test>::InvalidContentId
+        );
+
+        // Listings can be filtered by provenance.
+        Timestamp::set_timestamp(1_000);
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(2),
+            metadata()
+        ));
+        let of_patient = |provenance| -> Vec<_> {
+            MedicalHistory::records_of_patient(&PATIENT_ID, provenance, 0, 10)
+                .items
+                .into_iter()
+                .map(|record| record.file_hash)
+                .collect()
+        };
+        assert_eq!(of_patient(None), vec![hash(1), hash(2)]);
+        assert_eq!(of_patient(Some(Provenance::SelfReported)), vec![hash(1)]);
+        assert_eq!(of_patient(Some(Provenance::ClinicianAuthored)), vec![hash(2)]);
+        assert!(MedicalHistory::records_by_doctor(
+            &AUTHORIZED_DOCTOR,
+            Some(Provenance::SelfReported),
+            0,
+            10
+        )
+        .items
+        .is_empty());
+    });
+}
+
+#[test]
+fn endorse_record_requires_write_access_to_self_reported_record() {
+    new_test_ext().execute_with(|| {
+        Timestamp::set_timestamp(500);
+        assert_ok!(MedicalHistory::submit_own_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            metadata()
+        ));
+        assert_ok!(MedicalHistory::create_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(2),
+            metadata()
+        ));
+
+        let endorse = |doctor, file_hash| {
+            MedicalHistory::endorse_record(RuntimeOrigin::signed(doctor), file_hash)
+        };
+        assert_noop!(endorse(AUTHORIZED_DOCTOR, hash(9)), Error::<Test>::RecordNotFound);
+        assert_noop!(endorse(AUTHORIZED_DOCTOR, hash(2)), Error::<Test>::NotSelfReported);
+        assert_noop!(endorse(READ_ONLY_DOCTOR, hash(1)), Error::<Test>::NoPermission);
+        assert_noop!(endorse(UNREGISTERED_DOCTOR, hash(1)), Error::<Test>::DoctorNotRegistered);
+
+        assert_ok!(endorse(AUTHORIZED_DOCTOR, hash(1)));
+        System::assert_last_event(
+            Event::RecordEndorsed { patient: PATIENT_ID, doctor: AUTHORIZED_DOCTOR, hash: hash(1) }
+                .into(),
+        );
+        assert_eq!(
+            MedicalHistory::endorsement(&hash(1)),
+            Some(Endorsement { endorsed_by: AUTHORIZED_DOCTOR, endorsed_at: 500 })
+        );
+        assert_noop!(endorse(AUTHORIZED_DOCTOR, hash(1)), Error::<Test>::AlreadyEndorsed);
+
+        // The record itself is unchanged, and redaction drops the endorsement.
+        let record = MedicalHistory::record(&hash(1)).unwrap();
+        assert_eq!(record.created_by, PATIENT_ID);
+        assert_eq!(record.provenance, Provenance::SelfReported);
+
+        Timestamp::set_timestamp(10_500);
+        assert_ok!(MedicalHistory::redact_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            commitments(1)
+        ));
+        assert!(MedicalHistory::endorsement(&hash(1)).is_none());
+        assert_eq!(Balances::reserved_balance(PATIENT_ID), 0);
+    });
+}
+
+#[test]
+fn lab_result_is_authored_by_laboratory_and_linked_to_doctor() {
+    new_test_ext().execute_with(|| {
+        // Lab results skip the confirmation flow and need no grant.
+        RequireConfirmation::set(true);
+        assert_ok!(MedicalHistory::record_lab_result(
+            &PATIENT_ID,
+            &LABORATORY,
+            &AUTHORIZED_DOCTOR,
+            hash(1),
+            metadata()
+        ));
+        System::assert_last_event(
+            Event::LabResultRecorded {
+                patient: PATIENT_ID,
+                laboratory: LABORATORY,
+                ordered_by: AUTHORIZED_DOCTOR,
+                hash: hash(1),
+            }
+            .into(),
+        );
+
+        let record = MedicalHistory::get_patient_record(&PATIENT_ID, &hash(1)).unwrap();
+        assert_eq!(record.created_by, LABORATORY);
+        assert_eq!(record.provenance, Provenance::LaboratoryReported);
+        assert_eq!(MedicalHistory::ordering_doctor(&hash(1)), Some(AUTHORIZED_DOCTOR));
+        assert_eq!(Balances::reserved_balance(LABORATORY), RECORD_DEPOSIT);
+        assert_eq!(
+            MedicalHistory::records_by_doctor(&LABORATORY, None, 0, 10).items,
+            vec![(PATIENT_ID, record)]
+        );
+
+        assert_noop!(
+            MedicalHistory::record_lab_result(
+                &PATIENT_ID,
+                &LABORATORY,
+                &AUTHORIZED_DOCTOR,
+                hash(1),
+                metadata()
+            ),
+            Error::<Test>::RecordAlreadyExists
+        );
+
+        // Redaction drops the link to the ordering doctor.
+        Timestamp::set_timestamp(10_000);
+        assert_ok!(MedicalHistory::redact_record(
+            RuntimeOrigin::signed(PATIENT_ID),
+            hash(1),
+            commitments(1)
+        ));
+        assert!(MedicalHistory::ordering_doctor(&hash(1)).is_none());
+    });
+}
+
+#[test]
+fn migration_v1_to_v2_marks_records_clinician_authored() {
+    use codec::Encode;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(1).put::<MedicalHistory>();
+
+        let old = crate::migrations::v2::OldMedicalRecord {
+            created_by: AUTHORIZED_DOCTOR,
+            created_at: 500u64,
+            file_hash: hash(1),
+            metadata: metadata(),
+        };
+        frame_support::storage::unhashed::put_raw(
+            &crate::Records::<Test>::hashed_key_for(hash(1)),
+            &old.encode(),
+        );
+        frame_support::storage::unhashed::put_raw(
+            &crate::PatientRecords::<Test>::hashed_key_for(PATIENT_ID, hash(1)),
+            &old.encode(),
+        );
+
+        crate::migrations::v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+
+        let record = MedicalHistory::patient_records(PATIENT_ID, hash(1)).unwrap();
+        assert_eq!(record.created_at, 500);
+        assert_eq!(record.metadata, metadata());
+        assert_eq!(record.provenance, Provenance::ClinicianAuthored);
+        assert_eq!(MedicalHistory::records(hash(1)), Some(record));
+        assert_eq!(MedicalHistory::on_chain_storage_version(), 2);
+    });
+}
+
+#[test]
+fn migration_v2_to_v3_requeues_proposals() {
+    use codec::Encode;
+    use frame_support::{
+        storage::{unhashed, StoragePrefixedMap},
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        StorageHasher, Twox64Concat,
+    };
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(MedicalHistory::propose_record(
+            RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+            PATIENT_ID,
+            hash(7),
+            metadata()
+        ));
+        let _ = crate::ProposalExpiryQueue::<Test>::clear(u32::MAX, None);
+        StorageVersion::new(2).put::<MedicalHistory>();
+        // `hash(8)` was already answered.
+        let mut key = crate::ProposalExpiryQueue::<Test>::final_prefix().to_vec();
+        key.extend(Twox64Concat::hash(&11u64.encode()));
+        unhashed::put(&key, &vec![hash(7), hash(8)]);
+
+        crate::migrations::v3::MigrateV2ToV3::<Test>::on_runtime_upgrade();
+
+        assert!(crate::ProposalExpiryQueue::<Test>::contains_key(11, hash(7)));
+        assert_eq!(crate::ProposalExpiryQueue::<Test>::iter().count(), 1);
+        assert_eq!(crate::ProposalExpiryCursor::<Test>::get(), 1);
+        assert_eq!(MedicalHistory::on_chain_storage_version(), 3);
+    });
+}
+
+#[test]
+fn migration_v3_to_v4_indexes_records_in_creation_order() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        for (at, i) in [(2_000, 1), (1_000, 2)] {
+            Timestamp::set_timestamp(at);
+            assert_ok!(MedicalHistory::create_record(
+                RuntimeOrigin::signed(AUTHORIZED_DOCTOR),
+                PATIENT_ID,
+                hash(i),
+                metadata()
+            ));
+        }
+        // Version `3` had no ordered indexes.
+        let _ = crate::PatientRecordOrder::<Test>::clear(u32::MAX, None);
+        let _ = crate::DoctorRecordOrder::<Test>::clear(u32::MAX, None);
+        let _ = crate::RecordSeqs::<Test>::clear(u32::MAX, None);
+        crate::NextRecordSeq::<Test>::kill();
+        StorageVersion::new(3).put::<MedicalHistory>();
+
+        crate::migrations::v4::MigrateV3ToV4::<Test>::on_runtime_upgrade();
+
+        let of_patient: Vec<_> = MedicalHistory::records_of_patient(&PATIENT_ID, None, 0, 10)
+            .items
+            .into_iter()
+            .map(|record| record.file_hash)
+            .collect();
+        assert_eq!(of_patient, vec![hash(2), hash(1)]);
+        assert_eq!(
+            MedicalHistory::records_by_doctor(&AUTHORIZED_DOCTOR, None, 0, 10).items.len(),
+            2
+        );
+        assert_eq!(crate::NextRecordSeq::<Test>::get(), 2);
+        assert_eq!(MedicalHistory::on_chain_storage_version(), 4);
+    });
+}
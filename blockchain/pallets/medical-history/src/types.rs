@@ -1,25 +1,246 @@
-use codec::{Decode, Encode, MaxEncodedLen};
+use alloc::vec::Vec;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 // CORREÇÃO: RuntimeDebug vem de pallet_prelude, não da raiz
 use frame_support::{BoundedVec, pallet_prelude::RuntimeDebug, traits::ConstU32};
 use scale_info::TypeInfo;
 
-/// Hash of a medical file (fixed-length 64 bytes).
-/// 
+use crate::cid::ContentId;
+
+/// Content identifier of a medical file, as submitted (up to 64 bytes).
+///
+/// New records must hold a value accepted by [`ContentId::parse`]; records
+/// created before that check may hold arbitrary bytes.
+///
 /// Defined in types.rs to keep lib.rs clean.
 pub type FileHash = BoundedVec<u8, ConstU32<64>>;
 
+/// MIME type of a medical file (e.g. `application/pdf`).
+pub type ContentType = BoundedVec<u8, ConstU32<64>>;
+
+/// Symmetric file key wrapped to one recipient's public key.
+///
+/// Opaque to the chain: typically an ephemeral x25519 public key, a nonce
+/// and the sealed 32-byte file key.
+pub type WrappedKey = BoundedVec<u8, ConstU32<128>>;
+
+/// Kind of document a record refers to.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum RecordCategory {
+    /// Lab or diagnostic exam result.
+    Exam,
+    /// Prescription ("Receita").
+    Prescription,
+    /// Medical certificate / sick leave ("Atestado").
+    Certificate,
+    /// Imaging study (X-ray, MRI, ultrasound, ...).
+    Imaging,
+    /// Clinical note or consultation summary.
+    Note,
+    /// Referral to another professional ("Encaminhamento").
+    Referral,
+    /// Anything else, including records created before categories existed.
+    Other,
+}
+
+/// Who vouches for the content of a record.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum Provenance {
+    /// Authored by a doctor with write access, including every record
+    /// created before provenance existed.
+    ClinicianAuthored,
+    /// Submitted by the patient (e.g. an exam from abroad or a wearable
+    /// export). A doctor may later endorse it.
+    SelfReported,
+    /// Exam result attached by a laboratory to a doctor's order. The
+    /// ordering doctor is kept alongside the record.
+    LaboratoryReported,
+}
+
+/// Structured metadata describing a record without downloading the file.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct RecordMetadata<Moment> {
+    /// Kind of document.
+    pub category: RecordCategory,
+    /// MIME type of the file.
+    pub content_type: ContentType,
+    /// Date of the clinical event, supplied by the doctor.
+    ///
+    /// Must not be later than the block timestamp.
+    pub clinical_date: Moment,
+    /// Hash of the encrypted document title, if any.
+    pub title_hash: Option<[u8; 32]>,
+}
+
+impl<Moment> RecordMetadata<Moment> {
+    /// Returns `true` if `content_type` looks like a MIME type
+    /// (`type/subtype`, printable ASCII without spaces).
+    pub fn has_valid_content_type(&self) -> bool {
+        let content_type = &self.content_type;
+        content_type.iter().all(|c| c.is_ascii_graphic())
+            && content_type.iter().filter(|c| **c == b'/').count() == 1
+            && content_type.first() != Some(&b'/')
+            && content_type.last() != Some(&b'/')
+    }
+}
+
 /// Represents a medical record reference stored on-chain.
 ///
 /// This struct stores metadata about a medical file hash:
 /// - who created it (`created_by`)
 /// - when it was created (`created_at`)
 /// - the file hash itself (`file_hash`)
+/// - what it contains (`metadata`)
+/// - whether a doctor or the patient submitted it (`provenance`)
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct MedicalRecord<AccountId, Moment> {
-    /// The account that created the record (doctor).
+    /// The account that created the record (doctor, or the patient for
+    /// self-reported records).
     pub created_by: AccountId,
     /// Timestamp when the record was created.
     pub created_at: Moment,
     /// File hash reference.
     pub file_hash: FileHash,
-}
\ No newline at end of file
+    /// Category, content type and clinical date of the file.
+    pub metadata: RecordMetadata<Moment>,
+    /// Whether the record is clinician-authored or self-reported.
+    pub provenance: Provenance,
+}
+
+impl<AccountId, Moment> MedicalRecord<AccountId, Moment> {
+    /// Parses `file_hash` as a [`ContentId`].
+    ///
+    /// Returns `None` for legacy records whose hash was stored as raw bytes;
+    /// those are still readable through `file_hash` itself.
+    pub fn content_id(&self) -> Option<ContentId> {
+        ContentId::parse(&self.file_hash).ok()
+    }
+}
+
+/// A record proposed by a doctor and waiting for the patient's confirmation.
+///
+/// Proposals live outside the record indexes until the patient confirms
+/// them; unconfirmed proposals are discarded at `expires_at`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingRecord<AccountId, Moment, BlockNumber> {
+    /// The patient that must confirm the record.
+    pub patient: AccountId,
+    /// The doctor that proposed the record.
+    pub doctor: AccountId,
+    /// Timestamp when the record was proposed.
+    pub proposed_at: Moment,
+    /// Block from which the proposal can no longer be confirmed.
+    pub expires_at: BlockNumber,
+    /// Metadata the record will be created with.
+    pub metadata: RecordMetadata<Moment>,
+}
+
+/// Link from an amended record to the version it replaces.
+///
+/// The replaced record itself is never modified; the forward link lives in
+/// the pallet's `SupersededBy` index.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Amendment<Hash> {
+    /// Hash of the version this record replaces.
+    pub previous: FileHash,
+    /// Hash of the off-chain justification for the amendment.
+    pub reason: Hash,
+    /// Position of this record in its version chain (the original is `0`).
+    pub version: u32,
+}
+
+/// What remains of a record after the patient redacted it.
+///
+/// The file hash is erased; the tombstone is keyed by
+/// `hash(salt ++ file_hash)`, so whoever holds the salt can still match
+/// audit log entries to it without the hash being revealed on-chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Tombstone<AccountId, Moment> {
+    /// The doctor that created the record.
+    pub created_by: AccountId,
+    /// Timestamp when the record was created.
+    pub created_at: Moment,
+    /// Timestamp when the record was redacted.
+    pub redacted_at: Moment,
+}
+
+/// A doctor's endorsement of a self-reported record.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Endorsement<AccountId, Moment> {
+    /// The doctor that endorsed the record.
+    pub endorsed_by: AccountId,
+    /// Timestamp of the endorsement.
+    pub endorsed_at: Moment,
+}
+
+/// Position of a record in the creation-ordered patient and doctor indexes.
+///
+/// Stored big-endian so that, under the `Identity` hasher, the order of the
+/// storage keys is the numeric order and a prefix iteration walks records
+/// oldest first.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct RecordSeq([u8; 8]);
+
+impl From<u64> for RecordSeq {
+    fn from(seq: u64) -> Self {
+        Self(seq.to_be_bytes())
+    }
+}
+
+impl From<RecordSeq> for u64 {
+    fn from(seq: RecordSeq) -> Self {
+        u64::from_be_bytes(seq.0)
+    }
+}
+
+/// One page of a creation-ordered record listing.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RecordPage<Item> {
+    /// Entries of the page, oldest first.
+    pub items: Vec<Item>,
+    /// `from` of the next page, or `None` if this is the last one.
+    pub next: Option<u64>,
+}
@@ -1,87 +1,124 @@
-
-//! Autogenerated weights for `pallet_template`
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
-//! DATE: 2026-01-06, STEPS: `50`, REPEAT: `200`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `CAF-038841NB.local.inatel.br`, CPU: `<UNKNOWN>`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `Some("dev")`, DB CACHE: `1024`
-
-// Executed Command:
-// ./target/release/healthchain-node
-// benchmark
-// pallet
-// --chain=dev
-// --pallet=pallet_template
-// --extrinsic=*
-// --steps=50
-// --repeat=200
-// --wasm-execution=compiled
-// --output=pallets/template/src/weights.rs
-// --template=.maintain/frame-weight-template.hbs
-
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
-#![allow(missing_docs)]
-#![allow(dead_code)]
+
+// ==========================================================================
+// ⚠️  PLACEHOLDER / MOCK WEIGHTS
+// 
+// TODO: This file is currently manually maintained for MVP development.
+// Before Mainnet release, replace this entire file with the output of:
+// ./target/release/healthchain-node benchmark pallet ...
+// ==========================================================================
 
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use core::marker::PhantomData;
 
-/// Weight functions needed for `pallet_template`.
 pub trait WeightInfo {
-	fn do_something() -> Weight;
-	fn cause_error() -> Weight;
+    fn create_record() -> Weight { Weight::from_parts(10_000, 0) }
+    fn propose_record() -> Weight { Weight::from_parts(10_000, 0) }
+    fn confirm_record() -> Weight { Weight::from_parts(10_000, 0) }
+    fn reject_record() -> Weight { Weight::from_parts(10_000, 0) }
+    fn amend_record() -> Weight { Weight::from_parts(10_000, 0) }
+    fn redact_record(v: u32, e: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(v.into()).saturating_mul(e.into()) }
+    fn share_key() -> Weight { Weight::from_parts(10_000, 0) }
+    fn complete_key_rotation(e: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(e.into()) }
+    fn submit_own_record() -> Weight { Weight::from_parts(10_000, 0) }
+    fn endorse_record() -> Weight { Weight::from_parts(10_000, 0) }
 }
 
-/// Weights for `pallet_template` using the Substrate node and recommended hardware.
+/// Weights for pallet_medical_history using the Substrate node and recommended hardware.
+/// `redact_record` is charged per version (`v`) and per key envelope of each
+/// version (`e`); `complete_key_rotation` per envelope (`e`).
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: `Template::Something` (r:0 w:1)
-	/// Proof: `Template::Something` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 5_000_000 picoseconds.
-		Weight::from_parts(5_000_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
-	}
-	/// Storage: `Template::Something` (r:1 w:1)
-	/// Proof: `Template::Something` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 5_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
-	}
+    fn create_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 10))
+    }
+    fn propose_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 5))
+    }
+    fn confirm_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 11))
+    }
+    fn reject_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 5))
+    }
+    fn amend_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(7, 12))
+    }
+    fn redact_record(v: u32, e: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(5, 13))
+            .saturating_add(T::DbWeight::get().reads_writes(2, 3).saturating_mul(e.into()))
+            .saturating_mul(v.into())
+    }
+    fn share_key() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(5, 3))
+    }
+    fn complete_key_rotation(e: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(T::DbWeight::get().reads_writes(6, 6).saturating_mul(e.into()))
+    }
+    fn submit_own_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 9))
+    }
+    fn endorse_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(4, 1))
+    }
 }
 
 // For backwards compatibility and tests.
 impl WeightInfo for () {
-	/// Storage: `Template::Something` (r:0 w:1)
-	/// Proof: `Template::Something` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 5_000_000 picoseconds.
-		Weight::from_parts(5_000_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
-	}
-	/// Storage: `Template::Something` (r:1 w:1)
-	/// Proof: `Template::Something` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 5_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
-	}
+    fn create_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 10))
+    }
+    fn propose_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 5))
+    }
+    fn confirm_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 11))
+    }
+    fn reject_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 5))
+    }
+    fn amend_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(7, 12))
+    }
+    fn redact_record(v: u32, e: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 13))
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 3).saturating_mul(e.into()))
+            .saturating_mul(v.into())
+    }
+    fn share_key() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 3))
+    }
+    fn complete_key_rotation(e: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+            .saturating_add(RocksDbWeight::get().reads_writes(6, 6).saturating_mul(e.into()))
+    }
+    fn submit_own_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 9))
+    }
+    fn endorse_record() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(4, 1))
+    }
 }
@@ -2,6 +2,8 @@
 
 pub use pallet::*;
 
+extern crate alloc;
+
 #[cfg(test)]
 mod mock;
 
@@ -14,14 +16,90 @@ mod benchmarking;
 pub mod weights;
 pub use weights::*;
 
+pub mod types;
+pub use types::*;
+
+pub mod migrations;
+
+use alloc::vec::Vec;
+use frame_support::traits::{fungible, Time};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_doctor_registry::DoctorRegistryVerifier;
+
+/// Timestamp type of the configured [`Time`] provider.
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+
+/// Balance type of the configured [`fungible`] currency.
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as fungible::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Permission history log entry type for a runtime.
+pub type PermissionLogEntryOf<T> = PermissionLogEntry<
+    <T as frame_system::Config>::AccountId,
+    BlockNumberFor<T>,
+    MomentOf<T>,
+>;
+
+/// Break-glass incident type for a runtime.
+pub type EmergencyIncidentOf<T> = EmergencyIncident<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    BlockNumberFor<T>,
+    MomentOf<T>,
+>;
+
+/// Grant timestamp type for a runtime.
+pub type GrantTimestampOf<T> = GrantTimestamp<BlockNumberFor<T>, MomentOf<T>>;
+
+/// Grant summary type for a runtime.
+pub type GrantInfoOf<T> =
+    GrantInfo<<T as frame_system::Config>::AccountId, BlockNumberFor<T>, MomentOf<T>>;
+
+/// Grant listing page type for a runtime.
+pub type GrantPageOf<T> =
+    GrantPage<<T as frame_system::Config>::AccountId, BlockNumberFor<T>, MomentOf<T>>;
+
+/// Institution type for a runtime.
+pub type InstitutionOf<T> = Institution<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    BlockNumberFor<T>,
+>;
+
+/// Incident contest type for a runtime.
+pub type IncidentContestOf<T> =
+    IncidentContest<<T as frame_system::Config>::Hash, BlockNumberFor<T>>;
+
+/// Referral type for a runtime.
+pub type ReferralOf<T> = Referral<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    BlockNumberFor<T>,
+>;
+
+/// Referral listing page type for a runtime.
+pub type ReferralPageOf<T> = ReferralPage<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    BlockNumberFor<T>,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{One, Zero},
+        traits::{fungible::MutateHold, tokens::Precision},
+    };
     use frame_system::pallet_prelude::*;
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(11);
+
     /// Main pallet struct.
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Pallet configuration trait.
@@ -39,6 +117,93 @@ pub mod pallet {
 
         /// Weight information for extrinsics.
         type WeightInfo: WeightInfo;
+
+        /// Registry used to make sure grantees are licensed doctors.
+        type DoctorRegistry: DoctorRegistryVerifier<Self::AccountId>;
+
+        /// Time provider used to timestamp the permission history log.
+        type Time: Time;
+
+        /// Currency used to hold storage deposits.
+        type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Notified whenever a doctor loses read access to a patient.
+        type OnAccessRevoked: OnAccessRevoked<Self::AccountId>;
+
+        /// Deposit held from the patient for each stored grant.
+        ///
+        /// Released when the grant is fully revoked or expires.
+        #[pallet::constant]
+        type PermissionDeposit: Get<BalanceOf<Self>>;
+
+        /// Number of permission history entries kept per patient.
+        ///
+        /// Once reached, the oldest entry is dropped for each new one.
+        #[pallet::constant]
+        type MaxLogEntries: Get<u32>;
+
+        /// Maximum number of queued expiries processed per block.
+        ///
+        /// Bounds the work done by [`Hooks::on_initialize`] when cleaning
+        /// up expired grants. Any number of grants may expire in the same
+        /// block; the ones above this limit are cleaned up in the following
+        /// blocks.
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+
+        /// Number of blocks a doctor's access request stays pending before
+        /// it expires.
+        #[pallet::constant]
+        type RequestTimeout: Get<BlockNumberFor<Self>>;
+
+        /// Origin allowed to authorize non-doctor emergency accounts
+        /// (e.g. ambulance services) to use [`Pallet::emergency_access`].
+        type EmergencyAuthorityOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Number of blocks a break-glass read access stays valid.
+        #[pallet::constant]
+        type EmergencyAccessDuration: Get<BlockNumberFor<Self>>;
+
+        /// Origin allowed to appoint and remove guardians on behalf of
+        /// patients who cannot do it themselves (e.g. minors).
+        type GuardianRegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to register institutions and appoint their admins.
+        type InstitutionRegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of members of an institution.
+        #[pallet::constant]
+        type MaxInstitutionMembers: Get<u32>;
+
+        /// Maximum number of patients granting access to one institution.
+        ///
+        /// Bounds the work done by [`Pallet::remove_member`].
+        #[pallet::constant]
+        type MaxInstitutionPatients: Get<u32>;
+
+        /// Maximum number of institutions a doctor can be a member of.
+        ///
+        /// Bounds the work done by [`MedicalPermissionsVerifier`] when
+        /// resolving institution grants.
+        #[pallet::constant]
+        type MaxMembershipsPerDoctor: Get<u32>;
+
+        /// Maximum number of grants removed by one call to
+        /// [`Pallet::revoke_all_access`].
+        #[pallet::constant]
+        type MaxRevocationsPerCall: Get<u32>;
+    }
+
+    /// Reasons for which this pallet holds funds.
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Storage deposit of a grant in [`Permissions`] or
+        /// [`InstitutionPermissions`].
+        #[codec(index = 0)]
+        PermissionDeposit,
     }
 
     // ---------------------------------------------------------------------
@@ -47,22 +212,460 @@ pub mod pallet {
 
     /// Mapping of medical access permissions.
     ///
-    /// `(patient, doctor) -> has_access`
+    /// `(patient, doctor) -> scope`
     ///
-    /// If the key does not exist, the value defaults to `false`
-    /// due to the use of `ValueQuery`.
+    /// If the key does not exist, the value defaults to
+    /// [`PermissionScope::NONE`] due to the use of `ValueQuery`.
+    /// Entries are never stored with an empty scope.
     #[pallet::storage]
     #[pallet::getter(fn permissions)]
     pub type Permissions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,    // Patient
+        Blake2_128Concat,
+        T::AccountId,    // Doctor
+        PermissionScope, // Granted scope
+        ValueQuery,
+    >;
+
+    /// Reverse index of [`Permissions`], with the time of each grant.
+    ///
+    /// `(doctor, patient) -> granted`
+    ///
+    /// Present exactly when the pair has an entry in [`Permissions`]. Lets
+    /// doctors list their patients without scanning all grants.
+    #[pallet::storage]
+    #[pallet::getter(fn doctor_patients)]
+    pub type DoctorPatients<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Doctor
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        GrantTimestampOf<T>,
+        OptionQuery,
+    >;
+
+    /// Number of entries of each patient in [`Permissions`].
+    #[pallet::storage]
+    #[pallet::getter(fn grant_count)]
+    pub type GrantCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Patients whose grants are frozen, with the block of the freeze.
+    ///
+    /// While frozen, no grant of the patient (to doctors or institutions)
+    /// is honoured; the grants themselves are kept.
+    #[pallet::storage]
+    #[pallet::getter(fn frozen_since)]
+    pub type FrozenPatients<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+    /// Deposits held for grants.
+    ///
+    /// `(patient, doctor) -> amount held from the patient`
+    ///
+    /// Grants created before deposits existed have no entry here.
+    #[pallet::storage]
+    #[pallet::getter(fn grant_deposits)]
+    pub type GrantDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Expiry of time-limited grants.
+    ///
+    /// `(patient, doctor) -> expires_at`
+    ///
+    /// Present only for grants created through
+    /// [`Pallet::grant_temporary_access`]. From block `expires_at` on, the
+    /// grant is no longer honoured, even before it is cleaned up.
+    #[pallet::storage]
+    #[pallet::getter(fn expirations)]
+    pub type Expirations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Blake2_128Concat,
+        T::AccountId, // Doctor
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
+    /// Grants scheduled to expire at a given block.
+    ///
+    /// `(expires_at, (patient, doctor)) -> ()`
+    ///
+    /// Holds exactly one entry per entry of [`Expirations`]. Drained in
+    /// batches by [`Hooks::on_initialize`], starting at [`ExpiryCursor`].
+    #[pallet::storage]
+    pub type ExpiryQueue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        (T::AccountId, T::AccountId), // (Patient, Doctor)
+        (),
+        OptionQuery,
+    >;
+
+    /// First block of [`ExpiryQueue`] that may still hold entries.
+    #[pallet::storage]
+    pub type ExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Append-only permission history log ("Histórico de permissões").
+    ///
+    /// `(patient, sequence) -> entry`
+    ///
+    /// Sequence numbers grow monotonically per patient. Only the last
+    /// [`Config::MaxLogEntries`] entries are kept; entries are never
+    /// modified, only dropped once they fall out of the retention window.
+    #[pallet::storage]
+    pub type PermissionLog<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Twox64Concat,
+        u64, // Sequence number
+        PermissionLogEntryOf<T>,
+        OptionQuery,
+    >;
+
+    /// Next sequence number of each patient's permission history log.
+    #[pallet::storage]
+    #[pallet::getter(fn permission_log_len)]
+    pub type PermissionLogNext<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+    /// Pending access requests created by doctors.
+    ///
+    /// `(patient, doctor) -> request`
+    ///
+    /// A doctor can have at most one pending request per patient.
+    #[pallet::storage]
+    #[pallet::getter(fn access_requests)]
+    pub type AccessRequests<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Blake2_128Concat,
+        T::AccountId, // Doctor
+        AccessRequest<T::Hash, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Access requests scheduled to expire at a given block.
+    ///
+    /// `(expires_at, (patient, doctor)) -> ()`
+    ///
+    /// Holds exactly one entry per entry of [`AccessRequests`]. Drained in
+    /// batches like [`ExpiryQueue`], starting at [`RequestExpiryCursor`].
+    #[pallet::storage]
+    pub type RequestExpiryQueue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        (T::AccountId, T::AccountId), // (Patient, Doctor)
+        (),
+        OptionQuery,
+    >;
+
+    /// First block of [`RequestExpiryQueue`] that may still hold entries.
+    #[pallet::storage]
+    pub type RequestExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Accounts authorized to break the glass without being registered
+    /// doctors.
+    #[pallet::storage]
+    #[pallet::getter(fn emergency_accounts)]
+    pub type EmergencyAccounts<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Active break-glass read accesses.
+    ///
+    /// `(patient, accessor) -> expires_at`
+    ///
+    /// Kept apart from [`Permissions`] so an emergency never touches the
+    /// patient's own grants. From block `expires_at` on, the access is no
+    /// longer honoured. Expired entries are removed lazily, by
+    /// [`Pallet::clear_emergency_access`], by the accessor's next
+    /// [`Pallet::emergency_access`] or by [`Pallet::contest_incident`].
+    #[pallet::storage]
+    #[pallet::getter(fn emergency_grants)]
+    pub type EmergencyGrants<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Blake2_128Concat,
+        T::AccountId, // Accessor
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
+    /// Break-glass incidents ("incidentes de emergência").
+    ///
+    /// `(patient, incident id) -> incident`
+    ///
+    /// Entries are written once and never modified or removed.
+    #[pallet::storage]
+    #[pallet::getter(fn incidents)]
+    pub type Incidents<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Twox64Concat,
+        u32, // Incident id
+        EmergencyIncidentOf<T>,
+        OptionQuery,
+    >;
+
+    /// Next incident id of each patient.
+    #[pallet::storage]
+    #[pallet::getter(fn incident_count)]
+    pub type NextIncidentId<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Patients' objections to break-glass incidents.
+    ///
+    /// `(patient, incident id) -> contest`
+    #[pallet::storage]
+    #[pallet::getter(fn contests)]
+    pub type Contests<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Twox64Concat,
+        u32, // Incident id
+        IncidentContestOf<T>,
+        OptionQuery,
+    >;
+
+    /// Guardians and legal representatives ("responsáveis legais").
+    ///
+    /// `(patient, guardian) -> guardianship`
+    ///
+    /// A guardian manages the patient's grants and reads the patient's
+    /// records. From block `until` on, the guardianship is no longer
+    /// honoured; the entry is kept so the patient can audit it.
+    #[pallet::storage]
+    #[pallet::getter(fn guardians)]
+    pub type Guardians<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
         T::AccountId, // Patient
         Blake2_128Concat,
+        T::AccountId, // Guardian
+        Guardianship<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Guardianships scheduled to end at a given block.
+    ///
+    /// `(until, (patient, guardian)) -> ()`
+    ///
+    /// Holds one entry per entry of [`Guardians`] with an end date that was
+    /// not reached yet. Drained in batches like [`ExpiryQueue`], starting at
+    /// [`GuardianExpiryCursor`].
+    #[pallet::storage]
+    pub type GuardianExpiryQueue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        (T::AccountId, T::AccountId), // (Patient, Guardian)
+        (),
+        OptionQuery,
+    >;
+
+    /// First block of [`GuardianExpiryQueue`] that may still hold entries.
+    #[pallet::storage]
+    pub type GuardianExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Registered institutions.
+    #[pallet::storage]
+    #[pallet::getter(fn institutions)]
+    pub type Institutions<T: Config> =
+        StorageMap<_, Twox64Concat, InstitutionId, InstitutionOf<T>, OptionQuery>;
+
+    /// Id of the next institution to be registered.
+    #[pallet::storage]
+    pub type NextInstitutionId<T: Config> = StorageValue<_, InstitutionId, ValueQuery>;
+
+    /// Institution members.
+    ///
+    /// `(institution, doctor) -> block in which the doctor joined`
+    #[pallet::storage]
+    #[pallet::getter(fn institution_members)]
+    pub type InstitutionMembers<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        InstitutionId,
+        Blake2_128Concat,
         T::AccountId, // Doctor
-        bool,         // Access granted?
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
+    /// Number of members of each institution.
+    #[pallet::storage]
+    pub type InstitutionMemberCount<T: Config> =
+        StorageMap<_, Twox64Concat, InstitutionId, u32, ValueQuery>;
+
+    /// Reverse index of [`InstitutionMembers`]: `doctor -> institutions`.
+    #[pallet::storage]
+    #[pallet::getter(fn memberships)]
+    pub type Memberships<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<InstitutionId, T::MaxMembershipsPerDoctor>,
+        ValueQuery,
+    >;
+
+    /// Grants from patients to institutions.
+    ///
+    /// `(patient, institution) -> scope`
+    ///
+    /// Every member of the institution holds the scope, in addition to
+    /// their own grant in [`Permissions`].
+    #[pallet::storage]
+    #[pallet::getter(fn institution_permissions)]
+    pub type InstitutionPermissions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Twox64Concat,
+        InstitutionId,
+        PermissionScope,
         ValueQuery,
     >;
 
+    /// Deposits held for grants in [`InstitutionPermissions`].
+    ///
+    /// `(patient, institution) -> deposit`
+    #[pallet::storage]
+    pub type InstitutionDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Twox64Concat,
+        InstitutionId,
+        BalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Reverse index of [`InstitutionPermissions`]: `(institution, patient)`.
+    #[pallet::storage]
+    pub type InstitutionPatients<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        InstitutionId,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        (),
+        OptionQuery,
+    >;
+
+    /// Number of patients in [`InstitutionPatients`] of each institution.
+    #[pallet::storage]
+    pub type InstitutionPatientCount<T: Config> =
+        StorageMap<_, Twox64Concat, InstitutionId, u32, ValueQuery>;
+
+    /// Number of entries of each patient in [`InstitutionPermissions`].
+    #[pallet::storage]
+    pub type InstitutionGrantCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Referrals from doctors to specialists, in any status.
+    #[pallet::storage]
+    #[pallet::getter(fn referrals)]
+    pub type Referrals<T: Config> =
+        StorageMap<_, Twox64Concat, ReferralId, ReferralOf<T>, OptionQuery>;
+
+    /// Id of the next referral to be proposed.
+    #[pallet::storage]
+    #[pallet::getter(fn next_referral_id)]
+    pub type NextReferralId<T: Config> = StorageValue<_, ReferralId, ValueQuery>;
+
+    /// Proposed or active referral of each specialist.
+    ///
+    /// `(patient, specialist) -> referral`
+    ///
+    /// A specialist has at most one open referral per patient. The entry is
+    /// removed when the referral is closed or expires.
+    #[pallet::storage]
+    #[pallet::getter(fn open_referrals)]
+    pub type OpenReferrals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Blake2_128Concat,
+        T::AccountId, // Specialist
+        ReferralId,
+        OptionQuery,
+    >;
+
+    /// Index of the referrals of each patient.
+    ///
+    /// `(patient, referral) -> ()`
+    ///
+    /// Keyed by [`ReferralKey`], so iterating a patient's prefix walks their
+    /// referrals oldest first.
+    #[pallet::storage]
+    pub type PatientReferrals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        ReferralKey,
+        (),
+        OptionQuery,
+    >;
+
+    /// Index of the referrals addressed to each specialist.
+    ///
+    /// `(specialist, referral) -> ()`
+    ///
+    /// Same ordering as [`PatientReferrals`].
+    #[pallet::storage]
+    pub type SpecialistReferrals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        ReferralKey,
+        (),
+        OptionQuery,
+    >;
+
+    /// Active referrals scheduled to expire at a given block.
+    ///
+    /// `(expires_at, referral) -> ()`
+    ///
+    /// Holds one entry per active referral of [`Referrals`]. Drained in
+    /// batches like [`ExpiryQueue`], starting at [`ReferralExpiryCursor`].
+    #[pallet::storage]
+    pub type ReferralExpiryQueue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Twox64Concat,
+        ReferralId,
+        (),
+        OptionQuery,
+    >;
+
+    /// First block of [`ReferralExpiryQueue`] that may still hold entries.
+    #[pallet::storage]
+    pub type ReferralExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
     // ---------------------------------------------------------------------
     // Events
     // ---------------------------------------------------------------------
@@ -72,85 +675,2460 @@ pub mod pallet {
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Access was granted from a patient to a doctor.
+        ///
+        /// `scope` is the full scope held by the doctor after the grant and
+        /// `expires_at` is `None` for grants without expiry.
         AccessGranted {
             patient: T::AccountId,
             doctor: T::AccountId,
+            scope: PermissionScope,
+            expires_at: Option<BlockNumberFor<T>>,
         },
 
         /// Access was revoked from a patient to a doctor.
+        ///
+        /// `scope` is the scope that remains after the revocation
+        /// ([`PermissionScope::NONE`] when access was fully removed).
         AccessRevoked {
             patient: T::AccountId,
             doctor: T::AccountId,
+            scope: PermissionScope,
         },
-    }
 
-    // ---------------------------------------------------------------------
-    // Errors
-    // ---------------------------------------------------------------------
+        /// A time-limited grant reached its expiry block and was removed.
+        AccessExpired {
+            patient: T::AccountId,
+            doctor: T::AccountId,
+        },
 
-    /// Errors returned by the Medical Permissions pallet.
-    #[pallet::error]
-    pub enum Error<T> {
-        /// A patient attempted to grant permission to themselves.
-        SelfPermissionNotAllowed,
-    }
+        /// A doctor requested access to a patient's data.
+        AccessRequested {
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+            reason_hash: T::Hash,
+            expires_at: BlockNumberFor<T>,
+        },
 
-    // ---------------------------------------------------------------------
-    // Calls (extrinsics)
-    // ---------------------------------------------------------------------
+        /// A patient approved a doctor's access request.
+        AccessRequestApproved {
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+        },
+
+        /// A patient rejected a doctor's access request.
+        AccessRequestRejected {
+            patient: T::AccountId,
+            doctor: T::AccountId,
+        },
+
+        /// A doctor's access request timed out without an answer.
+        AccessRequestExpired {
+            patient: T::AccountId,
+            doctor: T::AccountId,
+        },
+
+        /// An account broke the glass and gained temporary read access.
+        ///
+        /// Meant to be picked up by the patient's notification tooling.
+        EmergencyAccessGranted {
+            patient: T::AccountId,
+            accessor: T::AccountId,
+            incident: u32,
+            justification_hash: T::Hash,
+            expires_at: BlockNumberFor<T>,
+        },
+
+        /// An expired break-glass read access was removed.
+        EmergencyAccessExpired {
+            patient: T::AccountId,
+            accessor: T::AccountId,
+        },
+
+        /// The patient contested a break-glass incident.
+        IncidentContested {
+            patient: T::AccountId,
+            accessor: T::AccountId,
+            incident: u32,
+            reason_hash: T::Hash,
+        },
+
+        /// An account was authorized to break the glass.
+        EmergencyAccountAdded { account: T::AccountId },
+
+        /// An emergency account lost its authorization.
+        EmergencyAccountRemoved { account: T::AccountId },
+
+        /// A guardian was appointed for a patient.
+        GuardianAppointed {
+            patient: T::AccountId,
+            guardian: T::AccountId,
+            appointed_by: GuardianAppointer,
+            until: Option<BlockNumberFor<T>>,
+        },
+
+        /// A guardian was removed.
+        GuardianRemoved {
+            patient: T::AccountId,
+            guardian: T::AccountId,
+        },
+
+        /// A guardian changed a patient's grants.
+        ///
+        /// Emitted right after the [`Event::AccessGranted`] or
+        /// [`Event::AccessRevoked`] it attributes.
+        GuardianActed {
+            patient: T::AccountId,
+            guardian: T::AccountId,
+            doctor: T::AccountId,
+            action: PermissionAction,
+            scope: PermissionScope,
+        },
+
+        /// An institution was registered.
+        InstitutionRegistered {
+            institution: InstitutionId,
+            admin: T::AccountId,
+        },
+
+        /// The registrar replaced an institution's admin.
+        InstitutionAdminChanged {
+            institution: InstitutionId,
+            admin: T::AccountId,
+        },
+
+        /// An admin added a doctor to an institution.
+        MemberAdded {
+            institution: InstitutionId,
+            doctor: T::AccountId,
+        },
+
+        /// An admin removed a doctor from an institution.
+        MemberRemoved {
+            institution: InstitutionId,
+            doctor: T::AccountId,
+        },
+
+        /// A patient granted access to an institution.
+        ///
+        /// `scope` is the full scope held by the institution after the grant.
+        InstitutionAccessGranted {
+            patient: T::AccountId,
+            institution: InstitutionId,
+            scope: PermissionScope,
+        },
+
+        /// A patient revoked access from an institution.
+        ///
+        /// `scope` is the scope that remains after the revocation.
+        InstitutionAccessRevoked {
+            patient: T::AccountId,
+            institution: InstitutionId,
+            scope: PermissionScope,
+        },
+
+        /// A patient revoked a batch of grants at once.
+        ///
+        /// `remaining` grants, to doctors and institutions, are left; call
+        /// again until it reaches zero.
+        BulkAccessRevoked {
+            patient: T::AccountId,
+            revoked: u32,
+            remaining: u32,
+        },
+
+        /// A patient froze all their grants. `grants` counts both doctor
+        /// and institution grants.
+        AccessFrozen { patient: T::AccountId, grants: u32 },
+
+        /// A patient unfroze their grants.
+        AccessUnfrozen { patient: T::AccountId, grants: u32 },
+
+        /// A doctor referred a patient to a specialist.
+        ReferralProposed {
+            referral: ReferralId,
+            patient: T::AccountId,
+            referring_doctor: T::AccountId,
+            specialist: T::AccountId,
+        },
+
+        /// The patient approved a referral; the specialist holds access
+        /// until `expires_at`.
+        ReferralApproved {
+            referral: ReferralId,
+            patient: T::AccountId,
+            specialist: T::AccountId,
+            expires_at: BlockNumberFor<T>,
+        },
+
+        /// A party closed a referral, removing the specialist's access.
+        ReferralClosed {
+            referral: ReferralId,
+            closed_by: T::AccountId,
+        },
+
+        /// A referral reached the end of its duration.
+        ReferralExpired {
+            referral: ReferralId,
+            patient: T::AccountId,
+            specialist: T::AccountId,
+        },
+
+        /// A guardianship reached its end date.
+        GuardianshipEnded {
+            patient: T::AccountId,
+            guardian: T::AccountId,
+        },
+    }
+
+    // ---------------------------------------------------------------------
+    // Errors
+    // ---------------------------------------------------------------------
+
+    /// Errors returned by the Medical Permissions pallet.
+    #[pallet::error]
+    pub enum Error<T> {
+        /// A patient attempted to grant permission to themselves.
+        SelfPermissionNotAllowed,
+
+        /// The provided scope is empty or contains unknown permission bits.
+        InvalidScope,
+
+        /// The expiry block is not in the future.
+        ExpiryInPast,
+
+        /// The doctor already has a pending request for this patient.
+        RequestAlreadyPending,
+
+        /// No pending request exists for this patient and doctor.
+        RequestNotFound,
+
+        /// The request timed out and can no longer be approved.
+        RequestExpired,
+
+        /// The doctor is not registered or is suspended.
+        DoctorNotRegistered,
+
+        /// The caller is neither an active doctor nor an emergency account.
+        NotEmergencyAccount,
+
+        /// The account is already an emergency account.
+        EmergencyAccountExists,
+
+        /// The account is not an emergency account.
+        EmergencyAccountNotFound,
+
+        /// No incident exists with this id.
+        IncidentNotFound,
+
+        /// The incident was already contested.
+        IncidentAlreadyContested,
+
+        /// The caller is not an active guardian of the patient.
+        NotGuardian,
+
+        /// No guardian was appointed by this origin for the patient.
+        GuardianNotFound,
+
+        /// No institution exists with this id.
+        InstitutionNotFound,
+
+        /// The caller is not the institution's admin.
+        NotInstitutionAdmin,
+
+        /// The doctor is already a member of the institution.
+        AlreadyMember,
+
+        /// The doctor is not a member of the institution.
+        NotMember,
+
+        /// The institution has [`Config::MaxInstitutionMembers`] members.
+        TooManyMembers,
+
+        /// The doctor is in [`Config::MaxMembershipsPerDoctor`] institutions.
+        TooManyMemberships,
+
+        /// The patient's grants are already frozen.
+        AlreadyFrozen,
+
+        /// The patient's grants are not frozen.
+        NotFrozen,
+
+        /// The specialist is the patient or the referring doctor, the
+        /// duration is zero or the record selection is empty.
+        InvalidReferral,
+
+        /// The referring doctor cannot read the patient's data.
+        ReferrerWithoutAccess,
+
+        /// The specialist already has an open referral for this patient.
+        ReferralAlreadyOpen,
+
+        /// No referral exists with this id.
+        ReferralNotFound,
+
+        /// The referral is not waiting for the patient's approval.
+        ReferralNotProposed,
+
+        /// The referral was already closed or expired.
+        ReferralNotOpen,
+
+        /// The caller is not the patient, the referring doctor or the
+        /// specialist of the referral.
+        NotReferralParty,
+
+        /// The doctor holds a grant without expiry, which a temporary grant
+        /// would shorten.
+        PermanentGrantExists,
+
+        /// The accessor holds no break-glass access to the patient.
+        EmergencyAccessNotFound,
+
+        /// The break-glass access has not expired yet.
+        EmergencyAccessActive,
+
+        /// The guardian is in force by another appointer's appointment.
+        GuardianAppointedByOther,
+
+        /// The institution is granted access by
+        /// [`Config::MaxInstitutionPatients`] patients.
+        TooManyInstitutionPatients,
+
+        /// The patient reached the maximum number of break-glass incidents.
+        TooManyIncidents,
+    }
+
+    // ---------------------------------------------------------------------
+    // Hooks
+    // ---------------------------------------------------------------------
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Removes the grants, access requests and referrals that expire at
+        /// block `n`, and ends the guardianships due by then. Expired
+        /// break-glass accesses are removed lazily (see [`EmergencyGrants`]).
+        ///
+        /// At most [`Config::MaxExpiriesPerBlock`] entries of each kind are
+        /// processed. Entries left over are handled in the following blocks.
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::expire_grants(n)
+                .saturating_add(Self::expire_requests(n))
+                .saturating_add(Self::expire_referrals(n))
+                .saturating_add(Self::expire_guardianships(n))
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Calls (extrinsics)
+    // ---------------------------------------------------------------------
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Grants full (read and write) access to a doctor.
+        ///
+        /// Equivalent to [`Pallet::grant_access_scoped`] with
+        /// [`PermissionScope::ALL`]. Any previous expiry is cleared.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `doctor`: The doctor account that will receive access.
+        ///
+        /// # Storage
+        /// - Writes to [`Permissions`], [`GrantDeposits`]
+        /// - Holds [`Config::PermissionDeposit`] from the patient for a new grant
+        ///
+        /// # Emits
+        /// - [`Event::AccessGranted`]
+        ///
+        /// # Errors
+        /// - [`Error::SelfPermissionNotAllowed`] if `patient == doctor`
+        /// - [`Error::DoctorNotRegistered`] if `doctor` is not an active doctor
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::grant_access())]
+        pub fn grant_access(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            Self::do_grant(patient, doctor, PermissionScope::ALL, None)
+        }
+
+        /// Revokes all access from a doctor.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `doctor`: The doctor account that will lose access.
+        ///
+        /// # Storage
+        /// - Writes to [`Permissions`], [`GrantDeposits`]
+        /// - Releases the grant's deposit once no permission bit is left
+        ///
+        /// # Emits
+        /// - [`Event::AccessRevoked`]
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::revoke_access())]
+        pub fn revoke_access(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            Self::do_revoke(patient, doctor, PermissionScope::ALL)
+        }
+
+        /// Grants a specific scope (read and/or write) to a doctor.
+        ///
+        /// The scope is added to whatever the doctor already holds, so
+        /// granting [`PermissionScope::WRITE`] after [`PermissionScope::READ`]
+        /// results in full access. Any previous expiry is cleared.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `doctor`: The doctor account that will receive access.
+        /// - `scope`: The permission bits to grant.
+        ///
+        /// # Storage
+        /// - Writes to [`Permissions`], [`GrantDeposits`]
+        /// - Holds [`Config::PermissionDeposit`] from the patient for a new grant
+        ///
+        /// # Emits
+        /// - [`Event::AccessGranted`]
+        ///
+        /// # Errors
+        /// - [`Error::SelfPermissionNotAllowed`] if `patient == doctor`
+        /// - [`Error::InvalidScope`] if `scope` is empty or has unknown bits
+        /// - [`Error::DoctorNotRegistered`] if `doctor` is not an active doctor
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::grant_access_scoped())]
+        pub fn grant_access_scoped(
+            origin: OriginFor<T>,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            Self::do_grant(patient, doctor, scope, None)
+        }
+
+        /// Revokes a specific scope (read and/or write) from a doctor.
+        ///
+        /// The remaining scope is kept; the entry is removed only when no
+        /// permission bit is left.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `doctor`: The doctor account that will lose access.
+        /// - `scope`: The permission bits to revoke.
+        ///
+        /// # Storage
+        /// - Writes to [`Permissions`], [`GrantDeposits`]
+        /// - Releases the grant's deposit once no permission bit is left
+        ///
+        /// # Emits
+        /// - [`Event::AccessRevoked`]
+        ///
+        /// # Errors
+        /// - [`Error::InvalidScope`] if `scope` is empty or has unknown bits
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::revoke_access_scoped())]
+        pub fn revoke_access_scoped(
+            origin: OriginFor<T>,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            Self::do_revoke(patient, doctor, scope)
+        }
+
+        /// Grants a scope to a doctor until block `expires_at`.
+        ///
+        /// The scope is added to whatever the doctor already holds and the
+        /// whole grant expires at `expires_at`. A grant without expiry is
+        /// never shortened this way; revoke it first. The entry is removed by
+        /// [`Hooks::on_initialize`] from that block on and
+        /// [`MedicalPermissionsVerifier`] stops honouring it at that block.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `doctor`: The doctor account that will receive access.
+        /// - `scope`: The permission bits to grant.
+        /// - `expires_at`: Block number at which the grant expires.
+        ///
+        /// # Storage
+        /// - Writes to [`Permissions`], [`GrantDeposits`], [`Expirations`], [`ExpiryQueue`]
+        /// - Holds [`Config::PermissionDeposit`] from the patient for a new grant
+        ///
+        /// # Emits
+        /// - [`Event::AccessGranted`]
+        ///
+        /// # Errors
+        /// - [`Error::SelfPermissionNotAllowed`] if `patient == doctor`
+        /// - [`Error::InvalidScope`] if `scope` is empty or has unknown bits
+        /// - [`Error::ExpiryInPast`] if `expires_at` is not in the future
+        /// - [`Error::PermanentGrantExists`] if `doctor` holds a grant without
+        ///   expiry
+        /// - [`Error::DoctorNotRegistered`] if `doctor` is not an active doctor
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::grant_temporary_access())]
+        pub fn grant_temporary_access(
+            origin: OriginFor<T>,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+            expires_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            ensure!(
+                expires_at > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::ExpiryInPast
+            );
+
+            Self::do_grant(patient, doctor, scope, Some(expires_at))
+        }
+
+        /// Requests access to a patient's data.
+        ///
+        /// The request stays pending for [`Config::RequestTimeout`] blocks,
+        /// until the patient approves or rejects it.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the doctor.
+        /// - `patient`: The patient whose data the doctor wants to access.
+        /// - `scope`: The permission bits requested.
+        /// - `reason_hash`: Hash of the off-chain justification.
+        ///
+        /// # Storage
+        /// - Writes to [`AccessRequests`], [`RequestExpiryQueue`]
+        ///
+        /// # Emits
+        /// - [`Event::AccessRequested`]
+        ///
+        /// # Errors
+        /// - [`Error::SelfPermissionNotAllowed`] if `patient == doctor`
+        /// - [`Error::InvalidScope`] if `scope` is empty or has unknown bits
+        /// - [`Error::RequestAlreadyPending`] if a valid request already exists
+        /// - [`Error::DoctorNotRegistered`] if the caller is not an active doctor
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::request_access())]
+        pub fn request_access(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            scope: PermissionScope,
+            reason_hash: T::Hash,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
+            ensure!(patient != doctor, Error::<T>::SelfPermissionNotAllowed);
+            ensure!(!scope.is_empty() && scope.is_valid(), Error::<T>::InvalidScope);
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&doctor),
+                Error::<T>::DoctorNotRegistered
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+
+            // An expired request that was not cleaned up yet can be replaced.
+            if let Some(pending) = AccessRequests::<T>::get(&patient, &doctor) {
+                ensure!(pending.expires_at <= now, Error::<T>::RequestAlreadyPending);
+                Self::remove_request(&patient, &doctor);
+            }
+
+            let expires_at = now.saturating_add(T::RequestTimeout::get());
+            RequestExpiryQueue::<T>::insert(expires_at, (&patient, &doctor), ());
+
+            AccessRequests::<T>::insert(
+                &patient,
+                &doctor,
+                AccessRequest {
+                    scope,
+                    reason_hash,
+                    requested_at: now,
+                    expires_at,
+                },
+            );
+
+            Self::deposit_event(Event::AccessRequested {
+                patient,
+                doctor,
+                scope,
+                reason_hash,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Approves a doctor's pending access request.
+        ///
+        /// The requested scope is granted without expiry, as if the patient
+        /// had called [`Pallet::grant_access_scoped`].
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `doctor`: The doctor who made the request.
+        ///
+        /// # Storage
+        /// - Removes from [`AccessRequests`], [`RequestExpiryQueue`]
+        /// - Writes to [`Permissions`], [`GrantDeposits`]
+        /// - Holds [`Config::PermissionDeposit`] from the patient for a new grant
+        ///
+        /// # Emits
+        /// - [`Event::AccessRequestApproved`]
+        /// - [`Event::AccessGranted`]
+        ///
+        /// # Errors
+        /// - [`Error::RequestNotFound`] if there is no pending request
+        /// - [`Error::RequestExpired`] if the request timed out
+        /// - [`Error::DoctorNotRegistered`] if the doctor was suspended meanwhile
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::approve_request())]
+        pub fn approve_request(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            let request = AccessRequests::<T>::get(&patient, &doctor)
+                .ok_or(Error::<T>::RequestNotFound)?;
+            ensure!(
+                request.expires_at > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::RequestExpired
+            );
+
+            Self::remove_request(&patient, &doctor);
+
+            Self::deposit_event(Event::AccessRequestApproved {
+                patient: patient.clone(),
+                doctor: doctor.clone(),
+                scope: request.scope,
+            });
+
+            Self::do_grant(patient, doctor, request.scope, None)
+        }
+
+        /// Rejects a doctor's pending access request.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `doctor`: The doctor who made the request.
+        ///
+        /// # Storage
+        /// - Removes from [`AccessRequests`], [`RequestExpiryQueue`]
+        ///
+        /// # Emits
+        /// - [`Event::AccessRequestRejected`]
+        ///
+        /// # Errors
+        /// - [`Error::RequestNotFound`] if there is no pending request
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::reject_request())]
+        pub fn reject_request(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            Self::remove_request(&patient, &doctor).ok_or(Error::<T>::RequestNotFound)?;
+
+            Self::deposit_event(Event::AccessRequestRejected { patient, doctor });
+
+            Ok(())
+        }
+
+        /// Breaks the glass: takes read access to a patient's data without
+        /// a grant, for [`Config::EmergencyAccessDuration`] blocks.
+        ///
+        /// Meant for emergency rooms, where the patient may be unable to
+        /// consent. The access is read-only, never touches the patient's
+        /// own grants, and leaves an immutable [`EmergencyIncident`] that
+        /// the patient can later contest.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed active doctor or emergency account.
+        /// - `patient`: The patient whose data is needed.
+        /// - `justification_hash`: Hash of the off-chain clinical justification.
+        ///
+        /// # Storage
+        /// - Writes to [`EmergencyGrants`], [`Incidents`], [`NextIncidentId`],
+        ///   [`PermissionLog`]
+        ///
+        /// # Emits
+        /// - [`Event::EmergencyAccessGranted`]
+        ///
+        /// # Errors
+        /// - [`Error::SelfPermissionNotAllowed`] if `patient` is the caller
+        /// - [`Error::NotEmergencyAccount`] if the caller may not break the glass
+        /// - [`Error::TooManyIncidents`] if the patient's incident ids are
+        ///   exhausted
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::emergency_access())]
+        pub fn emergency_access(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            justification_hash: T::Hash,
+        ) -> DispatchResult {
+            let accessor = ensure_signed(origin)?;
+
+            ensure!(patient != accessor, Error::<T>::SelfPermissionNotAllowed);
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&accessor) ||
+                    EmergencyAccounts::<T>::contains_key(&accessor),
+                Error::<T>::NotEmergencyAccount
+            );
+
+            // Incidents are written once: never reuse the last id.
+            let incident = NextIncidentId::<T>::get(&patient);
+            let next_incident = incident.checked_add(1).ok_or(Error::<T>::TooManyIncidents)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let expires_at = now.saturating_add(T::EmergencyAccessDuration::get());
+            EmergencyGrants::<T>::insert(&patient, &accessor, expires_at);
+
+            Incidents::<T>::insert(
+                &patient,
+                incident,
+                EmergencyIncident {
+                    accessor: accessor.clone(),
+                    justification_hash,
+                    block: now,
+                    timestamp: T::Time::now(),
+                    expires_at,
+                },
+            );
+            NextIncidentId::<T>::insert(&patient, next_incident);
+
+            Self::log_change(
+                &patient,
+                LogSubject::Doctor(accessor.clone()),
+                PermissionAction::EmergencyAccess,
+                PermissionScope::READ,
+            );
+
+            Self::deposit_event(Event::EmergencyAccessGranted {
+                patient,
+                accessor,
+                incident,
+                justification_hash,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Contests a break-glass incident.
+        ///
+        /// The incident itself is kept unchanged; the contest is stored next
+        /// to it for auditors. If the emergency access is still active, it
+        /// ends immediately; if it already expired, it is cleaned up.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `incident`: Id of the incident being contested.
+        /// - `reason_hash`: Hash of the off-chain statement of the objection.
+        ///
+        /// # Storage
+        /// - Writes to [`Contests`]
+        /// - Removes from [`EmergencyGrants`]
+        ///
+        /// # Emits
+        /// - [`Event::IncidentContested`]
+        ///
+        /// # Errors
+        /// - [`Error::IncidentNotFound`] if the patient has no such incident
+        /// - [`Error::IncidentAlreadyContested`] if it was already contested
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::contest_incident())]
+        pub fn contest_incident(
+            origin: OriginFor<T>,
+            incident: u32,
+            reason_hash: T::Hash,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            let record =
+                Incidents::<T>::get(&patient, incident).ok_or(Error::<T>::IncidentNotFound)?;
+            ensure!(
+                !Contests::<T>::contains_key(&patient, incident),
+                Error::<T>::IncidentAlreadyContested
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            Contests::<T>::insert(
+                &patient,
+                incident,
+                IncidentContest { reason_hash, contested_at: now },
+            );
+
+            // Only end the access this incident opened, not a later one.
+            if EmergencyGrants::<T>::get(&patient, &record.accessor) == Some(record.expires_at) {
+                EmergencyGrants::<T>::remove(&patient, &record.accessor);
+                if !Self::scope_of(&patient, &record.accessor).can_read() {
+                    T::OnAccessRevoked::on_access_revoked(&patient, &record.accessor);
+                }
+            }
+
+            Self::deposit_event(Event::IncidentContested {
+                patient,
+                accessor: record.accessor,
+                incident,
+                reason_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Authorizes an account that is not a registered doctor (e.g. an
+        /// ambulance service) to use [`Pallet::emergency_access`].
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::EmergencyAuthorityOrigin`].
+        /// - `account`: The account to authorize.
+        ///
+        /// # Storage
+        /// - Writes to [`EmergencyAccounts`]
+        ///
+        /// # Emits
+        /// - [`Event::EmergencyAccountAdded`]
+        ///
+        /// # Errors
+        /// - [`Error::EmergencyAccountExists`] if already authorized
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::set_emergency_account())]
+        pub fn add_emergency_account(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            T::EmergencyAuthorityOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !EmergencyAccounts::<T>::contains_key(&account),
+                Error::<T>::EmergencyAccountExists
+            );
+            EmergencyAccounts::<T>::insert(&account, ());
+
+            Self::deposit_event(Event::EmergencyAccountAdded { account });
+
+            Ok(())
+        }
+
+        /// Removes an emergency account's authorization.
+        ///
+        /// Accesses it already took stay valid until they expire.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::EmergencyAuthorityOrigin`].
+        /// - `account`: The account to deauthorize.
+        ///
+        /// # Storage
+        /// - Removes from [`EmergencyAccounts`]
+        ///
+        /// # Emits
+        /// - [`Event::EmergencyAccountRemoved`]
+        ///
+        /// # Errors
+        /// - [`Error::EmergencyAccountNotFound`] if not authorized
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::set_emergency_account())]
+        pub fn remove_emergency_account(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            T::EmergencyAuthorityOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                EmergencyAccounts::<T>::take(&account).is_some(),
+                Error::<T>::EmergencyAccountNotFound
+            );
+
+            Self::deposit_event(Event::EmergencyAccountRemoved { account });
+
+            Ok(())
+        }
+
+        /// Appoints a guardian who may manage grants and read records on
+        /// the caller's behalf.
+        ///
+        /// Appointing an existing guardian again replaces the end date.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `guardian`: The guardian account.
+        /// - `until`: Block at which the guardianship ends, if any.
+        ///
+        /// # Storage
+        /// - Writes to [`Guardians`], [`GuardianExpiryQueue`]
+        ///
+        /// # Emits
+        /// - [`Event::GuardianAppointed`]
+        ///
+        /// # Errors
+        /// - [`Error::SelfPermissionNotAllowed`] if `guardian` is the caller
+        /// - [`Error::ExpiryInPast`] if `until` is not in the future
+        /// - [`Error::GuardianAppointedByOther`] if `guardian` is in force by
+        ///   the registrar's appointment
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::appoint_guardian())]
+        pub fn appoint_guardian(
+            origin: OriginFor<T>,
+            guardian: T::AccountId,
+            until: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            Self::do_appoint(patient, guardian, GuardianAppointer::Patient, until)
+        }
+
+        /// Appoints a guardian for a patient who cannot do it themselves,
+        /// typically a minor until the age of majority.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::GuardianRegistrarOrigin`].
+        /// - `patient`: The patient under guardianship.
+        /// - `guardian`: The guardian account.
+        /// - `until`: Block at which the guardianship ends, if any.
+        ///
+        /// # Storage
+        /// - Writes to [`Guardians`], [`GuardianExpiryQueue`]
+        ///
+        /// # Emits
+        /// - [`Event::GuardianAppointed`]
+        ///
+        /// # Errors
+        /// - [`Error::SelfPermissionNotAllowed`] if `guardian == patient`
+        /// - [`Error::ExpiryInPast`] if `until` is not in the future
+        /// - [`Error::GuardianAppointedByOther`] if `guardian` is in force by
+        ///   the patient's own appointment
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::appoint_guardian())]
+        pub fn appoint_guardian_for(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            guardian: T::AccountId,
+            until: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::GuardianRegistrarOrigin::ensure_origin(origin)?;
+
+            Self::do_appoint(patient, guardian, GuardianAppointer::Registrar, until)
+        }
+
+        /// Removes a guardian the caller appointed.
+        ///
+        /// Guardians appointed by the registrar can only be removed by the
+        /// registrar.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `guardian`: The guardian to remove.
+        ///
+        /// # Storage
+        /// - Removes from [`Guardians`], [`GuardianExpiryQueue`]
+        ///
+        /// # Emits
+        /// - [`Event::GuardianRemoved`]
+        ///
+        /// # Errors
+        /// - [`Error::GuardianNotFound`] if the patient appointed no such guardian
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::remove_guardian())]
+        pub fn remove_guardian(origin: OriginFor<T>, guardian: T::AccountId) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            Self::do_remove_guardian(patient, guardian, GuardianAppointer::Patient)
+        }
+
+        /// Removes a guardian appointed by the registrar.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::GuardianRegistrarOrigin`].
+        /// - `patient`: The patient under guardianship.
+        /// - `guardian`: The guardian to remove.
+        ///
+        /// # Storage
+        /// - Removes from [`Guardians`], [`GuardianExpiryQueue`]
+        ///
+        /// # Emits
+        /// - [`Event::GuardianRemoved`]
+        ///
+        /// # Errors
+        /// - [`Error::GuardianNotFound`] if the registrar appointed no such guardian
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::remove_guardian())]
+        pub fn remove_guardian_for(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            guardian: T::AccountId,
+        ) -> DispatchResult {
+            T::GuardianRegistrarOrigin::ensure_origin(origin)?;
+
+            Self::do_remove_guardian(patient, guardian, GuardianAppointer::Registrar)
+        }
+
+        /// Grants a scope to a doctor on behalf of a patient.
+        ///
+        /// Same as [`Pallet::grant_access_scoped`] (or
+        /// [`Pallet::grant_temporary_access`] when `expires_at` is set), with
+        /// the deposit still held from the patient.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed active guardian of `patient`.
+        /// - `patient`: The patient under guardianship.
+        /// - `doctor`: The doctor account that will receive access.
+        /// - `scope`: The permission bits to grant.
+        /// - `expires_at`: Block number at which the grant expires, if any.
+        ///
+        /// # Storage
+        /// - Same as [`Pallet::grant_temporary_access`]
+        ///
+        /// # Emits
+        /// - [`Event::AccessGranted`]
+        /// - [`Event::GuardianActed`]
+        ///
+        /// # Errors
+        /// - [`Error::NotGuardian`] if the caller is not an active guardian
+        /// - Same as [`Pallet::grant_temporary_access`]
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::guardian_grant_access())]
+        pub fn guardian_grant_access(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let guardian = ensure_signed(origin)?;
+
+            ensure!(Self::is_guardian(&patient, &guardian), Error::<T>::NotGuardian);
+            if let Some(at) = expires_at {
+                ensure!(
+                    at > frame_system::Pallet::<T>::block_number(),
+                    Error::<T>::ExpiryInPast
+                );
+            }
+
+            Self::do_grant(patient.clone(), doctor.clone(), scope, expires_at)?;
+
+            Self::deposit_event(Event::GuardianActed {
+                patient,
+                guardian,
+                doctor,
+                action: PermissionAction::Granted,
+                scope,
+            });
+
+            Ok(())
+        }
+
+        /// Revokes a scope from a doctor on behalf of a patient.
+        ///
+        /// Same as [`Pallet::revoke_access_scoped`].
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed active guardian of `patient`.
+        /// - `patient`: The patient under guardianship.
+        /// - `doctor`: The doctor account that will lose access.
+        /// - `scope`: The permission bits to revoke.
+        ///
+        /// # Storage
+        /// - Same as [`Pallet::revoke_access_scoped`]
+        ///
+        /// # Emits
+        /// - [`Event::AccessRevoked`]
+        /// - [`Event::GuardianActed`]
+        ///
+        /// # Errors
+        /// - [`Error::NotGuardian`] if the caller is not an active guardian
+        /// - [`Error::InvalidScope`] if `scope` is empty or has unknown bits
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::guardian_revoke_access())]
+        pub fn guardian_revoke_access(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+        ) -> DispatchResult {
+            let guardian = ensure_signed(origin)?;
+
+            ensure!(Self::is_guardian(&patient, &guardian), Error::<T>::NotGuardian);
+
+            Self::do_revoke(patient.clone(), doctor.clone(), scope)?;
+
+            Self::deposit_event(Event::GuardianActed {
+                patient,
+                guardian,
+                doctor,
+                action: PermissionAction::Revoked,
+                scope,
+            });
+
+            Ok(())
+        }
+
+        /// Registers an institution.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::InstitutionRegistrarOrigin`].
+        /// - `admin`: Account that will manage the institution's members.
+        /// - `name_hash`: Hash of the institution's legal name and registration.
+        ///
+        /// # Storage
+        /// - Writes to [`Institutions`], [`NextInstitutionId`]
+        ///
+        /// # Emits
+        /// - [`Event::InstitutionRegistered`]
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::register_institution())]
+        pub fn register_institution(
+            origin: OriginFor<T>,
+            admin: T::AccountId,
+            name_hash: T::Hash,
+        ) -> DispatchResult {
+            T::InstitutionRegistrarOrigin::ensure_origin(origin)?;
+
+            let institution = NextInstitutionId::<T>::get();
+            Institutions::<T>::insert(
+                institution,
+                Institution {
+                    admin: admin.clone(),
+                    name_hash,
+                    registered_at: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+            NextInstitutionId::<T>::put(institution.saturating_add(1));
+
+            Self::deposit_event(Event::InstitutionRegistered { institution, admin });
+
+            Ok(())
+        }
+
+        /// Replaces an institution's admin.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::InstitutionRegistrarOrigin`].
+        /// - `institution`: The institution.
+        /// - `admin`: The new admin account.
+        ///
+        /// # Storage
+        /// - Writes to [`Institutions`]
+        ///
+        /// # Emits
+        /// - [`Event::InstitutionAdminChanged`]
+        ///
+        /// # Errors
+        /// - [`Error::InstitutionNotFound`] if the institution does not exist
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::set_institution_admin())]
+        pub fn set_institution_admin(
+            origin: OriginFor<T>,
+            institution: InstitutionId,
+            admin: T::AccountId,
+        ) -> DispatchResult {
+            T::InstitutionRegistrarOrigin::ensure_origin(origin)?;
+
+            Institutions::<T>::try_mutate(institution, |maybe_institution| {
+                let details = maybe_institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+                details.admin = admin.clone();
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::InstitutionAdminChanged { institution, admin });
+
+            Ok(())
+        }
+
+        /// Adds a doctor to an institution's care team.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be the institution's admin.
+        /// - `institution`: The institution.
+        /// - `doctor`: The doctor to add.
+        ///
+        /// # Storage
+        /// - Writes to [`InstitutionMembers`], [`InstitutionMemberCount`], [`Memberships`]
+        ///
+        /// # Emits
+        /// - [`Event::MemberAdded`]
+        ///
+        /// # Errors
+        /// - [`Error::InstitutionNotFound`] / [`Error::NotInstitutionAdmin`]
+        /// - [`Error::DoctorNotRegistered`] if `doctor` is not an active doctor
+        /// - [`Error::AlreadyMember`] if `doctor` is already a member
+        /// - [`Error::TooManyMembers`] / [`Error::TooManyMemberships`] when full
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::add_member())]
+        pub fn add_member(
+            origin: OriginFor<T>,
+            institution: InstitutionId,
+            doctor: T::AccountId,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+
+            Self::ensure_institution_admin(institution, &admin)?;
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&doctor),
+                Error::<T>::DoctorNotRegistered
+            );
+            ensure!(
+                !InstitutionMembers::<T>::contains_key(institution, &doctor),
+                Error::<T>::AlreadyMember
+            );
+
+            InstitutionMemberCount::<T>::try_mutate(institution, |count| {
+                ensure!(*count < T::MaxInstitutionMembers::get(), Error::<T>::TooManyMembers);
+                *count = count.saturating_add(1);
+                Ok::<_, DispatchError>(())
+            })?;
+            Memberships::<T>::try_mutate(&doctor, |memberships| memberships.try_push(institution))
+                .map_err(|_| Error::<T>::TooManyMemberships)?;
+            InstitutionMembers::<T>::insert(
+                institution,
+                &doctor,
+                frame_system::Pallet::<T>::block_number(),
+            );
+
+            Self::deposit_event(Event::MemberAdded { institution, doctor });
+
+            Ok(())
+        }
+
+        /// Removes a doctor from an institution's care team.
+        ///
+        /// The doctor immediately loses every access held through the
+        /// institution. Patients over whom the doctor loses read access are
+        /// reported to [`Config::OnAccessRevoked`].
+        ///
+        /// # Parameters
+        /// - `origin`: Must be the institution's admin.
+        /// - `institution`: The institution.
+        /// - `doctor`: The doctor to remove.
+        ///
+        /// # Storage
+        /// - Reads [`InstitutionPatients`]
+        /// - Writes to [`InstitutionMembers`], [`InstitutionMemberCount`], [`Memberships`]
+        ///
+        /// # Emits
+        /// - [`Event::MemberRemoved`]
+        ///
+        /// # Errors
+        /// - [`Error::InstitutionNotFound`] / [`Error::NotInstitutionAdmin`]
+        /// - [`Error::NotMember`] if `doctor` is not a member
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::remove_member(T::MaxInstitutionPatients::get()))]
+        pub fn remove_member(
+            origin: OriginFor<T>,
+            institution: InstitutionId,
+            doctor: T::AccountId,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+
+            Self::ensure_institution_admin(institution, &admin)?;
+            ensure!(
+                InstitutionMembers::<T>::take(institution, &doctor).is_some(),
+                Error::<T>::NotMember
+            );
+
+            InstitutionMemberCount::<T>::mutate(institution, |count| {
+                *count = count.saturating_sub(1)
+            });
+            Memberships::<T>::mutate_exists(&doctor, |maybe_memberships| {
+                if let Some(memberships) = maybe_memberships {
+                    memberships.retain(|id| *id != institution);
+                    if memberships.is_empty() {
+                        *maybe_memberships = None;
+                    }
+                }
+            });
+
+            for patient in InstitutionPatients::<T>::iter_key_prefix(institution) {
+                if InstitutionPermissions::<T>::get(&patient, institution).can_read() &&
+                    !Self::scope_of(&patient, &doctor).can_read()
+                {
+                    T::OnAccessRevoked::on_access_revoked(&patient, &doctor);
+                }
+            }
+
+            Self::deposit_event(Event::MemberRemoved { institution, doctor });
+
+            Ok(())
+        }
+
+        /// Grants a scope to every member of an institution.
+        ///
+        /// The scope is added to whatever the institution already holds.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `institution`: The institution that will receive access.
+        /// - `scope`: The permission bits to grant.
+        ///
+        /// # Storage
+        /// - Holds [`Config::PermissionDeposit`] from the patient for a new grant
+        /// - Writes to [`InstitutionPermissions`], [`InstitutionDeposits`],
+        ///   [`InstitutionPatients`], [`InstitutionPatientCount`], [`PermissionLog`]
+        ///
+        /// # Emits
+        /// - [`Event::InstitutionAccessGranted`]
+        ///
+        /// # Errors
+        /// - [`Error::InvalidScope`] if `scope` is empty or has unknown bits
+        /// - [`Error::InstitutionNotFound`] if the institution does not exist
+        /// - [`Error::TooManyInstitutionPatients`] if the institution is full
+        /// - Any error from holding the deposit
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::grant_institution_access())]
+        pub fn grant_institution_access(
+            origin: OriginFor<T>,
+            institution: InstitutionId,
+            scope: PermissionScope,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            ensure!(!scope.is_empty() && scope.is_valid(), Error::<T>::InvalidScope);
+            let details =
+                Institutions::<T>::get(institution).ok_or(Error::<T>::InstitutionNotFound)?;
+
+            if !InstitutionPatients::<T>::contains_key(institution, &patient) {
+                InstitutionPatientCount::<T>::try_mutate(institution, |count| {
+                    ensure!(
+                        *count < T::MaxInstitutionPatients::get(),
+                        Error::<T>::TooManyInstitutionPatients
+                    );
+                    *count = count.saturating_add(1);
+                    Ok::<_, DispatchError>(())
+                })?;
+                InstitutionPatients::<T>::insert(institution, &patient, ());
+                InstitutionGrantCount::<T>::mutate(&patient, |count| {
+                    *count = count.saturating_add(1)
+                });
+            }
+            if !InstitutionDeposits::<T>::contains_key(&patient, institution) {
+                let deposit = T::PermissionDeposit::get();
+                T::Currency::hold(&HoldReason::PermissionDeposit.into(), &patient, deposit)?;
+                InstitutionDeposits::<T>::insert(&patient, institution, deposit);
+            }
+
+            Self::log_change(
+                &patient,
+                LogSubject::Institution(institution),
+                PermissionAction::InstitutionGranted(institution),
+                scope,
+            );
+
+            let scope = InstitutionPermissions::<T>::get(&patient, institution).union(scope);
+            InstitutionPermissions::<T>::insert(&patient, institution, scope);
+
+            Self::deposit_event(Event::InstitutionAccessGranted { patient, institution, scope });
+
+            Ok(())
+        }
+
+        /// Revokes a scope from an institution.
+        ///
+        /// Members that lose read access to the patient are reported to
+        /// [`Config::OnAccessRevoked`].
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        /// - `institution`: The institution that will lose access.
+        /// - `scope`: The permission bits to revoke.
+        ///
+        /// # Storage
+        /// - Releases the grant's deposit once no scope remains
+        /// - Writes to [`InstitutionPermissions`], [`InstitutionDeposits`],
+        ///   [`InstitutionPatients`], [`InstitutionPatientCount`], [`PermissionLog`]
+        ///
+        /// # Emits
+        /// - [`Event::InstitutionAccessRevoked`]
+        ///
+        /// # Errors
+        /// - [`Error::InvalidScope`] if `scope` is empty or has unknown bits
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::revoke_institution_access(T::MaxInstitutionMembers::get()))]
+        pub fn revoke_institution_access(
+            origin: OriginFor<T>,
+            institution: InstitutionId,
+            scope: PermissionScope,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            ensure!(!scope.is_empty() && scope.is_valid(), Error::<T>::InvalidScope);
+
+            let previous = InstitutionPermissions::<T>::get(&patient, institution);
+            let remaining = previous.difference(scope);
+            if remaining.is_empty() {
+                Self::remove_institution_grant(&patient, institution);
+            } else {
+                InstitutionPermissions::<T>::insert(&patient, institution, remaining);
+            }
+            Self::institution_revoked(&patient, institution, previous.difference(remaining));
+
+            Self::deposit_event(Event::InstitutionAccessRevoked {
+                patient,
+                institution,
+                scope: remaining,
+            });
+
+            Ok(())
+        }
+
+        /// Revokes every grant to doctors and institutions, in batches.
+        ///
+        /// Removes up to [`Config::MaxRevocationsPerCall`] grants, to doctors
+        /// first and then to institutions, each logged and with its deposit
+        /// released, and reports how many are left. Call again until none
+        /// remain.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        ///
+        /// # Storage
+        /// - Writes to [`Permissions`], [`DoctorPatients`], [`Expirations`],
+        ///   [`GrantDeposits`], [`GrantCount`], [`InstitutionPermissions`],
+        ///   [`InstitutionDeposits`], [`InstitutionPatients`],
+        ///   [`InstitutionPatientCount`], [`InstitutionGrantCount`], [`PermissionLog`]
+        ///
+        /// # Emits
+        /// - [`Event::BulkAccessRevoked`]
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::revoke_all_access(
+            T::MaxRevocationsPerCall::get(),
+            T::MaxInstitutionMembers::get(),
+        ))]
+        pub fn revoke_all_access(origin: OriginFor<T>) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+            let max = T::MaxRevocationsPerCall::get() as usize;
+
+            let doctors: Vec<_> = Permissions::<T>::iter_key_prefix(&patient).take(max).collect();
+            let institutions: Vec<_> = InstitutionPermissions::<T>::iter_key_prefix(&patient)
+                .take(max.saturating_sub(doctors.len()))
+                .collect();
+            let revoked = doctors.len().saturating_add(institutions.len()) as u32;
+
+            for doctor in doctors {
+                let scope = Self::remove_grant(&patient, &doctor);
+                Self::log_change(
+                    &patient,
+                    LogSubject::Doctor(doctor.clone()),
+                    PermissionAction::Revoked,
+                    scope,
+                );
+                if scope.can_read() && !Self::scope_of(&patient, &doctor).can_read() {
+                    T::OnAccessRevoked::on_access_revoked(&patient, &doctor);
+                }
+            }
+            for institution in institutions {
+                let scope = InstitutionPermissions::<T>::get(&patient, institution);
+                Self::remove_institution_grant(&patient, institution);
+                Self::institution_revoked(&patient, institution, scope);
+            }
+
+            Self::deposit_event(Event::BulkAccessRevoked {
+                remaining: Self::grant_count(&patient),
+                patient,
+                revoked,
+            });
+
+            Ok(())
+        }
+
+        /// Freezes all the caller's grants without deleting them.
+        ///
+        /// While frozen, [`MedicalPermissionsVerifier`] honours no grant of
+        /// the patient, to doctors or institutions. Break-glass accesses
+        /// and guardianships are not affected.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the patient.
+        ///
+        /// # Storage
+        /// - Writes to [`FrozenPatients`]
+        ///
+        /// # Emits
+        /// - [`Event::AccessFrozen`]
+        ///
+        /// # Errors
+        /// - [`Error::AlreadyFrozen`] if the grants are already frozen
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::freeze_access())]
+        pub fn freeze_access(origin: OriginFor<T>) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Grants read access to a doctor.
+            ensure!(!FrozenPatients::<T>::contains_key(&patient), Error::<T>::AlreadyFrozen);
+            FrozenPatients::<T>::insert(&patient, frame_system::Pallet::<T>::block_number());
+
+            Self::deposit_event(Event::AccessFrozen {
+                grants: Self::grant_count(&patient),
+                patient,
+            });
+
+            Ok(())
+        }
+
+        /// Restores the caller's frozen grants.
+        ///
+        /// Time-limited grants that expired meanwhile are not restored.
         ///
         /// # Parameters
         /// - `origin`: Must be a signed account representing the patient.
-        /// - `doctor`: The doctor account that will receive access.
         ///
         /// # Storage
-        /// - Writes to [`Permissions`]
+        /// - Removes from [`FrozenPatients`]
         ///
         /// # Emits
-        /// - [`Event::AccessGranted`]
+        /// - [`Event::AccessUnfrozen`]
         ///
         /// # Errors
-        /// - [`Error::SelfPermissionNotAllowed`] if `patient == doctor`
-        #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
-        pub fn grant_access(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+        /// - [`Error::NotFrozen`] if the grants are not frozen
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::freeze_access())]
+        pub fn unfreeze_access(origin: OriginFor<T>) -> DispatchResult {
             let patient = ensure_signed(origin)?;
 
+            ensure!(FrozenPatients::<T>::take(&patient).is_some(), Error::<T>::NotFrozen);
+
+            Self::deposit_event(Event::AccessUnfrozen {
+                grants: Self::grant_count(&patient),
+                patient,
+            });
+
+            Ok(())
+        }
+
+        /// Refers a patient to a specialist.
+        ///
+        /// The specialist gets no access until the patient approves the
+        /// referral with [`Pallet::approve_referral`].
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the referring
+        ///   doctor.
+        /// - `patient`: The patient being referred.
+        /// - `specialist`: The doctor the patient is referred to.
+        /// - `reason_hash`: Hash of the off-chain referral reason.
+        /// - `scope`: The permission bits the specialist will hold.
+        /// - `duration`: Number of blocks the access lasts once approved.
+        /// - `records`: Records the specialist may read, or `None` for the
+        ///   whole history.
+        ///
+        /// # Storage
+        /// - Writes to [`Referrals`], [`NextReferralId`], [`OpenReferrals`],
+        ///   [`PatientReferrals`], [`SpecialistReferrals`]
+        ///
+        /// # Emits
+        /// - [`Event::ReferralProposed`]
+        ///
+        /// # Errors
+        /// - [`Error::SelfPermissionNotAllowed`] if `patient` is the caller
+        /// - [`Error::InvalidScope`] if `scope` is empty or has unknown bits
+        /// - [`Error::InvalidReferral`] if the specialist, duration or records
+        ///   are invalid
+        /// - [`Error::DoctorNotRegistered`] if the caller or the specialist is
+        ///   not an active doctor
+        /// - [`Error::ReferrerWithoutAccess`] if the caller cannot read the
+        ///   patient's data
+        /// - [`Error::ReferralAlreadyOpen`] if the specialist already has an
+        ///   open referral for the patient
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::propose_referral())]
+        pub fn propose_referral(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            specialist: T::AccountId,
+            reason_hash: T::Hash,
+            scope: PermissionScope,
+            duration: BlockNumberFor<T>,
+            records: Option<ReferralRecords>,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
             ensure!(patient != doctor, Error::<T>::SelfPermissionNotAllowed);
+            ensure!(!scope.is_empty() && scope.is_valid(), Error::<T>::InvalidScope);
+            ensure!(
+                specialist != patient &&
+                    specialist != doctor &&
+                    !duration.is_zero() &&
+                    records.as_ref().is_none_or(|records| !records.is_empty()),
+                Error::<T>::InvalidReferral
+            );
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&doctor) &&
+                    T::DoctorRegistry::is_active_doctor(&specialist),
+                Error::<T>::DoctorNotRegistered
+            );
+            ensure!(
+                Self::scope_of(&patient, &doctor).can_read(),
+                Error::<T>::ReferrerWithoutAccess
+            );
+            ensure!(
+                !OpenReferrals::<T>::contains_key(&patient, &specialist),
+                Error::<T>::ReferralAlreadyOpen
+            );
 
-            Permissions::<T>::insert(&patient, &doctor, true);
+            let referral = NextReferralId::<T>::get();
+            Referrals::<T>::insert(
+                referral,
+                Referral {
+                    patient: patient.clone(),
+                    referring_doctor: doctor.clone(),
+                    specialist: specialist.clone(),
+                    reason_hash,
+                    scope,
+                    duration,
+                    records,
+                    status: ReferralStatus::Proposed,
+                    proposed_at: frame_system::Pallet::<T>::block_number(),
+                    expires_at: None,
+                },
+            );
+            NextReferralId::<T>::put(referral.saturating_add(1));
+            OpenReferrals::<T>::insert(&patient, &specialist, referral);
+            PatientReferrals::<T>::insert(&patient, ReferralKey::from(referral), ());
+            SpecialistReferrals::<T>::insert(&specialist, ReferralKey::from(referral), ());
 
-            Self::deposit_event(Event::AccessGranted { patient, doctor });
+            Self::deposit_event(Event::ReferralProposed {
+                referral,
+                patient,
+                referring_doctor: doctor,
+                specialist,
+            });
 
             Ok(())
         }
 
-        /// Revokes read access from a doctor.
+        /// Approves a referral, giving the specialist access for the
+        /// referral's duration.
+        ///
+        /// The access is kept apart from the patient's own grants: it never
+        /// changes [`Permissions`] and ends when the referral is closed or
+        /// expires.
         ///
         /// # Parameters
         /// - `origin`: Must be a signed account representing the patient.
-        /// - `doctor`: The doctor account that will lose access.
+        /// - `referral`: The referral to approve.
         ///
         /// # Storage
-        /// - Writes to [`Permissions`]
+        /// - Writes to [`Referrals`], [`ReferralExpiryQueue`], [`PermissionLog`]
         ///
         /// # Emits
-        /// - [`Event::AccessRevoked`]
-        #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
-        pub fn revoke_access(origin: OriginFor<T>, doctor: T::AccountId) -> DispatchResult {
+        /// - [`Event::ReferralApproved`]
+        ///
+        /// # Errors
+        /// - [`Error::ReferralNotFound`] if the referral does not exist
+        /// - [`Error::NotReferralParty`] if the caller is not the patient
+        /// - [`Error::ReferralNotProposed`] if the referral is not pending
+        /// - [`Error::DoctorNotRegistered`] if the specialist was suspended
+        ///   meanwhile
+        #[pallet::call_index(28)]
+        #[pallet::weight(T::WeightInfo::approve_referral())]
+        pub fn approve_referral(origin: OriginFor<T>, referral: ReferralId) -> DispatchResult {
             let patient = ensure_signed(origin)?;
 
-            Permissions::<T>::remove(&patient, &doctor);
+            let mut entry = Referrals::<T>::get(referral).ok_or(Error::<T>::ReferralNotFound)?;
+            ensure!(entry.patient == patient, Error::<T>::NotReferralParty);
+            ensure!(entry.status == ReferralStatus::Proposed, Error::<T>::ReferralNotProposed);
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&entry.specialist),
+                Error::<T>::DoctorNotRegistered
+            );
+
+            let expires_at =
+                frame_system::Pallet::<T>::block_number().saturating_add(entry.duration);
+            ReferralExpiryQueue::<T>::insert(expires_at, referral, ());
+
+            Self::log_change(
+                &patient,
+                LogSubject::Doctor(entry.specialist.clone()),
+                PermissionAction::Granted,
+                entry.scope,
+            );
+
+            entry.status = ReferralStatus::Active;
+            entry.expires_at = Some(expires_at);
+            let specialist = entry.specialist.clone();
+            Referrals::<T>::insert(referral, entry);
+
+            Self::deposit_event(Event::ReferralApproved {
+                referral,
+                patient,
+                specialist,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Closes a referral, removing the specialist's access.
+        ///
+        /// A proposed referral can be closed too, which is how the patient
+        /// declines it.
+        ///
+        /// # Parameters
+        /// - `origin`: The patient, the referring doctor or the specialist.
+        /// - `referral`: The referral to close.
+        ///
+        /// # Storage
+        /// - Writes to [`Referrals`], [`OpenReferrals`], [`PermissionLog`]
+        /// - Removes from [`ReferralExpiryQueue`]
+        ///
+        /// # Emits
+        /// - [`Event::ReferralClosed`]
+        ///
+        /// # Errors
+        /// - [`Error::ReferralNotFound`] if the referral does not exist
+        /// - [`Error::NotReferralParty`] if the caller is not a party
+        /// - [`Error::ReferralNotOpen`] if the referral was already closed or
+        ///   expired
+        #[pallet::call_index(29)]
+        #[pallet::weight(T::WeightInfo::close_referral())]
+        pub fn close_referral(origin: OriginFor<T>, referral: ReferralId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let entry = Referrals::<T>::get(referral).ok_or(Error::<T>::ReferralNotFound)?;
+            ensure!(
+                who == entry.patient || who == entry.referring_doctor || who == entry.specialist,
+                Error::<T>::NotReferralParty
+            );
+            ensure!(entry.is_open(), Error::<T>::ReferralNotOpen);
+
+            Self::end_referral(referral, entry, ReferralStatus::Closed);
+
+            Self::deposit_event(Event::ReferralClosed { referral, closed_by: who });
+
+            Ok(())
+        }
+
+        /// Removes a break-glass access that reached its expiry block.
+        ///
+        /// Expired accesses are no longer honoured but stay in
+        /// [`EmergencyGrants`] until removed. Anyone may remove them, e.g. the
+        /// patient's wallet to trigger [`Config::OnAccessRevoked`].
+        ///
+        /// # Parameters
+        /// - `origin`: Any signed account.
+        /// - `patient`: The patient whose data was accessed.
+        /// - `accessor`: The account that broke the glass.
+        ///
+        /// # Storage
+        /// - Removes from [`EmergencyGrants`]
+        ///
+        /// # Emits
+        /// - [`Event::EmergencyAccessExpired`]
+        ///
+        /// # Errors
+        /// - [`Error::EmergencyAccessNotFound`] if `accessor` holds no
+        ///   break-glass access to `patient`
+        /// - [`Error::EmergencyAccessActive`] if the access has not expired yet
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::clear_emergency_access())]
+        pub fn clear_emergency_access(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            accessor: T::AccountId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let expires_at = EmergencyGrants::<T>::get(&patient, &accessor)
+                .ok_or(Error::<T>::EmergencyAccessNotFound)?;
+            ensure!(
+                expires_at <= frame_system::Pallet::<T>::block_number(),
+                Error::<T>::EmergencyAccessActive
+            );
+
+            EmergencyGrants::<T>::remove(&patient, &accessor);
+            if !Self::scope_of(&patient, &accessor).can_read() {
+                T::OnAccessRevoked::on_access_revoked(&patient, &accessor);
+            }
+
+            Self::deposit_event(Event::EmergencyAccessExpired { patient, accessor });
+
+            Ok(())
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // Internal helpers
+    // ---------------------------------------------------------------------
+
+    impl<T: Config> Pallet<T> {
+        /// Takes the queue entries due at or before block `n`, starting at
+        /// block `cursor`.
+        ///
+        /// `take(block, max)` removes and returns up to `max` entries queued
+        /// at `block`. At most [`Config::MaxExpiriesPerBlock`] units of work
+        /// are done, counting one per entry and one per block visited.
+        /// Returns the block to resume from, the entries with their block and
+        /// the units of work done.
+        fn take_due<K>(
+            cursor: BlockNumberFor<T>,
+            n: BlockNumberFor<T>,
+            take: impl Fn(BlockNumberFor<T>, usize) -> Vec<K>,
+        ) -> (BlockNumberFor<T>, Vec<(BlockNumberFor<T>, K)>, u32) {
+            let limit = T::MaxExpiriesPerBlock::get() as usize;
+            let mut budget = limit;
+            let mut block = cursor;
+            let mut due = Vec::new();
+
+            while block <= n && budget > 0 {
+                let batch = take(block, budget);
+                budget -= batch.len();
+                due.extend(batch.into_iter().map(|entry| (block, entry)));
+                // Out of budget before the block was drained: resume here.
+                if budget == 0 {
+                    break;
+                }
+                budget -= 1;
+                block += One::one();
+            }
+
+            (block, due, (limit - budget) as u32)
+        }
+
+        /// Removes the grants due at or before block `n`, in bounded batches.
+        fn expire_grants(n: BlockNumberFor<T>) -> Weight {
+            let (cursor, due, work) =
+                Self::take_due(ExpiryCursor::<T>::get(), n, |block, max| {
+                    ExpiryQueue::<T>::drain_prefix(block).take(max).map(|(key, ())| key).collect()
+                });
+            ExpiryCursor::<T>::put(cursor);
+
+            for (at, (patient, doctor)) in due {
+                // The queue mirrors `Expirations`; checked defensively.
+                if Expirations::<T>::get(&patient, &doctor) != Some(at) {
+                    continue;
+                }
+
+                let scope = Self::remove_grant(&patient, &doctor);
+
+                Self::log_change(
+                    &patient,
+                    LogSubject::Doctor(doctor.clone()),
+                    PermissionAction::Expired,
+                    scope,
+                );
+                if scope.can_read() && !Self::scope_of(&patient, &doctor).can_read() {
+                    T::OnAccessRevoked::on_access_revoked(&patient, &doctor);
+                }
+                Self::deposit_event(Event::AccessExpired { patient, doctor });
+            }
+
+            T::WeightInfo::expire_grants(work)
+        }
+
+        /// Removes the access requests due at or before block `n`, in
+        /// bounded batches.
+        fn expire_requests(n: BlockNumberFor<T>) -> Weight {
+            let (cursor, due, work) =
+                Self::take_due(RequestExpiryCursor::<T>::get(), n, |block, max| {
+                    RequestExpiryQueue::<T>::drain_prefix(block)
+                        .take(max)
+                        .map(|(key, ())| key)
+                        .collect()
+                });
+            RequestExpiryCursor::<T>::put(cursor);
+
+            for (at, (patient, doctor)) in due {
+                // The queue mirrors `AccessRequests`; checked defensively.
+                let expired = AccessRequests::<T>::get(&patient, &doctor)
+                    .is_some_and(|request| request.expires_at == at);
+                if !expired {
+                    continue;
+                }
+
+                AccessRequests::<T>::remove(&patient, &doctor);
+
+                Self::deposit_event(Event::AccessRequestExpired { patient, doctor });
+            }
+
+            T::WeightInfo::expire_requests(work)
+        }
+
+        /// Ends the guardianships due at or before block `n`, in bounded
+        /// batches. The [`Guardians`] entries are kept for auditing.
+        fn expire_guardianships(n: BlockNumberFor<T>) -> Weight {
+            let (cursor, due, work) =
+                Self::take_due(GuardianExpiryCursor::<T>::get(), n, |block, max| {
+                    GuardianExpiryQueue::<T>::drain_prefix(block)
+                        .take(max)
+                        .map(|(key, ())| key)
+                        .collect()
+                });
+            GuardianExpiryCursor::<T>::put(cursor);
+
+            for (at, (patient, guardian)) in due {
+                // The queue mirrors `Guardians`; checked defensively.
+                let ended = Guardians::<T>::get(&patient, &guardian)
+                    .is_some_and(|guardianship| !guardianship.is_active_at(&at));
+                if !ended {
+                    continue;
+                }
+
+                if !Self::scope_of(&patient, &guardian).can_read() {
+                    T::OnAccessRevoked::on_access_revoked(&patient, &guardian);
+                }
+
+                Self::deposit_event(Event::GuardianshipEnded { patient, guardian });
+            }
+
+            T::WeightInfo::expire_guardianships(work)
+        }
+
+        /// Removes the request of `doctor` to `patient` together with its
+        /// queue entry, returning it.
+        fn remove_request(
+            patient: &T::AccountId,
+            doctor: &T::AccountId,
+        ) -> Option<AccessRequest<T::Hash, BlockNumberFor<T>>> {
+            let request = AccessRequests::<T>::take(patient, doctor)?;
+            RequestExpiryQueue::<T>::remove(request.expires_at, (patient, doctor));
+            Some(request)
+        }
+
+        /// Ends the referrals due at or before block `n`, in bounded
+        /// batches.
+        fn expire_referrals(n: BlockNumberFor<T>) -> Weight {
+            let (cursor, due, work) =
+                Self::take_due(ReferralExpiryCursor::<T>::get(), n, |block, max| {
+                    ReferralExpiryQueue::<T>::drain_prefix(block)
+                        .take(max)
+                        .map(|(referral, ())| referral)
+                        .collect()
+                });
+            ReferralExpiryCursor::<T>::put(cursor);
+
+            for (at, referral) in due {
+                // The queue mirrors the active `Referrals`; checked
+                // defensively.
+                let Some(entry) = Referrals::<T>::get(referral) else { continue };
+                if entry.status != ReferralStatus::Active || entry.expires_at != Some(at) {
+                    continue;
+                }
+
+                let (patient, specialist) = (entry.patient.clone(), entry.specialist.clone());
+                Self::end_referral(referral, entry, ReferralStatus::Expired);
+
+                Self::deposit_event(Event::ReferralExpired { referral, patient, specialist });
+            }
+
+            T::WeightInfo::expire_referrals(work)
+        }
+
+        /// Moves `entry` to `status` and removes the access it granted,
+        /// logging the change and notifying [`Config::OnAccessRevoked`] when
+        /// the specialist loses read access.
+        fn end_referral(referral: ReferralId, mut entry: ReferralOf<T>, status: ReferralStatus) {
+            let was_active = entry.status == ReferralStatus::Active;
+            OpenReferrals::<T>::remove(&entry.patient, &entry.specialist);
+            entry.status = status;
+            Referrals::<T>::insert(referral, &entry);
+
+            if !was_active {
+                return;
+            }
+            if let Some(expires_at) = entry.expires_at {
+                ReferralExpiryQueue::<T>::remove(expires_at, referral);
+            }
+
+            let action = match status {
+                ReferralStatus::Expired => PermissionAction::Expired,
+                _ => PermissionAction::Revoked,
+            };
+            let (patient, specialist) = (&entry.patient, &entry.specialist);
+            Self::log_change(patient, LogSubject::Doctor(specialist.clone()), action, entry.scope);
+            if entry.scope.can_read() && !Self::scope_of(patient, specialist).can_read() {
+                T::OnAccessRevoked::on_access_revoked(patient, specialist);
+            }
+        }
+
+        /// Adds `scope` to the permissions held by `doctor` over `patient`.
+        ///
+        /// `expires_at` replaces any previous expiry; `None` makes the grant
+        /// permanent.
+        fn do_grant(
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            ensure!(patient != doctor, Error::<T>::SelfPermissionNotAllowed);
+            ensure!(!scope.is_empty() && scope.is_valid(), Error::<T>::InvalidScope);
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&doctor),
+                Error::<T>::DoctorNotRegistered
+            );
+
+            // An expired grant that was not cleaned up yet must not be extended.
+            let current = Self::active_scope(&patient, &doctor);
+            let expiry = Expirations::<T>::get(&patient, &doctor);
+            ensure!(
+                expires_at.is_none() || current.is_empty() || expiry.is_some(),
+                Error::<T>::PermanentGrantExists
+            );
+
+            if !GrantDeposits::<T>::contains_key(&patient, &doctor) {
+                let deposit = T::PermissionDeposit::get();
+                T::Currency::hold(&HoldReason::PermissionDeposit.into(), &patient, deposit)?;
+                GrantDeposits::<T>::insert(&patient, &doctor, deposit);
+            }
+
+            if let Some(at) = expiry {
+                ExpiryQueue::<T>::remove(at, (&patient, &doctor));
+            }
+            match expires_at {
+                Some(at) => {
+                    ExpiryQueue::<T>::insert(at, (&patient, &doctor), ());
+                    Expirations::<T>::insert(&patient, &doctor, at);
+                },
+                None => Expirations::<T>::remove(&patient, &doctor),
+            }
+
+            Self::log_change(
+                &patient,
+                LogSubject::Doctor(doctor.clone()),
+                PermissionAction::Granted,
+                scope,
+            );
+
+            if !Permissions::<T>::contains_key(&patient, &doctor) {
+                GrantCount::<T>::mutate(&patient, |count| *count = count.saturating_add(1));
+            }
+
+            let scope = current.union(scope);
+            Permissions::<T>::insert(&patient, &doctor, scope);
+            // Extending a grant keeps its original time.
+            if current.is_empty() {
+                DoctorPatients::<T>::insert(
+                    &doctor,
+                    &patient,
+                    GrantTimestamp {
+                        block: frame_system::Pallet::<T>::block_number(),
+                        timestamp: T::Time::now(),
+                    },
+                );
+            }
+
+            Self::deposit_event(Event::AccessGranted {
+                patient,
+                doctor,
+                scope,
+                expires_at,
+            });
 
-            Self::deposit_event(Event::AccessRevoked { patient, doctor });
+            Ok(())
+        }
+
+        /// Removes `scope` from the permissions held by `doctor` over `patient`.
+        fn do_revoke(
+            patient: T::AccountId,
+            doctor: T::AccountId,
+            scope: PermissionScope,
+        ) -> DispatchResult {
+            ensure!(!scope.is_empty() && scope.is_valid(), Error::<T>::InvalidScope);
+
+            let previous = Self::active_scope(&patient, &doctor);
+            let remaining = previous.difference(scope);
+
+            let revoked = previous.difference(remaining);
+            if !revoked.is_empty() {
+                Self::log_change(
+                    &patient,
+                    LogSubject::Doctor(doctor.clone()),
+                    PermissionAction::Revoked,
+                    revoked,
+                );
+            }
+
+            if remaining.is_empty() {
+                Self::remove_grant(&patient, &doctor);
+            } else {
+                Permissions::<T>::insert(&patient, &doctor, remaining);
+            }
+            if revoked.can_read() && !Self::scope_of(&patient, &doctor).can_read() {
+                T::OnAccessRevoked::on_access_revoked(&patient, &doctor);
+            }
+
+            Self::deposit_event(Event::AccessRevoked {
+                patient,
+                doctor,
+                scope: remaining,
+            });
+
+            Ok(())
+        }
+
+        /// Appoints `guardian` for `patient`, replacing any previous end date.
+        ///
+        /// A guardianship in force can only be renewed by whoever appointed
+        /// it, so that the other appointer cannot take over its removal.
+        fn do_appoint(
+            patient: T::AccountId,
+            guardian: T::AccountId,
+            appointed_by: GuardianAppointer,
+            until: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            ensure!(patient != guardian, Error::<T>::SelfPermissionNotAllowed);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            if let Some(until) = until {
+                ensure!(until > now, Error::<T>::ExpiryInPast);
+            }
 
+            let mut since = now;
+            if let Some(current) = Guardians::<T>::get(&patient, &guardian) {
+                if current.is_active_at(&now) {
+                    ensure!(
+                        current.appointed_by == appointed_by,
+                        Error::<T>::GuardianAppointedByOther
+                    );
+                    since = current.since;
+                }
+                if let Some(ends_at) = current.until {
+                    GuardianExpiryQueue::<T>::remove(ends_at, (&patient, &guardian));
+                }
+            }
+
+            Guardians::<T>::insert(
+                &patient,
+                &guardian,
+                Guardianship { appointed_by, since, until },
+            );
+            if let Some(until) = until {
+                GuardianExpiryQueue::<T>::insert(until, (&patient, &guardian), ());
+            }
+
+            Self::deposit_event(Event::GuardianAppointed {
+                patient,
+                guardian,
+                appointed_by,
+                until,
+            });
+
+            Ok(())
+        }
+
+        /// Removes `guardian` from `patient` if it was appointed by
+        /// `appointed_by`.
+        fn do_remove_guardian(
+            patient: T::AccountId,
+            guardian: T::AccountId,
+            appointed_by: GuardianAppointer,
+        ) -> DispatchResult {
+            let guardianship =
+                Guardians::<T>::get(&patient, &guardian).ok_or(Error::<T>::GuardianNotFound)?;
+            ensure!(guardianship.appointed_by == appointed_by, Error::<T>::GuardianNotFound);
+
+            Guardians::<T>::remove(&patient, &guardian);
+            if let Some(ends_at) = guardianship.until {
+                GuardianExpiryQueue::<T>::remove(ends_at, (&patient, &guardian));
+            }
+            if !Self::scope_of(&patient, &guardian).can_read() {
+                T::OnAccessRevoked::on_access_revoked(&patient, &guardian);
+            }
+
+            Self::deposit_event(Event::GuardianRemoved { patient, guardian });
+
+            Ok(())
+        }
+
+        /// Ensures `who` is the admin of `institution`.
+        fn ensure_institution_admin(
+            institution: InstitutionId,
+            who: &T::AccountId,
+        ) -> DispatchResult {
+            let details =
+                Institutions::<T>::get(institution).ok_or(Error::<T>::InstitutionNotFound)?;
+            ensure!(details.admin == *who, Error::<T>::NotInstitutionAdmin);
             Ok(())
         }
+
+        /// Removes the grant from `patient` to `doctor` together with its
+        /// expiry, index entry and deposit, returning the removed scope.
+        fn remove_grant(patient: &T::AccountId, doctor: &T::AccountId) -> PermissionScope {
+            let scope = Permissions::<T>::take(patient, doctor);
+            // Entries are never stored with an empty scope.
+            if !scope.is_empty() {
+                GrantCount::<T>::mutate_exists(patient, |count| {
+                    *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+                });
+            }
+            DoctorPatients::<T>::remove(doctor, patient);
+            if let Some(at) = Expirations::<T>::take(patient, doctor) {
+                ExpiryQueue::<T>::remove(at, (patient, doctor));
+            }
+            Self::release_deposit(patient, doctor);
+
+            scope
+        }
+
+        /// Removes the grant from `patient` to `institution` together with its
+        /// index entry and deposit.
+        fn remove_institution_grant(patient: &T::AccountId, institution: InstitutionId) {
+            InstitutionPermissions::<T>::remove(patient, institution);
+            if InstitutionPatients::<T>::take(institution, patient).is_some() {
+                InstitutionPatientCount::<T>::mutate_exists(institution, |count| {
+                    *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+                });
+                InstitutionGrantCount::<T>::mutate_exists(patient, |count| {
+                    *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+                });
+            }
+            if let Some(deposit) = InstitutionDeposits::<T>::take(patient, institution) {
+                // Best effort: the grant is gone either way.
+                let _ = T::Currency::release(
+                    &HoldReason::PermissionDeposit.into(),
+                    patient,
+                    deposit,
+                    Precision::BestEffort,
+                );
+            }
+        }
+
+        /// Logs the revocation of `revoked` from `institution` and reports
+        /// the members that lost read access over `patient`.
+        fn institution_revoked(
+            patient: &T::AccountId,
+            institution: InstitutionId,
+            revoked: PermissionScope,
+        ) {
+            if !revoked.is_empty() {
+                Self::log_change(
+                    patient,
+                    LogSubject::Institution(institution),
+                    PermissionAction::InstitutionRevoked(institution),
+                    revoked,
+                );
+            }
+
+            if revoked.can_read() {
+                for doctor in InstitutionMembers::<T>::iter_key_prefix(institution) {
+                    if !Self::scope_of(patient, &doctor).can_read() {
+                        T::OnAccessRevoked::on_access_revoked(patient, &doctor);
+                    }
+                }
+            }
+        }
+
+        /// Returns how many doctor and institution grants `patient` holds.
+        fn grant_count(patient: &T::AccountId) -> u32 {
+            GrantCount::<T>::get(patient).saturating_add(InstitutionGrantCount::<T>::get(patient))
+        }
+
+        /// Releases the deposit held for the grant from `patient` to `doctor`.
+        fn release_deposit(patient: &T::AccountId, doctor: &T::AccountId) {
+            if let Some(deposit) = GrantDeposits::<T>::take(patient, doctor) {
+                // Best effort: the grant is gone either way.
+                let _ = T::Currency::release(
+                    &HoldReason::PermissionDeposit.into(),
+                    patient,
+                    deposit,
+                    Precision::BestEffort,
+                );
+            }
+        }
+
+        /// Appends an entry to `patient`'s permission history log, dropping
+        /// the oldest entry once [`Config::MaxLogEntries`] is exceeded.
+        fn log_change(
+            patient: &T::AccountId,
+            subject: LogSubject<T::AccountId>,
+            action: PermissionAction,
+            scope: PermissionScope,
+        ) {
+            let seq = PermissionLogNext::<T>::get(patient);
+
+            PermissionLog::<T>::insert(
+                patient,
+                seq,
+                PermissionLogEntry {
+                    subject,
+                    action,
+                    scope,
+                    block: frame_system::Pallet::<T>::block_number(),
+                    timestamp: T::Time::now(),
+                },
+            );
+            PermissionLogNext::<T>::insert(patient, seq.saturating_add(1));
+
+            if let Some(oldest) = seq.checked_sub(T::MaxLogEntries::get().into()) {
+                PermissionLog::<T>::remove(patient, oldest);
+            }
+        }
+
+        /// Returns up to `limit` entries of `patient`'s permission history,
+        /// starting at sequence number `from` (oldest first).
+        ///
+        /// Entries that already fell out of the retention window are skipped.
+        /// Used by the `MedicalPermissionsApi` runtime API.
+        pub fn permission_log(
+            patient: &T::AccountId,
+            from: u64,
+            limit: u32,
+        ) -> Vec<(u64, PermissionLogEntryOf<T>)> {
+            let next = PermissionLogNext::<T>::get(patient);
+            let first_kept = next.saturating_sub(T::MaxLogEntries::get().into());
+
+            (from.max(first_kept)..next)
+                .take(limit as usize)
+                .filter_map(|seq| PermissionLog::<T>::get(patient, seq).map(|entry| (seq, entry)))
+                .collect()
+        }
+
+        /// Returns a page of the patients that granted `doctor` access,
+        /// walking at most `limit` entries of [`DoctorPatients`] after the
+        /// patient `after` (`None` for the first page).
+        ///
+        /// Grants that already expired but were not cleaned up yet are
+        /// skipped. Used by the `MedicalPermissionsApi` runtime API.
+        pub fn patients_of(
+            doctor: &T::AccountId,
+            after: Option<T::AccountId>,
+            limit: u32,
+        ) -> GrantPageOf<T> {
+            let entries = match after {
+                Some(after) => DoctorPatients::<T>::iter_prefix_from(
+                    doctor,
+                    DoctorPatients::<T>::hashed_key_for(doctor, after),
+                ),
+                None => DoctorPatients::<T>::iter_prefix(doctor),
+            };
+
+            Self::paginate_grants(entries, limit, |patient, granted| {
+                let scope = Self::active_scope(&patient, doctor);
+                (!scope.is_empty()).then(|| GrantInfo {
+                    scope,
+                    granted,
+                    expires_at: Expirations::<T>::get(&patient, doctor),
+                    account: patient,
+                })
+            })
+        }
+
+        /// Returns a page of the doctors `patient` granted access to,
+        /// walking at most `limit` entries of [`Permissions`] after the doctor
+        /// `after` (`None` for the first page).
+        ///
+        /// Same rules as [`Self::patients_of`].
+        pub fn doctors_of(
+            patient: &T::AccountId,
+            after: Option<T::AccountId>,
+            limit: u32,
+        ) -> GrantPageOf<T> {
+            let entries = match after {
+                Some(after) => Permissions::<T>::iter_prefix_from(
+                    patient,
+                    Permissions::<T>::hashed_key_for(patient, after),
+                ),
+                None => Permissions::<T>::iter_prefix(patient),
+            };
+
+            Self::paginate_grants(entries, limit, |doctor, _| {
+                let scope = Self::active_scope(patient, &doctor);
+                (!scope.is_empty()).then(|| GrantInfo {
+                    scope,
+                    granted: DoctorPatients::<T>::get(&doctor, patient).unwrap_or_default(),
+                    expires_at: Expirations::<T>::get(patient, &doctor),
+                    account: doctor,
+                })
+            })
+        }
+
+        /// Walks up to `limit` of `entries` and returns the grants `info`
+        /// keeps, with the key to resume from if entries remain.
+        fn paginate_grants<V>(
+            entries: impl Iterator<Item = (T::AccountId, V)>,
+            limit: u32,
+            info: impl Fn(T::AccountId, V) -> Option<GrantInfoOf<T>>,
+        ) -> GrantPageOf<T> {
+            let mut entries = entries.peekable();
+            let mut items = Vec::new();
+            let mut last = None;
+
+            for (account, value) in entries.by_ref().take(limit as usize) {
+                last = Some(account.clone());
+                items.extend(info(account, value));
+            }
+
+            let next = entries.peek().and(last);
+            GrantPage { items, next }
+        }
+
+        /// Returns `patient`'s guardians, including the ones whose
+        /// guardianship already ended.
+        ///
+        /// Used by the `MedicalPermissionsApi` runtime API.
+        pub fn guardians_of(
+            patient: &T::AccountId,
+        ) -> Vec<(T::AccountId, Guardianship<BlockNumberFor<T>>)> {
+            Guardians::<T>::iter_prefix(patient).collect()
+        }
+
+        /// Returns up to `limit` of `patient`'s break-glass incidents with
+        /// their contest, if any, starting at incident id `from`.
+        ///
+        /// Used by the `MedicalPermissionsApi` runtime API.
+        pub fn emergency_incidents(
+            patient: &T::AccountId,
+            from: u32,
+            limit: u32,
+        ) -> Vec<(u32, EmergencyIncidentOf<T>, Option<IncidentContestOf<T>>)> {
+            (from..NextIncidentId::<T>::get(patient))
+                .take(limit as usize)
+                .filter_map(|id| {
+                    Incidents::<T>::get(patient, id)
+                        .map(|incident| (id, incident, Contests::<T>::get(patient, id)))
+                })
+                .collect()
+        }
+
+        /// Returns a page of `patient`'s referrals, in any status, oldest
+        /// first, walking at most `limit` entries of [`PatientReferrals`]
+        /// after the referral `after` (`None` for the first page).
+        ///
+        /// Used by the `MedicalPermissionsApi` runtime API.
+        pub fn referrals_of_patient(
+            patient: &T::AccountId,
+            after: Option<ReferralId>,
+            limit: u32,
+        ) -> ReferralPageOf<T> {
+            let ids = match after {
+                Some(after) => PatientReferrals::<T>::iter_key_prefix_from(
+                    patient,
+                    PatientReferrals::<T>::hashed_key_for(patient, ReferralKey::from(after)),
+                ),
+                None => PatientReferrals::<T>::iter_key_prefix(patient),
+            };
+
+            Self::paginate_referrals(ids, limit)
+        }
+
+        /// Returns a page of the referrals addressed to `specialist`, in any
+        /// status, oldest first, walking at most `limit` entries of
+        /// [`SpecialistReferrals`] after the referral `after`.
+        ///
+        /// Used by the `MedicalPermissionsApi` runtime API.
+        pub fn referrals_of_specialist(
+            specialist: &T::AccountId,
+            after: Option<ReferralId>,
+            limit: u32,
+        ) -> ReferralPageOf<T> {
+            let ids = match after {
+                Some(after) => SpecialistReferrals::<T>::iter_key_prefix_from(
+                    specialist,
+                    SpecialistReferrals::<T>::hashed_key_for(specialist, ReferralKey::from(after)),
+                ),
+                None => SpecialistReferrals::<T>::iter_key_prefix(specialist),
+            };
+
+            Self::paginate_referrals(ids, limit)
+        }
+
+        /// Walks up to `limit` of the referral `keys` and returns the
+        /// referrals, with the id to resume from if keys remain.
+        fn paginate_referrals(
+            keys: impl Iterator<Item = ReferralKey>,
+            limit: u32,
+        ) -> ReferralPageOf<T> {
+            let mut keys = keys.peekable();
+            let mut items = Vec::new();
+            let mut last = None;
+
+            for id in keys.by_ref().take(limit as usize).map(ReferralId::from) {
+                last = Some(id);
+                items.extend(Referrals::<T>::get(id).map(|referral| (id, referral)));
+            }
+
+            let next = keys.peek().and(last);
+            ReferralPage { items, next }
+        }
     }
 
     //Para calibrar pesos nos benchmarks ----------------  START ------------------
@@ -158,7 +3136,11 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Concede permissão (somente para benchmarking).
         pub fn bench_grant_permission(patient: &T::AccountId, doctor: &T::AccountId) {
-            Permissions::<T>::insert(patient, doctor, true);
+            if !Permissions::<T>::contains_key(patient, doctor) {
+                GrantCount::<T>::mutate(patient, |count| *count = count.saturating_add(1));
+            }
+            Permissions::<T>::insert(patient, doctor, PermissionScope::ALL);
+            DoctorPatients::<T>::insert(doctor, patient, GrantTimestampOf::<T>::default());
         }
     }
     //Para calibrar pesos nos benchmarks ----------------  END ------------------
@@ -168,23 +3150,171 @@ pub mod pallet {
 // Public interface (cross-pallet)
 // -------------------------------------------------------------------------
 
+/// Hook for pallets that must react when a doctor loses read access to a
+/// patient's data, through revocation or expiry.
+pub trait OnAccessRevoked<AccountId> {
+    /// Called after `doctor`'s read access to `patient` was removed.
+    fn on_access_revoked(patient: &AccountId, doctor: &AccountId);
+}
+
+impl<AccountId> OnAccessRevoked<AccountId> for () {
+    fn on_access_revoked(_patient: &AccountId, _doctor: &AccountId) {}
+}
+
 /// Public verifier interface for checking whether a doctor
 /// has access to a patient's medical data.
 ///
 /// Other pallets can depend on this trait to enforce
 /// authorization rules without directly accessing storage.
 pub trait MedicalPermissionsVerifier<AccountId> {
-    /// Returns `true` if `doctor` has access to `patient`'s data.
+    /// Returns `true` if `doctor` has any access to `patient`'s data.
     fn has_access(patient: &AccountId, doctor: &AccountId) -> bool;
+
+    /// Returns `true` if `doctor` may read `patient`'s data.
+    ///
+    /// Defaults to [`Self::has_access`] for verifiers without scopes.
+    fn can_read(patient: &AccountId, doctor: &AccountId) -> bool {
+        Self::has_access(patient, doctor)
+    }
+
+    /// Returns `true` if `doctor` may write to `patient`'s history.
+    ///
+    /// Defaults to [`Self::has_access`] for verifiers without scopes.
+    fn can_write(patient: &AccountId, doctor: &AccountId) -> bool {
+        Self::has_access(patient, doctor)
+    }
+
+    /// Returns the institution through which `doctor` reads `patient`'s
+    /// data, if the doctor has no read grant of their own.
+    ///
+    /// Lets readers attribute institution-mediated accesses. Defaults to
+    /// `None` for verifiers without institutions.
+    fn acting_institution(_patient: &AccountId, _doctor: &AccountId) -> Option<InstitutionId> {
+        None
+    }
+
+    /// Returns `true` if `doctor` may read the record `file_hash` of
+    /// `patient`.
+    ///
+    /// Unlike [`Self::can_read`], also honours read access limited to
+    /// selected records, such as a referral's. Defaults to
+    /// [`Self::can_read`] for verifiers without per-record access.
+    fn can_read_record(patient: &AccountId, doctor: &AccountId, _file_hash: &[u8]) -> bool {
+        Self::can_read(patient, doctor)
+    }
+}
+
+impl<T: pallet::Config> pallet::Pallet<T> {
+    /// Returns the stored scope of `doctor` over `patient`, ignoring grants
+    /// that already expired but were not cleaned up yet.
+    fn active_scope(patient: &T::AccountId, doctor: &T::AccountId) -> PermissionScope {
+        if let Some(expires_at) = pallet::Expirations::<T>::get(patient, doctor) {
+            if expires_at <= frame_system::Pallet::<T>::block_number() {
+                return PermissionScope::NONE;
+            }
+        }
+
+        pallet::Permissions::<T>::get(patient, doctor)
+    }
+
+    /// Returns `true` if `accessor` holds an unexpired break-glass access
+    /// to `patient`.
+    fn has_emergency_access(patient: &T::AccountId, accessor: &T::AccountId) -> bool {
+        pallet::EmergencyGrants::<T>::get(patient, accessor)
+            .is_some_and(|expires_at| expires_at > frame_system::Pallet::<T>::block_number())
+    }
+
+    /// Returns the institutions of `doctor` holding a grant from `patient`,
+    /// with their scope.
+    fn institution_scopes<'a>(
+        patient: &'a T::AccountId,
+        doctor: &T::AccountId,
+    ) -> impl Iterator<Item = (InstitutionId, PermissionScope)> + 'a {
+        pallet::Memberships::<T>::get(doctor).into_iter().filter_map(move |institution| {
+            let scope = pallet::InstitutionPermissions::<T>::get(patient, institution);
+            (!scope.is_empty()).then_some((institution, scope))
+        })
+    }
+
+    /// Returns the active referral of `specialist` for `patient`, if any.
+    fn active_referral(patient: &T::AccountId, specialist: &T::AccountId) -> Option<ReferralOf<T>> {
+        let referral = pallet::OpenReferrals::<T>::get(patient, specialist)?;
+        pallet::Referrals::<T>::get(referral)
+            .filter(|entry| entry.is_active_at(&frame_system::Pallet::<T>::block_number()))
+    }
+
+    /// Returns `true` if `guardian` is an active guardian of `patient`.
+    fn is_guardian(patient: &T::AccountId, guardian: &T::AccountId) -> bool {
+        pallet::Guardians::<T>::get(patient, guardian).is_some_and(|guardianship| {
+            guardianship.is_active_at(&frame_system::Pallet::<T>::block_number())
+        })
+    }
+
+    /// Returns the scope `doctor` currently holds over `patient`.
+    ///
+    /// A patient always has full access to their own data. Grants to the
+    /// doctor's institutions and an active referral add to the doctor's own
+    /// grant, unless the patient froze their grants, and a break-glass
+    /// access or a guardianship adds read access on top. A referral limited
+    /// to selected records adds no read access here; see
+    /// [`MedicalPermissionsVerifier::can_read_record`].
+    fn scope_of(patient: &T::AccountId, doctor: &T::AccountId) -> PermissionScope {
+        if patient == doctor {
+            return PermissionScope::ALL;
+        }
+
+        let scope = if pallet::FrozenPatients::<T>::contains_key(patient) {
+            PermissionScope::NONE
+        } else {
+            let referred = Self::active_referral(patient, doctor)
+                .map_or(PermissionScope::NONE, |referral| referral.patient_scope());
+            Self::institution_scopes(patient, doctor)
+                .fold(Self::active_scope(patient, doctor).union(referred), |scope, (_, granted)| {
+                    scope.union(granted)
+                })
+        };
+        if Self::has_emergency_access(patient, doctor) || Self::is_guardian(patient, doctor) {
+            scope.union(PermissionScope::READ)
+        } else {
+            scope
+        }
+    }
 }
 
 impl<T: pallet::Config> MedicalPermissionsVerifier<T::AccountId> for pallet::Pallet<T> {
     fn has_access(patient: &T::AccountId, doctor: &T::AccountId) -> bool {
-        // A patient always has access to their own data.
-        if patient == doctor {
+        !Self::scope_of(patient, doctor).is_empty()
+    }
+
+    fn can_read(patient: &T::AccountId, doctor: &T::AccountId) -> bool {
+        Self::scope_of(patient, doctor).can_read()
+    }
+
+    fn can_write(patient: &T::AccountId, doctor: &T::AccountId) -> bool {
+        Self::scope_of(patient, doctor).can_write()
+    }
+
+    fn acting_institution(patient: &T::AccountId, doctor: &T::AccountId) -> Option<InstitutionId> {
+        if pallet::FrozenPatients::<T>::contains_key(patient) ||
+            Self::active_scope(patient, doctor).can_read() ||
+            Self::active_referral(patient, doctor)
+                .is_some_and(|referral| referral.patient_scope().can_read())
+        {
+            return None;
+        }
+
+        Self::institution_scopes(patient, doctor)
+            .find(|(_, scope)| scope.can_read())
+            .map(|(institution, _)| institution)
+    }
+
+    fn can_read_record(patient: &T::AccountId, doctor: &T::AccountId, file_hash: &[u8]) -> bool {
+        if Self::scope_of(patient, doctor).can_read() {
             return true;
         }
 
-        pallet::Permissions::<T>::get(patient, doctor)
+        !pallet::FrozenPatients::<T>::contains_key(patient) &&
+            Self::active_referral(patient, doctor)
+                .is_some_and(|referral| referral.scope.can_read() && referral.covers(file_hash))
     }
 }
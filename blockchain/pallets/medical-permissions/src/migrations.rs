@@ -0,0 +1,528 @@
+//! Storage migrations for the Medical Permissions pallet.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+    weights::Weight,
+};
+
+/// Migration from storage version `0` to `1`.
+///
+/// Version `0` stored `Permissions` as `bool`. Version `1` stores a
+/// [`PermissionScope`]; every `true` entry becomes [`PermissionScope::ALL`],
+/// which preserves the previous behaviour (one grant allowed read and write).
+pub mod v1 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`MigrateV0ToV1`] instead.
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+
+            Permissions::<T>::translate::<bool, _>(|_patient, _doctor, granted| {
+                translated = translated.saturating_add(1);
+                granted.then_some(PermissionScope::ALL)
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `0`.
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `1` to `2`.
+///
+/// Version `2` adds the [`DoctorPatients`] reverse index. Grants created
+/// before it existed get a zero [`GrantTimestamp`], since their original
+/// time is unknown.
+pub mod v2 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`MigrateV1ToV2`] instead.
+    pub struct InnerMigrateV1ToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut indexed: u64 = 0;
+
+            for (patient, doctor, _scope) in Permissions::<T>::iter() {
+                DoctorPatients::<T>::insert(&doctor, &patient, GrantTimestampOf::<T>::default());
+                indexed = indexed.saturating_add(1);
+            }
+
+            T::DbWeight::get().reads_writes(indexed, indexed)
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `1`.
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `2` to `3`.
+///
+/// Version `3` adds the per-patient [`GrantCount`], computed here from the
+/// existing [`Permissions`].
+pub mod v3 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`MigrateV2ToV3`] instead.
+    pub struct InnerMigrateV2ToV3<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut counted: u64 = 0;
+
+            for patient in Permissions::<T>::iter_keys().map(|(patient, _doctor)| patient) {
+                GrantCount::<T>::mutate(&patient, |count| *count = count.saturating_add(1));
+                counted = counted.saturating_add(1);
+            }
+
+            T::DbWeight::get().reads_writes(counted.saturating_mul(2), counted)
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `2`.
+    pub type MigrateV2ToV3<T> = VersionedMigration<
+        2,
+        3,
+        InnerMigrateV2ToV3<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `3` to `4`.
+///
+/// Version `3` stored [`ExpiryQueue`] as one bounded list per block, which
+/// could fill up and reject grants. Version `4` stores one entry per grant
+/// and drains the queue from [`ExpiryCursor`]. Stale entries are dropped and
+/// the cursor starts at the current block.
+pub mod v4 {
+    use super::*;
+    use frame_support::{storage_alias, BoundedVec, Twox64Concat};
+
+    /// [`pallet::ExpiryQueue`] as stored up to version `3`.
+    #[storage_alias]
+    type ExpiryQueue<T: Config> = StorageMap<
+        Pallet<T>,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<
+            (<T as frame_system::Config>::AccountId, <T as frame_system::Config>::AccountId),
+            <T as Config>::MaxExpiriesPerBlock,
+        >,
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV3ToV4`] instead.
+    pub struct InnerMigrateV3ToV4<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV3ToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let old: Vec<_> = ExpiryQueue::<T>::drain().collect();
+            let mut moved: u64 = 0;
+
+            for (at, queue) in old.iter() {
+                for (patient, doctor) in queue {
+                    if Expirations::<T>::get(patient, doctor) == Some(*at) {
+                        pallet::ExpiryQueue::<T>::insert(at, (patient, doctor), ());
+                        moved = moved.saturating_add(1);
+                    }
+                }
+            }
+            ExpiryCursor::<T>::put(frame_system::Pallet::<T>::block_number());
+
+            let entries = old.iter().map(|(_, queue)| queue.len() as u64).sum::<u64>();
+            T::DbWeight::get().reads_writes(
+                (old.len() as u64).saturating_add(entries),
+                (old.len() as u64).saturating_add(moved).saturating_add(1),
+            )
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `3`.
+    pub type MigrateV3ToV4<T> = VersionedMigration<
+        3,
+        4,
+        InnerMigrateV3ToV4<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `4` to `5`.
+///
+/// Moves [`RequestExpiryQueue`] to one entry per access request, drained
+/// from [`RequestExpiryCursor`], like [`v4`] did for [`ExpiryQueue`].
+pub mod v5 {
+    use super::*;
+    use frame_support::{storage_alias, BoundedVec, Twox64Concat};
+
+    /// [`pallet::RequestExpiryQueue`] as stored up to version `4`.
+    #[storage_alias]
+    type RequestExpiryQueue<T: Config> = StorageMap<
+        Pallet<T>,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<
+            (<T as frame_system::Config>::AccountId, <T as frame_system::Config>::AccountId),
+            <T as Config>::MaxExpiriesPerBlock,
+        >,
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV4ToV5`] instead.
+    pub struct InnerMigrateV4ToV5<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV4ToV5<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let old: Vec<_> = RequestExpiryQueue::<T>::drain().collect();
+            let mut moved: u64 = 0;
+
+            for (at, queue) in old.iter() {
+                for (patient, doctor) in queue {
+                    let pending = AccessRequests::<T>::get(patient, doctor)
+                        .is_some_and(|request| request.expires_at == *at);
+                    if pending {
+                        pallet::RequestExpiryQueue::<T>::insert(at, (patient, doctor), ());
+                        moved = moved.saturating_add(1);
+                    }
+                }
+            }
+            RequestExpiryCursor::<T>::put(frame_system::Pallet::<T>::block_number());
+
+            let entries = old.iter().map(|(_, queue)| queue.len() as u64).sum::<u64>();
+            T::DbWeight::get().reads_writes(
+                (old.len() as u64).saturating_add(entries),
+                (old.len() as u64).saturating_add(moved).saturating_add(1),
+            )
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `4`.
+    pub type MigrateV4ToV5<T> = VersionedMigration<
+        4,
+        5,
+        InnerMigrateV4ToV5<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `5` to `6`.
+///
+/// Version `5` queued break-glass expiries in a bounded list per block,
+/// which could fill up and refuse emergency access. Version `6` drops the
+/// queue: expired entries of [`pallet::EmergencyGrants`] are no longer
+/// honoured and are removed lazily. The old queue is cleared.
+pub mod v6 {
+    use super::*;
+    use frame_support::{storage_alias, BoundedVec, Twox64Concat};
+
+    /// The break-glass expiry queue as stored up to version `5`.
+    #[storage_alias]
+    type EmergencyExpiryQueue<T: Config> = StorageMap<
+        Pallet<T>,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<
+            (<T as frame_system::Config>::AccountId, <T as frame_system::Config>::AccountId),
+            <T as Config>::MaxExpiriesPerBlock,
+        >,
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV5ToV6`] instead.
+    pub struct InnerMigrateV5ToV6<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV5ToV6<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let removed = EmergencyExpiryQueue::<T>::clear(u32::MAX, None).unique as u64;
+
+            T::DbWeight::get().reads_writes(removed, removed)
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `5`.
+    pub type MigrateV5ToV6<T> = VersionedMigration<
+        5,
+        6,
+        InnerMigrateV5ToV6<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `6` to `7`.
+///
+/// Version `7` ends guardianships at their `until` block through
+/// [`GuardianExpiryQueue`]. Every guardianship with an end date is queued at
+/// that block, or at the current block if it already passed, and the
+/// cursor starts at the current block.
+pub mod v7 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`MigrateV6ToV7`] instead.
+    pub struct InnerMigrateV6ToV7<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV6ToV7<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut read: u64 = 0;
+            let mut queued: u64 = 0;
+
+            for (patient, guardian, guardianship) in Guardians::<T>::iter() {
+                read = read.saturating_add(1);
+                if let Some(until) = guardianship.until {
+                    GuardianExpiryQueue::<T>::insert(until.max(now), (patient, guardian), ());
+                    queued = queued.saturating_add(1);
+                }
+            }
+            GuardianExpiryCursor::<T>::put(now);
+
+            T::DbWeight::get().reads_writes(read, queued.saturating_add(1))
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `6`.
+    pub type MigrateV6ToV7<T> = VersionedMigration<
+        6,
+        7,
+        InnerMigrateV6ToV7<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `7` to `8`.
+///
+/// Version `8` indexes [`InstitutionPermissions`] by institution in
+/// [`InstitutionPatients`], so removing a member can report the patients
+/// it loses access to, and counts them per patient in
+/// [`InstitutionGrantCount`] for [`Pallet::revoke_all_access`]. Grants made
+/// before held no deposit and keep holding none;
+/// [`Config::MaxInstitutionPatients`] is not enforced on them.
+pub mod v8 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`MigrateV7ToV8`] instead.
+    pub struct InnerMigrateV7ToV8<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV7ToV8<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut indexed: u64 = 0;
+
+            for (patient, institution, _) in InstitutionPermissions::<T>::iter() {
+                InstitutionGrantCount::<T>::mutate(&patient, |count| {
+                    *count = count.saturating_add(1)
+                });
+                InstitutionPatients::<T>::insert(institution, patient, ());
+                InstitutionPatientCount::<T>::mutate(institution, |count| {
+                    *count = count.saturating_add(1)
+                });
+                indexed = indexed.saturating_add(1);
+            }
+
+            T::DbWeight::get().reads_writes(indexed.saturating_mul(3), indexed.saturating_mul(3))
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `7`.
+    pub type MigrateV7ToV8<T> = VersionedMigration<
+        7,
+        8,
+        InnerMigrateV7ToV8<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `8` to `9`.
+///
+/// Version `8` kept one bounded list of referrals per expiry block, so
+/// approving a referral failed once [`Config::MaxExpiriesPerBlock`] others
+/// expired in the same block. Version `9` queues one entry per active
+/// referral in [`ReferralExpiryQueue`] and drains it from
+/// [`ReferralExpiryCursor`], like [`v4`] did for [`ExpiryQueue`].
+pub mod v9 {
+    use super::*;
+    use frame_support::{storage_alias, BoundedVec, Twox64Concat};
+
+    /// [`pallet::ReferralExpiryQueue`] as stored up to version `8`.
+    #[storage_alias]
+    type ReferralExpiryQueue<T: Config> = StorageMap<
+        Pallet<T>,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<ReferralId, <T as Config>::MaxExpiriesPerBlock>,
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV8ToV9`] instead.
+    pub struct InnerMigrateV8ToV9<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV8ToV9<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let old: Vec<_> = ReferralExpiryQueue::<T>::drain().collect();
+            let mut moved: u64 = 0;
+
+            for (at, queue) in old.iter() {
+                for referral in queue {
+                    let active = Referrals::<T>::get(referral).is_some_and(|entry| {
+                        entry.status == ReferralStatus::Active && entry.expires_at == Some(*at)
+                    });
+                    if active {
+                        pallet::ReferralExpiryQueue::<T>::insert(at, referral, ());
+                        moved = moved.saturating_add(1);
+                    }
+                }
+            }
+            ReferralExpiryCursor::<T>::put(frame_system::Pallet::<T>::block_number());
+
+            let entries = old.iter().map(|(_, queue)| queue.len() as u64).sum::<u64>();
+            T::DbWeight::get().reads_writes(
+                (old.len() as u64).saturating_add(entries),
+                (old.len() as u64).saturating_add(moved).saturating_add(1),
+            )
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `8`.
+    pub type MigrateV8ToV9<T> = VersionedMigration<
+        8,
+        9,
+        InnerMigrateV8ToV9<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `9` to `10`.
+///
+/// Version `9` logged institution grants and revocations with the
+/// institution's admin in the `doctor` field of [`PermissionLogEntry`].
+/// Version `10` records a [`LogSubject`] instead, so institution entries
+/// name the institution rather than an account that was never a grantee.
+pub mod v10 {
+    use super::*;
+
+    /// Unversioned inner migration. Use [`MigrateV9ToV10`] instead.
+    pub struct InnerMigrateV9ToV10<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV9ToV10<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated: u64 = 0;
+
+            PermissionLog::<T>::translate::<
+                PermissionLogEntryV9<T::AccountId, BlockNumberFor<T>, MomentOf<T>>,
+                _,
+            >(|_patient, _seq, old| {
+                translated = translated.saturating_add(1);
+                let subject = match old.action {
+                    PermissionAction::InstitutionGranted(institution) |
+                    PermissionAction::InstitutionRevoked(institution) =>
+                        LogSubject::Institution(institution),
+                    _ => LogSubject::Doctor(old.doctor),
+                };
+                Some(PermissionLogEntry {
+                    subject,
+                    action: old.action,
+                    scope: old.scope,
+                    block: old.block,
+                    timestamp: old.timestamp,
+                })
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `9`.
+    pub type MigrateV9ToV10<T> = VersionedMigration<
+        9,
+        10,
+        InnerMigrateV9ToV10<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration from storage version `10` to `11`.
+///
+/// Version `10` keyed [`PatientReferrals`] and [`SpecialistReferrals`] by
+/// [`ReferralId`] under `Twox64Concat`, so a listing had to collect and sort
+/// every referral of the account. Version `11` keys them by
+/// [`ReferralKey`] under `Identity`, so a prefix iteration is already in id
+/// order and listings page from an `after` key.
+pub mod v11 {
+    use super::*;
+    use frame_support::{storage_alias, Blake2_128Concat, Twox64Concat};
+
+    /// [`pallet::PatientReferrals`] as stored up to version `10`.
+    #[storage_alias]
+    type PatientReferrals<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Twox64Concat,
+        ReferralId,
+        (),
+    >;
+
+    /// [`pallet::SpecialistReferrals`] as stored up to version `10`.
+    #[storage_alias]
+    type SpecialistReferrals<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Twox64Concat,
+        ReferralId,
+        (),
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV10ToV11`] instead.
+    pub struct InnerMigrateV10ToV11<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV10ToV11<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let patients: Vec<_> = PatientReferrals::<T>::drain().collect();
+            let specialists: Vec<_> = SpecialistReferrals::<T>::drain().collect();
+
+            for (patient, referral, ()) in patients.iter() {
+                pallet::PatientReferrals::<T>::insert(patient, ReferralKey::from(*referral), ());
+            }
+            for (specialist, referral, ()) in specialists.iter() {
+                pallet::SpecialistReferrals::<T>::insert(
+                    specialist,
+                    ReferralKey::from(*referral),
+                    (),
+                );
+            }
+
+            let moved = (patients.len() as u64).saturating_add(specialists.len() as u64);
+            T::DbWeight::get().reads_writes(moved, moved.saturating_mul(2))
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `10`.
+    pub type MigrateV10ToV11<T> = VersionedMigration<
+        10,
+        11,
+        InnerMigrateV10ToV11<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -2,11 +2,64 @@
 
 use crate as pallet_medical_permissions;
 
-use frame_support::derive_impl;
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64, Time},
+};
+use crate::OnAccessRevoked;
+use pallet_doctor_registry::DoctorRegistryVerifier;
 use sp_runtime::BuildStorage;
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
+/// Account that is not a registered doctor in [`MockDoctorRegistry`].
+pub const UNREGISTERED_DOCTOR: u64 = 99;
+
+/// Mock doctor registry: every account except [`UNREGISTERED_DOCTOR`] is an
+/// active doctor.
+pub struct MockDoctorRegistry;
+
+impl DoctorRegistryVerifier<u64> for MockDoctorRegistry {
+    fn is_active_doctor(who: &u64) -> bool {
+        *who != UNREGISTERED_DOCTOR
+    }
+}
+
+parameter_types! {
+    /// Timestamp returned by [`MockTime`]; tests set it explicitly.
+    pub static Now: u64 = 0;
+}
+
+parameter_types! {
+    /// Queued expiries processed per block; tests may lower it.
+    pub static MaxExpiries: u32 = 16;
+}
+
+parameter_types! {
+    /// `(patient, doctor)` pairs reported to [`MockOnAccessRevoked`].
+    pub static RevokedReads: Vec<(u64, u64)> = Vec::new();
+}
+
+/// Records every read revocation in [`RevokedReads`].
+pub struct MockOnAccessRevoked;
+
+impl OnAccessRevoked<u64> for MockOnAccessRevoked {
+    fn on_access_revoked(patient: &u64, doctor: &u64) {
+        RevokedReads::mutate(|revoked| revoked.push((*patient, *doctor)));
+    }
+}
+
+/// Mock time provider backed by the [`Now`] static.
+pub struct MockTime;
+
+impl Time for MockTime {
+    type Moment = u64;
+
+    fn now() -> u64 {
+        Now::get()
+    }
+}
+
 #[frame_support::runtime]
 mod runtime {
     //! Test runtime that wires the pallet under test.
@@ -33,22 +86,56 @@ mod runtime {
     /// Pallet under test.
     #[runtime::pallet_index(1)]
     pub type MedicalPermissions = pallet_medical_permissions::Pallet<Test>;
+
+    /// Balances pallet, holding grant deposits.
+    #[runtime::pallet_index(2)]
+    pub type Balances = pallet_balances::Pallet<Test>;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
     type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
 }
 
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+/// Deposit held for each grant.
+pub const PERMISSION_DEPOSIT: u64 = 10;
+
+/// Free balance of the funded accounts (`1..=3`) at genesis.
+pub const INITIAL_BALANCE: u64 = 100;
+
 impl pallet_medical_permissions::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type DoctorRegistry = MockDoctorRegistry;
+    type MaxExpiriesPerBlock = MaxExpiries;
+    type RequestTimeout = ConstU64<10>;
+    type Time = MockTime;
+    type MaxLogEntries = ConstU32<3>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type PermissionDeposit = ConstU64<PERMISSION_DEPOSIT>;
+    type OnAccessRevoked = MockOnAccessRevoked;
+    type EmergencyAuthorityOrigin = frame_system::EnsureRoot<u64>;
+    type EmergencyAccessDuration = ConstU64<5>;
+    type GuardianRegistrarOrigin = frame_system::EnsureRoot<u64>;
+    type InstitutionRegistrarOrigin = frame_system::EnsureRoot<u64>;
+    type MaxInstitutionMembers = ConstU32<2>;
+    type MaxInstitutionPatients = ConstU32<2>;
+    type MaxMembershipsPerDoctor = ConstU32<2>;
+    type MaxRevocationsPerCall = ConstU32<2>;
 }
 
 /// Builds the [`sp_io::TestExternalities`] environment for unit tests.
 ///
-/// This initializes the default system genesis storage and returns an
-/// externalities instance ready for `execute_with`.
+/// This initializes the default system genesis storage, funds accounts
+/// `1..=3` with [`INITIAL_BALANCE`] and returns an externalities instance
+/// ready for `execute_with`.
 ///
 /// # Returns
 /// A configured [`sp_io::TestExternalities`] for this mock runtime.
@@ -57,8 +144,16 @@ impl pallet_medical_permissions::Config for Test {
 /// Panics if the genesis storage cannot be built (should not happen in a
 /// correctly configured mock runtime).
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    frame_system::GenesisConfig::<Test>::default()
+    let mut storage = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
-        .expect("genesis storage should build")
-        .into()
+        .expect("genesis storage should build");
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=3).map(|who| (who, INITIAL_BALANCE)).collect(),
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .expect("balances genesis should build");
+
+    storage.into()
 }
@@ -1,7 +1,12 @@
 //! Unit tests for the Medical Permissions pallet.
 
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    mock::*, Error, Event, ExpiryQueue, GrantInfo, GrantTimestamp, GuardianAppointer, Guardianship,
+    LogSubject, MedicalPermissionsVerifier, PermissionAction, PermissionLog, PermissionScope,
+    RecordHash, ReferralExpiryQueue, ReferralId, ReferralRecords, ReferralStatus,
+    RequestExpiryQueue,
+};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 
 /// Ensures a patient can grant access to a doctor, emits the expected event,
 /// and persists the permission in storage.
@@ -21,10 +26,18 @@ fn grant_access_works() {
         ));
 
         // 2) Verify the emitted event.
-        System::assert_last_event(Event::AccessGranted { patient, doctor }.into());
+        System::assert_last_event(
+            Event::AccessGranted {
+                patient,
+                doctor,
+                scope: PermissionScope::ALL,
+                expires_at: None,
+            }
+            .into(),
+        );
 
         // 3) Verify the storage write.
-        assert!(MedicalPermissions::permissions(patient, doctor));
+        assert_eq!(MedicalPermissions::permissions(patient, doctor), PermissionScope::ALL);
     });
 }
 
@@ -52,10 +65,17 @@ fn revoke_access_works() {
         ));
 
         // 2) Verify the emitted event.
-        System::assert_last_event(Event::AccessRevoked { patient, doctor }.into());
+        System::assert_last_event(
+            Event::AccessRevoked {
+                patient,
+                doctor,
+                scope: PermissionScope::NONE,
+            }
+            .into(),
+        );
 
         // 3) Verify the storage removal.
-        assert!(!MedicalPermissions::permissions(patient, doctor));
+        assert!(!crate::Permissions::<Test>::contains_key(patient, doctor));
     });
 }
 
@@ -111,16 +131,2114 @@ fn doctor_cannot_change_patient_permission() {
         ));
 
         // Original permission must remain intact
-        assert!(
+        assert_eq!(
             MedicalPermissions::permissions(patient_account, doctor_account),
+            PermissionScope::ALL,
             "Permission granted by the original origin must remain unchanged"
         );
 
         // Revocation must apply only to the caller's mapping
         assert!(
-            !MedicalPermissions::permissions(unrelated_account, doctor_account),
+            MedicalPermissions::permissions(unrelated_account, doctor_account).is_empty(),
             "Revocation must be scoped to the caller only"
         );
     });
 }
 
+/// Ensures read and write scopes are granted and revoked independently.
+#[test]
+fn scoped_grant_and_revoke_are_independent() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+
+        // 1) Grant read only.
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::READ
+        ));
+        assert!(MedicalPermissions::can_read(&patient, &doctor));
+        assert!(!MedicalPermissions::can_write(&patient, &doctor));
+
+        // 2) Add write on top of read.
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::WRITE
+        ));
+        System::assert_last_event(
+            Event::AccessGranted {
+                patient,
+                doctor,
+                scope: PermissionScope::ALL,
+                expires_at: None,
+            }
+            .into(),
+        );
+
+        // 3) Revoke read; write must remain.
+        assert_ok!(MedicalPermissions::revoke_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::READ
+        ));
+        System::assert_last_event(
+            Event::AccessRevoked {
+                patient,
+                doctor,
+                scope: PermissionScope::WRITE,
+            }
+            .into(),
+        );
+        assert!(!MedicalPermissions::can_read(&patient, &doctor));
+        assert!(MedicalPermissions::can_write(&patient, &doctor));
+
+        // 4) Revoke write; the entry is removed.
+        assert_ok!(MedicalPermissions::revoke_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::WRITE
+        ));
+        assert!(!MedicalPermissions::has_access(&patient, &doctor));
+        assert!(!crate::Permissions::<Test>::contains_key(patient, doctor));
+    });
+}
+
+/// Ensures empty or unknown scopes are rejected.
+#[test]
+fn invalid_scope_is_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_noop!(
+            MedicalPermissions::grant_access_scoped(
+                RuntimeOrigin::signed(patient),
+                doctor,
+                PermissionScope::NONE
+            ),
+            Error::<Test>::InvalidScope
+        );
+
+        assert_eq!(PermissionScope::from_bits(0b100), None);
+    });
+}
+
+/// Ensures `bool` entries from storage version 0 become full-scope grants.
+#[test]
+fn migration_v0_to_v1_translates_bool_grants() {
+    use codec::Encode;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let patient: u64 = 1;
+        let doctor: u64 = 2;
+
+        // Simulate a v0 entry.
+        StorageVersion::new(0).put::<MedicalPermissions>();
+        frame_support::storage::unhashed::put_raw(
+            &crate::Permissions::<Test>::hashed_key_for(patient, doctor),
+            &true.encode(),
+        );
+
+        crate::migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(MedicalPermissions::permissions(patient, doctor), PermissionScope::ALL);
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 1);
+    });
+}
+
+/// Ensures a temporary grant stops being honoured at its expiry block and
+/// is cleaned up by `on_initialize`.
+#[test]
+fn temporary_access_expires() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::READ,
+            5
+        ));
+        System::assert_last_event(
+            Event::AccessGranted {
+                patient,
+                doctor,
+                scope: PermissionScope::READ,
+                expires_at: Some(5),
+            }
+            .into(),
+        );
+        assert!(MedicalPermissions::can_read(&patient, &doctor));
+
+        // Before cleanup, an expired grant is already ignored.
+        System::set_block_number(5);
+        assert!(!MedicalPermissions::has_access(&patient, &doctor));
+
+        // Cleanup removes storage and emits the expiry event.
+        MedicalPermissions::on_initialize(5);
+        System::assert_last_event(Event::AccessExpired { patient, doctor }.into());
+        assert!(!crate::Permissions::<Test>::contains_key(patient, doctor));
+        assert_eq!(MedicalPermissions::expirations(patient, doctor), None);
+    });
+}
+
+/// Ensures a permanent grant clears a pending expiry, so the stale queue
+/// entry does not remove it.
+#[test]
+fn permanent_grant_overrides_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::READ,
+            5
+        ));
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+
+        System::set_block_number(5);
+        MedicalPermissions::on_initialize(5);
+
+        assert_eq!(MedicalPermissions::permissions(patient, doctor), PermissionScope::ALL);
+        assert!(MedicalPermissions::can_write(&patient, &doctor));
+    });
+}
+
+/// Ensures expiry blocks are validated and permanent grants never shortened.
+#[test]
+fn temporary_access_checks_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_noop!(
+            MedicalPermissions::grant_temporary_access(
+                RuntimeOrigin::signed(patient),
+                doctor,
+                PermissionScope::READ,
+                3
+            ),
+            Error::<Test>::ExpiryInPast
+        );
+
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+        assert_noop!(
+            MedicalPermissions::grant_temporary_access(
+                RuntimeOrigin::signed(patient),
+                doctor,
+                PermissionScope::READ,
+                10
+            ),
+            Error::<Test>::PermanentGrantExists
+        );
+    });
+}
+
+/// Ensures any number of grants may expire in the same block, the surplus
+/// being cleaned up in the following blocks.
+#[test]
+fn expiries_beyond_block_limit_are_deferred() {
+    new_test_ext().execute_with(|| {
+        MaxExpiries::set(2);
+        System::set_block_number(1);
+
+        let patient = 1;
+
+        for doctor in [2, 3, 4] {
+            assert_ok!(MedicalPermissions::grant_temporary_access(
+                RuntimeOrigin::signed(patient),
+                doctor,
+                PermissionScope::READ,
+                5
+            ));
+        }
+        // Re-scheduling leaves no stale entry behind.
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(patient),
+            4,
+            PermissionScope::READ,
+            5
+        ));
+        assert_eq!(ExpiryQueue::<Test>::iter_prefix(5).count(), 3);
+
+        for n in 1..=5 {
+            System::set_block_number(n);
+            MedicalPermissions::on_initialize(n);
+        }
+
+        // Only two were cleaned up, but none is honoured any more.
+        assert_eq!(MedicalPermissions::grant_count(patient), 1);
+        for doctor in [2, 3, 4] {
+            assert!(!MedicalPermissions::has_access(&patient, &doctor));
+        }
+
+        System::set_block_number(6);
+        MedicalPermissions::on_initialize(6);
+
+        assert_eq!(MedicalPermissions::grant_count(patient), 0);
+        assert_eq!(ExpiryQueue::<Test>::iter().count(), 0);
+        assert_eq!(Balances::free_balance(patient), INITIAL_BALANCE);
+    });
+}
+
+/// Ensures a doctor's request becomes a grant once the patient approves it.
+#[test]
+fn request_access_and_approve_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+        let reason_hash = sp_core::H256::repeat_byte(7);
+
+        assert_ok!(MedicalPermissions::request_access(
+            RuntimeOrigin::signed(doctor),
+            patient,
+            PermissionScope::WRITE,
+            reason_hash
+        ));
+        System::assert_last_event(
+            Event::AccessRequested {
+                patient,
+                doctor,
+                scope: PermissionScope::WRITE,
+                reason_hash,
+                expires_at: 11,
+            }
+            .into(),
+        );
+
+        // A second request while the first is pending is rejected.
+        assert_noop!(
+            MedicalPermissions::request_access(
+                RuntimeOrigin::signed(doctor),
+                patient,
+                PermissionScope::READ,
+                reason_hash
+            ),
+            Error::<Test>::RequestAlreadyPending
+        );
+
+        // The request alone grants nothing.
+        assert!(!MedicalPermissions::has_access(&patient, &doctor));
+
+        assert_ok!(MedicalPermissions::approve_request(RuntimeOrigin::signed(patient), doctor));
+        System::assert_has_event(
+            Event::AccessRequestApproved {
+                patient,
+                doctor,
+                scope: PermissionScope::WRITE,
+            }
+            .into(),
+        );
+        assert!(MedicalPermissions::can_write(&patient, &doctor));
+        assert!(!MedicalPermissions::can_read(&patient, &doctor));
+        assert_eq!(MedicalPermissions::access_requests(patient, doctor), None);
+    });
+}
+
+/// Ensures any number of requests may expire in the same block and that
+/// answered requests leave nothing queued.
+#[test]
+fn requests_beyond_block_limit_are_accepted() {
+    new_test_ext().execute_with(|| {
+        MaxExpiries::set(2);
+        System::set_block_number(1);
+
+        let patient = 1;
+        let reason_hash = sp_core::H256::repeat_byte(7);
+
+        for doctor in [2, 3, 4] {
+            assert_ok!(MedicalPermissions::request_access(
+                RuntimeOrigin::signed(doctor),
+                patient,
+                PermissionScope::READ,
+                reason_hash
+            ));
+        }
+        assert_ok!(MedicalPermissions::reject_request(RuntimeOrigin::signed(patient), 2));
+        assert_eq!(RequestExpiryQueue::<Test>::iter_prefix(11).count(), 2);
+
+        for n in 1..=11 {
+            System::set_block_number(n);
+            MedicalPermissions::on_initialize(n);
+        }
+
+        assert_eq!(MedicalPermissions::access_requests(patient, 3), None);
+        assert_eq!(MedicalPermissions::access_requests(patient, 4), None);
+        assert_eq!(RequestExpiryQueue::<Test>::iter().count(), 0);
+    });
+}
+
+/// Ensures a rejected request grants nothing.
+#[test]
+fn reject_request_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_ok!(MedicalPermissions::request_access(
+            RuntimeOrigin::signed(doctor),
+            patient,
+            PermissionScope::READ,
+            sp_core::H256::zero()
+        ));
+        assert_ok!(MedicalPermissions::reject_request(RuntimeOrigin::signed(patient), doctor));
+        System::assert_last_event(Event::AccessRequestRejected { patient, doctor }.into());
+
+        assert!(!MedicalPermissions::has_access(&patient, &doctor));
+        assert_noop!(
+            MedicalPermissions::approve_request(RuntimeOrigin::signed(patient), doctor),
+            Error::<Test>::RequestNotFound
+        );
+    });
+}
+
+/// Ensures requests time out and cannot be approved afterwards.
+#[test]
+fn request_access_times_out() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_ok!(MedicalPermissions::request_access(
+            RuntimeOrigin::signed(doctor),
+            patient,
+            PermissionScope::READ,
+            sp_core::H256::zero()
+        ));
+
+        // `RequestTimeout` is 10 blocks in the mock runtime.
+        System::set_block_number(11);
+        assert_noop!(
+            MedicalPermissions::approve_request(RuntimeOrigin::signed(patient), doctor),
+            Error::<Test>::RequestExpired
+        );
+
+        MedicalPermissions::on_initialize(11);
+        System::assert_last_event(Event::AccessRequestExpired { patient, doctor }.into());
+        assert_eq!(MedicalPermissions::access_requests(patient, doctor), None);
+    });
+}
+
+/// Ensures grants and requests are limited to registered doctors.
+#[test]
+fn unregistered_doctor_cannot_receive_access() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+
+        assert_noop!(
+            MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), UNREGISTERED_DOCTOR),
+            Error::<Test>::DoctorNotRegistered
+        );
+        assert_noop!(
+            MedicalPermissions::request_access(
+                RuntimeOrigin::signed(UNREGISTERED_DOCTOR),
+                patient,
+                PermissionScope::READ,
+                sp_core::H256::zero()
+            ),
+            Error::<Test>::DoctorNotRegistered
+        );
+    });
+}
+
+/// Ensures grants, revocations and expiries are appended to the patient's
+/// permission history with block and timestamp.
+#[test]
+fn permission_history_is_logged() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Now::set(1_000);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::ALL,
+            5
+        ));
+
+        System::set_block_number(2);
+        Now::set(2_000);
+        assert_ok!(MedicalPermissions::revoke_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::WRITE
+        ));
+
+        // Revoking a scope the doctor does not hold is not logged.
+        assert_ok!(MedicalPermissions::revoke_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::WRITE
+        ));
+
+        System::set_block_number(5);
+        MedicalPermissions::on_initialize(5);
+
+        let log = MedicalPermissions::permission_log(&patient, 0, 10);
+        assert!(log.iter().all(|(_, entry)| entry.subject == LogSubject::Doctor(doctor)));
+        let summary: Vec<_> = log
+            .iter()
+            .map(|(seq, entry)| (*seq, entry.action, entry.scope, entry.block))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, PermissionAction::Granted, PermissionScope::ALL, 1),
+                (1, PermissionAction::Revoked, PermissionScope::WRITE, 2),
+                (2, PermissionAction::Expired, PermissionScope::READ, 5),
+            ]
+        );
+        assert_eq!(log[0].1.timestamp, 1_000);
+        assert_eq!(log[1].1.timestamp, 2_000);
+
+        // Other patients have their own log.
+        assert!(MedicalPermissions::permission_log(&doctor, 0, 10).is_empty());
+    });
+}
+
+/// Ensures the log keeps only the newest `MaxLogEntries` entries and
+/// supports pagination by sequence number.
+#[test]
+fn permission_history_is_bounded_and_paginated() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+
+        for doctor in 2..=6 {
+            assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+        }
+
+        // MaxLogEntries = 3: the two oldest entries were dropped.
+        assert_eq!(MedicalPermissions::permission_log_len(patient), 5);
+        assert!(!PermissionLog::<Test>::contains_key(patient, 1));
+
+        let doctors = |from, limit| -> Vec<(u64, LogSubject<u64>)> {
+            MedicalPermissions::permission_log(&patient, from, limit)
+                .into_iter()
+                .map(|(seq, entry)| (seq, entry.subject))
+                .collect()
+        };
+        let doctor = LogSubject::Doctor;
+        assert_eq!(doctors(0, 10), vec![(2, doctor(4)), (3, doctor(5)), (4, doctor(6))]);
+        assert_eq!(doctors(0, 2), vec![(2, doctor(4)), (3, doctor(5))]);
+        assert_eq!(doctors(4, 2), vec![(4, doctor(6))]);
+        assert!(doctors(5, 2).is_empty());
+    });
+}
+
+/// Ensures a deposit is held once per grant and released when the grant is
+/// fully revoked or expires.
+#[test]
+fn grant_deposit_is_held_and_released() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::READ
+        ));
+        assert_eq!(Balances::reserved_balance(patient), PERMISSION_DEPOSIT);
+        assert_eq!(MedicalPermissions::grant_deposits(patient, doctor), Some(PERMISSION_DEPOSIT));
+
+        // Extending the scope does not take a second deposit.
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::WRITE
+        ));
+        assert_eq!(Balances::reserved_balance(patient), PERMISSION_DEPOSIT);
+
+        // A partial revoke keeps the deposit, a full one releases it.
+        assert_ok!(MedicalPermissions::revoke_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::WRITE
+        ));
+        assert_eq!(Balances::reserved_balance(patient), PERMISSION_DEPOSIT);
+        assert_ok!(MedicalPermissions::revoke_access(RuntimeOrigin::signed(patient), doctor));
+        assert_eq!(Balances::reserved_balance(patient), 0);
+        assert_eq!(Balances::free_balance(patient), INITIAL_BALANCE);
+        assert!(MedicalPermissions::grant_deposits(patient, doctor).is_none());
+
+        // Expiry releases the deposit too.
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::READ,
+            5
+        ));
+        assert_eq!(Balances::reserved_balance(patient), PERMISSION_DEPOSIT);
+        System::set_block_number(5);
+        MedicalPermissions::on_initialize(5);
+        assert_eq!(Balances::reserved_balance(patient), 0);
+
+        // An account that cannot cover the deposit cannot grant access.
+        let unfunded = 4;
+        assert!(MedicalPermissions::grant_access(RuntimeOrigin::signed(unfunded), doctor).is_err());
+        assert!(MedicalPermissions::permissions(unfunded, doctor).is_empty());
+    });
+}
+
+/// Ensures `OnAccessRevoked` fires only when read access is lost.
+#[test]
+fn losing_read_access_notifies_hook() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+
+        // Losing write access only is not reported.
+        assert_ok!(MedicalPermissions::revoke_access_scoped(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::WRITE
+        ));
+        assert!(RevokedReads::get().is_empty());
+
+        assert_ok!(MedicalPermissions::revoke_access(RuntimeOrigin::signed(patient), doctor));
+        assert_eq!(RevokedReads::get(), vec![(patient, doctor)]);
+
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(patient),
+            3,
+            PermissionScope::READ,
+            5
+        ));
+        System::set_block_number(5);
+        MedicalPermissions::on_initialize(5);
+        assert_eq!(RevokedReads::get(), vec![(patient, doctor), (patient, 3)]);
+    });
+}
+
+/// Ensures break-glass access grants short-lived read access and leaves an
+/// incident behind.
+#[test]
+fn emergency_access_grants_temporary_read_access() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Now::set(1_000);
+
+        let patient = 1;
+        let doctor = 2;
+        let justification = sp_core::H256::repeat_byte(9);
+
+        assert_noop!(
+            MedicalPermissions::emergency_access(
+                RuntimeOrigin::signed(patient),
+                patient,
+                justification
+            ),
+            Error::<Test>::SelfPermissionNotAllowed
+        );
+        assert_noop!(
+            MedicalPermissions::emergency_access(
+                RuntimeOrigin::signed(UNREGISTERED_DOCTOR),
+                patient,
+                justification
+            ),
+            Error::<Test>::NotEmergencyAccount
+        );
+
+        assert_ok!(MedicalPermissions::emergency_access(
+            RuntimeOrigin::signed(doctor),
+            patient,
+            justification
+        ));
+        System::assert_last_event(
+            Event::EmergencyAccessGranted {
+                patient,
+                accessor: doctor,
+                incident: 0,
+                justification_hash: justification,
+                expires_at: 6,
+            }
+            .into(),
+        );
+
+        // Read-only, and the patient's grants are untouched.
+        assert!(<MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_read(
+            &patient, &doctor
+        ));
+        assert!(!<MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_write(
+            &patient, &doctor
+        ));
+        assert!(!crate::Permissions::<Test>::contains_key(patient, doctor));
+
+        let incident = MedicalPermissions::incidents(patient, 0).unwrap();
+        assert_eq!(incident.accessor, doctor);
+        assert_eq!(incident.timestamp, 1_000);
+        assert_eq!(
+            PermissionLog::<Test>::get(patient, 0).unwrap().action,
+            PermissionAction::EmergencyAccess
+        );
+
+        // The access stops being honoured at its expiry block, and is then
+        // removed lazily by anyone.
+        assert_noop!(
+            MedicalPermissions::clear_emergency_access(RuntimeOrigin::signed(3), patient, doctor),
+            Error::<Test>::EmergencyAccessActive
+        );
+        System::set_block_number(6);
+        assert!(!<MedicalPermissions as MedicalPermissionsVerifier<u64>>::has_access(
+            &patient, &doctor
+        ));
+        MedicalPermissions::on_initialize(6);
+        assert!(MedicalPermissions::emergency_grants(patient, doctor).is_some());
+        assert_ok!(MedicalPermissions::clear_emergency_access(
+            RuntimeOrigin::signed(3),
+            patient,
+            doctor
+        ));
+        System::assert_last_event(
+            Event::EmergencyAccessExpired { patient, accessor: doctor }.into(),
+        );
+        assert_noop!(
+            MedicalPermissions::clear_emergency_access(RuntimeOrigin::signed(3), patient, doctor),
+            Error::<Test>::EmergencyAccessNotFound
+        );
+        assert!(MedicalPermissions::emergency_grants(patient, doctor).is_none());
+        assert_eq!(RevokedReads::get(), vec![(patient, doctor)]);
+
+        // The incident outlives the access.
+        assert_eq!(MedicalPermissions::incidents(patient, 0), Some(incident));
+    });
+}
+
+/// Ensures break-glass access is refused once the patient's incident ids are
+/// exhausted, instead of overwriting the last incident.
+#[test]
+fn emergency_access_never_overwrites_incidents() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (patient, doctor) = (1, 2);
+        let justification = sp_core::H256::repeat_byte(9);
+
+        crate::NextIncidentId::<Test>::insert(patient, u32::MAX - 1);
+        assert_ok!(MedicalPermissions::emergency_access(
+            RuntimeOrigin::signed(doctor),
+            patient,
+            justification
+        ));
+        let incident = MedicalPermissions::incidents(patient, u32::MAX - 1).unwrap();
+
+        assert_noop!(
+            MedicalPermissions::emergency_access(RuntimeOrigin::signed(3), patient, justification),
+            Error::<Test>::TooManyIncidents
+        );
+        assert_eq!(MedicalPermissions::incidents(patient, u32::MAX - 1), Some(incident));
+    });
+}
+
+/// Ensures only authorized accounts can break the glass without being
+/// registered doctors.
+#[test]
+fn emergency_accounts_are_managed_by_authority() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let ambulance = UNREGISTERED_DOCTOR;
+
+        assert_noop!(
+            MedicalPermissions::add_emergency_account(RuntimeOrigin::signed(1), ambulance),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(MedicalPermissions::add_emergency_account(RuntimeOrigin::root(), ambulance));
+        assert_noop!(
+            MedicalPermissions::add_emergency_account(RuntimeOrigin::root(), ambulance),
+            Error::<Test>::EmergencyAccountExists
+        );
+
+        assert_ok!(MedicalPermissions::emergency_access(
+            RuntimeOrigin::signed(ambulance),
+            1,
+            sp_core::H256::zero()
+        ));
+
+        assert_ok!(MedicalPermissions::remove_emergency_account(RuntimeOrigin::root(), ambulance));
+        System::assert_last_event(Event::EmergencyAccountRemoved { account: ambulance }.into());
+        assert_noop!(
+            MedicalPermissions::emergency_access(
+                RuntimeOrigin::signed(ambulance),
+                1,
+                sp_core::H256::zero()
+            ),
+            Error::<Test>::NotEmergencyAccount
+        );
+        assert_noop!(
+            MedicalPermissions::remove_emergency_account(RuntimeOrigin::root(), ambulance),
+            Error::<Test>::EmergencyAccountNotFound
+        );
+    });
+}
+
+/// Ensures a patient can contest an incident, which ends the access.
+#[test]
+fn patient_can_contest_incident() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+        let reason = sp_core::H256::repeat_byte(3);
+
+        assert_ok!(MedicalPermissions::emergency_access(
+            RuntimeOrigin::signed(doctor),
+            patient,
+            sp_core::H256::zero()
+        ));
+        let incident = MedicalPermissions::incidents(patient, 0).unwrap();
+
+        assert_noop!(
+            MedicalPermissions::contest_incident(RuntimeOrigin::signed(patient), 1, reason),
+            Error::<Test>::IncidentNotFound
+        );
+        // Incidents are per patient.
+        assert_noop!(
+            MedicalPermissions::contest_incident(RuntimeOrigin::signed(3), 0, reason),
+            Error::<Test>::IncidentNotFound
+        );
+
+        System::set_block_number(2);
+        assert_ok!(MedicalPermissions::contest_incident(RuntimeOrigin::signed(patient), 0, reason));
+        System::assert_last_event(
+            Event::IncidentContested { patient, accessor: doctor, incident: 0, reason_hash: reason }
+                .into(),
+        );
+        assert!(!<MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_read(
+            &patient, &doctor
+        ));
+        assert_eq!(RevokedReads::get(), vec![(patient, doctor)]);
+
+        assert_noop!(
+            MedicalPermissions::contest_incident(RuntimeOrigin::signed(patient), 0, reason),
+            Error::<Test>::IncidentAlreadyContested
+        );
+
+        // The incident is kept as is, with the contest next to it.
+        let contest = crate::IncidentContest { reason_hash: reason, contested_at: 2 };
+        assert_eq!(
+            MedicalPermissions::emergency_incidents(&patient, 0, 10),
+            vec![(0, incident, Some(contest))]
+        );
+
+        // The stale queue entry is skipped at the original expiry block.
+        System::set_block_number(6);
+        MedicalPermissions::on_initialize(6);
+        assert_eq!(RevokedReads::get(), vec![(patient, doctor)]);
+    });
+}
+
+/// Ensures a guardian can manage grants and read on the patient's behalf,
+/// with every action attributed, until the guardianship ends.
+#[test]
+fn guardian_acts_on_behalf_of_patient() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+        let guardian = 3;
+
+        assert_ok!(MedicalPermissions::appoint_guardian(
+            RuntimeOrigin::signed(patient),
+            guardian,
+            Some(10)
+        ));
+        System::assert_last_event(
+            Event::GuardianAppointed {
+                patient,
+                guardian,
+                appointed_by: GuardianAppointer::Patient,
+                until: Some(10),
+            }
+            .into(),
+        );
+
+        // The guardian reads, but does not write.
+        assert!(<MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_read(
+            &patient, &guardian
+        ));
+        assert!(!<MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_write(
+            &patient, &guardian
+        ));
+
+        assert_ok!(MedicalPermissions::guardian_grant_access(
+            RuntimeOrigin::signed(guardian),
+            patient,
+            doctor,
+            PermissionScope::READ,
+            None
+        ));
+        System::assert_has_event(
+            Event::AccessGranted { patient, doctor, scope: PermissionScope::READ, expires_at: None }
+                .into(),
+        );
+        System::assert_last_event(
+            Event::GuardianActed {
+                patient,
+                guardian,
+                doctor,
+                action: PermissionAction::Granted,
+                scope: PermissionScope::READ,
+            }
+            .into(),
+        );
+        assert_eq!(MedicalPermissions::permissions(patient, doctor), PermissionScope::READ);
+        // The deposit is still held from the patient.
+        assert_eq!(Balances::free_balance(patient), INITIAL_BALANCE - PERMISSION_DEPOSIT);
+
+        assert_ok!(MedicalPermissions::guardian_revoke_access(
+            RuntimeOrigin::signed(guardian),
+            patient,
+            doctor,
+            PermissionScope::READ
+        ));
+        System::assert_last_event(
+            Event::GuardianActed {
+                patient,
+                guardian,
+                doctor,
+                action: PermissionAction::Revoked,
+                scope: PermissionScope::READ,
+            }
+            .into(),
+        );
+        assert!(!crate::Permissions::<Test>::contains_key(patient, doctor));
+
+        // Doctors are not guardians.
+        assert_noop!(
+            MedicalPermissions::guardian_grant_access(
+                RuntimeOrigin::signed(doctor),
+                patient,
+                doctor,
+                PermissionScope::READ,
+                None
+            ),
+            Error::<Test>::NotGuardian
+        );
+
+        // The guardianship ends at block 10.
+        System::set_block_number(10);
+        assert!(!<MedicalPermissions as MedicalPermissionsVerifier<u64>>::has_access(
+            &patient, &guardian
+        ));
+        assert_noop!(
+            MedicalPermissions::guardian_grant_access(
+                RuntimeOrigin::signed(guardian),
+                patient,
+                doctor,
+                PermissionScope::READ,
+                None
+            ),
+            Error::<Test>::NotGuardian
+        );
+    });
+}
+
+/// Ensures the registrar appoints guardians for minors and only the
+/// registrar can remove them.
+#[test]
+fn registrar_manages_guardians_of_minors() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let minor = 1;
+        let guardian = 3;
+
+        assert_noop!(
+            MedicalPermissions::appoint_guardian_for(
+                RuntimeOrigin::signed(guardian),
+                minor,
+                guardian,
+                None
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            MedicalPermissions::appoint_guardian_for(RuntimeOrigin::root(), minor, minor, None),
+            Error::<Test>::SelfPermissionNotAllowed
+        );
+        assert_noop!(
+            MedicalPermissions::appoint_guardian_for(
+                RuntimeOrigin::root(),
+                minor,
+                guardian,
+                Some(1)
+            ),
+            Error::<Test>::ExpiryInPast
+        );
+
+        assert_ok!(MedicalPermissions::appoint_guardian_for(
+            RuntimeOrigin::root(),
+            minor,
+            guardian,
+            None
+        ));
+        assert_eq!(
+            MedicalPermissions::guardians_of(&minor),
+            vec![(
+                guardian,
+                Guardianship { appointed_by: GuardianAppointer::Registrar, since: 1, until: None }
+            )]
+        );
+
+        // The minor can neither dismiss a guardian appointed by the registrar
+        // nor take it over by appointing it again.
+        assert_noop!(
+            MedicalPermissions::remove_guardian(RuntimeOrigin::signed(minor), guardian),
+            Error::<Test>::GuardianNotFound
+        );
+        assert_noop!(
+            MedicalPermissions::appoint_guardian(RuntimeOrigin::signed(minor), guardian, Some(5)),
+            Error::<Test>::GuardianAppointedByOther
+        );
+
+        assert_ok!(MedicalPermissions::remove_guardian_for(RuntimeOrigin::root(), minor, guardian));
+        System::assert_last_event(Event::GuardianRemoved { patient: minor, guardian }.into());
+        assert!(MedicalPermissions::guardians(minor, guardian).is_none());
+        assert_eq!(RevokedReads::get(), vec![(minor, guardian)]);
+    });
+}
+
+/// Ensures a guardianship that reaches its end date revokes the guardian's
+/// read access, and that renewing it moves the end date.
+#[test]
+fn guardianship_ends_at_until() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let guardian = 3;
+
+        assert_ok!(MedicalPermissions::appoint_guardian(
+            RuntimeOrigin::signed(patient),
+            guardian,
+            Some(5)
+        ));
+        // Renewed: the entry at block 5 is replaced by one at block 8.
+        assert_ok!(MedicalPermissions::appoint_guardian(
+            RuntimeOrigin::signed(patient),
+            guardian,
+            Some(8)
+        ));
+        assert!(!crate::GuardianExpiryQueue::<Test>::contains_key(5, (patient, guardian)));
+
+        System::set_block_number(5);
+        MedicalPermissions::on_initialize(5);
+        assert!(RevokedReads::get().is_empty());
+
+        System::set_block_number(8);
+        MedicalPermissions::on_initialize(8);
+        System::assert_last_event(Event::GuardianshipEnded { patient, guardian }.into());
+        assert_eq!(RevokedReads::get(), vec![(patient, guardian)]);
+        assert!(!<MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_read(
+            &patient, &guardian
+        ));
+        // The ended guardianship is kept for auditing.
+        assert_eq!(MedicalPermissions::guardians(patient, guardian).unwrap().until, Some(8));
+        assert!(crate::GuardianExpiryQueue::<Test>::iter().next().is_none());
+    });
+}
+
+/// Ensures grants to an institution are honoured for its members only.
+#[test]
+fn institution_grants_resolve_membership() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let doctor = 2;
+        let admin = 3;
+        let verifier_can_read = |doctor: u64| {
+            <MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_read(&patient, &doctor)
+        };
+
+        assert_noop!(
+            MedicalPermissions::register_institution(
+                RuntimeOrigin::signed(admin),
+                admin,
+                sp_core::H256::zero()
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(MedicalPermissions::register_institution(
+            RuntimeOrigin::root(),
+            admin,
+            sp_core::H256::zero()
+        ));
+        System::assert_last_event(Event::InstitutionRegistered { institution: 0, admin }.into());
+
+        assert_noop!(
+            MedicalPermissions::add_member(RuntimeOrigin::signed(patient), 0, doctor),
+            Error::<Test>::NotInstitutionAdmin
+        );
+        assert_noop!(
+            MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, UNREGISTERED_DOCTOR),
+            Error::<Test>::DoctorNotRegistered
+        );
+        assert_ok!(MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, doctor));
+        System::assert_last_event(Event::MemberAdded { institution: 0, doctor }.into());
+        assert_noop!(
+            MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, doctor),
+            Error::<Test>::AlreadyMember
+        );
+
+        assert_noop!(
+            MedicalPermissions::grant_institution_access(
+                RuntimeOrigin::signed(patient),
+                1,
+                PermissionScope::READ
+            ),
+            Error::<Test>::InstitutionNotFound
+        );
+        assert_ok!(MedicalPermissions::grant_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::READ
+        ));
+        System::assert_last_event(
+            Event::InstitutionAccessGranted {
+                patient,
+                institution: 0,
+                scope: PermissionScope::READ,
+            }
+            .into(),
+        );
+
+        // Members read through the institution; other accounts do not.
+        assert!(verifier_can_read(doctor));
+        assert!(!verifier_can_read(4));
+        assert!(!<MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_write(
+            &patient, &doctor
+        ));
+        assert_eq!(
+            <MedicalPermissions as MedicalPermissionsVerifier<u64>>::acting_institution(
+                &patient, &doctor
+            ),
+            Some(0)
+        );
+
+        // A grant of the doctor's own takes precedence for attribution.
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+        assert_eq!(
+            <MedicalPermissions as MedicalPermissionsVerifier<u64>>::acting_institution(
+                &patient, &doctor
+            ),
+            None
+        );
+        assert_ok!(MedicalPermissions::revoke_access(RuntimeOrigin::signed(patient), doctor));
+        RevokedReads::set(Vec::new());
+
+        // Leaving the care team ends the access.
+        assert_ok!(MedicalPermissions::remove_member(RuntimeOrigin::signed(admin), 0, doctor));
+        System::assert_last_event(Event::MemberRemoved { institution: 0, doctor }.into());
+        assert!(!verifier_can_read(doctor));
+        assert_eq!(RevokedReads::get(), vec![(patient, doctor)]);
+        assert!(MedicalPermissions::memberships(doctor).is_empty());
+        assert_noop!(
+            MedicalPermissions::remove_member(RuntimeOrigin::signed(admin), 0, doctor),
+            Error::<Test>::NotMember
+        );
+    });
+}
+
+/// Ensures revoking an institution's read access reports its members and
+/// that memberships are bounded.
+#[test]
+fn institution_revocation_and_bounds() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let admin = 3;
+
+        for _ in 0..3 {
+            assert_ok!(MedicalPermissions::register_institution(
+                RuntimeOrigin::root(),
+                admin,
+                sp_core::H256::zero()
+            ));
+        }
+
+        // MaxInstitutionMembers = 2.
+        assert_ok!(MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, 2));
+        assert_ok!(MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, 4));
+        assert_noop!(
+            MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, 5),
+            Error::<Test>::TooManyMembers
+        );
+
+        // MaxMembershipsPerDoctor = 2.
+        assert_ok!(MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 1, 2));
+        assert_noop!(
+            MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 2, 2),
+            Error::<Test>::TooManyMemberships
+        );
+
+        assert_ok!(MedicalPermissions::grant_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::ALL
+        ));
+        // Doctor 4 keeps reading through a grant of their own.
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(patient),
+            4,
+            PermissionScope::READ
+        ));
+
+        assert_ok!(MedicalPermissions::revoke_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::READ
+        ));
+        System::assert_last_event(
+            Event::InstitutionAccessRevoked {
+                patient,
+                institution: 0,
+                scope: PermissionScope::WRITE,
+            }
+            .into(),
+        );
+        assert_eq!(RevokedReads::get(), vec![(patient, 2)]);
+        assert!(<MedicalPermissions as MedicalPermissionsVerifier<u64>>::can_write(&patient, &2));
+    });
+}
+
+/// Ensures institution grants hold a deposit, are logged and are bounded per
+/// institution.
+#[test]
+fn institution_grants_hold_deposit_and_are_logged() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let patient = 1;
+        let admin = 3;
+        assert_ok!(MedicalPermissions::register_institution(
+            RuntimeOrigin::root(),
+            admin,
+            sp_core::H256::zero()
+        ));
+
+        assert_ok!(MedicalPermissions::grant_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::READ
+        ));
+        // Extending the grant holds no second deposit.
+        assert_ok!(MedicalPermissions::grant_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::WRITE
+        ));
+        assert_eq!(Balances::reserved_balance(patient), PERMISSION_DEPOSIT);
+        assert_eq!(crate::InstitutionPatientCount::<Test>::get(0), 1);
+
+        // MaxInstitutionPatients = 2.
+        assert_ok!(MedicalPermissions::grant_institution_access(
+            RuntimeOrigin::signed(2),
+            0,
+            PermissionScope::READ
+        ));
+        assert_noop!(
+            MedicalPermissions::grant_institution_access(
+                RuntimeOrigin::signed(4),
+                0,
+                PermissionScope::READ
+            ),
+            Error::<Test>::TooManyInstitutionPatients
+        );
+
+        assert_ok!(MedicalPermissions::revoke_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::WRITE
+        ));
+        assert_eq!(Balances::reserved_balance(patient), PERMISSION_DEPOSIT);
+        assert_ok!(MedicalPermissions::revoke_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::ALL
+        ));
+        assert_eq!(Balances::reserved_balance(patient), 0);
+        assert!(!crate::InstitutionPatients::<Test>::contains_key(0, patient));
+        assert_eq!(crate::InstitutionPatientCount::<Test>::get(0), 1);
+
+        let log: Vec<_> = MedicalPermissions::permission_log(&patient, 0, 10)
+            .into_iter()
+            .map(|(_, entry)| (entry.subject, entry.action, entry.scope))
+            .collect();
+        let institution = || LogSubject::Institution(0);
+        assert_eq!(
+            log,
+            vec![
+                (institution(), PermissionAction::InstitutionGranted(0), PermissionScope::READ),
+                (institution(), PermissionAction::InstitutionGranted(0), PermissionScope::WRITE),
+                (institution(), PermissionAction::InstitutionRevoked(0), PermissionScope::WRITE),
+                (institution(), PermissionAction::InstitutionRevoked(0), PermissionScope::READ),
+            ]
+        );
+    });
+}
+
+/// Ensures losing a direct grant is not reported as lost read access while
+/// the doctor still reads through an institution.
+#[test]
+fn revoking_direct_grant_keeps_institution_access_silent() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (patient, doctor, admin) = (1, 2, 3);
+        assert_ok!(MedicalPermissions::register_institution(
+            RuntimeOrigin::root(),
+            admin,
+            sp_core::H256::zero()
+        ));
+        assert_ok!(MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, doctor));
+        assert_ok!(MedicalPermissions::grant_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::READ
+        ));
+
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+        assert_ok!(MedicalPermissions::revoke_access(RuntimeOrigin::signed(patient), doctor));
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(patient),
+            doctor,
+            PermissionScope::READ,
+            5
+        ));
+        MedicalPermissions::on_initialize(5);
+        assert!(!crate::Permissions::<Test>::contains_key(patient, doctor));
+
+        assert!(MedicalPermissions::can_read(&patient, &doctor));
+        assert!(RevokedReads::get().is_empty());
+
+        // `revoke_all_access` removes the institution grant too.
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+        assert_ok!(MedicalPermissions::revoke_all_access(RuntimeOrigin::signed(patient)));
+        assert!(!MedicalPermissions::can_read(&patient, &doctor));
+        assert_eq!(RevokedReads::get(), vec![(patient, doctor)]);
+    });
+}
+
+/// Ensures the v8 migration indexes existing institution grants.
+#[test]
+fn migration_v7_to_v8_indexes_institution_grants() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(7).put::<MedicalPermissions>();
+        crate::InstitutionPermissions::<Test>::insert(1, 0, PermissionScope::READ);
+        crate::InstitutionPermissions::<Test>::insert(2, 0, PermissionScope::ALL);
+        crate::InstitutionPermissions::<Test>::insert(1, 1, PermissionScope::READ);
+
+        crate::migrations::v8::MigrateV7ToV8::<Test>::on_runtime_upgrade();
+
+        assert!(crate::InstitutionPatients::<Test>::contains_key(0, 1));
+        assert!(crate::InstitutionPatients::<Test>::contains_key(0, 2));
+        assert!(crate::InstitutionPatients::<Test>::contains_key(1, 1));
+        assert_eq!(crate::InstitutionPatientCount::<Test>::get(0), 2);
+        assert_eq!(crate::InstitutionPatientCount::<Test>::get(1), 1);
+        assert_eq!(crate::InstitutionGrantCount::<Test>::get(1), 2);
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 8);
+    });
+}
+
+/// Ensures the doctor-keyed reverse index follows grants, revocations and
+/// expiries, and that both listings are paginated by key.
+#[test]
+fn reverse_index_lists_patients_and_doctors() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Now::set(1_000);
+
+        let doctor = 2;
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(1), doctor));
+
+        System::set_block_number(2);
+        Now::set(2_000);
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(3),
+            doctor,
+            PermissionScope::READ,
+            10
+        ));
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(1), 3));
+
+        let first = GrantInfo {
+            account: 1,
+            scope: PermissionScope::ALL,
+            granted: GrantTimestamp { block: 1, timestamp: 1_000 },
+            expires_at: None,
+        };
+        let second = GrantInfo {
+            account: 3,
+            scope: PermissionScope::READ,
+            granted: GrantTimestamp { block: 2, timestamp: 2_000 },
+            expires_at: Some(10),
+        };
+        let sorted = |mut grants: Vec<GrantInfo<u64, u64, u64>>| {
+            grants.sort_by_key(|grant| grant.account);
+            grants
+        };
+        let page = MedicalPermissions::patients_of(&doctor, None, 10);
+        assert_eq!(page.next, None);
+        assert_eq!(sorted(page.items), vec![first.clone(), second.clone()]);
+
+        // Each page resumes after the last patient walked.
+        let page = MedicalPermissions::patients_of(&doctor, None, 1);
+        assert_eq!(page.items.len(), 1);
+        let rest = MedicalPermissions::patients_of(&doctor, page.next, 1);
+        assert_eq!(page.next, Some(page.items[0].account));
+        assert_eq!(rest.next, None);
+        assert_eq!(sorted([page.items, rest.items].concat()), vec![first, second]);
+
+        let mut doctors: Vec<_> = MedicalPermissions::doctors_of(&1, None, 10)
+            .items
+            .into_iter()
+            .map(|grant| (grant.account, grant.granted.block))
+            .collect();
+        doctors.sort();
+        assert_eq!(doctors, vec![(doctor, 1), (3, 2)]);
+
+        // Extending a grant keeps its original time.
+        System::set_block_number(3);
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(1),
+            doctor,
+            PermissionScope::READ
+        ));
+        assert_eq!(
+            MedicalPermissions::doctor_patients(doctor, 1),
+            Some(GrantTimestamp { block: 1, timestamp: 1_000 })
+        );
+
+        // Revocation and expiry remove the pair from the index.
+        assert_ok!(MedicalPermissions::revoke_access(RuntimeOrigin::signed(1), doctor));
+        assert!(MedicalPermissions::doctor_patients(doctor, 1).is_none());
+
+        System::set_block_number(10);
+        assert!(MedicalPermissions::patients_of(&doctor, None, 10).items.is_empty());
+        MedicalPermissions::on_initialize(10);
+        assert!(MedicalPermissions::doctor_patients(doctor, 3).is_none());
+    });
+}
+
+/// Ensures grants from storage version 1 are added to the reverse index.
+#[test]
+fn migration_v1_to_v2_builds_reverse_index() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(1).put::<MedicalPermissions>();
+        crate::Permissions::<Test>::insert(1, 2, PermissionScope::READ);
+
+        crate::migrations::v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(MedicalPermissions::doctor_patients(2, 1), Some(GrantTimestamp::default()));
+        assert_eq!(MedicalPermissions::patients_of(&2, None, 10).items.len(), 1);
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 2);
+    });
+}
+
+/// Ensures revoking all access works in batches of
+/// `MaxRevocationsPerCall`, doctors first and then institutions, releasing
+/// deposits and notifying read holders.
+#[test]
+fn revoke_all_access_works_in_batches() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1;
+        let admin = 3;
+
+        for doctor in [2, 3, 4] {
+            assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+        }
+        assert_ok!(MedicalPermissions::register_institution(
+            RuntimeOrigin::root(),
+            admin,
+            sp_core::H256::zero()
+        ));
+        assert_ok!(MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, 2));
+        assert_ok!(MedicalPermissions::grant_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::READ
+        ));
+        assert_eq!(MedicalPermissions::grant_count(patient), 3);
+        assert_eq!(Balances::reserved_balance(patient), 4 * PERMISSION_DEPOSIT);
+
+        assert_ok!(MedicalPermissions::revoke_all_access(RuntimeOrigin::signed(patient)));
+        System::assert_last_event(
+            Event::BulkAccessRevoked { patient, revoked: 2, remaining: 2 }.into(),
+        );
+        assert_eq!(MedicalPermissions::doctors_of(&patient, None, 10).items.len(), 1);
+
+        assert_ok!(MedicalPermissions::revoke_all_access(RuntimeOrigin::signed(patient)));
+        System::assert_last_event(
+            Event::BulkAccessRevoked { patient, revoked: 2, remaining: 0 }.into(),
+        );
+
+        for doctor in [2, 3, 4] {
+            assert!(!MedicalPermissions::has_access(&patient, &doctor));
+            assert!(MedicalPermissions::doctor_patients(doctor, patient).is_none());
+        }
+        assert!(MedicalPermissions::institution_permissions(patient, 0).is_empty());
+        assert_eq!(MedicalPermissions::grant_count(patient), 0);
+        assert_eq!(crate::InstitutionGrantCount::<Test>::get(patient), 0);
+        assert_eq!(Balances::reserved_balance(patient), 0);
+        // Doctor 2 is reported again once the institution grant is gone.
+        assert_eq!(RevokedReads::get().len(), 4);
+        assert_eq!(RevokedReads::get().last(), Some(&(patient, 2)));
+    });
+}
+
+/// Ensures the grant count follows grants, revocations and expiries.
+#[test]
+fn grant_count_tracks_grants() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1;
+
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(patient),
+            2,
+            PermissionScope::READ
+        ));
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(patient),
+            2,
+            PermissionScope::WRITE
+        ));
+        assert_ok!(MedicalPermissions::grant_temporary_access(
+            RuntimeOrigin::signed(patient),
+            3,
+            PermissionScope::READ,
+            5
+        ));
+        assert_eq!(MedicalPermissions::grant_count(patient), 2);
+
+        assert_ok!(MedicalPermissions::revoke_access_scoped(
+            RuntimeOrigin::signed(patient),
+            2,
+            PermissionScope::READ
+        ));
+        assert_eq!(MedicalPermissions::grant_count(patient), 2);
+        assert_ok!(MedicalPermissions::revoke_access(RuntimeOrigin::signed(patient), 2));
+        assert_eq!(MedicalPermissions::grant_count(patient), 1);
+
+        System::set_block_number(5);
+        MedicalPermissions::on_initialize(5);
+        assert_eq!(MedicalPermissions::grant_count(patient), 0);
+    });
+}
+
+/// Ensures freezing hides every grant without deleting it, and unfreezing
+/// restores them.
+#[test]
+fn freeze_access_suspends_and_restores_grants() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1;
+        let doctor = 2;
+        let admin = 3;
+        let member = 4;
+
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), doctor));
+        assert_ok!(MedicalPermissions::register_institution(
+            RuntimeOrigin::root(),
+            admin,
+            sp_core::H256::zero()
+        ));
+        assert_ok!(MedicalPermissions::add_member(RuntimeOrigin::signed(admin), 0, member));
+        assert_ok!(MedicalPermissions::grant_institution_access(
+            RuntimeOrigin::signed(patient),
+            0,
+            PermissionScope::READ
+        ));
+
+        assert_ok!(MedicalPermissions::freeze_access(RuntimeOrigin::signed(patient)));
+        System::assert_last_event(Event::AccessFrozen { patient, grants: 2 }.into());
+        assert_eq!(MedicalPermissions::frozen_since(patient), Some(1));
+        assert_noop!(
+            MedicalPermissions::freeze_access(RuntimeOrigin::signed(patient)),
+            Error::<Test>::AlreadyFrozen
+        );
+
+        assert!(!MedicalPermissions::has_access(&patient, &doctor));
+        assert!(!MedicalPermissions::has_access(&patient, &member));
+        assert!(MedicalPermissions::acting_institution(&patient, &member).is_none());
+        assert!(MedicalPermissions::has_access(&patient, &patient));
+        assert_eq!(MedicalPermissions::permissions(patient, doctor), PermissionScope::ALL);
+
+        assert_ok!(MedicalPermissions::unfreeze_access(RuntimeOrigin::signed(patient)));
+        System::assert_last_event(Event::AccessUnfrozen { patient, grants: 2 }.into());
+        assert_noop!(
+            MedicalPermissions::unfreeze_access(RuntimeOrigin::signed(patient)),
+            Error::<Test>::NotFrozen
+        );
+
+        assert!(MedicalPermissions::has_access(&patient, &doctor));
+        assert!(MedicalPermissions::has_access(&patient, &member));
+        assert_eq!(MedicalPermissions::acting_institution(&patient, &member), Some(0));
+    });
+}
+
+/// Ensures grants from storage version 2 are counted per patient.
+#[test]
+fn migration_v2_to_v3_counts_grants() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(2).put::<MedicalPermissions>();
+        crate::Permissions::<Test>::insert(1, 2, PermissionScope::READ);
+        crate::Permissions::<Test>::insert(1, 3, PermissionScope::ALL);
+        crate::Permissions::<Test>::insert(2, 3, PermissionScope::WRITE);
+
+        crate::migrations::v3::MigrateV2ToV3::<Test>::on_runtime_upgrade();
+
+        assert_eq!(MedicalPermissions::grant_count(1), 2);
+        assert_eq!(MedicalPermissions::grant_count(2), 1);
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 3);
+    });
+}
+
+/// Ensures the per-block expiry lists of storage version 3 become one queue
+/// entry per grant, dropping stale entries.
+#[test]
+fn migration_v3_to_v4_requeues_expiries() {
+    use codec::Encode;
+    use frame_support::{
+        storage::{unhashed, StoragePrefixedMap},
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        StorageHasher, Twox64Concat,
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(4);
+        StorageVersion::new(3).put::<MedicalPermissions>();
+        crate::Expirations::<Test>::insert(1, 2, 7);
+        crate::Expirations::<Test>::insert(1, 3, 9);
+        // `(1, 3)` was re-scheduled from block 7 to block 9.
+        for (at, queue) in [(7u64, vec![(1u64, 2u64), (1, 3)]), (9, vec![(1, 3)])] {
+            let mut key = ExpiryQueue::<Test>::final_prefix().to_vec();
+            key.extend(Twox64Concat::hash(&at.encode()));
+            unhashed::put(&key, &queue);
+        }
+
+        crate::migrations::v4::MigrateV3ToV4::<Test>::on_runtime_upgrade();
+
+        assert!(ExpiryQueue::<Test>::contains_key(7, (1, 2)));
+        assert!(ExpiryQueue::<Test>::contains_key(9, (1, 3)));
+        assert_eq!(ExpiryQueue::<Test>::iter().count(), 2);
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), 4);
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 4);
+    });
+}
+
+/// Ensures the v6 migration clears the bounded break-glass expiry queue.
+#[test]
+fn migration_v5_to_v6_clears_emergency_queue() {
+    use codec::Encode;
+    use frame_support::{
+        storage::unhashed,
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        StorageHasher, Twox64Concat,
+    };
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(5).put::<MedicalPermissions>();
+        let mut key = frame_support::storage::storage_prefix(
+            b"MedicalPermissions",
+            b"EmergencyExpiryQueue",
+        )
+        .to_vec();
+        key.extend(Twox64Concat::hash(&7u64.encode()));
+        unhashed::put(&key, &vec![(1u64, 2u64)]);
+
+        crate::migrations::v6::MigrateV5ToV6::<Test>::on_runtime_upgrade();
+
+        assert!(!unhashed::exists(&key));
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 6);
+    });
+}
+
+/// Ensures the v7 migration queues existing guardianships at their end date,
+/// or at the current block if it already passed.
+#[test]
+fn migration_v6_to_v7_queues_guardianship_ends() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(10);
+        StorageVersion::new(6).put::<MedicalPermissions>();
+        let guardianship = |until| Guardianship {
+            appointed_by: GuardianAppointer::Patient,
+            since: 1,
+            until,
+        };
+        crate::Guardians::<Test>::insert(1, 2, guardianship(None));
+        crate::Guardians::<Test>::insert(1, 3, guardianship(Some(4)));
+        crate::Guardians::<Test>::insert(1, 4, guardianship(Some(20)));
+
+        crate::migrations::v7::MigrateV6ToV7::<Test>::on_runtime_upgrade();
+
+        let mut queued: Vec<_> = crate::GuardianExpiryQueue::<Test>::iter_keys().collect();
+        queued.sort();
+        assert_eq!(queued, vec![(10, (1, 3)), (20, (1, 4))]);
+        assert_eq!(crate::GuardianExpiryCursor::<Test>::get(), 10);
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 7);
+
+        MedicalPermissions::on_initialize(10);
+        System::assert_last_event(Event::GuardianshipEnded { patient: 1, guardian: 3 }.into());
+    });
+}
+
+/// Ensures the per-block referral expiry lists of storage version 8 become
+/// one queue entry per active referral, dropping closed ones.
+#[test]
+fn migration_v8_to_v9_requeues_referral_expiries() {
+    use codec::Encode;
+    use frame_support::{
+        storage::{unhashed, StoragePrefixedMap},
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        StorageHasher, Twox64Concat,
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let active = propose_referral(PermissionScope::READ, 5, None);
+        assert_ok!(MedicalPermissions::approve_referral(RuntimeOrigin::signed(1), active));
+        let closed = MedicalPermissions::next_referral_id();
+        assert_ok!(MedicalPermissions::propose_referral(
+            RuntimeOrigin::signed(2),
+            1,
+            5,
+            sp_core::H256::repeat_byte(1),
+            PermissionScope::READ,
+            5,
+            None
+        ));
+        assert_ok!(MedicalPermissions::approve_referral(RuntimeOrigin::signed(1), closed));
+        assert_ok!(MedicalPermissions::close_referral(RuntimeOrigin::signed(1), closed));
+
+        System::set_block_number(4);
+        StorageVersion::new(8).put::<MedicalPermissions>();
+        let _ = ReferralExpiryQueue::<Test>::clear(u32::MAX, None);
+        let mut key = ReferralExpiryQueue::<Test>::final_prefix().to_vec();
+        key.extend(Twox64Concat::hash(&6u64.encode()));
+        unhashed::put(&key, &vec![active, closed]);
+
+        crate::migrations::v9::MigrateV8ToV9::<Test>::on_runtime_upgrade();
+
+        assert!(!unhashed::exists(&key));
+        assert_eq!(ReferralExpiryQueue::<Test>::iter_keys().collect::<Vec<_>>(), vec![(6, active)]);
+        assert_eq!(crate::ReferralExpiryCursor::<Test>::get(), 4);
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 9);
+
+        MedicalPermissions::on_initialize(6);
+        System::assert_last_event(
+            Event::ReferralExpired { referral: active, patient: 1, specialist: 4 }.into(),
+        );
+    });
+}
+
+/// Proposes a referral of patient `1` from doctor `2` to specialist `4`,
+/// after patient `1` granted doctor `2` read access. Returns its id.
+fn propose_referral(
+    scope: PermissionScope,
+    duration: u64,
+    records: Option<ReferralRecords>,
+) -> ReferralId {
+    if !MedicalPermissions::permissions(1, 2).can_read() {
+        assert_ok!(MedicalPermissions::grant_access_scoped(
+            RuntimeOrigin::signed(1),
+            2,
+            PermissionScope::READ
+        ));
+    }
+    let referral = MedicalPermissions::next_referral_id();
+    assert_ok!(MedicalPermissions::propose_referral(
+        RuntimeOrigin::signed(2),
+        1,
+        4,
+        sp_core::H256::repeat_byte(1),
+        scope,
+        duration,
+        records
+    ));
+    referral
+}
+
+/// Ensures an approved referral gives the specialist access, apart from the
+/// patient's grants, until it is closed.
+#[test]
+fn referral_grants_access_until_closed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (patient, specialist) = (1, 4);
+
+        let referral = propose_referral(PermissionScope::ALL, 10, None);
+        System::assert_last_event(
+            Event::ReferralProposed { referral, patient, referring_doctor: 2, specialist }.into(),
+        );
+        assert!(!MedicalPermissions::has_access(&patient, &specialist));
+        assert_noop!(
+            MedicalPermissions::approve_referral(RuntimeOrigin::signed(2), referral),
+            Error::<Test>::NotReferralParty
+        );
+
+        assert_ok!(MedicalPermissions::approve_referral(RuntimeOrigin::signed(patient), referral));
+        System::assert_last_event(
+            Event::ReferralApproved { referral, patient, specialist, expires_at: 11 }.into(),
+        );
+        assert!(MedicalPermissions::can_read(&patient, &specialist));
+        assert!(MedicalPermissions::can_write(&patient, &specialist));
+        assert_eq!(MedicalPermissions::permissions(patient, specialist), PermissionScope::NONE);
+        assert_eq!(
+            MedicalPermissions::referrals_of_specialist(&specialist, None, 10).items[0].1.status,
+            ReferralStatus::Active
+        );
+
+        assert_noop!(
+            MedicalPermissions::close_referral(RuntimeOrigin::signed(3), referral),
+            Error::<Test>::NotReferralParty
+        );
+        assert_ok!(MedicalPermissions::close_referral(RuntimeOrigin::signed(specialist), referral));
+        System::assert_last_event(Event::ReferralClosed { referral, closed_by: specialist }.into());
+        assert!(!MedicalPermissions::has_access(&patient, &specialist));
+        assert_eq!(RevokedReads::get(), vec![(patient, specialist)]);
+        assert_eq!(MedicalPermissions::open_referrals(patient, specialist), None);
+
+        assert_noop!(
+            MedicalPermissions::close_referral(RuntimeOrigin::signed(patient), referral),
+            Error::<Test>::ReferralNotOpen
+        );
+        assert_noop!(
+            MedicalPermissions::approve_referral(RuntimeOrigin::signed(patient), referral),
+            Error::<Test>::ReferralNotProposed
+        );
+    });
+}
+
+/// Ensures referral listings walk ids in numeric order and resume after
+/// the last id of the previous page.
+#[test]
+fn referral_listings_are_paginated_by_id() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (patient, doctor) = (1, 2);
+
+        // Little-endian or hashed keys would not keep these in order.
+        crate::NextReferralId::<Test>::put(255);
+        propose_referral(PermissionScope::READ, 5, None);
+        for specialist in [5, 6] {
+            assert_ok!(MedicalPermissions::propose_referral(
+                RuntimeOrigin::signed(doctor),
+                patient,
+                specialist,
+                sp_core::H256::repeat_byte(1),
+                PermissionScope::READ,
+                5,
+                None
+            ));
+        }
+
+        let ids = |page: crate::ReferralPageOf<Test>| -> (Vec<ReferralId>, Option<ReferralId>) {
+            (page.items.into_iter().map(|(id, _)| id).collect(), page.next)
+        };
+        assert_eq!(
+            ids(MedicalPermissions::referrals_of_patient(&patient, None, 2)),
+            (vec![255, 256], Some(256))
+        );
+        assert_eq!(
+            ids(MedicalPermissions::referrals_of_patient(&patient, Some(256), 2)),
+            (vec![257], None)
+        );
+        assert_eq!(
+            ids(MedicalPermissions::referrals_of_specialist(&6, None, 2)),
+            (vec![257], None)
+        );
+    });
+}
+
+/// Ensures the specialist's access ends with the referral's duration.
+#[test]
+fn referral_expires_after_duration() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (patient, specialist) = (1, 4);
+
+        let referral = propose_referral(PermissionScope::READ, 5, None);
+        assert_ok!(MedicalPermissions::approve_referral(RuntimeOrigin::signed(patient), referral));
+        assert!(MedicalPermissions::can_read(&patient, &specialist));
+
+        // Not honoured from the expiry block on, even before the cleanup.
+        System::set_block_number(6);
+        assert!(!MedicalPermissions::can_read(&patient, &specialist));
+
+        MedicalPermissions::on_initialize(6);
+        System::assert_last_event(Event::ReferralExpired { referral, patient, specialist }.into());
+        assert_eq!(
+            MedicalPermissions::referrals(referral).unwrap().status,
+            ReferralStatus::Expired
+        );
+        assert_eq!(RevokedReads::get(), vec![(patient, specialist)]);
+
+        // The specialist can be referred again.
+        let again = propose_referral(PermissionScope::READ, 5, None);
+        assert_eq!(MedicalPermissions::referrals_of_patient(&patient, None, 10).items.len(), 2);
+        assert_eq!(MedicalPermissions::open_referrals(patient, specialist), Some(again));
+    });
+}
+
+/// Ensures any number of referrals may expire in the same block, the surplus
+/// being ended in the following blocks, and closed referrals leave the queue.
+#[test]
+fn referral_expiries_beyond_block_limit_are_deferred() {
+    new_test_ext().execute_with(|| {
+        MaxExpiries::set(2);
+        System::set_block_number(1);
+        let patient = 1;
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(patient), 2));
+
+        let referrals: Vec<ReferralId> = [4, 5, 6, 7]
+            .into_iter()
+            .map(|specialist| {
+                let referral = MedicalPermissions::next_referral_id();
+                assert_ok!(MedicalPermissions::propose_referral(
+                    RuntimeOrigin::signed(2),
+                    patient,
+                    specialist,
+                    sp_core::H256::repeat_byte(1),
+                    PermissionScope::READ,
+                    5,
+                    None
+                ));
+                assert_ok!(MedicalPermissions::approve_referral(
+                    RuntimeOrigin::signed(patient),
+                    referral
+                ));
+                referral
+            })
+            .collect();
+        assert_eq!(ReferralExpiryQueue::<Test>::iter_prefix(6).count(), 4);
+
+        assert_ok!(MedicalPermissions::close_referral(RuntimeOrigin::signed(5), referrals[1]));
+        assert!(!ReferralExpiryQueue::<Test>::contains_key(6, referrals[1]));
+
+        for n in 1..=8 {
+            System::set_block_number(n);
+            MedicalPermissions::on_initialize(n);
+        }
+
+        assert_eq!(ReferralExpiryQueue::<Test>::iter().count(), 0);
+        let statuses: Vec<_> = referrals
+            .iter()
+            .map(|referral| MedicalPermissions::referrals(referral).unwrap().status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ReferralStatus::Expired,
+                ReferralStatus::Closed,
+                ReferralStatus::Expired,
+                ReferralStatus::Expired
+            ]
+        );
+    });
+}
+
+/// Ensures a referral limited to selected records only lets the specialist
+/// read those records.
+#[test]
+fn referral_limited_to_selected_records() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (patient, specialist) = (1, 4);
+        let selected = b"selected".to_vec();
+        let record: RecordHash = selected.clone().try_into().unwrap();
+        let records: ReferralRecords = vec![record].try_into().unwrap();
+
+        let referral = propose_referral(PermissionScope::ALL, 10, Some(records));
+        assert_ok!(MedicalPermissions::approve_referral(RuntimeOrigin::signed(patient), referral));
+
+        assert!(!MedicalPermissions::can_read(&patient, &specialist));
+        assert!(MedicalPermissions::can_write(&patient, &specialist));
+        assert!(MedicalPermissions::can_read_record(&patient, &specialist, &selected));
+        assert!(!MedicalPermissions::can_read_record(&patient, &specialist, b"other"));
+
+        assert_ok!(MedicalPermissions::freeze_access(RuntimeOrigin::signed(patient)));
+        assert!(!MedicalPermissions::can_read_record(&patient, &specialist, &selected));
+    });
+}
+
+/// Ensures referrals are validated when proposed.
+#[test]
+fn propose_referral_validates_input() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let propose = |doctor: u64, specialist: u64, duration: u64, records| {
+            MedicalPermissions::propose_referral(
+                RuntimeOrigin::signed(doctor),
+                1,
+                specialist,
+                sp_core::H256::zero(),
+                PermissionScope::READ,
+                duration,
+                records,
+            )
+        };
+
+        assert_noop!(propose(2, 4, 10, None), Error::<Test>::ReferrerWithoutAccess);
+        assert_ok!(MedicalPermissions::grant_access(RuntimeOrigin::signed(1), 2));
+
+        assert_noop!(propose(1, 4, 10, None), Error::<Test>::SelfPermissionNotAllowed);
+        assert_noop!(propose(2, 1, 10, None), Error::<Test>::InvalidReferral);
+        assert_noop!(propose(2, 2, 10, None), Error::<Test>::InvalidReferral);
+        assert_noop!(propose(2, 4, 0, None), Error::<Test>::InvalidReferral);
+        assert_noop!(
+            propose(2, 4, 10, Some(ReferralRecords::default())),
+            Error::<Test>::InvalidReferral
+        );
+        assert_noop!(propose(2, UNREGISTERED_DOCTOR, 10, None), Error::<Test>::DoctorNotRegistered);
+
+        assert_ok!(propose(2, 4, 10, None));
+        assert_noop!(propose(2, 4, 10, None), Error::<Test>::ReferralAlreadyOpen);
+    });
+}
+
+/// Ensures the v10 migration names the institution of institution log
+/// entries and keeps the doctor of the others.
+#[test]
+fn migration_v9_to_v10_names_log_subjects() {
+    use frame_support::{
+        storage::unhashed,
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    };
+
+    new_test_ext().execute_with(|| {
+        let (patient, doctor, admin) = (1, 2, 3);
+        let old = |doctor, action| crate::PermissionLogEntryV9 {
+            doctor,
+            action,
+            scope: PermissionScope::READ,
+            block: 1u64,
+            timestamp: 1_000u64,
+        };
+
+        StorageVersion::new(9).put::<MedicalPermissions>();
+        unhashed::put(
+            &PermissionLog::<Test>::hashed_key_for(patient, 0),
+            &old(doctor, PermissionAction::Granted),
+        );
+        unhashed::put(
+            &PermissionLog::<Test>::hashed_key_for(patient, 1),
+            &old(admin, PermissionAction::InstitutionGranted(7)),
+        );
+        crate::PermissionLogNext::<Test>::insert(patient, 2);
+
+        crate::migrations::v10::MigrateV9ToV10::<Test>::on_runtime_upgrade();
+
+        let subjects: Vec<_> = MedicalPermissions::permission_log(&patient, 0, 10)
+            .into_iter()
+            .map(|(seq, entry)| (seq, entry.subject, entry.action, entry.block))
+            .collect();
+        assert_eq!(
+            subjects,
+            vec![
+                (0, LogSubject::Doctor(doctor), PermissionAction::Granted, 1),
+                (1, LogSubject::Institution(7), PermissionAction::InstitutionGranted(7), 1),
+            ]
+        );
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 10);
+    });
+}
+
+/// Ensures the v11 migration re-keys the referral indexes in id order.
+#[test]
+fn migration_v10_to_v11_orders_referral_indexes() {
+    use codec::Encode;
+    use frame_support::{
+        storage::{unhashed, StoragePrefixedMap},
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        Blake2_128Concat, StorageHasher, Twox64Concat,
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        crate::NextReferralId::<Test>::put(255);
+        let first = propose_referral(PermissionScope::READ, 5, None);
+        assert_ok!(MedicalPermissions::close_referral(RuntimeOrigin::signed(1), first));
+        let second = propose_referral(PermissionScope::READ, 5, None);
+
+        StorageVersion::new(10).put::<MedicalPermissions>();
+        let _ = crate::PatientReferrals::<Test>::clear(u32::MAX, None);
+        let _ = crate::SpecialistReferrals::<Test>::clear(u32::MAX, None);
+        let old_key = |prefix: [u8; 32], account: u64, referral: ReferralId| {
+            let mut key = prefix.to_vec();
+            key.extend(Blake2_128Concat::hash(&account.encode()));
+            key.extend(Twox64Concat::hash(&referral.encode()));
+            key
+        };
+        for referral in [first, second] {
+            let patients = crate::PatientReferrals::<Test>::final_prefix();
+            unhashed::put(&old_key(patients, 1, referral), &());
+            let specialists = crate::SpecialistReferrals::<Test>::final_prefix();
+            unhashed::put(&old_key(specialists, 4, referral), &());
+        }
+
+        crate::migrations::v11::MigrateV10ToV11::<Test>::on_runtime_upgrade();
+
+        let ids = |page: crate::ReferralPageOf<Test>| -> Vec<ReferralId> {
+            page.items.into_iter().map(|(id, _)| id).collect()
+        };
+        assert_eq!(
+            ids(MedicalPermissions::referrals_of_patient(&1, None, 10)),
+            vec![first, second]
+        );
+        assert_eq!(
+            ids(MedicalPermissions::referrals_of_specialist(&4, None, 10)),
+            vec![first, second]
+        );
+        assert_eq!(crate::PatientReferrals::<Test>::iter().count(), 2);
+        assert_eq!(MedicalPermissions::on_chain_storage_version(), 11);
+    });
+}
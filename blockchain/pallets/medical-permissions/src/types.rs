@@ -0,0 +1,420 @@
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+
+/// Set of permissions a patient grants to a doctor, encoded as bitflags.
+///
+/// Matches the "Qual permissão liberada (RW)" requirement: read and write
+/// access are independent bits, so each one can be granted and revoked
+/// without touching the other.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct PermissionScope(u8);
+
+impl PermissionScope {
+    /// No permission at all.
+    pub const NONE: Self = Self(0);
+    /// Read access to the patient's history (`read_patient_data`).
+    pub const READ: Self = Self(0b01);
+    /// Write access to the patient's history (`create_record`).
+    pub const WRITE: Self = Self(0b10);
+    /// Read and write access.
+    pub const ALL: Self = Self(Self::READ.0 | Self::WRITE.0);
+
+    /// Builds a scope from raw bits, rejecting unknown flags.
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        if bits & !Self::ALL.0 == 0 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the raw bits of this scope.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if no permission bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if only known permission bits are set.
+    pub fn is_valid(&self) -> bool {
+        Self::from_bits(self.0).is_some()
+    }
+
+    /// Returns `true` if every bit of `other` is also set in `self`.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the bits set in either scope.
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns `self` without the bits set in `other`.
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns `true` if the scope allows reading.
+    pub fn can_read(&self) -> bool {
+        self.contains(Self::READ)
+    }
+
+    /// Returns `true` if the scope allows writing.
+    pub fn can_write(&self) -> bool {
+        self.contains(Self::WRITE)
+    }
+}
+
+/// A pending access request created by a doctor.
+///
+/// The patient approves or rejects it; once `expires_at` is reached the
+/// request can no longer be approved and is cleaned up.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AccessRequest<Hash, BlockNumber> {
+    /// Scope requested by the doctor.
+    pub scope: PermissionScope,
+    /// Hash of the off-chain justification for the request.
+    pub reason_hash: Hash,
+    /// Block in which the request was made.
+    pub requested_at: BlockNumber,
+    /// Block from which the request is no longer valid.
+    pub expires_at: BlockNumber,
+}
+
+/// Kind of change recorded in the permission history log.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum PermissionAction {
+    /// The patient granted the scope ("Liberação").
+    Granted,
+    /// The patient revoked the scope ("Revogação").
+    Revoked,
+    /// A time-limited grant expired.
+    Expired,
+    /// Read access was taken through the break-glass procedure.
+    EmergencyAccess,
+    /// The patient granted the scope to an institution.
+    InstitutionGranted(InstitutionId),
+    /// The patient revoked the scope from an institution.
+    InstitutionRevoked(InstitutionId),
+}
+
+/// Grantee affected by a permission history log entry.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum LogSubject<AccountId> {
+    /// A doctor, specialist or break-glass accessor.
+    Doctor(AccountId),
+    /// An institution, whose member doctors share the grant.
+    Institution(InstitutionId),
+}
+
+/// One entry of a patient's permission history log.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PermissionLogEntry<AccountId, BlockNumber, Moment> {
+    /// Grantee affected by the change.
+    pub subject: LogSubject<AccountId>,
+    /// What happened.
+    pub action: PermissionAction,
+    /// Scope granted, revoked or expired by this change.
+    pub scope: PermissionScope,
+    /// Block in which the change happened.
+    pub block: BlockNumber,
+    /// Timestamp of that block.
+    pub timestamp: Moment,
+}
+
+/// [`PermissionLogEntry`] as stored up to storage version `9` and returned
+/// up to version `2` of the `MedicalPermissionsApi` runtime API, before
+/// institution entries named the institution.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PermissionLogEntryV9<AccountId, BlockNumber, Moment> {
+    /// Doctor affected by the change; for institution changes, the
+    /// institution's admin at the time.
+    pub doctor: AccountId,
+    /// What happened.
+    pub action: PermissionAction,
+    /// Scope granted, revoked or expired by this change.
+    pub scope: PermissionScope,
+    /// Block in which the change happened.
+    pub block: BlockNumber,
+    /// Timestamp of that block.
+    pub timestamp: Moment,
+}
+
+/// Immutable record of a break-glass access ("acesso de emergência").
+///
+/// Written once by `emergency_access` and never modified; a contest by the
+/// patient is stored separately as an [`IncidentContest`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EmergencyIncident<AccountId, Hash, BlockNumber, Moment> {
+    /// Account that broke the glass.
+    pub accessor: AccountId,
+    /// Hash of the off-chain clinical justification.
+    pub justification_hash: Hash,
+    /// Block in which the access was taken.
+    pub block: BlockNumber,
+    /// Timestamp of that block.
+    pub timestamp: Moment,
+    /// Block from which the emergency read access is no longer honoured.
+    pub expires_at: BlockNumber,
+}
+
+/// A patient's objection to an [`EmergencyIncident`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct IncidentContest<Hash, BlockNumber> {
+    /// Hash of the off-chain statement explaining the objection.
+    pub reason_hash: Hash,
+    /// Block in which the incident was contested.
+    pub contested_at: BlockNumber,
+}
+
+/// Who appointed a guardian.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum GuardianAppointer {
+    /// The patient, for themselves.
+    Patient,
+    /// The guardianship registrar (e.g. for minors).
+    Registrar,
+}
+
+/// A guardian or legal representative ("responsável legal") acting for a
+/// patient.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Guardianship<BlockNumber> {
+    /// Who appointed the guardian.
+    pub appointed_by: GuardianAppointer,
+    /// Block in which the guardian was appointed.
+    pub since: BlockNumber,
+    /// Block from which the guardianship ends (e.g. the age of majority);
+    /// `None` for an open-ended guardianship.
+    pub until: Option<BlockNumber>,
+}
+
+impl<BlockNumber: PartialOrd> Guardianship<BlockNumber> {
+    /// Returns `true` if the guardianship is still in force at block `now`.
+    pub fn is_active_at(&self, now: &BlockNumber) -> bool {
+        self.until.as_ref().is_none_or(|until| until > now)
+    }
+}
+
+/// Identifier of an institution (hospital, clinic, laboratory).
+pub type InstitutionId = u32;
+
+/// An institution whose member doctors share the grants given to it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Institution<AccountId, Hash, BlockNumber> {
+    /// Account that manages the institution's members.
+    pub admin: AccountId,
+    /// Hash of the institution's legal name and registration (CNES).
+    pub name_hash: Hash,
+    /// Block in which the institution was registered.
+    pub registered_at: BlockNumber,
+}
+
+/// When a grant was created.
+#[derive(
+    Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct GrantTimestamp<BlockNumber, Moment> {
+    /// Block in which the grant was created.
+    pub block: BlockNumber,
+    /// Timestamp of that block.
+    pub timestamp: Moment,
+}
+
+/// A grant as seen from one of its sides, returned by the
+/// `patients_of` / `doctors_of` queries.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GrantInfo<AccountId, BlockNumber, Moment> {
+    /// The other side of the grant: the patient for `patients_of`, the
+    /// doctor for `doctors_of`.
+    pub account: AccountId,
+    /// Scope currently granted.
+    pub scope: PermissionScope,
+    /// When the grant was created.
+    pub granted: GrantTimestamp<BlockNumber, Moment>,
+    /// Expiry of a time-limited grant.
+    pub expires_at: Option<BlockNumber>,
+}
+
+/// One page of a `patients_of` / `doctors_of` listing.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GrantPage<AccountId, BlockNumber, Moment> {
+    /// Active grants of the page, in storage order.
+    pub items: Vec<GrantInfo<AccountId, BlockNumber, Moment>>,
+    /// `after` of the next page, or `None` if this is the last one.
+    pub next: Option<AccountId>,
+}
+
+/// Identifier of a referral, assigned sequentially.
+pub type ReferralId = u64;
+
+/// Key of a referral in the per-patient and per-specialist indexes.
+///
+/// Stored big-endian so that, under the `Identity` hasher, the order of the
+/// storage keys is the numeric order of the ids and a prefix iteration walks
+/// referrals oldest first.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct ReferralKey([u8; 8]);
+
+impl From<ReferralId> for ReferralKey {
+    fn from(id: ReferralId) -> Self {
+        Self(id.to_be_bytes())
+    }
+}
+
+impl From<ReferralKey> for ReferralId {
+    fn from(key: ReferralKey) -> Self {
+        ReferralId::from_be_bytes(key.0)
+    }
+}
+
+/// Content identifier of a record, as indexed by the history pallet.
+pub type RecordHash = BoundedVec<u8, ConstU32<64>>;
+
+/// Records a referral is limited to (up to 16).
+pub type ReferralRecords = BoundedVec<RecordHash, ConstU32<16>>;
+
+/// Lifecycle of a referral.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum ReferralStatus {
+    /// Proposed by the referring doctor, waiting for the patient.
+    Proposed,
+    /// Approved by the patient; the specialist holds access.
+    Active,
+    /// Closed by one of the parties before expiring.
+    Closed,
+    /// Reached the end of its duration.
+    Expired,
+}
+
+/// A referral ("encaminhamento") from a doctor to a specialist.
+///
+/// Once approved by the patient, the specialist holds `scope` until
+/// `expires_at`, restricted to `records` when given. The access is kept
+/// apart from the patient's own grants and ends with the referral.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Referral<AccountId, Hash, BlockNumber> {
+    /// The patient being referred.
+    pub patient: AccountId,
+    /// The doctor who proposed the referral.
+    pub referring_doctor: AccountId,
+    /// The specialist the patient is referred to.
+    pub specialist: AccountId,
+    /// Hash of the off-chain referral reason.
+    pub reason_hash: Hash,
+    /// Scope granted to the specialist.
+    pub scope: PermissionScope,
+    /// Number of blocks the access lasts once approved.
+    pub duration: BlockNumber,
+    /// Records the specialist may read; `None` for the whole history.
+    pub records: Option<ReferralRecords>,
+    /// Current status.
+    pub status: ReferralStatus,
+    /// Block in which the referral was proposed.
+    pub proposed_at: BlockNumber,
+    /// Block from which the access is no longer honoured, set on approval.
+    pub expires_at: Option<BlockNumber>,
+}
+
+impl<AccountId, Hash, BlockNumber: PartialOrd> Referral<AccountId, Hash, BlockNumber> {
+    /// Returns `true` if the referral grants access at block `now`.
+    pub fn is_active_at(&self, now: &BlockNumber) -> bool {
+        self.status == ReferralStatus::Active &&
+            self.expires_at.as_ref().is_some_and(|expires_at| expires_at > now)
+    }
+
+    /// Returns `true` if the referral may still be closed.
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, ReferralStatus::Proposed | ReferralStatus::Active)
+    }
+
+    /// Returns the scope the referral grants over the whole history.
+    ///
+    /// Read access limited to selected records is left out; see
+    /// [`Self::covers`].
+    pub fn patient_scope(&self) -> PermissionScope {
+        if self.records.is_some() {
+            self.scope.difference(PermissionScope::READ)
+        } else {
+            self.scope
+        }
+    }
+
+    /// Returns `true` if `file_hash` is one of the selected records.
+    pub fn covers(&self, file_hash: &[u8]) -> bool {
+        self.records
+            .as_ref()
+            .is_some_and(|records| records.iter().any(|record| &record[..] == file_hash))
+    }
+}
+
+/// One page of a `referrals_of_patient` / `referrals_of_specialist` listing.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReferralPage<AccountId, Hash, BlockNumber> {
+    /// Referrals of the page, oldest first.
+    pub items: Vec<(ReferralId, Referral<AccountId, Hash, BlockNumber>)>,
+    /// `after` of the next page, or `None` if this is the last one.
+    pub next: Option<ReferralId>,
+}
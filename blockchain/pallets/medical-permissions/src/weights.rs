@@ -16,6 +16,35 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
     fn grant_access() -> Weight { Weight::from_parts(10_000, 0) }
     fn revoke_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn grant_access_scoped() -> Weight { Weight::from_parts(10_000, 0) }
+    fn revoke_access_scoped() -> Weight { Weight::from_parts(10_000, 0) }
+    fn grant_temporary_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn expire_grants(n: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(n.into()) }
+    fn request_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn approve_request() -> Weight { Weight::from_parts(10_000, 0) }
+    fn reject_request() -> Weight { Weight::from_parts(10_000, 0) }
+    fn expire_requests(n: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(n.into()) }
+    fn emergency_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn contest_incident() -> Weight { Weight::from_parts(10_000, 0) }
+    fn set_emergency_account() -> Weight { Weight::from_parts(10_000, 0) }
+    fn clear_emergency_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn appoint_guardian() -> Weight { Weight::from_parts(10_000, 0) }
+    fn remove_guardian() -> Weight { Weight::from_parts(10_000, 0) }
+    fn guardian_grant_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn guardian_revoke_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn register_institution() -> Weight { Weight::from_parts(10_000, 0) }
+    fn set_institution_admin() -> Weight { Weight::from_parts(10_000, 0) }
+    fn add_member() -> Weight { Weight::from_parts(10_000, 0) }
+    fn remove_member(p: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(p.into()) }
+    fn grant_institution_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn revoke_institution_access(m: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(m.into()) }
+    fn revoke_all_access(n: u32, m: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(n.into()).saturating_mul(m.into()) }
+    fn freeze_access() -> Weight { Weight::from_parts(10_000, 0) }
+    fn propose_referral() -> Weight { Weight::from_parts(10_000, 0) }
+    fn approve_referral() -> Weight { Weight::from_parts(10_000, 0) }
+    fn close_referral() -> Weight { Weight::from_parts(10_000, 0) }
+    fn expire_referrals(n: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(n.into()) }
+    fn expire_guardianships(n: u32) -> Weight { Weight::from_parts(10_000, 0).saturating_mul(n.into()) }
 }
 
 /// Weights for pallet_medical_permissions using the Substrate node and recommended hardware.
@@ -23,9 +52,140 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn grant_access() -> Weight {
         Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 4))
     }
     fn revoke_access() -> Weight {
         Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(12, 5))
+    }
+    fn grant_access_scoped() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 4))
+    }
+    fn revoke_access_scoped() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(12, 5))
+    }
+    fn grant_temporary_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(5, 6))
+    }
+    fn expire_grants(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(T::DbWeight::get().reads_writes(12, 5).saturating_mul(n.into()))
+    }
+    fn request_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 2))
+    }
+    fn approve_request() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(5, 6))
+    }
+    fn reject_request() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn expire_requests(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1).saturating_mul(n.into()))
+    }
+    fn emergency_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(5, 6))
+    }
+    fn contest_incident() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(11, 2))
+    }
+    fn set_emergency_account() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn clear_emergency_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(10, 1))
+    }
+    fn appoint_guardian() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 3))
+    }
+    fn remove_guardian() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(5, 2))
+    }
+    fn guardian_grant_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(6, 6))
+    }
+    fn guardian_revoke_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(13, 5))
+    }
+    fn register_institution() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 2))
+    }
+    fn set_institution_admin() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn add_member() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(5, 3))
+    }
+    fn remove_member(p: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(4, 3))
+            .saturating_add(T::DbWeight::get().reads_writes(9, 0).saturating_mul(p.into()))
+    }
+    fn grant_institution_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(7, 8))
+    }
+    fn revoke_institution_access(m: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(5, 7))
+            .saturating_add(T::DbWeight::get().reads_writes(8, 0).saturating_mul(m.into()))
+    }
+    fn revoke_all_access(n: u32, m: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 0))
+            .saturating_add(T::DbWeight::get().reads_writes(15, 9).saturating_mul(n.into()))
+            .saturating_add(
+                T::DbWeight::get()
+                    .reads_writes(8, 0)
+                    .saturating_mul(n.into())
+                    .saturating_mul(m.into()),
+            )
+    }
+    fn freeze_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 1))
+    }
+    fn propose_referral() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(7, 5))
+    }
+    fn approve_referral() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 4))
+    }
+    fn close_referral() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(8, 6))
+    }
+    fn expire_referrals(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(T::DbWeight::get().reads_writes(8, 6).saturating_mul(n.into()))
+    }
+    fn expire_guardianships(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(T::DbWeight::get().reads_writes(7, 2).saturating_mul(n.into()))
     }
 }
 
@@ -33,8 +193,139 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 impl WeightInfo for () {
     fn grant_access() -> Weight {
         Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 4))
     }
     fn revoke_access() -> Weight {
         Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(12, 5))
+    }
+    fn grant_access_scoped() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 4))
+    }
+    fn revoke_access_scoped() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(12, 5))
+    }
+    fn grant_temporary_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 6))
+    }
+    fn expire_grants(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+            .saturating_add(RocksDbWeight::get().reads_writes(12, 5).saturating_mul(n.into()))
+    }
+    fn request_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+    }
+    fn approve_request() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 6))
+    }
+    fn reject_request() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn expire_requests(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1).saturating_mul(n.into()))
+    }
+    fn emergency_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 6))
+    }
+    fn contest_incident() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(11, 2))
+    }
+    fn set_emergency_account() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn clear_emergency_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(10, 1))
+    }
+    fn appoint_guardian() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 3))
+    }
+    fn remove_guardian() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 2))
+    }
+    fn guardian_grant_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(6, 6))
+    }
+    fn guardian_revoke_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(13, 5))
+    }
+    fn register_institution() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 2))
+    }
+    fn set_institution_admin() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn add_member() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 3))
+    }
+    fn remove_member(p: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(4, 3))
+            .saturating_add(RocksDbWeight::get().reads_writes(9, 0).saturating_mul(p.into()))
+    }
+    fn grant_institution_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(7, 8))
+    }
+    fn revoke_institution_access(m: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 7))
+            .saturating_add(RocksDbWeight::get().reads_writes(8, 0).saturating_mul(m.into()))
+    }
+    fn revoke_all_access(n: u32, m: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 0))
+            .saturating_add(RocksDbWeight::get().reads_writes(15, 9).saturating_mul(n.into()))
+            .saturating_add(
+                RocksDbWeight::get()
+                    .reads_writes(8, 0)
+                    .saturating_mul(n.into())
+                    .saturating_mul(m.into()),
+            )
+    }
+    fn freeze_access() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 1))
+    }
+    fn propose_referral() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(7, 5))
+    }
+    fn approve_referral() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 4))
+    }
+    fn close_referral() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(8, 6))
+    }
+    fn expire_referrals(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+            .saturating_add(RocksDbWeight::get().reads_writes(8, 6).saturating_mul(n.into()))
+    }
+    fn expire_guardianships(n: u32) -> Weight {
+        Weight::from_parts(5_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+            .saturating_add(RocksDbWeight::get().reads_writes(7, 2).saturating_mul(n.into()))
     }
 }
\ No newline at end of file
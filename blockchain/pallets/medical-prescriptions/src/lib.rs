@@ -0,0 +1,601 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Medical Prescriptions pallet (HealthChain).
+//!
+//! Registered doctors issue electronic prescriptions for a patient: a list
+//! of medication hashes with quantities, a validity period and a flag for
+//! controlled substances. Pharmacies registered by a configurable origin
+//! dispense them, partially or fully, and the prescription keeps track of
+//! how many units are left so nothing is dispensed twice.
+//!
+//! ## Patient authorization
+//! Every dispensation needs a [`DispensePayload`] signed by the patient,
+//! naming the pharmacy, the quantities and how many dispensations came
+//! before, so a pharmacy that learns a prescription id cannot dispense it
+//! and an authorization cannot be replayed.
+//!
+//! ## Controlled substances
+//! A controlled prescription is retained by the pharmacy that dispenses
+//! it: its first dispensation closes it, even if some units are left.
+//!
+//! ## Queries
+//! Patients and doctors follow the dispensing status through
+//! [`Pallet::prescriptions_of_patient`] and [`Pallet::prescriptions_by_doctor`],
+//! exposed by the runtime API. Prescriptions name the patient and their
+//! medications, so every query takes a [`QueryPayload`] signed by the
+//! reader, and lists only the reader's own prescriptions.
+
+pub use pallet::*;
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::*;
+
+pub mod types;
+pub use types::*;
+
+pub mod migrations;
+
+use alloc::vec::Vec;
+use frame_support::{
+    ensure,
+    sp_runtime::traits::{Saturating, Verify},
+    traits::{Get, Time},
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_doctor_registry::DoctorRegistryVerifier;
+
+/// Timestamp type of the configured [`Time`] provider.
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+
+/// Prescription type for a runtime.
+pub type PrescriptionOf<T> = Prescription<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    MomentOf<T>,
+>;
+
+/// Prescription listing page type for a runtime.
+pub type PrescriptionPageOf<T> = PrescriptionPage<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::Hash,
+    MomentOf<T>,
+>;
+
+/// Pharmacy type for a runtime.
+pub type PharmacyOf<T> = Pharmacy<<T as frame_system::Config>::Hash, BlockNumberFor<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{pallet_prelude::*, sp_runtime::traits::IdentifyAccount};
+    use frame_system::pallet_prelude::*;
+
+    /// Current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    /// Main pallet struct.
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
+    /// Pallet configuration trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Registry used to make sure prescribers are licensed doctors.
+        type DoctorRegistry: DoctorRegistryVerifier<Self::AccountId>;
+
+        /// Time provider used for validity periods and timestamps.
+        type Time: Time;
+
+        /// Origin allowed to register and remove pharmacies.
+        type PharmacyRegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Signature used by patients to authorize dispensations and by
+        /// readers to sign queries.
+        type Signature: Verify<Signer = Self::Signer> + Parameter;
+
+        /// Public key type matching [`Config::Signature`].
+        type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Maximum number of blocks a signed query stays valid.
+        #[pallet::constant]
+        type MaxQueryValidity: Get<BlockNumberFor<Self>>;
+
+        /// Weight information for extrinsics.
+        type WeightInfo: WeightInfo;
+    }
+
+    // ---------------------------------------------------------------------
+    // Storage
+    // ---------------------------------------------------------------------
+
+    /// Registered pharmacies: `pharmacy -> profile`.
+    #[pallet::storage]
+    #[pallet::getter(fn pharmacies)]
+    pub type Pharmacies<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, PharmacyOf<T>, OptionQuery>;
+
+    /// Prescriptions: `id -> prescription`.
+    #[pallet::storage]
+    #[pallet::getter(fn prescriptions)]
+    pub type Prescriptions<T: Config> =
+        StorageMap<_, Twox64Concat, PrescriptionId, PrescriptionOf<T>, OptionQuery>;
+
+    /// Id of the next prescription to be issued.
+    #[pallet::storage]
+    #[pallet::getter(fn next_prescription_id)]
+    pub type NextPrescriptionId<T: Config> = StorageValue<_, PrescriptionId, ValueQuery>;
+
+    /// Prescriptions of each patient: `(patient, id) -> ()`.
+    ///
+    /// Keyed by [`PrescriptionKey`], so iterating a patient's prefix walks
+    /// their prescriptions oldest first.
+    #[pallet::storage]
+    pub type PatientPrescriptions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Patient
+        Identity,
+        PrescriptionKey,
+        (),
+        OptionQuery,
+    >;
+
+    /// Prescriptions issued by each doctor: `(doctor, id) -> ()`.
+    ///
+    /// Same ordering as [`PatientPrescriptions`].
+    #[pallet::storage]
+    pub type DoctorPrescriptions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Doctor
+        Identity,
+        PrescriptionKey,
+        (),
+        OptionQuery,
+    >;
+
+    // ---------------------------------------------------------------------
+    // Events
+    // ---------------------------------------------------------------------
+
+    /// Events emitted by the Medical Prescriptions pallet.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The registrar registered a pharmacy.
+        PharmacyRegistered { pharmacy: T::AccountId },
+
+        /// The registrar removed a pharmacy.
+        PharmacyRemoved { pharmacy: T::AccountId },
+
+        /// A doctor issued a prescription.
+        PrescriptionIssued {
+            doctor: T::AccountId,
+            patient: T::AccountId,
+            prescription: PrescriptionId,
+        },
+
+        /// A pharmacy dispensed (part of) a prescription.
+        PrescriptionDispensed {
+            pharmacy: T::AccountId,
+            prescription: PrescriptionId,
+            status: PrescriptionStatus,
+        },
+    }
+
+    // ---------------------------------------------------------------------
+    // Errors
+    // ---------------------------------------------------------------------
+
+    /// Errors returned by the Medical Prescriptions pallet.
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The caller is not a registered, active doctor.
+        DoctorNotRegistered,
+
+        /// The account is already a registered pharmacy.
+        AlreadyPharmacy,
+
+        /// The account is not a registered pharmacy.
+        NotPharmacy,
+
+        /// The prescription has no medications.
+        NoMedications,
+
+        /// A prescribed quantity is zero, or a dispensation dispenses nothing.
+        InvalidQuantity,
+
+        /// The validity period has already ended.
+        InvalidValidity,
+
+        /// No prescription has this id.
+        PrescriptionNotFound,
+
+        /// The prescription's validity period has ended.
+        PrescriptionExpired,
+
+        /// The prescription was already fully dispensed or retained.
+        AlreadyDispensed,
+
+        /// The quantities do not match the prescription's medications.
+        QuantityMismatch,
+
+        /// Dispensing would exceed the prescribed quantity.
+        ExceedsPrescribed,
+
+        /// The prescription reached the maximum number of dispensations.
+        TooManyDispensations,
+
+        /// The authorization was not signed by the patient for this
+        /// pharmacy, dispensation and quantities.
+        BadAuthorization,
+    }
+
+    // ---------------------------------------------------------------------
+    // Calls (extrinsics)
+    // ---------------------------------------------------------------------
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Registers a pharmacy.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::PharmacyRegistrarOrigin`].
+        /// - `pharmacy`: The pharmacy account.
+        /// - `license_hash`: Hash of the pharmacy's license.
+        ///
+        /// # Storage
+        /// - Writes to [`Pharmacies`]
+        ///
+        /// # Emits
+        /// - [`Event::PharmacyRegistered`]
+        ///
+        /// # Errors
+        /// - [`Error::AlreadyPharmacy`] if the account is already registered
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::register_pharmacy())]
+        pub fn register_pharmacy(
+            origin: OriginFor<T>,
+            pharmacy: T::AccountId,
+            license_hash: T::Hash,
+        ) -> DispatchResult {
+            T::PharmacyRegistrarOrigin::ensure_origin(origin)?;
+
+            ensure!(!Pharmacies::<T>::contains_key(&pharmacy), Error::<T>::AlreadyPharmacy);
+
+            Pharmacies::<T>::insert(
+                &pharmacy,
+                Pharmacy {
+                    license_hash,
+                    registered_at: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::PharmacyRegistered { pharmacy });
+
+            Ok(())
+        }
+
+        /// Removes a pharmacy. Its past dispensations are kept.
+        ///
+        /// # Parameters
+        /// - `origin`: Must satisfy [`Config::PharmacyRegistrarOrigin`].
+        /// - `pharmacy`: The pharmacy account.
+        ///
+        /// # Storage
+        /// - Writes to [`Pharmacies`]
+        ///
+        /// # Emits
+        /// - [`Event::PharmacyRemoved`]
+        ///
+        /// # Errors
+        /// - [`Error::NotPharmacy`] if the account is not registered
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::remove_pharmacy())]
+        pub fn remove_pharmacy(origin: OriginFor<T>, pharmacy: T::AccountId) -> DispatchResult {
+            T::PharmacyRegistrarOrigin::ensure_origin(origin)?;
+
+            ensure!(Pharmacies::<T>::contains_key(&pharmacy), Error::<T>::NotPharmacy);
+            Pharmacies::<T>::remove(&pharmacy);
+
+            Self::deposit_event(Event::PharmacyRemoved { pharmacy });
+
+            Ok(())
+        }
+
+        /// Issues a prescription for `patient`.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a signed account representing the doctor.
+        /// - `patient`: The patient.
+        /// - `medications`: Medication hashes with their quantities.
+        /// - `valid_until`: Last moment at which it can be dispensed.
+        /// - `controlled`: Whether it contains controlled substances.
+        ///
+        /// # Storage
+        /// - Writes to [`Prescriptions`], [`NextPrescriptionId`],
+        ///   [`PatientPrescriptions`], [`DoctorPrescriptions`]
+        ///
+        /// # Emits
+        /// - [`Event::PrescriptionIssued`]
+        ///
+        /// # Errors
+        /// - [`Error::DoctorNotRegistered`] if the caller is not an active doctor
+        /// - [`Error::NoMedications`] if `medications` is empty
+        /// - [`Error::InvalidQuantity`] if a quantity is zero
+        /// - [`Error::InvalidValidity`] if `valid_until` is in the past
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::issue())]
+        pub fn issue(
+            origin: OriginFor<T>,
+            patient: T::AccountId,
+            medications: Medications<T::Hash>,
+            valid_until: MomentOf<T>,
+            controlled: bool,
+        ) -> DispatchResult {
+            let doctor = ensure_signed(origin)?;
+
+            ensure!(
+                T::DoctorRegistry::is_active_doctor(&doctor),
+                Error::<T>::DoctorNotRegistered
+            );
+            ensure!(!medications.is_empty(), Error::<T>::NoMedications);
+            ensure!(
+                medications.iter().all(|medication| medication.quantity > 0),
+                Error::<T>::InvalidQuantity
+            );
+            let now = T::Time::now();
+            ensure!(valid_until > now, Error::<T>::InvalidValidity);
+
+            let dispensed = BoundedVec::truncate_from(alloc::vec![0; medications.len()]);
+            let prescription = NextPrescriptionId::<T>::get();
+            Prescriptions::<T>::insert(
+                prescription,
+                Prescription {
+                    doctor: doctor.clone(),
+                    patient: patient.clone(),
+                    medications,
+                    dispensed,
+                    controlled,
+                    valid_until,
+                    issued_at: now,
+                    status: PrescriptionStatus::Issued,
+                    dispensations: Default::default(),
+                },
+            );
+            NextPrescriptionId::<T>::put(prescription.saturating_add(1));
+            PatientPrescriptions::<T>::insert(&patient, PrescriptionKey::from(prescription), ());
+            DoctorPrescriptions::<T>::insert(&doctor, PrescriptionKey::from(prescription), ());
+
+            Self::deposit_event(Event::PrescriptionIssued { doctor, patient, prescription });
+
+            Ok(())
+        }
+
+        /// Dispenses (part of) a prescription.
+        ///
+        /// The prescription is closed once every unit is dispensed, or right
+        /// away if it is controlled.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be a registered pharmacy.
+        /// - `prescription`: The prescription.
+        /// - `quantities`: Units dispensed now, one entry per medication
+        ///   (zero for medications not dispensed).
+        /// - `authorization`: The patient's signature of the
+        ///   [`DispensePayload`] for this pharmacy, dispensation and
+        ///   `quantities`.
+        ///
+        /// # Storage
+        /// - Writes to [`Prescriptions`]
+        ///
+        /// # Emits
+        /// - [`Event::PrescriptionDispensed`] with the new status
+        ///
+        /// # Errors
+        /// - [`Error::NotPharmacy`] if the caller is not a registered pharmacy
+        /// - [`Error::PrescriptionNotFound`] if the prescription does not exist
+        /// - [`Error::AlreadyDispensed`] if the prescription is closed
+        /// - [`Error::PrescriptionExpired`] if its validity period has ended
+        /// - [`Error::BadAuthorization`] if `authorization` is not the patient's
+        /// - [`Error::QuantityMismatch`] if `quantities` has the wrong length
+        /// - [`Error::InvalidQuantity`] if every quantity is zero
+        /// - [`Error::ExceedsPrescribed`] if a quantity exceeds what is left
+        /// - [`Error::TooManyDispensations`] if no more dispensations fit
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::dispense())]
+        pub fn dispense(
+            origin: OriginFor<T>,
+            prescription: PrescriptionId,
+            quantities: Quantities,
+            authorization: T::Signature,
+        ) -> DispatchResult {
+            let pharmacy = ensure_signed(origin)?;
+
+            ensure!(Pharmacies::<T>::contains_key(&pharmacy), Error::<T>::NotPharmacy);
+
+            let status = Prescriptions::<T>::try_mutate(
+                prescription,
+                |maybe_prescription| -> Result<PrescriptionStatus, DispatchError> {
+                    let entry =
+                        maybe_prescription.as_mut().ok_or(Error::<T>::PrescriptionNotFound)?;
+                    ensure!(
+                        entry.status != PrescriptionStatus::Dispensed,
+                        Error::<T>::AlreadyDispensed
+                    );
+                    let now = T::Time::now();
+                    ensure!(now <= entry.valid_until, Error::<T>::PrescriptionExpired);
+                    let payload = DispensePayload {
+                        prescription,
+                        pharmacy: pharmacy.clone(),
+                        dispensation: entry.dispensations.len() as u32,
+                        quantities: quantities.clone(),
+                    };
+                    ensure!(
+                        authorization.verify(&payload.signing_message()[..], &entry.patient),
+                        Error::<T>::BadAuthorization
+                    );
+                    ensure!(
+                        quantities.len() == entry.medications.len(),
+                        Error::<T>::QuantityMismatch
+                    );
+                    ensure!(quantities.iter().any(|q| *q > 0), Error::<T>::InvalidQuantity);
+
+                    for ((dispensed, quantity), medication) in
+                        entry.dispensed.iter_mut().zip(quantities.iter()).zip(&entry.medications)
+                    {
+                        *dispensed = dispensed
+                            .checked_add(*quantity)
+                            .filter(|total| *total <= medication.quantity)
+                            .ok_or(Error::<T>::ExceedsPrescribed)?;
+                    }
+                    entry
+                        .dispensations
+                        .try_push(Dispensation {
+                            pharmacy: pharmacy.clone(),
+                            quantities,
+                            dispensed_at: now,
+                        })
+                        .map_err(|_| Error::<T>::TooManyDispensations)?;
+
+                    let complete = entry
+                        .dispensed
+                        .iter()
+                        .zip(&entry.medications)
+                        .all(|(dispensed, medication)| *dispensed == medication.quantity);
+                    entry.status = if complete || entry.controlled {
+                        PrescriptionStatus::Dispensed
+                    } else {
+                        PrescriptionStatus::PartiallyDispensed
+                    };
+                    Ok(entry.status)
+                },
+            )?;
+
+            Self::deposit_event(Event::PrescriptionDispensed { pharmacy, prescription, status });
+
+            Ok(())
+        }
+    }
+}
+
+// -------------------------------------------------------------------------
+// Public queries (runtime API)
+// -------------------------------------------------------------------------
+
+impl<T: Config> Pallet<T> {
+    /// Returns the prescription `id`, if any, to its patient, its prescriber
+    /// or a registered pharmacy.
+    pub fn prescription(
+        payload: QueryPayload<T::AccountId, BlockNumberFor<T>>,
+        signature: T::Signature,
+        id: PrescriptionId,
+    ) -> Result<Option<PrescriptionOf<T>>, QueryError> {
+        Self::verify_query(&payload, &signature)?;
+        let reader = &payload.reader;
+
+        let Some(entry) = Prescriptions::<T>::get(id) else {
+            return Ok(None);
+        };
+        ensure!(
+            *reader == entry.patient ||
+                *reader == entry.doctor ||
+                Pharmacies::<T>::contains_key(reader),
+            QueryError::AccessDenied
+        );
+
+        Ok(Some(entry))
+    }
+
+    /// Returns a page of the prescriptions of the patient `payload.reader`,
+    /// oldest first, walking at most `limit` entries of
+    /// [`PatientPrescriptions`] after the prescription `after` (`None` for
+    /// the first page).
+    pub fn prescriptions_of_patient(
+        payload: QueryPayload<T::AccountId, BlockNumberFor<T>>,
+        signature: T::Signature,
+        after: Option<PrescriptionId>,
+        limit: u32,
+    ) -> Result<PrescriptionPageOf<T>, QueryError> {
+        Self::verify_query(&payload, &signature)?;
+        let reader = &payload.reader;
+
+        let ids = match after {
+            Some(after) => PatientPrescriptions::<T>::iter_key_prefix_from(
+                reader,
+                PatientPrescriptions::<T>::hashed_key_for(reader, PrescriptionKey::from(after)),
+            ),
+            None => PatientPrescriptions::<T>::iter_key_prefix(reader),
+        };
+
+        Ok(Self::page(ids, limit))
+    }
+
+    /// Returns a page of the prescriptions issued by the doctor
+    /// `payload.reader`, oldest first, walking at most `limit` entries of
+    /// [`DoctorPrescriptions`] after the prescription `after`.
+    pub fn prescriptions_by_doctor(
+        payload: QueryPayload<T::AccountId, BlockNumberFor<T>>,
+        signature: T::Signature,
+        after: Option<PrescriptionId>,
+        limit: u32,
+    ) -> Result<PrescriptionPageOf<T>, QueryError> {
+        Self::verify_query(&payload, &signature)?;
+        let reader = &payload.reader;
+
+        let ids = match after {
+            Some(after) => DoctorPrescriptions::<T>::iter_key_prefix_from(
+                reader,
+                DoctorPrescriptions::<T>::hashed_key_for(reader, PrescriptionKey::from(after)),
+            ),
+            None => DoctorPrescriptions::<T>::iter_key_prefix(reader),
+        };
+
+        Ok(Self::page(ids, limit))
+    }
+
+    /// Checks that a signed query is still valid, not valid for longer than
+    /// [`Config::MaxQueryValidity`], and signed by its reader.
+    fn verify_query(
+        payload: &QueryPayload<T::AccountId, BlockNumberFor<T>>,
+        signature: &T::Signature,
+    ) -> Result<(), QueryError> {
+        let now = frame_system::Pallet::<T>::block_number();
+        ensure!(payload.valid_until >= now, QueryError::Expired);
+        ensure!(
+            payload.valid_until <= now.saturating_add(T::MaxQueryValidity::get()),
+            QueryError::ValidityTooLong
+        );
+        ensure!(
+            signature.verify(&payload.signing_message()[..], &payload.reader),
+            QueryError::BadSignature
+        );
+
+        Ok(())
+    }
+
+    /// Walks up to `limit` of the prescription `keys` and returns the
+    /// prescriptions, with the id to resume from if keys remain.
+    fn page(keys: impl Iterator<Item = PrescriptionKey>, limit: u32) -> PrescriptionPageOf<T> {
+        let mut keys = keys.peekable();
+        let mut items = Vec::new();
+        let mut last = None;
+
+        for id in keys.by_ref().take(limit as usize).map(PrescriptionId::from) {
+            last = Some(id);
+            items.extend(Prescriptions::<T>::get(id).map(|entry| (id, entry)));
+        }
+
+        let next = keys.peek().and(last);
+        PrescriptionPage { items, next }
+    }
+}
@@ -0,0 +1,71 @@
+//! Storage migrations for the Medical Prescriptions pallet.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+    weights::Weight,
+};
+
+/// Migration from storage version `0` to `1`.
+///
+/// Version `0` keyed [`PatientPrescriptions`] and [`DoctorPrescriptions`] by
+/// [`PrescriptionId`] under `Twox64Concat`, so a listing had to collect and
+/// sort every prescription of the account. Version `1` keys them by
+/// [`PrescriptionKey`] under `Identity`, so a prefix iteration is already in
+/// id order and listings page from an `after` key.
+pub mod v1 {
+    use super::*;
+    use frame_support::{storage_alias, Blake2_128Concat, Twox64Concat};
+
+    /// [`pallet::PatientPrescriptions`] as stored in version `0`.
+    #[storage_alias]
+    type PatientPrescriptions<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Twox64Concat,
+        PrescriptionId,
+        (),
+    >;
+
+    /// [`pallet::DoctorPrescriptions`] as stored in version `0`.
+    #[storage_alias]
+    type DoctorPrescriptions<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Twox64Concat,
+        PrescriptionId,
+        (),
+    >;
+
+    /// Unversioned inner migration. Use [`MigrateV0ToV1`] instead.
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let patients: Vec<_> = PatientPrescriptions::<T>::drain().collect();
+            let doctors: Vec<_> = DoctorPrescriptions::<T>::drain().collect();
+
+            for (patient, id, ()) in patients.iter() {
+                pallet::PatientPrescriptions::<T>::insert(patient, PrescriptionKey::from(*id), ());
+            }
+            for (doctor, id, ()) in doctors.iter() {
+                pallet::DoctorPrescriptions::<T>::insert(doctor, PrescriptionKey::from(*id), ());
+            }
+
+            let moved = (patients.len() as u64).saturating_add(doctors.len() as u64);
+            T::DbWeight::get().reads_writes(moved, moved.saturating_mul(2))
+        }
+    }
+
+    /// Versioned migration: only runs while the on-chain version is `0`.
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,103 @@
+//! Mock runtime for unit testing the Medical Prescriptions pallet.
+
+use crate as pallet_medical_prescriptions;
+
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU64, Time},
+};
+use frame_system::EnsureRoot;
+use pallet_doctor_registry::DoctorRegistryVerifier;
+use sp_runtime::{
+    testing::{TestSignature, UintAuthorityId},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// Account that is not a registered doctor in [`MockDoctorRegistry`].
+pub const UNREGISTERED_DOCTOR: u64 = 99;
+
+parameter_types! {
+    /// Timestamp returned by [`MockTime`]; tests set it explicitly.
+    pub static Now: u64 = 0;
+}
+
+/// Mock doctor registry: every account except [`UNREGISTERED_DOCTOR`] is an
+/// active doctor.
+pub struct MockDoctorRegistry;
+
+impl DoctorRegistryVerifier<u64> for MockDoctorRegistry {
+    fn is_active_doctor(who: &u64) -> bool {
+        *who != UNREGISTERED_DOCTOR
+    }
+}
+
+/// Mock time provider backed by the [`Now`] static.
+pub struct MockTime;
+
+impl Time for MockTime {
+    type Moment = u64;
+
+    fn now() -> u64 {
+        Now::get()
+    }
+}
+
+#[frame_support::runtime]
+mod runtime {
+    //! Test runtime that wires the pallet under test.
+
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    /// System pallet.
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    /// Pallet under test.
+    #[runtime::pallet_index(1)]
+    pub type MedicalPrescriptions = pallet_medical_prescriptions::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+impl pallet_medical_prescriptions::Config for Test {
+    type DoctorRegistry = MockDoctorRegistry;
+    type Time = MockTime;
+    /// Root plays the registrar role in tests.
+    type PharmacyRegistrarOrigin = EnsureRoot<u64>;
+    type Signature = TestSignature;
+    type Signer = UintAuthorityId;
+    type MaxQueryValidity = ConstU64<10>;
+    type WeightInfo = ();
+}
+
+/// Builds the [`sp_io::TestExternalities`] environment for unit tests.
+///
+/// # Panics
+/// Panics if the genesis storage cannot be built (should not happen in a
+/// correctly configured mock runtime).
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .expect("genesis storage should build")
+        .into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,410 @@
+//! Unit tests for the Medical Prescriptions pallet.
+
+use crate::{
+    mock::*, Dispensation, DispensePayload, Error, Event, Medication, Medications,
+    PrescriptionPage, PrescriptionStatus, Quantities, QueryError, QueryPayload,
+};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchResult};
+use sp_core::H256;
+use sp_runtime::{testing::TestSignature, DispatchError};
+
+/// Patient account used across tests.
+const PATIENT: u64 = 1;
+
+/// Doctor account used across tests.
+const DOCTOR: u64 = 10;
+
+/// Pharmacy account used across tests.
+const PHARMACY: u64 = 20;
+
+/// Validity used across tests.
+const VALID_UNTIL: u64 = 1_000;
+
+/// Medications with the given quantities.
+fn medications(quantities: &[u32]) -> Medications<H256> {
+    quantities
+        .iter()
+        .enumerate()
+        .map(|(i, quantity)| Medication {
+            medication_hash: H256::repeat_byte(i as u8 + 1),
+            quantity: *quantity,
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+fn quantities(quantities: &[u32]) -> Quantities {
+    quantities.to_vec().try_into().unwrap()
+}
+
+/// Registers [`PHARMACY`] and issues a prescription with the given
+/// quantities, returning its id.
+fn setup(prescribed: &[u32], controlled: bool) -> u64 {
+    assert_ok!(MedicalPrescriptions::register_pharmacy(
+        RuntimeOrigin::root(),
+        PHARMACY,
+        H256::repeat_byte(9),
+    ));
+    let id = MedicalPrescriptions::next_prescription_id();
+    assert_ok!(MedicalPrescriptions::issue(
+        RuntimeOrigin::signed(DOCTOR),
+        PATIENT,
+        medications(prescribed),
+        VALID_UNTIL,
+        controlled,
+    ));
+    id
+}
+
+/// Issues a prescription from [`DOCTOR`] to [`PATIENT`], returning its id.
+fn issue() -> u64 {
+    let id = MedicalPrescriptions::next_prescription_id();
+    assert_ok!(MedicalPrescriptions::issue(
+        RuntimeOrigin::signed(DOCTOR),
+        PATIENT,
+        medications(&[1]),
+        VALID_UNTIL,
+        false,
+    ));
+    id
+}
+
+/// Signature by `signer` of the next dispensation of `id` by `pharmacy`.
+fn authorization(signer: u64, pharmacy: u64, id: u64, dispensed: &[u32]) -> TestSignature {
+    let dispensation =
+        MedicalPrescriptions::prescriptions(id).map_or(0, |p| p.dispensations.len() as u32);
+    let payload = DispensePayload {
+        prescription: id,
+        pharmacy,
+        dispensation,
+        quantities: quantities(dispensed),
+    };
+    TestSignature(signer, payload.signing_message())
+}
+
+/// Dispenses with the patient's authorization.
+fn dispense(pharmacy: u64, id: u64, dispensed: &[u32]) -> DispatchResult {
+    MedicalPrescriptions::dispense(
+        RuntimeOrigin::signed(pharmacy),
+        id,
+        quantities(dispensed),
+        authorization(PATIENT, pharmacy, id, dispensed),
+    )
+}
+
+/// Query by `reader`, valid until block 5, with its signature.
+fn query(reader: u64) -> (QueryPayload<u64, u64>, TestSignature) {
+    let payload = QueryPayload { reader, valid_until: 5 };
+    let signature = TestSignature(reader, payload.signing_message());
+    (payload, signature)
+}
+
+/// Ensures a doctor can issue a prescription and both parties can list it.
+#[test]
+fn issue_works() {
+    new_test_ext().execute_with(|| {
+        Now::set(100);
+        let id = setup(&[2, 1], false);
+        System::assert_last_event(
+            Event::PrescriptionIssued { doctor: DOCTOR, patient: PATIENT, prescription: id }
+                .into(),
+        );
+
+        let prescription = MedicalPrescriptions::prescriptions(id).unwrap();
+        assert_eq!(prescription.status, PrescriptionStatus::Issued);
+        assert_eq!(prescription.dispensed.to_vec(), vec![0, 0]);
+        assert_eq!(prescription.issued_at, 100);
+
+        let (payload, signature) = query(PATIENT);
+        let of_patient =
+            MedicalPrescriptions::prescriptions_of_patient(payload, signature, None, 10).unwrap();
+        assert_eq!(of_patient.items, vec![(id, prescription)]);
+        let (payload, signature) = query(DOCTOR);
+        let by_doctor =
+            MedicalPrescriptions::prescriptions_by_doctor(payload, signature, None, 10).unwrap();
+        assert_eq!(by_doctor.items[0].0, id);
+    });
+}
+
+/// Ensures prescriptions are validated on issuance.
+#[test]
+fn issue_validates_input() {
+    new_test_ext().execute_with(|| {
+        Now::set(100);
+        let issue = |doctor: u64, prescribed: &[u32], valid_until: u64| {
+            MedicalPrescriptions::issue(
+                RuntimeOrigin::signed(doctor),
+                PATIENT,
+                medications(prescribed),
+                valid_until,
+                false,
+            )
+        };
+
+        assert_noop!(
+            issue(UNREGISTERED_DOCTOR, &[1], VALID_UNTIL),
+            Error::<Test>::DoctorNotRegistered
+        );
+        assert_noop!(issue(DOCTOR, &[], VALID_UNTIL), Error::<Test>::NoMedications);
+        assert_noop!(issue(DOCTOR, &[1, 0], VALID_UNTIL), Error::<Test>::InvalidQuantity);
+        assert_noop!(issue(DOCTOR, &[1], 100), Error::<Test>::InvalidValidity);
+    });
+}
+
+/// Ensures partial dispensing tracks what is left and rejects dispensing
+/// more than prescribed.
+#[test]
+fn partial_dispensing_works() {
+    new_test_ext().execute_with(|| {
+        let id = setup(&[2, 1], false);
+
+        Now::set(200);
+        assert_ok!(dispense(PHARMACY, id, &[1, 0]));
+        System::assert_last_event(
+            Event::PrescriptionDispensed {
+                pharmacy: PHARMACY,
+                prescription: id,
+                status: PrescriptionStatus::PartiallyDispensed,
+            }
+            .into(),
+        );
+
+        assert_noop!(dispense(PHARMACY, id, &[2, 0]), Error::<Test>::ExceedsPrescribed);
+
+        assert_ok!(dispense(PHARMACY, id, &[1, 1]));
+        let prescription = MedicalPrescriptions::prescriptions(id).unwrap();
+        assert_eq!(prescription.status, PrescriptionStatus::Dispensed);
+        assert_eq!(prescription.dispensed.to_vec(), vec![2, 1]);
+        assert_eq!(
+            prescription.dispensations[0],
+            Dispensation { pharmacy: PHARMACY, quantities: quantities(&[1, 0]), dispensed_at: 200 }
+        );
+
+        assert_noop!(dispense(PHARMACY, id, &[0, 1]), Error::<Test>::AlreadyDispensed);
+    });
+}
+
+/// Ensures a controlled prescription is closed by its first dispensation.
+#[test]
+fn controlled_prescription_is_retained() {
+    new_test_ext().execute_with(|| {
+        let id = setup(&[30], true);
+
+        assert_ok!(dispense(PHARMACY, id, &[10]));
+        assert_eq!(
+            MedicalPrescriptions::prescriptions(id).unwrap().status,
+            PrescriptionStatus::Dispensed
+        );
+        assert_noop!(dispense(PHARMACY, id, &[10]), Error::<Test>::AlreadyDispensed);
+    });
+}
+
+/// Ensures only registered pharmacies dispense valid, unexpired prescriptions.
+#[test]
+fn dispense_validates_pharmacy_and_prescription() {
+    new_test_ext().execute_with(|| {
+        let id = setup(&[2], false);
+
+        assert_noop!(dispense(DOCTOR, id, &[1]), Error::<Test>::NotPharmacy);
+        assert_noop!(dispense(PHARMACY, id + 1, &[1]), Error::<Test>::PrescriptionNotFound);
+        assert_noop!(dispense(PHARMACY, id, &[1, 1]), Error::<Test>::QuantityMismatch);
+        assert_noop!(dispense(PHARMACY, id, &[0]), Error::<Test>::InvalidQuantity);
+
+        Now::set(VALID_UNTIL + 1);
+        assert_noop!(dispense(PHARMACY, id, &[1]), Error::<Test>::PrescriptionExpired);
+
+        assert_ok!(MedicalPrescriptions::remove_pharmacy(RuntimeOrigin::root(), PHARMACY));
+        assert_noop!(dispense(PHARMACY, id, &[1]), Error::<Test>::NotPharmacy);
+    });
+}
+
+/// Ensures only the registrar origin manages pharmacies.
+#[test]
+fn pharmacy_registration_requires_registrar() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            MedicalPrescriptions::register_pharmacy(
+                RuntimeOrigin::signed(PHARMACY),
+                PHARMACY,
+                H256::zero(),
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(MedicalPrescriptions::register_pharmacy(
+            RuntimeOrigin::root(),
+            PHARMACY,
+            H256::zero(),
+        ));
+        System::assert_last_event(Event::PharmacyRegistered { pharmacy: PHARMACY }.into());
+        assert_noop!(
+            MedicalPrescriptions::register_pharmacy(RuntimeOrigin::root(), PHARMACY, H256::zero()),
+            Error::<Test>::AlreadyPharmacy
+        );
+    });
+}
+
+/// Ensures a dispensation needs the patient's authorization for that
+/// pharmacy, dispensation and quantities, and that it cannot be replayed.
+#[test]
+fn dispense_requires_patient_authorization() {
+    new_test_ext().execute_with(|| {
+        let id = setup(&[2], false);
+        let other_pharmacy = PHARMACY + 1;
+        assert_ok!(MedicalPrescriptions::register_pharmacy(
+            RuntimeOrigin::root(),
+            other_pharmacy,
+            H256::zero(),
+        ));
+        let dispense_with = |pharmacy: u64, signature: TestSignature| {
+            MedicalPrescriptions::dispense(
+                RuntimeOrigin::signed(pharmacy),
+                id,
+                quantities(&[1]),
+                signature,
+            )
+        };
+
+        assert_noop!(
+            dispense_with(PHARMACY, authorization(DOCTOR, PHARMACY, id, &[1])),
+            Error::<Test>::BadAuthorization
+        );
+        assert_noop!(
+            dispense_with(PHARMACY, authorization(PATIENT, other_pharmacy, id, &[1])),
+            Error::<Test>::BadAuthorization
+        );
+        assert_noop!(
+            dispense_with(PHARMACY, authorization(PATIENT, PHARMACY, id, &[2])),
+            Error::<Test>::BadAuthorization
+        );
+
+        let signature = authorization(PATIENT, PHARMACY, id, &[1]);
+        assert_ok!(dispense_with(PHARMACY, signature.clone()));
+        assert_noop!(dispense_with(PHARMACY, signature), Error::<Test>::BadAuthorization);
+    });
+}
+
+/// Ensures prescriptions are only served to signed queries of the patient,
+/// the prescriber or a pharmacy.
+#[test]
+fn queries_require_signed_reader() {
+    new_test_ext().execute_with(|| {
+        let id = setup(&[2], false);
+        let stranger = 30;
+
+        for reader in [PATIENT, DOCTOR, PHARMACY] {
+            let (payload, signature) = query(reader);
+            assert!(MedicalPrescriptions::prescription(payload, signature, id).unwrap().is_some());
+        }
+        let (payload, signature) = query(stranger);
+        assert_eq!(
+            MedicalPrescriptions::prescription(payload, signature, id),
+            Err(QueryError::AccessDenied)
+        );
+
+        // Listings only return the reader's own prescriptions.
+        let (payload, signature) = query(stranger);
+        assert_eq!(
+            MedicalPrescriptions::prescriptions_of_patient(payload, signature, None, 10),
+            Ok(PrescriptionPage { items: vec![], next: None })
+        );
+
+        let (payload, _) = query(PATIENT);
+        assert_eq!(
+            MedicalPrescriptions::prescriptions_of_patient(
+                payload.clone(),
+                TestSignature(stranger, payload.signing_message()),
+                None,
+                10
+            ),
+            Err(QueryError::BadSignature)
+        );
+
+        let payload = QueryPayload { reader: PATIENT, valid_until: 20 };
+        let signature = TestSignature(PATIENT, payload.signing_message());
+        assert_eq!(
+            MedicalPrescriptions::prescriptions_of_patient(payload, signature, None, 10),
+            Err(QueryError::ValidityTooLong)
+        );
+
+        System::set_block_number(6);
+        let (payload, signature) = query(PATIENT);
+        assert_eq!(
+            MedicalPrescriptions::prescriptions_of_patient(payload, signature, None, 10),
+            Err(QueryError::Expired)
+        );
+    });
+}
+
+/// Ensures listings walk ids in numeric order and resume after the last id
+/// of the previous page.
+#[test]
+fn listings_are_paginated_by_id() {
+    new_test_ext().execute_with(|| {
+        // Little-endian or hashed keys would not keep these in order.
+        crate::NextPrescriptionId::<Test>::put(255);
+        let ids: Vec<_> = (0..3).map(|_| issue()).collect();
+        assert_eq!(ids, vec![255, 256, 257]);
+
+        let page = |reader, after| {
+            let (payload, signature) = query(reader);
+            let page = if reader == PATIENT {
+                MedicalPrescriptions::prescriptions_of_patient(payload, signature, after, 2)
+            } else {
+                MedicalPrescriptions::prescriptions_by_doctor(payload, signature, after, 2)
+            }
+            .unwrap();
+            (page.items.into_iter().map(|(id, _)| id).collect::<Vec<_>>(), page.next)
+        };
+        for reader in [PATIENT, DOCTOR] {
+            assert_eq!(page(reader, None), (vec![255, 256], Some(256)));
+            assert_eq!(page(reader, Some(256)), (vec![257], None));
+        }
+    });
+}
+
+/// Ensures the v1 migration re-keys the listing indexes in id order.
+#[test]
+fn migration_v0_to_v1_orders_indexes() {
+    use codec::Encode;
+    use frame_support::{
+        storage::{unhashed, StoragePrefixedMap},
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        Blake2_128Concat, StorageHasher, Twox64Concat,
+    };
+
+    new_test_ext().execute_with(|| {
+        crate::NextPrescriptionId::<Test>::put(255);
+        let ids = [issue(), issue()];
+
+        StorageVersion::new(0).put::<MedicalPrescriptions>();
+        let _ = crate::PatientPrescriptions::<Test>::clear(u32::MAX, None);
+        let _ = crate::DoctorPrescriptions::<Test>::clear(u32::MAX, None);
+        let old_key = |prefix: [u8; 32], account: u64, id: u64| {
+            let mut key = prefix.to_vec();
+            key.extend(Blake2_128Concat::hash(&account.encode()));
+            key.extend(Twox64Concat::hash(&id.encode()));
+            key
+        };
+        for id in ids {
+            let patients = crate::PatientPrescriptions::<Test>::final_prefix();
+            unhashed::put(&old_key(patients, PATIENT, id), &());
+            let doctors = crate::DoctorPrescriptions::<Test>::final_prefix();
+            unhashed::put(&old_key(doctors, DOCTOR, id), &());
+        }
+
+        crate::migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        let (payload, signature) = query(PATIENT);
+        let page =
+            MedicalPrescriptions::prescriptions_of_patient(payload, signature, None, 10).unwrap();
+        assert_eq!(page.items.into_iter().map(|(id, _)| id).collect::<Vec<_>>(), ids);
+        let (payload, signature) = query(DOCTOR);
+        let page =
+            MedicalPrescriptions::prescriptions_by_doctor(payload, signature, None, 10).unwrap();
+        assert_eq!(page.items.into_iter().map(|(id, _)| id).collect::<Vec<_>>(), ids);
+        assert_eq!(crate::PatientPrescriptions::<Test>::iter().count(), 2);
+        assert_eq!(MedicalPrescriptions::on_chain_storage_version(), 1);
+    });
+}
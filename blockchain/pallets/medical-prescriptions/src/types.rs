@@ -0,0 +1,215 @@
+use alloc::vec::Vec;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+
+/// Identifier of a prescription, assigned sequentially.
+pub type PrescriptionId = u64;
+
+/// Key of a prescription in the per-patient and per-doctor indexes.
+///
+/// Stored big-endian so that, under the `Identity` hasher, the order of the
+/// storage keys is the numeric order of the ids and a prefix iteration walks
+/// prescriptions oldest first.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct PrescriptionKey([u8; 8]);
+
+impl From<PrescriptionId> for PrescriptionKey {
+    fn from(id: PrescriptionId) -> Self {
+        Self(id.to_be_bytes())
+    }
+}
+
+impl From<PrescriptionKey> for PrescriptionId {
+    fn from(key: PrescriptionKey) -> Self {
+        PrescriptionId::from_be_bytes(key.0)
+    }
+}
+
+/// Medications of a prescription (up to 16).
+pub type Medications<Hash> = BoundedVec<Medication<Hash>, ConstU32<16>>;
+
+/// Quantities, one per medication of a prescription, in the same order.
+pub type Quantities = BoundedVec<u32, ConstU32<16>>;
+
+/// Dispensations of a prescription (up to 16).
+pub type Dispensations<AccountId, Moment> =
+    BoundedVec<Dispensation<AccountId, Moment>, ConstU32<16>>;
+
+/// A prescribed medication.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct Medication<Hash> {
+    /// Hash of the medication description (drug, dosage and posology).
+    pub medication_hash: Hash,
+    /// Number of units prescribed.
+    pub quantity: u32,
+}
+
+/// Dispensing status of a prescription.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum PrescriptionStatus {
+    /// Nothing dispensed yet.
+    Issued,
+    /// Some units were dispensed; the rest may still be.
+    PartiallyDispensed,
+    /// Closed: every unit was dispensed, or the prescription was retained.
+    Dispensed,
+}
+
+/// A dispensation made by a pharmacy.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Dispensation<AccountId, Moment> {
+    /// The pharmacy that dispensed.
+    pub pharmacy: AccountId,
+    /// Units dispensed, one entry per medication.
+    pub quantities: Quantities,
+    /// Timestamp of the dispensation.
+    pub dispensed_at: Moment,
+}
+
+/// An electronic prescription.
+///
+/// Medications are stored as hashes only; the pharmacy gets the clear-text
+/// prescription from the patient and checks it against them.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Prescription<AccountId, Hash, Moment> {
+    /// The prescribing doctor.
+    pub doctor: AccountId,
+    /// The patient.
+    pub patient: AccountId,
+    /// Prescribed medications.
+    pub medications: Medications<Hash>,
+    /// Units dispensed so far, one entry per medication.
+    pub dispensed: Quantities,
+    /// Whether the prescription contains controlled substances.
+    ///
+    /// Controlled prescriptions are retained by the pharmacy, so their
+    /// first dispensation closes them.
+    pub controlled: bool,
+    /// Last moment at which the prescription can be dispensed.
+    pub valid_until: Moment,
+    /// Timestamp of issuance.
+    pub issued_at: Moment,
+    /// Dispensing status.
+    pub status: PrescriptionStatus,
+    /// Dispensations, oldest first.
+    pub dispensations: Dispensations<AccountId, Moment>,
+}
+
+/// One page of a `prescriptions_of_patient` / `prescriptions_by_doctor`
+/// listing.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PrescriptionPage<AccountId, Hash, Moment> {
+    /// Prescriptions of the page, oldest first.
+    pub items: Vec<(PrescriptionId, Prescription<AccountId, Hash, Moment>)>,
+    /// `after` of the next page, or `None` if this is the last one.
+    pub next: Option<PrescriptionId>,
+}
+
+/// A registered pharmacy.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Pharmacy<Hash, BlockNumber> {
+    /// Hash of the pharmacy's license (e.g. CNPJ and health authority permit).
+    pub license_hash: Hash,
+    /// Block in which the pharmacy was registered.
+    pub registered_at: BlockNumber,
+}
+
+/// Domain separator prepended to [`DispensePayload`] before signing.
+pub const DISPENSE_DOMAIN: &[u8] = b"healthchain/dispense:";
+
+/// Domain separator prepended to [`QueryPayload`] before signing.
+pub const QUERY_DOMAIN: &[u8] = b"healthchain/prescriptions:";
+
+/// Dispensation authorized by the patient.
+///
+/// The patient signs it off-chain (e.g. shown as a QR code at the counter)
+/// and the pharmacy submits the signature with `dispense`. It is bound to
+/// one pharmacy, one dispensation of the prescription and its quantities,
+/// so it cannot be reused.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DispensePayload<AccountId> {
+    /// The prescription.
+    pub prescription: PrescriptionId,
+    /// The pharmacy allowed to dispense.
+    pub pharmacy: AccountId,
+    /// Number of dispensations the prescription had before this one.
+    pub dispensation: u32,
+    /// Units to dispense, one entry per medication.
+    pub quantities: Quantities,
+}
+
+impl<AccountId: Encode> DispensePayload<AccountId> {
+    /// Bytes the patient must sign: [`DISPENSE_DOMAIN`] followed by the
+    /// SCALE-encoded payload.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = DISPENSE_DOMAIN.to_vec();
+        self.encode_to(&mut message);
+        message
+    }
+}
+
+/// Off-chain prescription query, signed by `reader`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct QueryPayload<AccountId, BlockNumber> {
+    /// Account asking: the patient, the prescriber or a pharmacy.
+    pub reader: AccountId,
+    /// Last block at which the query is accepted.
+    pub valid_until: BlockNumber,
+}
+
+impl<AccountId: Encode, BlockNumber: Encode> QueryPayload<AccountId, BlockNumber> {
+    /// Bytes the reader must sign: [`QUERY_DOMAIN`] followed by the
+    /// SCALE-encoded payload.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = QUERY_DOMAIN.to_vec();
+        self.encode_to(&mut message);
+        message
+    }
+}
+
+/// Reasons a signed prescription query is refused.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum QueryError {
+    /// The signature does not match `reader`.
+    BadSignature,
+    /// `valid_until` is in the past.
+    Expired,
+    /// `valid_until` is further ahead than `MaxQueryValidity` allows.
+    ValidityTooLong,
+    /// `reader` is not the patient, the prescriber or a registered pharmacy.
+    AccessDenied,
+}
@@ -0,0 +1,62 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+// ==========================================================================
+// ⚠️  PLACEHOLDER / MOCK WEIGHTS
+// 
+// TODO: This file is currently manually maintained for MVP development.
+// Before Mainnet release, replace this entire file with the output of:
+// ./target/release/healthchain-node benchmark pallet ...
+// ==========================================================================
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+pub trait WeightInfo {
+    fn register_pharmacy() -> Weight { Weight::from_parts(10_000, 0) }
+    fn remove_pharmacy() -> Weight { Weight::from_parts(10_000, 0) }
+    fn issue() -> Weight { Weight::from_parts(10_000, 0) }
+    fn dispense() -> Weight { Weight::from_parts(10_000, 0) }
+}
+
+/// Weights for pallet_medical_prescriptions using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn register_pharmacy() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn remove_pharmacy() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+    fn issue() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 4))
+    }
+    fn dispense() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 1))
+    }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+    fn register_pharmacy() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn remove_pharmacy() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+    fn issue() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 4))
+    }
+    fn dispense() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 1))
+    }
+}
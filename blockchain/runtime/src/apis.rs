@@ -43,15 +43,34 @@ use sp_version::RuntimeVersion;
 // Local module imports
 // ATENÇÃO: Adicionei AllPalletsWithSystem aqui
 use super::{
-    AccountId, Aura, Balance, Block, Executive, Grandpa, InherentDataExt, Nonce, Runtime,
-    RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment, VERSION,
+    AccountId, Aura, Balance, Block, BlockNumber, Executive, Grandpa, Hash, InherentDataExt,
+    MedicalCertificates, MedicalExams, MedicalHistory, MedicalHistoryReader, MedicalPermissions,
+    MedicalPrescriptions, Nonce, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, Signature,
+    System, TransactionPayment, VERSION,
+};
+use pallet_medical_certificates::{CertificateVerification, VerificationCode};
+use pallet_medical_exams::{
+    ExamOrder, ExamOrderId, ExamOrderPage, ExamOrderV0, QueryError as ExamQueryError,
+    QueryPayload as ExamQueryPayload,
+};
+use pallet_medical_history::{
+    Endorsement, FileHash, MedicalRecord, Provenance, RecordPage, WrappedKey,
+};
+use pallet_medical_history_reader::{
+    AccessLogEntry, AccessLogEntryV1, ListPayload, ReadError, ReadPayload, ReadPayloadV1,
+};
+use pallet_medical_permissions::{
+    EmergencyIncident, GrantInfo, GrantPage, Guardianship, IncidentContest, PermissionLogEntry,
+    PermissionLogEntryV9, Referral, ReferralId, ReferralPage,
+};
+use pallet_medical_prescriptions::{
+    Prescription, PrescriptionId, PrescriptionPage, QueryError, QueryPayload,
 };
 
 // Para possibilitar ativar os benchmarks e calcular na real qual é o peso de cada extrínseco  ----- START ---------
 #[cfg(feature = "runtime-benchmarks")]
 use crate::{
-    AllPalletsWithSystem, Balances, MedicalHistory, MedicalHistoryReader, MedicalPermissions, Sudo,
-    Timestamp,
+    AllPalletsWithSystem, Balances, Sudo, Timestamp,
 };
 // Para possibilitar ativar os benchmarks e calcular na real qual é o peso de cada extrínseco  ----- END ---------
 
@@ -72,6 +91,477 @@ frame_benchmarking::define_benchmarks! {
 
 }
 
+// ==========================================================================
+// APIS DO HEALTHCHAIN
+// Consultas somente leitura expostas aos clientes (frontend / RPC)
+// ==========================================================================
+sp_api::decl_runtime_apis! {
+    /// Public queries over the Medical History pallet.
+    ///
+    /// Version `1` listed records by offset with `records_of_patient(patient,
+    /// cursor, limit)` / `records_by_doctor(doctor, cursor, limit)`; the
+    /// `provenance` filter was later inserted after the account without a
+    /// version bump, so version `1` runtimes disagree on those arguments.
+    /// Version `2` paged both listings by position and always took
+    /// `provenance`. Version `3` removed the record lookups and listings
+    /// (`record`, `latest_record`, `record_history`, `records_of_patient`,
+    /// `records_by_doctor`): they returned any patient's records to anyone.
+    /// They are now served by `MedicalHistoryReaderApi` to signed requests
+    /// from readers with access, so clients must check the version before
+    /// calling them and never call them on version `3`.
+    #[api_version(3)]
+    pub trait MedicalHistoryApi<AccountId, Moment>
+    where
+        AccountId: codec::Codec,
+        Moment: codec::Codec,
+    {
+        /// Returns the endorsement of the self-reported record `file_hash`, if any.
+        fn endorsement(file_hash: FileHash) -> Option<Endorsement<AccountId, Moment>>;
+
+        /// Returns the doctor that ordered the laboratory result `file_hash`, if any.
+        fn ordering_doctor(file_hash: FileHash) -> Option<AccountId>;
+    }
+
+    /// Key envelope queries over the Medical History pallet.
+    pub trait MedicalKeysApi<AccountId, BlockNumber>
+    where
+        AccountId: codec::Codec,
+        BlockNumber: codec::Codec,
+    {
+        /// Returns the envelope wrapping the key of `file_hash` for
+        /// `recipient`, if `recipient` may still read the record.
+        fn key_envelope(recipient: AccountId, file_hash: FileHash) -> Option<WrappedKey>;
+
+        /// Returns the doctors whose revocation still requires `patient` to
+        /// rotate keys, with the block of the revocation.
+        fn key_rotations_required(patient: AccountId) -> Vec<(AccountId, BlockNumber)>;
+    }
+
+    /// Read-only queries over the Medical Permissions pallet.
+    ///
+    /// Version `2` pages `patients_of` / `doctors_of` by account key instead
+    /// of by position. Version `3` names the grantee of each permission log
+    /// entry with a `LogSubject`, so institution entries carry the
+    /// institution instead of its admin. Version `4` pages
+    /// `referrals_of_patient` / `referrals_of_specialist` by referral id
+    /// instead of by position.
+    #[api_version(4)]
+    pub trait MedicalPermissionsApi<AccountId, BlockNumber, Moment>
+    where
+        AccountId: codec::Codec,
+        BlockNumber: codec::Codec,
+        Moment: codec::Codec,
+    {
+        /// Returns up to `limit` entries of `patient`'s permission history,
+        /// starting at sequence number `from` (oldest first).
+        fn permission_log(
+            patient: AccountId,
+            from: u64,
+            limit: u32,
+        ) -> Vec<(u64, PermissionLogEntry<AccountId, BlockNumber, Moment>)>;
+
+        /// Same as above, with the institution's admin as the doctor of
+        /// institution entries.
+        #[changed_in(3)]
+        fn permission_log(
+            patient: AccountId,
+            from: u64,
+            limit: u32,
+        ) -> Vec<(u64, PermissionLogEntryV9<AccountId, BlockNumber, Moment>)>;
+
+        /// Returns up to `limit` of `patient`'s break-glass incidents, with
+        /// the patient's contest if any, starting at incident id `from`.
+        fn emergency_incidents(
+            patient: AccountId,
+            from: u32,
+            limit: u32,
+        ) -> Vec<(
+            u32,
+            EmergencyIncident<AccountId, Hash, BlockNumber, Moment>,
+            Option<IncidentContest<Hash, BlockNumber>>,
+        )>;
+
+        /// Returns `patient`'s guardians, including ended guardianships.
+        fn guardians(patient: AccountId) -> Vec<(AccountId, Guardianship<BlockNumber>)>;
+
+        /// Returns a page of the patients that granted `doctor` access,
+        /// walking at most `limit` grants after the patient `after` (`None`
+        /// for the first page), in storage order.
+        fn patients_of(
+            doctor: AccountId,
+            after: Option<AccountId>,
+            limit: u32,
+        ) -> GrantPage<AccountId, BlockNumber, Moment>;
+
+        /// Returns up to `limit` patients that granted `doctor` access,
+        /// oldest grant first, skipping `cursor` entries.
+        #[changed_in(2)]
+        fn patients_of(
+            doctor: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<GrantInfo<AccountId, BlockNumber, Moment>>;
+
+        /// Returns a page of the doctors `patient` granted access to,
+        /// walking at most `limit` grants after the doctor `after`.
+        fn doctors_of(
+            patient: AccountId,
+            after: Option<AccountId>,
+            limit: u32,
+        ) -> GrantPage<AccountId, BlockNumber, Moment>;
+
+        /// Returns up to `limit` doctors `patient` granted access to,
+        /// oldest grant first, skipping `cursor` entries.
+        #[changed_in(2)]
+        fn doctors_of(
+            patient: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<GrantInfo<AccountId, BlockNumber, Moment>>;
+
+        /// Returns the referral `id`, if any.
+        fn referral(id: ReferralId) -> Option<Referral<AccountId, Hash, BlockNumber>>;
+
+        /// Returns a page of `patient`'s referrals, oldest first, walking
+        /// at most `limit` referrals after the referral `after` (`None` for
+        /// the first page).
+        fn referrals_of_patient(
+            patient: AccountId,
+            after: Option<ReferralId>,
+            limit: u32,
+        ) -> ReferralPage<AccountId, Hash, BlockNumber>;
+
+        /// Returns up to `limit` of `patient`'s referrals, oldest first,
+        /// skipping `cursor` entries.
+        #[changed_in(4)]
+        fn referrals_of_patient(
+            patient: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<(ReferralId, Referral<AccountId, Hash, BlockNumber>)>;
+
+        /// Returns a page of the referrals addressed to `specialist`, oldest
+        /// first, walking at most `limit` referrals after the referral
+        /// `after`.
+        fn referrals_of_specialist(
+            specialist: AccountId,
+            after: Option<ReferralId>,
+            limit: u32,
+        ) -> ReferralPage<AccountId, Hash, BlockNumber>;
+
+        /// Returns up to `limit` referrals addressed to `specialist`, oldest
+        /// first, skipping `cursor` entries.
+        #[changed_in(4)]
+        fn referrals_of_specialist(
+            specialist: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<(ReferralId, Referral<AccountId, Hash, BlockNumber>)>;
+    }
+
+    /// Read-only queries over the Medical History Reader pallet.
+    ///
+    /// Version `2` added a nonce to [`ReadPayload`] and the signed history
+    /// and listing reads. Version `3` returns access log entries whose
+    /// record was redacted with its tombstone commitment instead of its hash.
+    #[api_version(3)]
+    pub trait MedicalHistoryReaderApi<AccountId, Moment, BlockNumber, Signature>
+    where
+        AccountId: codec::Codec,
+        Moment: codec::Codec,
+        BlockNumber: codec::Codec,
+        Signature: codec::Codec,
+    {
+        /// Returns the requested record if `signature` proves `payload` was
+        /// signed by its reader and the reader may read the patient's data.
+        ///
+        /// Transaction-free alternative to the `read_own_data` and
+        /// `read_patient_data` extrinsics.
+        fn read_record(
+            payload: ReadPayload<AccountId, BlockNumber>,
+            signature: Signature,
+        ) -> Result<MedicalRecord<AccountId, Moment>, ReadError>;
+
+        /// Same as above, for requests signed without a nonce.
+        #[changed_in(2)]
+        fn read_record(
+            payload: ReadPayloadV1<AccountId, BlockNumber>,
+            signature: Signature,
+        ) -> Result<MedicalRecord<AccountId, Moment>, ReadError>;
+
+        /// Returns the versions of the requested record the reader may read,
+        /// oldest first, under the same checks as `read_record`.
+        fn read_record_history(
+            payload: ReadPayload<AccountId, BlockNumber>,
+            signature: Signature,
+        ) -> Result<Vec<MedicalRecord<AccountId, Moment>>, ReadError>;
+
+        /// Returns a page of the records of the patient `payload.owner` that
+        /// the reader may read, starting at position `from` (`0` for the
+        /// first page).
+        ///
+        /// At most `limit` entries are walked, capped at `MAX_PAGE_SIZE`. If
+        /// `provenance` is set, only records with that provenance are listed.
+        fn read_records_of_patient(
+            payload: ListPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> Result<RecordPage<MedicalRecord<AccountId, Moment>>, ReadError>;
+
+        /// Returns a page of the `(patient, record)` pairs created by the
+        /// doctor `payload.owner`, who must be the reader.
+        fn read_records_by_doctor(
+            payload: ListPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> Result<RecordPage<(AccountId, MedicalRecord<AccountId, Moment>)>, ReadError>;
+
+        /// Returns up to `limit` entries of `patient`'s access audit trail,
+        /// starting at sequence number `from` (oldest first).
+        fn access_log(
+            patient: AccountId,
+            from: u64,
+            limit: u32,
+        ) -> Vec<(u64, AccessLogEntry<AccountId, Moment, BlockNumber, Hash>)>;
+
+        /// Same as above, with the record hash of every entry.
+        #[changed_in(3)]
+        fn access_log(
+            patient: AccountId,
+            from: u64,
+            limit: u32,
+        ) -> Vec<(u64, AccessLogEntryV1<AccountId, Moment, BlockNumber>)>;
+
+        /// Returns every retained entry of `patient`'s access audit trail.
+        fn export_access_log(
+            patient: AccountId,
+        ) -> Vec<(u64, AccessLogEntry<AccountId, Moment, BlockNumber, Hash>)>;
+
+        /// Same as above, with the record hash of every entry.
+        #[changed_in(3)]
+        fn export_access_log(
+            patient: AccountId,
+        ) -> Vec<(u64, AccessLogEntryV1<AccountId, Moment, BlockNumber>)>;
+    }
+
+    /// Public verification of medical certificates.
+    pub trait MedicalCertificatesApi<AccountId, Moment>
+    where
+        AccountId: codec::Codec,
+        Moment: codec::Codec,
+    {
+        /// Returns the certificate with verification code `code`, without
+        /// its diagnosis hash, if any.
+        fn verify(
+            code: VerificationCode,
+        ) -> Option<CertificateVerification<AccountId, Hash, Moment>>;
+    }
+
+    /// Dispensing status queries over the Medical Prescriptions pallet.
+    ///
+    /// Lists are ordered by prescription id and paginated with an `after`
+    /// id (`None` for the first page) and a `limit`.
+    ///
+    /// Version `2` only answers queries signed by their reader
+    /// (`QueryPayload`): the lookup to the patient, the prescriber or a
+    /// pharmacy, and each listing to the patient or doctor it lists.
+    /// Version `3` pages the listings by prescription id instead of by
+    /// position.
+    #[api_version(3)]
+    pub trait MedicalPrescriptionsApi<AccountId, Moment, BlockNumber, Signature>
+    where
+        AccountId: codec::Codec,
+        Moment: codec::Codec,
+        BlockNumber: codec::Codec,
+        Signature: codec::Codec,
+    {
+        /// Returns the prescription `id`, if any, if the query is accepted.
+        fn prescription(
+            payload: QueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            id: PrescriptionId,
+        ) -> Result<Option<Prescription<AccountId, Hash, Moment>>, QueryError>;
+
+        /// Returns the prescription `id`, if any.
+        #[changed_in(2)]
+        fn prescription(id: PrescriptionId) -> Option<Prescription<AccountId, Hash, Moment>>;
+
+        /// Returns a page of the prescriptions of the patient
+        /// `payload.reader`, walking at most `limit` prescriptions after
+        /// the prescription `after`.
+        fn prescriptions_of_patient(
+            payload: QueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            after: Option<PrescriptionId>,
+            limit: u32,
+        ) -> Result<PrescriptionPage<AccountId, Hash, Moment>, QueryError>;
+
+        /// Returns up to `limit` prescriptions of the patient
+        /// `payload.reader`, skipping `cursor`.
+        #[changed_in(3)]
+        fn prescriptions_of_patient(
+            payload: QueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            cursor: u32,
+            limit: u32,
+        ) -> Result<Vec<(PrescriptionId, Prescription<AccountId, Hash, Moment>)>, QueryError>;
+
+        /// Returns up to `limit` prescriptions of `patient`, skipping `cursor`.
+        #[changed_in(2)]
+        fn prescriptions_of_patient(
+            patient: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<(PrescriptionId, Prescription<AccountId, Hash, Moment>)>;
+
+        /// Returns a page of the prescriptions issued by the doctor
+        /// `payload.reader`, walking at most `limit` prescriptions after
+        /// the prescription `after`.
+        fn prescriptions_by_doctor(
+            payload: QueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            after: Option<PrescriptionId>,
+            limit: u32,
+        ) -> Result<PrescriptionPage<AccountId, Hash, Moment>, QueryError>;
+
+        /// Returns up to `limit` prescriptions issued by the doctor
+        /// `payload.reader`, skipping `cursor`.
+        #[changed_in(3)]
+        fn prescriptions_by_doctor(
+            payload: QueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            cursor: u32,
+            limit: u32,
+        ) -> Result<Vec<(PrescriptionId, Prescription<AccountId, Hash, Moment>)>, QueryError>;
+
+        /// Returns up to `limit` prescriptions issued by `doctor`, skipping
+        /// `cursor`.
+        #[changed_in(2)]
+        fn prescriptions_by_doctor(
+            doctor: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<(PrescriptionId, Prescription<AccountId, Hash, Moment>)>;
+    }
+
+    /// Read-only queries over the Medical Exams pallet.
+    ///
+    /// Lists are ordered by order id and paginated with an `after` id
+    /// (`None` for the first page) and a `limit`.
+    ///
+    /// Version `2` only answers queries signed by their reader
+    /// (`QueryPayload`): the lookup to the patient, the ordering doctor or
+    /// the designated laboratory, and each listing to the patient or doctor
+    /// it lists. Version `3` returns orders whose result was redacted with
+    /// its tombstone commitment instead of its hash. Version `4` pages the
+    /// listings by order id instead of by position.
+    #[api_version(4)]
+    pub trait MedicalExamsApi<AccountId, Moment, BlockNumber, Signature>
+    where
+        AccountId: codec::Codec,
+        Moment: codec::Codec,
+        BlockNumber: codec::Codec,
+        Signature: codec::Codec,
+    {
+        /// Returns the exam order `id`, if any, if the query is accepted.
+        fn exam_order(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            id: ExamOrderId,
+        ) -> Result<Option<ExamOrder<AccountId, Hash, Moment>>, ExamQueryError>;
+
+        /// Same as above, with the result hash of every order.
+        #[changed_in(3)]
+        fn exam_order(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            id: ExamOrderId,
+        ) -> Result<Option<ExamOrderV0<AccountId, Hash, Moment>>, ExamQueryError>;
+
+        /// Returns the exam order `id`, if any.
+        #[changed_in(2)]
+        fn exam_order(id: ExamOrderId) -> Option<ExamOrderV0<AccountId, Hash, Moment>>;
+
+        /// Returns a page of the exam orders of the patient `payload.reader`,
+        /// walking at most `limit` orders after the order `after`.
+        fn exam_orders_of_patient(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            after: Option<ExamOrderId>,
+            limit: u32,
+        ) -> Result<ExamOrderPage<AccountId, Hash, Moment>, ExamQueryError>;
+
+        /// Returns up to `limit` exam orders of the patient `payload.reader`,
+        /// skipping `cursor`.
+        #[changed_in(4)]
+        fn exam_orders_of_patient(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            cursor: u32,
+            limit: u32,
+        ) -> Result<Vec<(ExamOrderId, ExamOrder<AccountId, Hash, Moment>)>, ExamQueryError>;
+
+        /// Same as above, with the result hash of every order.
+        #[changed_in(3)]
+        fn exam_orders_of_patient(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            cursor: u32,
+            limit: u32,
+        ) -> Result<Vec<(ExamOrderId, ExamOrderV0<AccountId, Hash, Moment>)>, ExamQueryError>;
+
+        /// Returns up to `limit` exam orders of `patient`, skipping `cursor`.
+        #[changed_in(2)]
+        fn exam_orders_of_patient(
+            patient: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<(ExamOrderId, ExamOrderV0<AccountId, Hash, Moment>)>;
+
+        /// Returns a page of the exam orders placed by the doctor
+        /// `payload.reader`, walking at most `limit` orders after the order
+        /// `after`.
+        fn exam_orders_by_doctor(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            after: Option<ExamOrderId>,
+            limit: u32,
+        ) -> Result<ExamOrderPage<AccountId, Hash, Moment>, ExamQueryError>;
+
+        /// Returns up to `limit` exam orders placed by the doctor
+        /// `payload.reader`, skipping `cursor`.
+        #[changed_in(4)]
+        fn exam_orders_by_doctor(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            cursor: u32,
+            limit: u32,
+        ) -> Result<Vec<(ExamOrderId, ExamOrder<AccountId, Hash, Moment>)>, ExamQueryError>;
+
+        /// Same as above, with the result hash of every order.
+        #[changed_in(3)]
+        fn exam_orders_by_doctor(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            cursor: u32,
+            limit: u32,
+        ) -> Result<Vec<(ExamOrderId, ExamOrderV0<AccountId, Hash, Moment>)>, ExamQueryError>;
+
+        /// Returns up to `limit` exam orders placed by `doctor`, skipping
+        /// `cursor`.
+        #[changed_in(2)]
+        fn exam_orders_by_doctor(
+            doctor: AccountId,
+            cursor: u32,
+            limit: u32,
+        ) -> Vec<(ExamOrderId, ExamOrderV0<AccountId, Hash, Moment>)>;
+    }
+}
+
 impl_runtime_apis! {
     impl sp_api::Core<Block> for Runtime {
         fn version() -> RuntimeVersion {
@@ -202,6 +692,204 @@ impl_runtime_apis! {
         }
     }
 
+    impl MedicalHistoryApi<Block, AccountId, u64> for Runtime {
+        fn endorsement(file_hash: FileHash) -> Option<Endorsement<AccountId, u64>> {
+            MedicalHistory::endorsement(&file_hash)
+        }
+
+        fn ordering_doctor(file_hash: FileHash) -> Option<AccountId> {
+            MedicalHistory::ordering_doctor(&file_hash)
+        }
+    }
+
+    impl MedicalKeysApi<Block, AccountId, BlockNumber> for Runtime {
+        fn key_envelope(recipient: AccountId, file_hash: FileHash) -> Option<WrappedKey> {
+            MedicalHistory::key_envelope(&recipient, &file_hash)
+        }
+
+        fn key_rotations_required(patient: AccountId) -> Vec<(AccountId, BlockNumber)> {
+            MedicalHistory::key_rotations_required(&patient)
+        }
+    }
+
+    impl MedicalPermissionsApi<Block, AccountId, BlockNumber, u64> for Runtime {
+        fn permission_log(
+            patient: AccountId,
+            from: u64,
+            limit: u32,
+        ) -> Vec<(u64, PermissionLogEntry<AccountId, BlockNumber, u64>)> {
+            MedicalPermissions::permission_log(&patient, from, limit)
+        }
+
+        fn emergency_incidents(
+            patient: AccountId,
+            from: u32,
+            limit: u32,
+        ) -> Vec<(
+            u32,
+            EmergencyIncident<AccountId, Hash, BlockNumber, u64>,
+            Option<IncidentContest<Hash, BlockNumber>>,
+        )> {
+            MedicalPermissions::emergency_incidents(&patient, from, limit)
+        }
+
+        fn guardians(patient: AccountId) -> Vec<(AccountId, Guardianship<BlockNumber>)> {
+            MedicalPermissions::guardians_of(&patient)
+        }
+
+        fn patients_of(
+            doctor: AccountId,
+            after: Option<AccountId>,
+            limit: u32,
+        ) -> GrantPage<AccountId, BlockNumber, u64> {
+            MedicalPermissions::patients_of(&doctor, after, limit)
+        }
+
+        fn doctors_of(
+            patient: AccountId,
+            after: Option<AccountId>,
+            limit: u32,
+        ) -> GrantPage<AccountId, BlockNumber, u64> {
+            MedicalPermissions::doctors_of(&patient, after, limit)
+        }
+
+        fn referral(id: ReferralId) -> Option<Referral<AccountId, Hash, BlockNumber>> {
+            MedicalPermissions::referrals(id)
+        }
+
+        fn referrals_of_patient(
+            patient: AccountId,
+            after: Option<ReferralId>,
+            limit: u32,
+        ) -> ReferralPage<AccountId, Hash, BlockNumber> {
+            MedicalPermissions::referrals_of_patient(&patient, after, limit)
+        }
+
+        fn referrals_of_specialist(
+            specialist: AccountId,
+            after: Option<ReferralId>,
+            limit: u32,
+        ) -> ReferralPage<AccountId, Hash, BlockNumber> {
+            MedicalPermissions::referrals_of_specialist(&specialist, after, limit)
+        }
+    }
+
+    impl MedicalHistoryReaderApi<Block, AccountId, u64, BlockNumber, Signature> for Runtime {
+        fn read_record(
+            payload: ReadPayload<AccountId, BlockNumber>,
+            signature: Signature,
+        ) -> Result<MedicalRecord<AccountId, u64>, ReadError> {
+            MedicalHistoryReader::read_record(payload, signature)
+        }
+
+        fn read_record_history(
+            payload: ReadPayload<AccountId, BlockNumber>,
+            signature: Signature,
+        ) -> Result<Vec<MedicalRecord<AccountId, u64>>, ReadError> {
+            MedicalHistoryReader::read_record_history(payload, signature)
+        }
+
+        fn read_records_of_patient(
+            payload: ListPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> Result<RecordPage<MedicalRecord<AccountId, u64>>, ReadError> {
+            MedicalHistoryReader::read_records_of_patient(
+                payload, signature, provenance, from, limit,
+            )
+        }
+
+        fn read_records_by_doctor(
+            payload: ListPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            provenance: Option<Provenance>,
+            from: u64,
+            limit: u32,
+        ) -> Result<RecordPage<(AccountId, MedicalRecord<AccountId, u64>)>, ReadError> {
+            MedicalHistoryReader::read_records_by_doctor(
+                payload, signature, provenance, from, limit,
+            )
+        }
+
+        fn access_log(
+            patient: AccountId,
+            from: u64,
+            limit: u32,
+        ) -> Vec<(u64, AccessLogEntry<AccountId, u64, BlockNumber, Hash>)> {
+            MedicalHistoryReader::access_log(&patient, from, limit)
+        }
+
+        fn export_access_log(
+            patient: AccountId,
+        ) -> Vec<(u64, AccessLogEntry<AccountId, u64, BlockNumber, Hash>)> {
+            MedicalHistoryReader::export_access_log(&patient)
+        }
+    }
+
+    impl MedicalCertificatesApi<Block, AccountId, u64> for Runtime {
+        fn verify(code: VerificationCode) -> Option<CertificateVerification<AccountId, Hash, u64>> {
+            MedicalCertificates::verify(code)
+        }
+    }
+
+    impl MedicalPrescriptionsApi<Block, AccountId, u64, BlockNumber, Signature> for Runtime {
+        fn prescription(
+            payload: QueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            id: PrescriptionId,
+        ) -> Result<Option<Prescription<AccountId, Hash, u64>>, QueryError> {
+            MedicalPrescriptions::prescription(payload, signature, id)
+        }
+
+        fn prescriptions_of_patient(
+            payload: QueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            after: Option<PrescriptionId>,
+            limit: u32,
+        ) -> Result<PrescriptionPage<AccountId, Hash, u64>, QueryError> {
+            MedicalPrescriptions::prescriptions_of_patient(payload, signature, after, limit)
+        }
+
+        fn prescriptions_by_doctor(
+            payload: QueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            after: Option<PrescriptionId>,
+            limit: u32,
+        ) -> Result<PrescriptionPage<AccountId, Hash, u64>, QueryError> {
+            MedicalPrescriptions::prescriptions_by_doctor(payload, signature, after, limit)
+        }
+    }
+
+    impl MedicalExamsApi<Block, AccountId, u64, BlockNumber, Signature> for Runtime {
+        fn exam_order(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            id: ExamOrderId,
+        ) -> Result<Option<ExamOrder<AccountId, Hash, u64>>, ExamQueryError> {
+            MedicalExams::exam_order(payload, signature, id)
+        }
+
+        fn exam_orders_of_patient(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            after: Option<ExamOrderId>,
+            limit: u32,
+        ) -> Result<ExamOrderPage<AccountId, Hash, u64>, ExamQueryError> {
+            MedicalExams::exam_orders_of_patient(payload, signature, after, limit)
+        }
+
+        fn exam_orders_by_doctor(
+            payload: ExamQueryPayload<AccountId, BlockNumber>,
+            signature: Signature,
+            after: Option<ExamOrderId>,
+            limit: u32,
+        ) -> Result<ExamOrderPage<AccountId, Hash, u64>, ExamQueryError> {
+            MedicalExams::exam_orders_by_doctor(payload, signature, after, limit)
+        }
+    }
+
     impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
         fn query_info(
             uxt: <Block as BlockT>::Extrinsic,
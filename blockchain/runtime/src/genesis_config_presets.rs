@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{AccountId, BalancesConfig, RuntimeGenesisConfig, SudoConfig};
+use crate::{AccountId, BalancesConfig, DoctorRegistryConfig, RuntimeGenesisConfig, SudoConfig};
 use alloc::{vec, vec::Vec};
 use frame_support::build_struct_json_patch;
 use serde_json::Value;
@@ -29,6 +29,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	endowed_accounts: Vec<AccountId>,
 	root: AccountId,
+	doctors: Vec<(AccountId, &'static str)>,
 ) -> Value {
 	build_struct_json_patch!(RuntimeGenesisConfig {
 		balances: BalancesConfig {
@@ -45,6 +46,19 @@ fn testnet_genesis(
 			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect::<Vec<_>>(),
 		},
 		sudo: SudoConfig { key: Some(root) },
+		doctor_registry: DoctorRegistryConfig {
+			doctors: doctors
+				.into_iter()
+				.map(|(doctor, crm)| {
+					(
+						doctor,
+						crm.as_bytes().to_vec().try_into().expect("CRM fits; qed"),
+						*b"MG",
+						b"Clinica Medica".to_vec().try_into().expect("specialty fits; qed"),
+					)
+				})
+				.collect::<Vec<_>>(),
+		},
 	})
 }
 
@@ -58,10 +72,16 @@ pub fn development_config_genesis() -> Value {
 		vec![
 			Sr25519Keyring::Alice.to_account_id(),
 			Sr25519Keyring::Bob.to_account_id(),
+			Sr25519Keyring::Charlie.to_account_id(),
 			Sr25519Keyring::AliceStash.to_account_id(),
 			Sr25519Keyring::BobStash.to_account_id(),
 		],
 		sp_keyring::Sr25519Keyring::Alice.to_account_id(),
+		// Doctors of the frontend's development flow.
+		vec![
+			(Sr25519Keyring::Bob.to_account_id(), "100001"),
+			(Sr25519Keyring::Charlie.to_account_id(), "100002"),
+		],
 	)
 }
 
@@ -83,6 +103,11 @@ pub fn local_config_genesis() -> Value {
 			.map(|v| v.to_account_id())
 			.collect::<Vec<_>>(),
 		Sr25519Keyring::Alice.to_account_id(),
+		vec![
+			(Sr25519Keyring::Bob.to_account_id(), "100001"),
+			(Sr25519Keyring::Charlie.to_account_id(), "100002"),
+			(Sr25519Keyring::Dave.to_account_id(), "100003"),
+		],
 	)
 }
 
@@ -107,3 +132,42 @@ pub fn preset_names() -> Vec<PresetId> {
 		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
 	]
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::DoctorRegistry;
+	use pallet_doctor_registry::DoctorRegistryVerifier;
+	use sp_runtime::BuildStorage;
+
+	/// Merges a preset `patch` into `config`, as the chain spec builder does.
+	fn merge(config: &mut Value, patch: Value) {
+		match (config, patch) {
+			(Value::Object(config), Value::Object(patch)) =>
+				for (key, value) in patch {
+					merge(config.entry(key).or_insert(Value::Null), value);
+				},
+			(config, patch) => *config = patch,
+		}
+	}
+
+	/// Ensures the dev preset builds and registers the frontend's doctors.
+	#[test]
+	fn dev_preset_registers_doctors() {
+		let preset = get_preset(&PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET))
+			.expect("dev preset exists");
+		let mut config = serde_json::to_value(RuntimeGenesisConfig::default())
+			.expect("default genesis config serializes");
+		merge(&mut config, serde_json::from_slice(&preset).expect("preset is JSON"));
+		let storage = serde_json::from_value::<RuntimeGenesisConfig>(config)
+			.expect("dev preset deserializes")
+			.build_storage()
+			.expect("dev preset builds");
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			for doctor in [Sr25519Keyring::Bob, Sr25519Keyring::Charlie] {
+				assert!(DoctorRegistry::is_active_doctor(&doctor.to_account_id()));
+			}
+		});
+	}
+}
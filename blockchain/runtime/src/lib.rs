@@ -16,7 +16,7 @@ use alloc::vec::Vec;
 
 use frame_support::{
     construct_runtime, derive_impl,
-    traits::{ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, Get},
+    traits::{ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, ConstU8, Get},
     weights::IdentityFee,
 };
 use sp_runtime::{
@@ -63,10 +63,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: alloc::borrow::Cow::Borrowed("healthchain-runtime"),
     impl_name: alloc::borrow::Cow::Borrowed("healthchain-runtime"),
     authoring_version: 1,
-    spec_version: 101,
+    spec_version: 151,
     impl_version: 1,
     apis: apis::RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 5,
     system_version: 1,
 };
 
@@ -90,6 +90,14 @@ pub const MILLI_UNIT: Balance = 1_000_000_000;
 pub const MICRO_UNIT: Balance = 1_000_000;
 pub const EXISTENTIAL_DEPOSIT: Balance = MILLI_UNIT;
 
+/// Storage deposit held from the doctor for each medical record or proposal.
+pub const RECORD_DEPOSIT: Balance = 10 * MILLI_UNIT;
+/// Storage deposit held from the patient for each permission grant.
+pub const PERMISSION_DEPOSIT: Balance = MILLI_UNIT;
+/// Blocks a signed read request may stay valid for (~10 minutes with 6s
+/// blocks). The node's RPC also refuses reads at blocks older than this.
+pub const MAX_READ_VALIDITY: BlockNumber = 100;
+
 // ----------------------------------------------------------------------------
 // Primitive Types
 // ----------------------------------------------------------------------------
@@ -124,6 +132,10 @@ construct_runtime!(
         MedicalHistory: pallet_medical_history,
         MedicalPermissions: pallet_medical_permissions,
         MedicalHistoryReader: pallet_medical_history_reader,
+        DoctorRegistry: pallet_doctor_registry,
+        MedicalCertificates: pallet_medical_certificates,
+        MedicalPrescriptions: pallet_medical_prescriptions,
+        MedicalExams: pallet_medical_exams,
     }
 );
 
@@ -146,12 +158,36 @@ pub type UncheckedExtrinsic =
 
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, TxExtension>;
 
+/// Storage migrations applied on runtime upgrade.
+pub type Migrations = (
+    pallet_medical_permissions::migrations::v1::MigrateV0ToV1<Runtime>,
+    pallet_medical_permissions::migrations::v2::MigrateV1ToV2<Runtime>,
+    pallet_medical_permissions::migrations::v3::MigrateV2ToV3<Runtime>,
+    pallet_medical_permissions::migrations::v4::MigrateV3ToV4<Runtime>,
+    pallet_medical_permissions::migrations::v5::MigrateV4ToV5<Runtime>,
+    pallet_medical_permissions::migrations::v6::MigrateV5ToV6<Runtime>,
+    pallet_medical_permissions::migrations::v7::MigrateV6ToV7<Runtime>,
+    pallet_medical_permissions::migrations::v8::MigrateV7ToV8<Runtime>,
+    pallet_medical_permissions::migrations::v9::MigrateV8ToV9<Runtime>,
+    pallet_medical_permissions::migrations::v10::MigrateV9ToV10<Runtime>,
+    pallet_medical_permissions::migrations::v11::MigrateV10ToV11<Runtime>,
+    pallet_medical_history::migrations::v1::MigrateV0ToV1<Runtime>,
+    pallet_medical_history::migrations::v2::MigrateV1ToV2<Runtime>,
+    pallet_medical_history::migrations::v3::MigrateV2ToV3<Runtime>,
+    pallet_medical_history::migrations::v4::MigrateV3ToV4<Runtime>,
+    pallet_medical_history_reader::migrations::v1::MigrateV0ToV1<Runtime>,
+    pallet_medical_prescriptions::migrations::v1::MigrateV0ToV1<Runtime>,
+    pallet_medical_exams::migrations::v1::MigrateV0ToV1<Runtime>,
+    pallet_medical_exams::migrations::v2::MigrateV1ToV2<Runtime>,
+);
+
 pub type Executive = frame_executive::Executive<
     Runtime,
     Block,
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
 // ----------------------------------------------------------------------------
@@ -231,11 +267,49 @@ impl pallet_sudo::Config for Runtime {
 impl pallet_medical_history::Config for Runtime {
     type WeightInfo = ();
     type Permissions = MedicalPermissions;
+    type DoctorRegistry = DoctorRegistry;
+    /// ~1 day with 6s blocks.
+    type ConfirmationWindow = ConstU32<14_400>;
+    type MaxProposalsPerBlock = ConstU32<64>;
+    /// Doctors must use `propose_record`; the patient confirms each insertion.
+    type RequireConfirmation = ConstBool<true>;
+    type MaxVersions = ConstU32<32>;
+    /// 20 years (CFM Resolution 1.821/2007), in milliseconds.
+    type RetentionPeriod = ConstU64<{ 20 * 365 * 24 * 60 * 60 * 1_000 }>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type RecordDeposit = ConstU128<RECORD_DEPOSIT>;
+    type MaxKeyEnvelopes = ConstU32<16>;
+    /// Scrubs redacted hashes from the access audit trail and exam orders.
+    type OnRecordRedacted = (MedicalHistoryReader, MedicalExams);
 }
 
 impl pallet_medical_permissions::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type DoctorRegistry = DoctorRegistry;
+    type MaxExpiriesPerBlock = ConstU32<64>;
+    /// ~1 day with 6s blocks.
+    type RequestTimeout = ConstU32<14_400>;
+    type Time = Timestamp;
+    type MaxLogEntries = ConstU32<256>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type PermissionDeposit = ConstU128<PERMISSION_DEPOSIT>;
+    /// Flags key rotation for doctors that held envelopes.
+    type OnAccessRevoked = MedicalHistory;
+    /// Sudo authorizes ambulance services until a council is in place.
+    type EmergencyAuthorityOrigin = frame_system::EnsureRoot<AccountId>;
+    /// ~4 hours with 6s blocks.
+    type EmergencyAccessDuration = ConstU32<2_400>;
+    /// Sudo appoints guardians for minors until a council is in place.
+    type GuardianRegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+    /// Sudo registers hospitals until a council is in place.
+    type InstitutionRegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxInstitutionMembers = ConstU32<512>;
+    type MaxInstitutionPatients = ConstU32<512>;
+    type MaxMembershipsPerDoctor = ConstU32<8>;
+    type MaxRevocationsPerCall = ConstU32<64>;
 }
 
 impl pallet_medical_history_reader::Config for Runtime {
@@ -243,40 +317,56 @@ impl pallet_medical_history_reader::Config for Runtime {
     type WeightInfo = MedicalHistoryReaderWeightInfo<Runtime>; //Para o cálculo de pesos funcionar corretamente
     type HistoryProvider = MedicalHistory;
     type Permissions = MedicalPermissions;
+    type MaxAccessLogEntries = ConstU32<1_024>;
+    type ReadSignature = Signature;
+    type ReadSigner = <Signature as Verify>::Signer;
+    type MaxReadValidity = ConstU32<MAX_READ_VALIDITY>;
 }
 
-// ----------------------------------------------------------------------------
-// Genesis Presets
-// ----------------------------------------------------------------------------
+impl pallet_doctor_registry::Config for Runtime {
+    /// Sudo acts as the CRM registrar until a council is in place.
+    type RegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+    type WeightInfo = pallet_doctor_registry::weights::SubstrateWeight<Runtime>;
+}
 
-pub mod genesis_config_presets {
-    use super::*;
-    use alloc::{string::String, vec, vec::Vec};
-    use sp_genesis_builder::PresetId;
-    use sp_keyring::Sr25519Keyring;
+impl pallet_medical_certificates::Config for Runtime {
+    type DoctorRegistry = DoctorRegistry;
+    type Time = Timestamp;
+    type MaxCertificateDays = ConstU16<365>;
+    type WeightInfo = pallet_medical_certificates::weights::SubstrateWeight<Runtime>;
+}
 
-    pub fn preset_names() -> Vec<String> {
-        vec![String::from("dev"), String::from("local")]
-    }
+impl pallet_medical_prescriptions::Config for Runtime {
+    type DoctorRegistry = DoctorRegistry;
+    type Time = Timestamp;
+    /// Sudo registers pharmacies until a council is in place.
+    type PharmacyRegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+    type Signature = Signature;
+    type Signer = <Signature as Verify>::Signer;
+    /// ~10 minutes with 6s blocks.
+    type MaxQueryValidity = ConstU32<100>;
+    type WeightInfo = pallet_medical_prescriptions::weights::SubstrateWeight<Runtime>;
+}
 
-    pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
-        let patch = match id.as_ref() {
-            sp_genesis_builder::DEV_RUNTIME_PRESET => serde_json::json!({
-                "balances": {
-                    "balances": vec![
-                        (Sr25519Keyring::Alice.to_account_id(), 1u64 << 60),
-                        (Sr25519Keyring::Bob.to_account_id(), 1u64 << 60),
-                    ],
-                },
-                "sudo": { "key": Sr25519Keyring::Alice.to_account_id() },
-                "aura": { "authorities": vec![Sr25519Keyring::Alice.public()] },
-                "grandpa": { "authorities": vec![(Sr25519Keyring::Alice.public(), 1)] },
-            }),
-            _ => return None,
-        };
-
-        Some(serde_json::to_vec(&patch).expect("genesis preset patch serialization must succeed"))
-    }
+impl pallet_medical_exams::Config for Runtime {
+    type DoctorRegistry = DoctorRegistry;
+    type Permissions = MedicalPermissions;
+    type History = MedicalHistory;
+    type Time = Timestamp;
+    /// Sudo registers laboratories until a council is in place.
+    type LaboratoryRegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+    type Signature = Signature;
+    type Signer = <Signature as Verify>::Signer;
+    /// ~10 minutes with 6s blocks.
+    type MaxQueryValidity = ConstU32<100>;
+    type MaxRedactedVersions = pallet_medical_history::MaxChainLength<Runtime>;
+    type WeightInfo = pallet_medical_exams::weights::SubstrateWeight<Runtime>;
 }
 
+// ----------------------------------------------------------------------------
+// Genesis Presets
+// ----------------------------------------------------------------------------
+
+mod genesis_config_presets;
+
 pub mod apis;
@@ -8,12 +8,20 @@ import { PatientHistory } from './components/PatientHistory';
 import { DoctorPatientView } from './components/DoctorPatientView';
 import { CreateRecord } from './components/CreateRecord';
 import { AllHistory } from './components/AllHistory';
+import { PendingRecords } from './components/PendingRecords';
 
-type Tab = 'permissions' | 'history' | 'doctor' | 'upload' | 'allHistory';
+type Tab =
+  | 'permissions'
+  | 'history'
+  | 'pending'
+  | 'doctor'
+  | 'upload'
+  | 'allHistory';
 
 const tabs: { id: Tab; label: string }[] = [
   { id: 'permissions', label: 'Permissões' },
   { id: 'history', label: 'Meu Histórico' },
+  { id: 'pending', label: 'Confirmar Registros' },
   { id: 'doctor', label: 'Busca Médica' },
   { id: 'upload', label: 'Criar Registro' },
   { id: 'allHistory', label: 'Histórico Completo' },
@@ -125,6 +133,7 @@ function AppContent(): JSX.Element {
       <main className='container mx-auto px-6 py-8'>
         {activeTab === 'permissions' && <PermissionManagement />}
         {activeTab === 'history' && <PatientHistory />}
+        {activeTab === 'pending' && <PendingRecords />}
         {activeTab === 'doctor' && <DoctorPatientView />}
         {activeTab === 'upload' && <CreateRecord />}
         {activeTab === 'allHistory' && <AllHistory />}
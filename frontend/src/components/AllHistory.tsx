@@ -116,6 +116,7 @@ export function AllHistory(): JSX.Element {
     try {
       const result = await readPatientHistory({
         patientAddress: patientAddress,
+        doctorAddress,
       });
 
       if (!result.length) {
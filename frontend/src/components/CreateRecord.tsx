@@ -97,6 +97,11 @@ export function CreateRecord(): JSX.Element {
         fileHashHex: cid,
         doctorAddress: doctorId,
         patientAddress: patientId,
+        metadata: {
+          category: 'Other',
+          contentType: file.type || 'application/octet-stream',
+          clinicalDate: Date.now(),
+        },
       });
 
       if (!result.success) {
@@ -109,7 +114,10 @@ export function CreateRecord(): JSX.Element {
       setUploadStep('complete');
       setResult({ cid, txHash, blockNumber });
 
-      showToast('success', 'Registro médico criado com sucesso!');
+      showToast(
+        'success',
+        'Registro proposto! O paciente deve confirmá-lo em "Confirmar Registros".',
+      );
     } catch (err) {
       showToast('error', 'Falha no upload. Por favor, tente novamente.');
       setUploadStep('idle');
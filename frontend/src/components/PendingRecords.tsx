@@ -0,0 +1,223 @@
+import { useState, JSX, FormEvent } from 'react';
+import { Check, ClipboardCheck, Loader2, Search, X } from 'lucide-react';
+
+import { useToast } from '../contexts/ToastContext';
+import { useWallet } from '../contexts/WalletContext';
+
+import { Input } from './Input';
+import {
+  confirmRecord,
+  PendingRecordEntry,
+  readPendingRecords,
+  rejectRecord,
+} from '../utils/polkadot.api';
+import { getIpfsUrl } from '../utils/ipfs-functions';
+
+type PendingItemProps = PendingRecordEntry & {
+  busy: boolean;
+  onConfirm: (fileHash: string) => void;
+  onReject: (fileHash: string) => void;
+};
+
+function Header(): JSX.Element {
+  return (
+    <div className='flex items-center gap-3 mb-6'>
+      <div className='p-3 bg-gradient-to-br from-blue-500 to-teal-600 rounded-xl'>
+        <ClipboardCheck className='w-6 h-6 text-white' />
+      </div>
+      <h2 className='text-2xl font-bold text-gray-800'>
+        Registros Pendentes de Confirmação
+      </h2>
+    </div>
+  );
+}
+
+function PendingItem({
+  fileHash,
+  doctor,
+  expiresAt,
+  busy,
+  onConfirm,
+  onReject,
+}: Readonly<PendingItemProps>): JSX.Element {
+  return (
+    <div className='bg-white rounded-lg p-4 space-y-6'>
+      <label className='text-xs font-medium text-gray-500 uppercase mb-1 block'>
+        Médico:
+      </label>
+      <code className='text-sm text-gray-800 font-mono'>{doctor}</code>
+
+      <label className='text-xs font-medium text-gray-500 uppercase mb-1 block'>
+        CID do arquivo:
+      </label>
+      <code className='text-sm text-gray-800 font-mono'>{fileHash}</code>
+
+      <label className='text-xs font-medium text-gray-500 uppercase mb-1 block'>
+        Expira no bloco:
+      </label>
+      <code className='text-sm text-gray-800 font-mono'>{expiresAt}</code>
+
+      <label className='text-xs font-medium text-gray-500 uppercase mb-1 block'>
+        <a
+          href={getIpfsUrl(fileHash)}
+          target='_blank'
+          rel='noopener noreferrer'
+          className='text-blue-600 hover:text-blue-700'
+        >
+          Abrir registro
+        </a>
+      </label>
+
+      <div className='flex gap-3'>
+        <button
+          type='button'
+          disabled={busy}
+          onClick={() => onConfirm(fileHash)}
+          className='flex-1 bg-gradient-to-r from-teal-500 to-blue-600 text-white py-2 rounded-xl font-semibold hover:shadow-lg transition-all disabled:opacity-50 disabled:cursor-not-allowed flex items-center justify-center gap-2'
+        >
+          <Check className='w-4 h-4' />
+          Confirmar
+        </button>
+        <button
+          type='button'
+          disabled={busy}
+          onClick={() => onReject(fileHash)}
+          className='flex-1 bg-white border border-red-200 text-red-600 py-2 rounded-xl font-semibold hover:bg-red-50 transition-all disabled:opacity-50 disabled:cursor-not-allowed flex items-center justify-center gap-2'
+        >
+          <X className='w-4 h-4' />
+          Recusar
+        </button>
+      </div>
+    </div>
+  );
+}
+
+export function PendingRecords(): JSX.Element {
+  const { showToast } = useToast();
+  const { accountId } = useWallet();
+
+  const [loading, setLoading] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [patientAddress, setPatientAddress] = useState('');
+  const [pending, setPending] = useState<PendingRecordEntry[]>();
+
+  const load = async (): Promise<void> => {
+    const result = await readPendingRecords({ patientAddress });
+    setPending(result);
+  };
+
+  const handleSearch = async (e: FormEvent): Promise<void> => {
+    e.preventDefault();
+
+    if (!patientAddress) {
+      showToast('error', 'Por favor, preencha todos os campos');
+      return;
+    }
+
+    setLoading(true);
+    try {
+      await load();
+    } catch (err) {
+      setPending(undefined);
+      console.log('Error reading pending records:', err);
+      showToast('error', 'Busca falhou. Por favor, tente novamente.');
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const answer = async (fileHash: string, confirm: boolean): Promise<void> => {
+    setBusy(true);
+    showToast('info', 'Enviando resposta para a blockchain...');
+
+    try {
+      const action = confirm ? confirmRecord : rejectRecord;
+      const result = await action({
+        fileHashHex: fileHash,
+        patientAddress,
+      });
+
+      if (!result.success) {
+        throw result.error;
+      }
+
+      showToast(
+        'success',
+        confirm ? 'Registro confirmado!' : 'Registro recusado.',
+      );
+      await load();
+    } catch (err) {
+      console.log('Error answering proposal:', err);
+      showToast('error', 'Operação falhou. Por favor, tente novamente.');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handlePatientAddressChange = (value: string) => {
+    setPatientAddress(value);
+    setPending(undefined);
+  };
+
+  return (
+    <div className='max-w-2xl mx-auto'>
+      <div className='bg-white/70 backdrop-blur-md rounded-2xl shadow-xl p-8 border border-blue-100'>
+        <Header />
+
+        <form onSubmit={handleSearch} className='space-y-6'>
+          <Input
+            value={patientAddress}
+            title='Conta do paciente'
+            onChange={handlePatientAddressChange}
+          >
+            {accountId && (
+              <button
+                type='button'
+                onClick={() => handlePatientAddressChange(accountId)}
+                className='mt-2 text-sm text-blue-600 hover:text-blue-700 font-medium'
+              >
+                Usar carteira conectada: {accountId.slice(0, 8)}...
+              </button>
+            )}
+          </Input>
+
+          <button
+            type='submit'
+            disabled={loading}
+            className='w-full bg-gradient-to-r from-blue-600 to-teal-500 text-white py-4 rounded-xl font-semibold hover:shadow-lg transition-all disabled:opacity-50 disabled:cursor-not-allowed flex items-center justify-center gap-2'
+          >
+            {loading ? (
+              <>
+                <Loader2 className='w-5 h-5 animate-spin' />
+                Buscando propostas...
+              </>
+            ) : (
+              <>
+                <Search className='w-5 h-5' />
+                Buscar propostas
+              </>
+            )}
+          </button>
+        </form>
+
+        {pending && (
+          <div className='mt-8 bg-gradient-to-br from-blue-50 to-teal-50 border border-blue-200 rounded-xl p-6 space-y-6'>
+            {pending.length === 0 ? (
+              <p className='text-gray-500'>Nenhuma proposta pendente</p>
+            ) : (
+              pending.map(entry => (
+                <PendingItem
+                  key={entry.fileHash}
+                  {...entry}
+                  busy={busy}
+                  onConfirm={fileHash => answer(fileHash, true)}
+                  onReject={fileHash => answer(fileHash, false)}
+                />
+              ))
+            )}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -6,7 +6,8 @@ import { KeyringPair } from '@polkadot/keyring/types';
 
 import { WS_URL } from './shared';
 import { withResolvers } from './promises';
-import { Codec } from '@polkadot/types/types';
+import { hexToString, hexToU8a, stringToU8a, u8aConcat } from '@polkadot/util';
+import { blake2AsHex } from '@polkadot/util-crypto';
 import { getIpfsUrl } from './ipfs-functions';
 
 const provider = new WsProvider(WS_URL);
@@ -14,21 +15,33 @@ const api = await ApiPromise.create({ provider });
 
 const keyring = new Keyring({ type: 'sr25519', ss58Format: 2 });
 
+const PAGE_SIZE = 100;
+
 type BlockchainActionProps = { patientAddress: string; doctorAddress: string };
 type BlockchainCreateRecordProps = BlockchainActionProps & {
   fileHashHex: string;
 };
+export type RecordCategory =
+  | 'Exam'
+  | 'Prescription'
+  | 'Certificate'
+  | 'Imaging'
+  | 'Note'
+  | 'Referral'
+  | 'Other';
+export type RecordMetadata = {
+  category: RecordCategory;
+  contentType: string;
+  // Data do evento clínico (ms); não pode ser posterior ao timestamp do bloco.
+  clinicalDate: number;
+  titleHash?: string;
+};
 type BlockchainReadOwnDataProps = Omit<
   BlockchainCreateRecordProps,
   'doctorAddress'
 >;
 type BlockchainDoctorReadDataProps = BlockchainCreateRecordProps;
 type ReadPatientHistoryProps = Omit<BlockchainActionProps, 'doctorAddress'>;
-type PatientHistory = {
-  createdBy: string;
-  createdAt: string;
-  fileHash: string;
-};
 export type PatientHistoryConverted = {
   createdBy: string;
   createdAt: string;
@@ -72,7 +85,9 @@ export async function checkAccess({
       doctorAddress,
     );
 
-    const hasAccess = stored.toJSON() as boolean;
+    // `Permissions` guarda um bitflag de escopo (1 = leitura, 2 = escrita).
+    const scope = stored.toJSON() as number;
+    const hasAccess = scope !== 0;
 
     console.log(
       `O médico ${doctorAddress} tem acesso ao paciente ${patientAddress}? -> ${hasAccess ? 'Sim' : 'Não'}`,
@@ -183,21 +198,131 @@ export async function revokeAccess({
   return submitExtrinsic(extrinsic, patient);
 }
 
+function toChainMetadata({
+  category,
+  contentType,
+  clinicalDate,
+  titleHash,
+}: RecordMetadata) {
+  return {
+    category,
+    contentType,
+    clinicalDate,
+    titleHash: titleHash ?? null,
+  };
+}
+
 export async function createRecord({
   patientAddress,
   doctorAddress,
   fileHashHex,
-}: Readonly<BlockchainCreateRecordProps>): Promise<BlockchainActionResult> {
+  metadata,
+}: Readonly<
+  BlockchainCreateRecordProps & { metadata: RecordMetadata }
+>): Promise<BlockchainActionResult> {
   const doctor = keyring.getPair(doctorAddress);
 
-  const extrinsic = api.tx.medicalHistory.createRecord(
+  // O registro fica pendente até o paciente confirmar (`confirmRecord`).
+  const extrinsic = api.tx.medicalHistory.proposeRecord(
     patientAddress,
     fileHashHex,
+    toChainMetadata(metadata),
   );
 
   return submitExtrinsic(extrinsic, doctor);
 }
 
+export async function submitOwnRecord({
+  patientAddress,
+  fileHashHex,
+  metadata,
+}: Readonly<{
+  patientAddress: string;
+  fileHashHex: string;
+  metadata: RecordMetadata;
+}>): Promise<BlockchainActionResult> {
+  const patient = keyring.getPair(patientAddress);
+
+  // Registro autodeclarado (`SelfReported`): não precisa de confirmação.
+  const extrinsic = api.tx.medicalHistory.submitOwnRecord(
+    fileHashHex,
+    toChainMetadata(metadata),
+  );
+
+  return submitExtrinsic(extrinsic, patient);
+}
+
+export async function endorseRecord({
+  doctorAddress,
+  fileHashHex,
+}: Readonly<{
+  doctorAddress: string;
+  fileHashHex: string;
+}>): Promise<BlockchainActionResult> {
+  const doctor = keyring.getPair(doctorAddress);
+
+  const extrinsic = api.tx.medicalHistory.endorseRecord(fileHashHex);
+
+  return submitExtrinsic(extrinsic, doctor);
+}
+
+export async function confirmRecord({
+  fileHashHex,
+  patientAddress,
+}: Readonly<BlockchainReadOwnDataProps>): Promise<BlockchainActionResult> {
+  const patient = keyring.getPair(patientAddress);
+
+  const extrinsic = api.tx.medicalHistory.confirmRecord(fileHashHex);
+
+  return submitExtrinsic(extrinsic, patient);
+}
+
+export async function rejectRecord({
+  fileHashHex,
+  patientAddress,
+}: Readonly<BlockchainReadOwnDataProps>): Promise<BlockchainActionResult> {
+  const patient = keyring.getPair(patientAddress);
+
+  const extrinsic = api.tx.medicalHistory.rejectRecord(fileHashHex);
+
+  return submitExtrinsic(extrinsic, patient);
+}
+
+type PendingRecordJson = {
+  patient: string;
+  doctor: string;
+  expiresAt: number;
+};
+export type PendingRecordEntry = {
+  fileHash: string;
+  doctor: string;
+  expiresAt: number;
+};
+
+// Propostas de registro (`PendingRecords`) aguardando a confirmação do paciente.
+export async function readPendingRecords({
+  patientAddress,
+}: Readonly<ReadPatientHistoryProps>): Promise<PendingRecordEntry[]> {
+  const entries = await api.query.medicalHistory.pendingRecords.entries();
+  const patient = encodeAddress(decodeAddress(patientAddress), 42);
+
+  return entries.flatMap(([key, value]) => {
+    const pending = value.toJSON() as PendingRecordJson | null;
+    if (!pending) return [];
+
+    const owner = encodeAddress(decodeAddress(pending.patient), 42);
+    if (owner !== patient) return [];
+
+    return [
+      {
+        fileHash: key.args[0].toHuman() as string,
+        doctor: encodeAddress(decodeAddress(pending.doctor), 42),
+        expiresAt: pending.expiresAt,
+      },
+    ];
+  });
+}
+
 export async function readOwnData({
   fileHashHex,
   patientAddress,
@@ -224,33 +349,214 @@ export async function readPatientData({
   return submitExtrinsic(extrinsic, doctor);
 }
 
+// Registro como devolvido pelas RPCs `healthchain_*`.
+type RecordInfoJson = {
+  createdBy: string;
+  createdAt: number;
+  fileHash: string;
+};
+
+const convertRecordInfo = ({
+  createdBy,
+  createdAt,
+  fileHash: fileHashHex,
+}: RecordInfoJson): PatientHistoryConverted => {
+  const fileHash = hexToString(fileHashHex);
+
+  return {
+    createdBy,
+    createdAt: new Date(createdAt).toLocaleString('pt-BR'),
+    fileHash,
+    url: getIpfsUrl(fileHash),
+  };
+};
+
 export async function readPatientHistory({
   patientAddress,
-}: Readonly<ReadPatientHistoryProps>): Promise<PatientHistoryConverted[]> {
-  const entries =
-    await api.query.medicalHistory.patientRecords.entries(patientAddress);
+  doctorAddress: readerAddress,
+}: Readonly<BlockchainActionProps>): Promise<PatientHistoryConverted[]> {
+  // RPC `healthchain_readRecordsOfPatient`: o leitor assina o pedido de
+  // listagem e recebe, em páginas de `PAGE_SIZE`, os registros que pode ler,
+  // ordenados por data de criação. Cada página informa onde começa a próxima
+  // (`next`), que não muda se um registro for removido no meio.
+  const reader = keyring.getPair(readerAddress);
+  const entries: RecordInfoJson[] = [];
+  for (let from = 0; ; ) {
+    const header = await api.rpc.chain.getHeader();
+    const payload = api.createType('(AccountId, AccountId, u32, u64)', [
+      readerAddress,
+      patientAddress,
+      header.number.toNumber() + READ_VALIDITY_BLOCKS,
+      randomNonce(),
+    ]);
+    const signature = reader.sign(
+      u8aConcat(stringToU8a(LIST_DOMAIN), payload.toU8a()),
+    );
+
+    const page = (await provider.send('healthchain_readRecordsOfPatient', [
+      payload.toHex(),
+      encodeSignature(signature),
+      from,
+      PAGE_SIZE,
+      null, // at
+      null, // provenance
+    ])) as { items: RecordInfoJson[]; next: number | null };
+
+    entries.push(...page.items);
+    if (page.next === null) break;
+    from = page.next;
+  }
 
   if (!entries.length) {
     console.log('No records found for this patient');
-    return [];
   }
 
-  const convertToHuman = (value: Codec): PatientHistoryConverted => {
-    const { fileHash, ...data } = value.toHuman() as PatientHistory;
+  return entries.map(convertRecordInfo);
+}
+
+export type AccessLogEntry = {
+  reader: string;
+  // `redacted` traz o compromisso do tombstone de um registro apagado.
+  record: { file: string } | { redacted: string };
+  accessedAt: string;
+  block: number;
+};
+
+// Exporta a trilha de auditoria de leituras do paciente
+// (runtime API `MedicalHistoryReaderApi::export_access_log`).
+export async function exportAccessLog({
+  patientAddress,
+}: Readonly<ReadPatientHistoryProps>): Promise<AccessLogEntry[]> {
+  const log =
+    await api.call.medicalHistoryReaderApi.exportAccessLog(patientAddress);
 
-    // decodifica para public key (Uint8Array)
-    const publicKey = decodeAddress(data.createdBy);
+  const entries = log.toJSON() as [number, AccessLogEntry][];
 
-    // re-encoda usando SS58 = 42 (Substrate generic)
-    const createdBy = encodeAddress(publicKey, 42);
+  return entries.map(([, entry]) => ({
+    ...entry,
+    accessedAt: new Date(Number(entry.accessedAt)).toLocaleString('pt-BR'),
+  }));
+}
 
-    const timestamp = Number(data.createdAt.replace(/,/g, ''));
-    const date = new Date(timestamp);
+// Leitura instantânea (sem transação): o leitor assina o pedido e a RPC
+// `healthchain_readRecord` (runtime API `MedicalHistoryReaderApi::read_record`)
+// confere assinatura e permissão.
+// Os extrínsecos `readOwnData`/`readPatientData` continuam disponíveis como
+// acesso notarizado.
+const READ_DOMAIN = 'healthchain/read:';
+const LIST_DOMAIN = 'healthchain/list:';
+const READ_VALIDITY_BLOCKS = 10;
+
+// Valor aleatório de cada pedido assinado: o nó atende cada
+// `(leitor, nonce)` uma única vez.
+function randomNonce(): bigint {
+  const bytes = crypto.getRandomValues(new Uint8Array(8));
+  return new DataView(bytes.buffer).getBigUint64(0);
+}
 
-    const createdAt = date.toLocaleString('pt-BR');
+export async function readRecordOffchain({
+  patientAddress,
+  doctorAddress: readerAddress,
+  fileHashHex,
+}: Readonly<BlockchainDoctorReadDataProps>): Promise<PatientHistoryConverted> {
+  const reader = keyring.getPair(readerAddress);
+  const header = await api.rpc.chain.getHeader();
 
-    return { createdBy, createdAt, fileHash, url: getIpfsUrl(fileHash) };
-  };
+  const payload = api.createType('(AccountId, AccountId, Bytes, u32, u64)', [
+    readerAddress,
+    patientAddress,
+    fileHashHex,
+    header.number.toNumber() + READ_VALIDITY_BLOCKS,
+    randomNonce(),
+  ]);
+  const signature = reader.sign(
+    u8aConcat(stringToU8a(READ_DOMAIN), payload.toU8a()),
+  );
+
+  try {
+    const record = (await provider.send('healthchain_readRecord', [
+      payload.toHex(),
+      encodeSignature(signature),
+      null,
+    ])) as RecordInfoJson;
+
+    return convertRecordInfo(record);
+  } catch (err) {
+    throw new Error(`Leitura recusada: ${(err as Error).message}`);
+  }
+}
+
+// Assinatura sr25519 codificada como `MultiSignature`, como esperam as RPCs.
+function encodeSignature(signature: Uint8Array): string {
+  return api.createType('MultiSignature', { Sr25519: signature }).toHex();
+}
+
+export async function amendRecord({
+  doctorAddress,
+  originalHashHex,
+  fileHashHex,
+  reasonHashHex,
+  metadata,
+}: Readonly<{
+  doctorAddress: string;
+  originalHashHex: string;
+  fileHashHex: string;
+  reasonHashHex: string;
+  metadata: RecordMetadata;
+}>): Promise<BlockchainActionResult> {
+  const doctor = keyring.getPair(doctorAddress);
+
+  // A nova versão aguarda confirmação do paciente (`confirmRecord`).
+  const extrinsic = api.tx.medicalHistory.amendRecord(
+    originalHashHex,
+    fileHashHex,
+    reasonHashHex,
+    toChainMetadata(metadata),
+  );
+
+  return submitExtrinsic(extrinsic, doctor);
+}
+
+export async function redactRecord({
+  patientAddress,
+  versionHashesHex,
+  saltHex,
+}: Readonly<{
+  patientAddress: string;
+  versionHashesHex: string[];
+  saltHex: string;
+}>): Promise<BlockchainActionResult> {
+  const patient = keyring.getPair(patientAddress);
+
+  // Um compromisso por versão, da mais antiga para a mais nova, calculado
+  // aqui: o salt nunca vai para a chain. Guarde o salt: sem ele não é
+  // possível provar a qual registro o tombstone (`hash(salt ++ fileHash)`)
+  // se refere.
+  const salt = hexToU8a(saltHex);
+  const commitments = versionHashesHex.map((fileHashHex) =>
+    blake2AsHex(u8aConcat(salt, api.createType('Bytes', fileHashHex).toU8a())),
+  );
+  const extrinsic = api.tx.medicalHistory.redactRecord(versionHashesHex[0], commitments);
+
+  return submitExtrinsic(extrinsic, patient);
+}
+
+export async function shareKey({
+  senderAddress,
+  fileHashHex,
+  recipientAddress,
+  envelopeHex,
+}: Readonly<{
+  senderAddress: string;
+  fileHashHex: string;
+  recipientAddress: string;
+  envelopeHex: string;
+}>): Promise<BlockchainActionResult> {
+  const sender = keyring.getPair(senderAddress);
+
+  // `envelopeHex` é a chave simétrica do arquivo cifrada com a chave
+  // pública do destinatário; a chave em claro nunca vai para a chain.
+  const extrinsic = api.tx.medicalHistory.shareKey(fileHashHex, recipientAddress, envelopeHex);
 
-  return entries.map(([, value]) => convertToHuman(value));
+  return submitExtrinsic(extrinsic, sender);
 }